@@ -21,6 +21,7 @@ struct ChannelThresholdUniform {
     threshold: f32,
     smoothing: f32,
     invert: u32,
+    source: u32,
 }
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(100)
@@ -36,13 +37,21 @@ fn fragment(
     // Get PBR input from the standard material
     var pbr_input = pbr_input_from_standard_material(in, is_front);
 
-    // Read the selected channel from base_color
-    let base = pbr_input.material.base_color;
+    // Read the selected channel from base_color, or from the vertex color
+    // when the mesh has one and it is the configured source
+    var base = pbr_input.material.base_color;
+#ifdef VERTEX_COLORS
+    if params.source == 1u {
+        base = in.color;
+    }
+#endif
     var channel_value: f32;
     if params.channel == 1u {
         channel_value = base.g;
     } else if params.channel == 2u {
         channel_value = base.b;
+    } else if params.channel == 3u {
+        channel_value = base.a;
     } else {
         channel_value = base.r;
     }
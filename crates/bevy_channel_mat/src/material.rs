@@ -23,7 +23,7 @@ impl Plugin for ChannelThresholdPlugin {
 /// Uniform data sent to the GPU for the channel threshold shader.
 #[derive(Clone, Copy, ShaderType, Debug)]
 pub struct ChannelThresholdUniform {
-    /// Which color channel to threshold: 0=R, 1=G, 2=B
+    /// Which color channel to threshold: 0=R, 1=G, 2=B, 3=A
     pub channel: u32,
     /// Cutoff value (0.0–1.0)
     pub threshold: f32,
@@ -31,13 +31,16 @@ pub struct ChannelThresholdUniform {
     pub smoothing: f32,
     /// Whether to invert the alpha (0 or 1)
     pub invert: u32,
+    /// Color to read the channel from: 0=base color, 1=vertex color
+    pub source: u32,
 }
 
 /// A material that controls opacity based on a color channel threshold.
 ///
 /// Extends Bevy's `StandardMaterial` to discard or fade fragments based on
-/// whether the selected color channel (R, G, or B) of the base color exceeds
-/// a configurable threshold, with optional smoothing and inversion.
+/// whether the selected color channel (R, G, B or A) of the base color exceeds
+/// a configurable threshold, with optional smoothing and inversion. Reading
+/// the vertex color instead turns painted vertex colors into a blend mask.
 #[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
 pub struct ChannelThresholdMaterial {
     #[uniform(100)]
@@ -52,6 +55,7 @@ impl Default for ChannelThresholdMaterial {
                 threshold: 0.5,
                 smoothing: 0.05,
                 invert: 0,
+                source: 0,
             },
         }
     }
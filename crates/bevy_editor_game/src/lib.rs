@@ -8,6 +8,8 @@
 //! - Tag entities with `GameCamera`, `GameEntity`
 //! - Register custom entity types via `register_custom_entity::<T>()`
 //! - Send `PlayEvent`, `PauseEvent`, `ResetEvent` messages
+//! - Fire effect sequence steps with `GameEffectEvent`
+//! - React to trigger volumes with `TriggerEnter` / `TriggerExit`
 //! - Listen for lifecycle events (`GameStartedEvent`, etc.)
 //! - Register custom components for scene serialization
 
@@ -93,6 +95,58 @@ impl Default for MeshRef {
     }
 }
 
+/// Where an `AnimationRef` takes its clip from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Reflect)]
+pub enum AnimationSource {
+    /// A named clip in the AnimationLibrary (e.g. "characters::Walk")
+    Library(String),
+    /// A named animation in the file of the entity's own `GltfSource`
+    Gltf(String),
+}
+
+impl Default for AnimationSource {
+    fn default() -> Self {
+        AnimationSource::Library(String::new())
+    }
+}
+
+impl AnimationSource {
+    /// Name of the referenced clip
+    pub fn clip(&self) -> &str {
+        match self {
+            AnimationSource::Library(name) | AnimationSource::Gltf(name) => name,
+        }
+    }
+}
+
+/// Component that plays an animation clip on the entity's model.
+///
+/// Serialized to scene files. The editor binds the clip to the `AnimationPlayer`
+/// spawned with the entity's GLTF scene, previews it while editing, and starts
+/// playback when the game starts.
+#[derive(Component, Serialize, Deserialize, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct AnimationRef {
+    pub source: AnimationSource,
+    /// Start playing on `GameStartedEvent`
+    pub autoplay: bool,
+    /// Repeat forever instead of stopping at the end of the clip
+    pub looping: bool,
+    /// Playback speed multiplier
+    pub speed: f32,
+}
+
+impl Default for AnimationRef {
+    fn default() -> Self {
+        Self {
+            source: AnimationSource::default(),
+            autoplay: true,
+            looping: true,
+            speed: 1.0,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // State
 // ---------------------------------------------------------------------------
@@ -100,7 +154,9 @@ impl Default for MeshRef {
 /// The current game/editor state.
 ///
 /// Controls whether physics is running and the editor is active.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, States)]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, States, Serialize, Deserialize, Reflect,
+)]
 pub enum GameState {
     /// Physics paused, editor active (default state)
     #[default]
@@ -186,6 +242,81 @@ pub struct GamePausedEvent;
 #[derive(Message)]
 pub struct GameResetEvent;
 
+// ---------------------------------------------------------------------------
+// Effect events
+// ---------------------------------------------------------------------------
+
+/// Games write this message to fire `OnGameEvent` steps on playing effects.
+///
+/// # Example
+///
+/// ```ignore
+/// fn open_gate(mut events: MessageWriter<GameEffectEvent>) {
+///     events.write(GameEffectEvent::new("gate_opened"));
+/// }
+/// ```
+#[derive(Message, Clone, Debug)]
+pub struct GameEffectEvent {
+    /// Event name matched against `OnGameEvent` triggers
+    pub name: String,
+    /// Only deliver to this effect entity; `None` broadcasts to all effects
+    pub target: Option<Entity>,
+}
+
+impl GameEffectEvent {
+    /// Broadcast a named event to every playing effect.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            target: None,
+        }
+    }
+
+    /// Deliver a named event to a single effect entity.
+    pub fn to(entity: Entity, name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            target: Some(entity),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Trigger volume events
+// ---------------------------------------------------------------------------
+
+/// Fired while playing when an entity passing a trigger volume's filter
+/// starts overlapping it.
+///
+/// # Example
+///
+/// ```ignore
+/// fn on_enter(mut events: MessageReader<TriggerEnter>, names: Query<&Name>) {
+///     for event in events.read() {
+///         if names.get(event.volume).is_ok_and(|n| n.as_str() == "Goal") {
+///             info!("{:?} reached the goal", event.entity);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Message, Clone, Copy, Debug)]
+pub struct TriggerEnter {
+    /// The trigger volume entity
+    pub volume: Entity,
+    /// The entity that entered it
+    pub entity: Entity,
+}
+
+/// Fired while playing when an entity stops overlapping a trigger volume
+/// (or is despawned inside it).
+#[derive(Message, Clone, Copy, Debug)]
+pub struct TriggerExit {
+    /// The trigger volume entity
+    pub volume: Entity,
+    /// The entity that left it
+    pub entity: Entity,
+}
+
 // ---------------------------------------------------------------------------
 // Prefab spawning
 // ---------------------------------------------------------------------------
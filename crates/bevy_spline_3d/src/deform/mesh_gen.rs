@@ -0,0 +1,284 @@
+use std::collections::HashSet;
+
+use avian3d::prelude::*;
+use bevy::{
+    prelude::*,
+    mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+};
+
+use crate::geometry::CoordinateFrame;
+use crate::spline::{ArcLengthTable, Spline, DEFAULT_ARC_LENGTH_SAMPLES};
+
+use super::{DeformMode, GeneratedDeformMesh, ResolvedMeshDeform, SplineMeshDeform};
+
+/// Upper bound on repeated copies, guarding against tiny meshes on long splines.
+pub const MAX_DEFORM_COPIES: usize = 4096;
+
+/// Bend a mesh along a spline.
+///
+/// Source Z is mapped to arc length and source X/Y to the spline's local frame,
+/// so copies keep their proportions regardless of control point spacing.
+/// Positions, normals, UVs and indices are kept; other attributes are dropped.
+///
+/// Returns `None` for non-triangle meshes, meshes with no extent along Z, or
+/// splines with no length.
+pub fn generate_deformed_mesh(
+    spline: &Spline,
+    table: &ArcLengthTable,
+    source: &Mesh,
+    mode: DeformMode,
+    spacing: f32,
+) -> Option<Mesh> {
+    if source.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let positions = match source.attribute(Mesh::ATTRIBUTE_POSITION)? {
+        VertexAttributeValues::Float32x3(v) => v,
+        _ => return None,
+    };
+    let normals = match source.attribute(Mesh::ATTRIBUTE_NORMAL) {
+        Some(VertexAttributeValues::Float32x3(v)) => Some(v),
+        _ => None,
+    };
+    let uvs = match source.attribute(Mesh::ATTRIBUTE_UV_0) {
+        Some(VertexAttributeValues::Float32x2(v)) => Some(v),
+        _ => None,
+    };
+
+    let (min_z, max_z) = positions
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(lo, hi), p| (lo.min(p[2]), hi.max(p[2])));
+    let mesh_length = max_z - min_z;
+    let total_length = table.total_length();
+    if mesh_length <= f32::EPSILON || total_length <= f32::EPSILON {
+        return None;
+    }
+
+    // Length of one copy plus its gap, and how many copies fit
+    let (tile, copies) = match mode {
+        DeformMode::Stretch => (mesh_length, 1),
+        DeformMode::Repeat => {
+            let tile = mesh_length + spacing.max(0.0);
+            let copies = ((total_length / tile).round() as usize).clamp(1, MAX_DEFORM_COPIES);
+            (tile, copies)
+        }
+    };
+    // Scale along Z so the copies exactly cover the spline
+    let scale = total_length / (copies as f32 * tile);
+
+    let source_indices: Vec<u32> = match source.indices() {
+        Some(indices) => indices.iter().map(|i| i as u32).collect(),
+        None => (0..positions.len() as u32).collect(),
+    };
+
+    let vertex_count = positions.len();
+    let mut out_positions = Vec::with_capacity(vertex_count * copies);
+    let mut out_normals = Vec::with_capacity(if normals.is_some() { vertex_count * copies } else { 0 });
+    let mut out_uvs = Vec::with_capacity(if uvs.is_some() { vertex_count * copies } else { 0 });
+    let mut out_indices = Vec::with_capacity(source_indices.len() * copies);
+
+    for copy in 0..copies {
+        let start = copy as f32 * tile * scale;
+
+        for (i, p) in positions.iter().enumerate() {
+            let t = table.length_to_t(start + (p[2] - min_z) * scale);
+            let origin = spline.evaluate(t)?;
+            let tangent = spline
+                .evaluate_tangent(t)
+                .map(|t| t.normalize_or_zero())
+                .unwrap_or(Vec3::Z);
+            let frame = CoordinateFrame::from_tangent(tangent);
+
+            // `right` is tangent × up; flip it so the mesh keeps its handedness and winding
+            let side = -frame.right;
+
+            let world_pos = origin + side * p[0] + frame.up * p[1];
+            out_positions.push([world_pos.x, world_pos.y, world_pos.z]);
+
+            if let Some(normals) = normals {
+                let n = normals[i];
+                let normal = (side * n[0] + frame.up * n[1] + frame.tangent * n[2]).normalize_or_zero();
+                out_normals.push([normal.x, normal.y, normal.z]);
+            }
+            if let Some(uvs) = uvs {
+                out_uvs.push(uvs[i]);
+            }
+        }
+
+        let offset = (copy * vertex_count) as u32;
+        out_indices.extend(source_indices.iter().map(|i| i + offset));
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, out_positions);
+    if !out_uvs.is_empty() {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, out_uvs);
+    }
+    mesh.insert_indices(Indices::U32(out_indices));
+    if out_normals.is_empty() {
+        mesh.compute_normals();
+    } else {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, out_normals);
+    }
+
+    Some(mesh)
+}
+
+/// System to update deformed meshes when the deform, its spline or its source mesh changes.
+pub fn update_deform_meshes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh_events: MessageReader<AssetEvent<Mesh>>,
+    deforms: Query<(
+        Entity,
+        Ref<SplineMeshDeform>,
+        Ref<Spline>,
+        Option<&ResolvedMeshDeform>,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+    )>,
+    existing_deform_meshes: Query<(Entity, &GeneratedDeformMesh)>,
+    deform_mesh_children: Query<&Children>,
+) {
+    let modified_meshes: HashSet<AssetId<Mesh>> = mesh_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (deform_entity, deform, spline, resolved, material) in &deforms {
+        let needs_update = match resolved {
+            None => true,
+            Some(_) if deform.is_changed() => true,
+            Some(resolved) => {
+                deform.auto_update
+                    && (spline.is_changed()
+                        || resolved.mesh != deform.mesh.id()
+                        || modified_meshes.contains(&resolved.mesh))
+            }
+        };
+        if !needs_update || !spline.is_valid() {
+            continue;
+        }
+
+        // Source mesh may still be loading; try again next frame
+        let Some(source) = meshes.get(&deform.mesh) else {
+            continue;
+        };
+
+        let table = ArcLengthTable::compute(&spline, DEFAULT_ARC_LENGTH_SAMPLES);
+        let Some(generated) =
+            generate_deformed_mesh(&spline, &table, source, deform.mode, deform.spacing)
+        else {
+            continue;
+        };
+
+        let mesh_handle = meshes.add(generated);
+
+        commands.entity(deform_entity).insert(ResolvedMeshDeform {
+            mesh: deform.mesh.id(),
+        });
+
+        // Find or create the mesh entity
+        let found_mesh_entity = deform_mesh_children
+            .get(deform_entity)
+            .ok()
+            .and_then(|children| {
+                children
+                    .iter()
+                    .find(|child| existing_deform_meshes.get(*child).is_ok())
+            });
+
+        let mesh_entity = match found_mesh_entity {
+            Some(mesh_entity) => {
+                commands.entity(mesh_entity).insert(Mesh3d(mesh_handle));
+                mesh_entity
+            }
+            None => {
+                let mesh_entity = commands
+                    .spawn((
+                        Mesh3d(mesh_handle),
+                        Transform::default(),
+                        Visibility::default(),
+                        GeneratedDeformMesh {
+                            deform: deform_entity,
+                        },
+                    ))
+                    .id();
+                commands.entity(deform_entity).add_child(mesh_entity);
+                mesh_entity
+            }
+        };
+
+        let mut entity_commands = commands.entity(mesh_entity);
+
+        // Copy material from the deform entity
+        if let Some(mat) = material {
+            entity_commands.insert(mat.clone());
+        }
+
+        // Avian removes the constructor once the collider is built, so
+        // re-inserting it rebuilds the collider from the new mesh
+        if deform.generate_collider {
+            entity_commands.insert((RigidBody::Static, ColliderConstructor::TrimeshFromMesh));
+        } else {
+            entity_commands.remove::<(RigidBody, Collider, ColliderConstructor)>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    fn straight_spline() -> Spline {
+        Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 2.0),
+                Vec3::new(0.0, 0.0, 4.0),
+                Vec3::new(0.0, 0.0, 6.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_repeat_fits_whole_copies() {
+        let spline = straight_spline();
+        let table = ArcLengthTable::compute(&spline, 100);
+        let source = Cuboid::new(0.5, 0.5, 1.0).mesh().build();
+        let source_vertices = source.count_vertices();
+
+        let mesh = generate_deformed_mesh(&spline, &table, &source, DeformMode::Repeat, 0.0)
+            .expect("deformed mesh");
+
+        let copies = table.total_length().round() as usize;
+        assert_eq!(mesh.count_vertices(), source_vertices * copies);
+    }
+
+    #[test]
+    fn test_stretch_spans_spline() {
+        let spline = straight_spline();
+        let table = ArcLengthTable::compute(&spline, 100);
+        let source = Cuboid::new(0.5, 0.5, 1.0).mesh().build();
+
+        let mesh = generate_deformed_mesh(&spline, &table, &source, DeformMode::Stretch, 0.0)
+            .expect("deformed mesh");
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("missing positions");
+        };
+
+        assert_eq!(positions.len(), source.count_vertices());
+        let start = spline.evaluate(0.0).unwrap();
+        let end = spline.evaluate(1.0).unwrap();
+        let min_z = positions.iter().map(|p| p[2]).fold(f32::MAX, f32::min);
+        let max_z = positions.iter().map(|p| p[2]).fold(f32::MIN, f32::max);
+        assert!((min_z - start.z).abs() < 0.01);
+        assert!((max_z - end.z).abs() < 0.01);
+    }
+}
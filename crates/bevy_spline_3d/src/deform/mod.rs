@@ -0,0 +1,144 @@
+mod mesh_gen;
+
+pub use mesh_gen::*;
+
+use bevy::prelude::*;
+
+use crate::spline::SplinePlugin;
+
+/// Plugin for bending whole meshes along splines.
+///
+/// Where [`SplineRoadPlugin`](crate::road::SplineRoadPlugin) sweeps a 2D
+/// cross-section, this plugin deforms every vertex of a full 3D mesh, which
+/// suits pipes, fences, rails and cables.
+///
+/// # Mesh Requirements
+///
+/// The source mesh uses the same axes as road segments:
+/// - **X axis**: Side to side
+/// - **Y axis**: Up
+/// - **Z axis**: Forward (the mesh is bent along this axis)
+///
+/// Vertices only move, so the source needs enough edge loops along Z to
+/// follow tight curves smoothly.
+///
+/// # Usage
+///
+/// ```ignore
+/// use bevy_spline_3d::prelude::*;
+///
+/// fn setup(
+///     mut commands: Commands,
+///     mut meshes: ResMut<Assets<Mesh>>,
+///     mut materials: ResMut<Assets<StandardMaterial>>,
+/// ) {
+///     let fence_section = meshes.add(Cuboid::new(0.1, 1.0, 2.0).mesh().build());
+///
+///     commands.spawn((
+///         Spline::new(SplineType::CatmullRom, vec![/* ... */]),
+///         SplineMeshDeform::new(fence_section).with_mode(DeformMode::Repeat),
+///         // Copied to the generated mesh
+///         MeshMaterial3d(materials.add(Color::srgb(0.6, 0.4, 0.2))),
+///     ));
+/// }
+/// ```
+pub struct SplineMeshDeformPlugin;
+
+impl Plugin for SplineMeshDeformPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<SplinePlugin>() {
+            app.add_plugins(SplinePlugin);
+        }
+
+        app.register_type::<SplineMeshDeform>()
+            .register_type::<DeformMode>()
+            .add_systems(Update, mesh_gen::update_deform_meshes);
+    }
+}
+
+/// How the source mesh is laid out along the spline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum DeformMode {
+    /// Tile copies end to end, scaled slightly so a whole number fits.
+    #[default]
+    Repeat,
+    /// Stretch a single copy over the whole spline.
+    Stretch,
+}
+
+/// Component that bends a mesh along the `Spline` on the same entity.
+///
+/// Like [`SplineRoad::segment_mesh`](crate::road::SplineRoad::segment_mesh), the
+/// source mesh is a runtime handle and is not serialized; scenes store their
+/// own reference to the mesh and set the handle when loaded. A
+/// `MeshMaterial3d<StandardMaterial>` on this entity is copied to the
+/// generated mesh.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct SplineMeshDeform {
+    /// Mesh to bend, in its local space.
+    #[reflect(ignore)]
+    pub mesh: Handle<Mesh>,
+    /// Whether to repeat or stretch the mesh.
+    pub mode: DeformMode,
+    /// Gap between repeated copies, in source mesh units.
+    pub spacing: f32,
+    /// Whether to automatically update when the spline or source mesh changes.
+    pub auto_update: bool,
+    /// Whether to add a static trimesh collider to the generated mesh.
+    pub generate_collider: bool,
+}
+
+impl Default for SplineMeshDeform {
+    fn default() -> Self {
+        Self {
+            mesh: Handle::default(),
+            mode: DeformMode::Repeat,
+            spacing: 0.0,
+            auto_update: true,
+            generate_collider: true,
+        }
+    }
+}
+
+impl SplineMeshDeform {
+    /// Create a new deform configuration for the given source mesh.
+    pub fn new(mesh: Handle<Mesh>) -> Self {
+        Self {
+            mesh,
+            ..default()
+        }
+    }
+
+    /// Set the layout mode.
+    pub fn with_mode(mut self, mode: DeformMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the gap between repeated copies.
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set whether a collider is generated.
+    pub fn with_collider(mut self, generate_collider: bool) -> Self {
+        self.generate_collider = generate_collider;
+        self
+    }
+}
+
+/// Marker component for the generated deformed mesh entity.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GeneratedDeformMesh {
+    /// The SplineMeshDeform entity this mesh belongs to.
+    pub deform: Entity,
+}
+
+/// Source mesh the generated mesh was last built from.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ResolvedMeshDeform {
+    /// The source mesh at generation time.
+    pub mesh: AssetId<Mesh>,
+}
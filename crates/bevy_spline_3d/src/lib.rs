@@ -53,6 +53,7 @@
 //! - [`SplinePlugin`]: Core spline functionality and type registration (required)
 //! - [`SplineEditorPlugin`]: Interactive editing with gizmos and hotkeys (optional)
 //! - [`SplineRoadPlugin`]: Generate road meshes along splines (optional)
+//! - [`SplineMeshDeformPlugin`]: Bend whole meshes along splines (optional)
 //! - [`SplineFollowPlugin`]: Animate entities following spline paths (optional)
 //! - [`CameraPlugin`]: Orbit and fly camera controls (optional)
 //!
@@ -68,6 +69,7 @@
 //! ```
 
 pub mod camera;
+pub mod deform;
 pub mod geometry;
 pub mod path_follow;
 pub mod road;
@@ -78,6 +80,7 @@ pub mod surface;
 pub mod editor;
 
 pub use camera::CameraPlugin;
+pub use deform::SplineMeshDeformPlugin;
 pub use path_follow::SplineFollowPlugin;
 pub use road::SplineRoadPlugin;
 pub use spline::SplinePlugin;
@@ -89,13 +92,18 @@ pub use editor::SplineEditorPlugin;
 /// Convenient re-exports of commonly used types.
 pub mod prelude {
     pub use crate::camera::{CameraMode, CameraPlugin, FlyCamera, OrbitCamera};
+    pub use crate::deform::{
+        DeformMode, GeneratedDeformMesh, SplineMeshDeform, SplineMeshDeformPlugin,
+    };
     pub use crate::path_follow::{
         FollowerEvent, FollowerEventKind, FollowerState, LoopMode, ResolvedSplineFollower,
         SplineFollowPlugin, SplineFollower,
     };
     pub use crate::road::{
-        create_road_segment_mesh, GeneratedIntersectionMesh, GeneratedRoadMesh,
-        RoadConnection, RoadEnd, RoadIntersection, SplineRoad, SplineRoadPlugin,
+        create_road_segment_mesh, ExportRoadGraph, GeneratedIntersectionMesh,
+        GeneratedLaneMarkings, GeneratedRoadMesh, LaneDirection, LaneId, RoadConnection,
+        RoadEnd, RoadGraph, RoadGraphId, RoadIntersection, RoadLane, SplineRoad,
+        SplineRoadPlugin,
     };
     pub use crate::spline::{
         CachedSplineCurve, ControlPointMarker, ProjectedSplineCache, SelectedControlPoint,
@@ -0,0 +1,541 @@
+//! Lane-level road graph for traffic AI.
+//!
+//! The [`RoadGraph`] resource is rebuilt from `SplineRoad` lanes and
+//! `RoadIntersection` connections whenever they change. It exposes lane
+//! centerlines and the turns allowed through each intersection, and can be
+//! exported to RON for use by a game runtime.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::CoordinateFrame;
+use crate::spline::{ArcLengthTable, Spline, SplineType, DEFAULT_ARC_LENGTH_SAMPLES};
+
+use super::{lane_offsets, LaneDirection, RoadEnd, RoadIntersection, SplineRoad};
+
+/// Number of points sampled along each turn path when exporting.
+pub const TURN_EXPORT_SAMPLES: usize = 8;
+
+/// Stable id of a road or intersection in exported road graphs.
+///
+/// Entity ids change between runs and names needn't be unique, so apps with
+/// their own persistent ids (e.g. saved scene ids) insert this on roads and
+/// intersections to let exports be matched up reliably.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RoadGraphId(pub u64);
+
+/// Identifies a lane by its road and index (left to right across the road).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LaneId {
+    /// The SplineRoad entity.
+    pub road: Entity,
+    /// Index into `SplineRoad::lanes`.
+    pub index: usize,
+}
+
+/// A lane in the road graph.
+#[derive(Debug, Clone)]
+pub struct GraphLane {
+    /// Which lane this is.
+    pub id: LaneId,
+    /// Lane width in world units.
+    pub width: f32,
+    /// Direction of travel relative to the road spline.
+    pub direction: LaneDirection,
+    /// Centerline points in world space, ordered in the direction of travel
+    /// (`Both` lanes follow the road spline).
+    pub points: Vec<Vec3>,
+    /// Centerline spline passing through `points`.
+    pub centerline: Spline,
+    /// Length of the centerline.
+    pub length: f32,
+    /// Intersection at the first point, if any.
+    pub start: Option<Entity>,
+    /// Intersection at the last point, if any.
+    pub end: Option<Entity>,
+}
+
+impl GraphLane {
+    /// Whether traffic in this lane can drive into `intersection`.
+    pub fn enters(&self, intersection: Entity) -> bool {
+        self.end == Some(intersection)
+            || (self.direction == LaneDirection::Both && self.start == Some(intersection))
+    }
+
+    /// Whether traffic can leave `intersection` through this lane.
+    pub fn exits(&self, intersection: Entity) -> bool {
+        self.start == Some(intersection)
+            || (self.direction == LaneDirection::Both && self.end == Some(intersection))
+    }
+
+    /// Position and outward direction of the lane end touching `intersection`.
+    fn port(&self, intersection: Entity) -> Option<(Vec3, Vec3)> {
+        if self.end == Some(intersection) {
+            let tangent = self.centerline.evaluate_tangent(1.0)?.normalize_or_zero();
+            Some((*self.points.last()?, tangent))
+        } else if self.start == Some(intersection) {
+            let tangent = self.centerline.evaluate_tangent(0.0)?.normalize_or_zero();
+            Some((*self.points.first()?, -tangent))
+        } else {
+            None
+        }
+    }
+}
+
+/// A permitted movement from one lane to another through an intersection.
+#[derive(Debug, Clone)]
+pub struct TurnConnection {
+    /// The RoadIntersection entity.
+    pub intersection: Entity,
+    /// Lane the turn starts in.
+    pub from: LaneId,
+    /// Lane the turn ends in.
+    pub to: LaneId,
+    /// Cubic Bézier path from the end of `from` to the start of `to`, in world space.
+    pub path: Spline,
+}
+
+/// Lane-level graph of every `SplineRoad` with lanes.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RoadGraph {
+    /// All lanes, grouped by road.
+    pub lanes: Vec<GraphLane>,
+    /// All turns through intersections.
+    pub turns: Vec<TurnConnection>,
+}
+
+impl RoadGraph {
+    /// Look up a lane.
+    pub fn lane(&self, id: LaneId) -> Option<&GraphLane> {
+        self.lanes.iter().find(|lane| lane.id == id)
+    }
+
+    /// All lanes on a road, left to right.
+    pub fn road_lanes(&self, road: Entity) -> impl Iterator<Item = &GraphLane> {
+        self.lanes.iter().filter(move |lane| lane.id.road == road)
+    }
+
+    /// Turns available at the end of a lane.
+    pub fn turns_from(&self, lane: LaneId) -> impl Iterator<Item = &TurnConnection> {
+        self.turns.iter().filter(move |turn| turn.from == lane)
+    }
+
+    /// All turns through an intersection.
+    pub fn intersection_turns(&self, intersection: Entity) -> impl Iterator<Item = &TurnConnection> {
+        self.turns
+            .iter()
+            .filter(move |turn| turn.intersection == intersection)
+    }
+
+    /// Convert to a serializable form, identifying roads and intersections with `identify`.
+    pub fn to_export(&self, identify: impl Fn(Entity) -> ExportRef) -> RoadGraphExport {
+        let lane_index: HashMap<LaneId, usize> = self
+            .lanes
+            .iter()
+            .enumerate()
+            .map(|(i, lane)| (lane.id, i))
+            .collect();
+
+        RoadGraphExport {
+            lanes: self
+                .lanes
+                .iter()
+                .map(|lane| LaneExport {
+                    road: identify(lane.id.road),
+                    index: lane.id.index,
+                    width: lane.width,
+                    direction: lane.direction,
+                    points: lane.points.clone(),
+                    length: lane.length,
+                    start: lane.start.map(&identify),
+                    end: lane.end.map(&identify),
+                })
+                .collect(),
+            turns: self
+                .turns
+                .iter()
+                .filter_map(|turn| {
+                    Some(TurnExport {
+                        intersection: identify(turn.intersection),
+                        from: *lane_index.get(&turn.from)?,
+                        to: *lane_index.get(&turn.to)?,
+                        points: (0..=TURN_EXPORT_SAMPLES)
+                            .filter_map(|i| {
+                                turn.path.evaluate(i as f32 / TURN_EXPORT_SAMPLES as f32)
+                            })
+                            .collect(),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Serializable road graph for game runtimes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoadGraphExport {
+    /// All lanes.
+    pub lanes: Vec<LaneExport>,
+    /// All turns, referencing lanes by index.
+    pub turns: Vec<TurnExport>,
+}
+
+/// A road or intersection in an exported graph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExportRef {
+    /// The entity's [`RoadGraphId`], if it has one.
+    pub id: Option<u64>,
+    /// The entity's `Name`, or empty.
+    pub name: String,
+}
+
+/// Serializable lane.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaneExport {
+    /// The road this lane is on.
+    pub road: ExportRef,
+    /// Lane index on the road, left to right.
+    pub index: usize,
+    /// Lane width.
+    pub width: f32,
+    /// Direction of travel relative to the road spline.
+    pub direction: LaneDirection,
+    /// Centerline points in world space, in the direction of travel.
+    pub points: Vec<Vec3>,
+    /// Centerline length.
+    pub length: f32,
+    /// Intersection at the first point.
+    pub start: Option<ExportRef>,
+    /// Intersection at the last point.
+    pub end: Option<ExportRef>,
+}
+
+/// Serializable turn connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnExport {
+    /// The intersection the turn goes through.
+    pub intersection: ExportRef,
+    /// Index of the lane the turn starts in.
+    pub from: usize,
+    /// Index of the lane the turn ends in.
+    pub to: usize,
+    /// Sampled turn path in world space.
+    pub points: Vec<Vec3>,
+}
+
+/// Message to write the current [`RoadGraph`] to a RON file.
+#[derive(Message, Debug, Clone)]
+pub struct ExportRoadGraph {
+    /// Destination file path.
+    pub path: PathBuf,
+}
+
+/// Build a Catmull-Rom spline that passes through every point, end points included.
+fn spline_through(points: &[Vec3]) -> Spline {
+    let mut control_points = Vec::with_capacity(points.len() + 2);
+    if let [first, second, ..] = points {
+        control_points.push(*first * 2.0 - *second);
+    }
+    control_points.extend_from_slice(points);
+    if let [.., second_last, last] = points {
+        control_points.push(*last * 2.0 - *second_last);
+    }
+    Spline::new(SplineType::CatmullRom, control_points)
+}
+
+/// Build the graph lanes for one road.
+fn build_road_lanes(
+    road_entity: Entity,
+    road: &SplineRoad,
+    spline: &Spline,
+    transform: &GlobalTransform,
+    ends: &HashMap<(Entity, RoadEnd), Entity>,
+) -> Vec<GraphLane> {
+    let table = ArcLengthTable::compute(spline, DEFAULT_ARC_LENGTH_SAMPLES);
+    let samples: Vec<(Vec3, CoordinateFrame)> = table
+        .uniform_t_values(road.segments_per_curve.max(1) + 1)
+        .into_iter()
+        .filter_map(|t| {
+            let position = spline.evaluate(t)?;
+            let tangent = spline
+                .evaluate_tangent(t)
+                .map(|t| t.normalize_or_zero())
+                .unwrap_or(Vec3::Z);
+            Some((position, CoordinateFrame::from_tangent(tangent)))
+        })
+        .collect();
+    if samples.len() < 2 {
+        return Vec::new();
+    }
+
+    let start = ends.get(&(road_entity, RoadEnd::Start)).copied();
+    let end = ends.get(&(road_entity, RoadEnd::End)).copied();
+
+    road.lanes
+        .iter()
+        .zip(lane_offsets(&road.lanes))
+        .enumerate()
+        .map(|(index, (lane, offset))| {
+            let mut points: Vec<Vec3> = samples
+                .iter()
+                .map(|(position, frame)| {
+                    transform.transform_point(*position + frame.transform_profile_point(offset, 0.0))
+                })
+                .collect();
+            let (lane_start, lane_end) = if lane.direction == LaneDirection::Backward {
+                points.reverse();
+                (end, start)
+            } else {
+                (start, end)
+            };
+            let length = points.windows(2).map(|w| w[0].distance(w[1])).sum();
+
+            GraphLane {
+                id: LaneId {
+                    road: road_entity,
+                    index,
+                },
+                width: lane.width,
+                direction: lane.direction,
+                centerline: spline_through(&points),
+                points,
+                length,
+                start: lane_start,
+                end: lane_end,
+            }
+        })
+        .collect()
+}
+
+/// Build the turns through one intersection.
+fn build_turns(intersection: Entity, lanes: &[GraphLane]) -> Vec<TurnConnection> {
+    let mut turns = Vec::new();
+    for from in lanes.iter().filter(|lane| lane.enters(intersection)) {
+        let Some((p0, out0)) = from.port(intersection) else {
+            continue;
+        };
+        for to in lanes.iter().filter(|lane| lane.exits(intersection)) {
+            // No U-turns back onto the same road
+            if to.id.road == from.id.road {
+                continue;
+            }
+            let Some((p3, out3)) = to.port(intersection) else {
+                continue;
+            };
+            let handle = p0.distance(p3) / 3.0;
+            turns.push(TurnConnection {
+                intersection,
+                from: from.id,
+                to: to.id,
+                path: Spline::new(
+                    SplineType::CubicBezier,
+                    vec![p0, p0 + out0 * handle, p3 + out3 * handle, p3],
+                ),
+            });
+        }
+    }
+    turns
+}
+
+/// System to rebuild the road graph when roads, intersections or splines change.
+#[allow(clippy::too_many_arguments)]
+pub fn update_road_graph(
+    mut graph: ResMut<RoadGraph>,
+    roads: Query<(Entity, &SplineRoad, Option<&GlobalTransform>)>,
+    intersections: Query<(Entity, &RoadIntersection)>,
+    splines: Query<&Spline>,
+    changed_roads: Query<(), (With<SplineRoad>, Or<(Changed<SplineRoad>, Changed<GlobalTransform>)>)>,
+    changed_intersections: Query<(), Changed<RoadIntersection>>,
+    changed_splines: Query<Entity, Changed<Spline>>,
+    mut removed_roads: RemovedComponents<SplineRoad>,
+    mut removed_intersections: RemovedComponents<RoadIntersection>,
+) {
+    let removed = removed_roads.read().count() + removed_intersections.read().count() > 0;
+    let splines_changed = changed_splines
+        .iter()
+        .any(|spline| roads.iter().any(|(_, road, _)| road.spline == spline));
+    if !removed
+        && !splines_changed
+        && changed_roads.is_empty()
+        && changed_intersections.is_empty()
+    {
+        return;
+    }
+
+    let mut ends: HashMap<(Entity, RoadEnd), Entity> = HashMap::new();
+    for (entity, intersection) in &intersections {
+        for connection in &intersection.connections {
+            ends.insert((connection.road, connection.end), entity);
+        }
+    }
+
+    let mut lanes = Vec::new();
+    for (entity, road, transform) in &roads {
+        if road.lanes.is_empty() {
+            continue;
+        }
+        let Ok(spline) = splines.get(road.spline) else {
+            continue;
+        };
+        if !spline.is_valid() {
+            continue;
+        }
+        let transform = transform.copied().unwrap_or_default();
+        lanes.extend(build_road_lanes(entity, road, spline, &transform, &ends));
+    }
+
+    let turns = intersections
+        .iter()
+        .flat_map(|(entity, _)| build_turns(entity, &lanes))
+        .collect();
+
+    *graph = RoadGraph { lanes, turns };
+}
+
+/// Identify `entity` by its `RoadGraphId` and `Name`.
+fn export_ref(entity: Entity, ids: &Query<(Option<&Name>, Option<&RoadGraphId>)>) -> ExportRef {
+    let (name, id) = ids.get(entity).unwrap_or_default();
+    ExportRef {
+        id: id.map(|id| id.0),
+        name: name.map(|name| name.to_string()).unwrap_or_default(),
+    }
+}
+
+/// System to write the road graph to disk on request.
+pub fn handle_export_road_graph(
+    mut events: MessageReader<ExportRoadGraph>,
+    graph: Res<RoadGraph>,
+    ids: Query<(Option<&Name>, Option<&RoadGraphId>)>,
+) {
+    for event in events.read() {
+        let export = graph.to_export(|entity| export_ref(entity, &ids));
+        let pretty = ron::ser::PrettyConfig::default();
+        match ron::ser::to_string_pretty(&export, pretty) {
+            Ok(ron_str) => {
+                if let Err(e) = std::fs::write(&event.path, ron_str) {
+                    warn!("Failed to write road graph to {:?}: {}", event.path, e);
+                } else {
+                    info!(
+                        "Exported road graph ({} lanes, {} turns) to {:?}",
+                        export.lanes.len(),
+                        export.turns.len(),
+                        event.path
+                    );
+                }
+            }
+            Err(e) => warn!("Failed to serialize road graph: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::road::RoadLane;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_spline_through_hits_endpoints() {
+        let points = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 1.0),
+            Vec3::new(2.0, 0.0, 0.0),
+        ];
+        let spline = spline_through(&points);
+        assert!((spline.evaluate(0.0).unwrap() - points[0]).length() < 0.001);
+        assert!((spline.evaluate(1.0).unwrap() - points[2]).length() < 0.001);
+    }
+
+    #[test]
+    fn test_backward_lanes_are_reversed() {
+        let road_entity = Entity::from_raw_u32(1).unwrap();
+        let intersection = Entity::from_raw_u32(2).unwrap();
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, -10.0),
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 10.0),
+                Vec3::new(0.0, 0.0, 20.0),
+            ],
+        );
+        let road = SplineRoad {
+            lanes: RoadLane::two_way(1, 3.0),
+            ..default()
+        };
+        let ends = HashMap::from([((road_entity, RoadEnd::End), intersection)]);
+
+        let lanes = build_road_lanes(
+            road_entity,
+            &road,
+            &spline,
+            &GlobalTransform::IDENTITY,
+            &ends,
+        );
+
+        assert_eq!(lanes.len(), 2);
+        let (backward, forward) = (&lanes[0], &lanes[1]);
+        assert_eq!(forward.end, Some(intersection));
+        assert_eq!(backward.start, Some(intersection));
+        assert!(forward.points.first().unwrap().z < forward.points.last().unwrap().z);
+        assert!(backward.points.first().unwrap().z > backward.points.last().unwrap().z);
+        assert!(forward.enters(intersection));
+        assert!(backward.exits(intersection));
+    }
+
+    #[test]
+    fn test_export_tells_same_named_roads_apart() {
+        let mut world = World::new();
+        let spline = world
+            .spawn(Spline::new(
+                SplineType::CatmullRom,
+                vec![
+                    Vec3::new(0.0, 0.0, -10.0),
+                    Vec3::new(0.0, 0.0, 0.0),
+                    Vec3::new(0.0, 0.0, 10.0),
+                    Vec3::new(0.0, 0.0, 20.0),
+                ],
+            ))
+            .id();
+        for id in [7, 9] {
+            world.spawn((
+                Name::new("Road"),
+                RoadGraphId(id),
+                SplineRoad {
+                    spline,
+                    lanes: vec![RoadLane::forward(3.0)],
+                    ..default()
+                },
+            ));
+        }
+        world.init_resource::<RoadGraph>();
+        world.run_system_once(update_road_graph).unwrap();
+
+        let export = world
+            .run_system_once(
+                |graph: Res<RoadGraph>, ids: Query<(Option<&Name>, Option<&RoadGraphId>)>| {
+                    graph.to_export(|entity| export_ref(entity, &ids))
+                },
+            )
+            .unwrap();
+
+        let mut roads: Vec<&ExportRef> = export.lanes.iter().map(|lane| &lane.road).collect();
+        roads.sort_by_key(|road| road.id);
+        assert_eq!(
+            roads,
+            vec![
+                &ExportRef {
+                    id: Some(7),
+                    name: "Road".into()
+                },
+                &ExportRef {
+                    id: Some(9),
+                    name: "Road".into()
+                },
+            ]
+        );
+    }
+}
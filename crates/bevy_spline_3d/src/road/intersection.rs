@@ -26,7 +26,7 @@ fn calculate_frame(spline: &Spline, t: f32, direction: f32) -> Option<(Vec3, Coo
 }
 
 /// Which end of a road connects to an intersection.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
 pub enum RoadEnd {
     /// The start of the road (t = 0).
     #[default]
@@ -0,0 +1,346 @@
+//! Road lane definitions and generated lane markings.
+//!
+//! Lanes are laid out across the road from left (-X) to right (+X), centered
+//! on the road spline, using the same profile axes as the segment mesh.
+//! Markings are clustered decals projected onto the road surface.
+
+use bevy::{
+    asset::RenderAssetUsages,
+    light::ClusteredDecal,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::CoordinateFrame;
+use crate::spline::{ArcLengthTable, Spline, DEFAULT_ARC_LENGTH_SAMPLES};
+
+use super::SplineRoad;
+
+/// Width of a painted lane line.
+pub const MARKING_WIDTH: f32 = 0.12;
+/// Length of each dash in a dashed lane line.
+pub const MARKING_DASH_LENGTH: f32 = 3.0;
+/// Gap between dashes in a dashed lane line.
+pub const MARKING_DASH_GAP: f32 = 6.0;
+/// Depth of each marking decal's projection box, centered on the road surface.
+pub const MARKING_DEPTH: f32 = 0.5;
+
+/// Direction of travel in a lane, relative to the road spline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum LaneDirection {
+    /// Travel from the start of the spline (t = 0) to the end (t = 1).
+    #[default]
+    Forward,
+    /// Travel from the end of the spline to the start.
+    Backward,
+    /// Travel in either direction (e.g. single-lane country roads).
+    Both,
+}
+
+/// A single lane across a road.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct RoadLane {
+    /// Lane width in world units.
+    pub width: f32,
+    /// Direction of travel.
+    pub direction: LaneDirection,
+}
+
+impl Default for RoadLane {
+    fn default() -> Self {
+        Self {
+            width: 3.5,
+            direction: LaneDirection::Forward,
+        }
+    }
+}
+
+impl RoadLane {
+    /// Create a new lane.
+    pub fn new(width: f32, direction: LaneDirection) -> Self {
+        Self { width, direction }
+    }
+
+    /// Create a lane travelling along the spline.
+    pub fn forward(width: f32) -> Self {
+        Self::new(width, LaneDirection::Forward)
+    }
+
+    /// Create a lane travelling against the spline.
+    pub fn backward(width: f32) -> Self {
+        Self::new(width, LaneDirection::Backward)
+    }
+
+    /// Create a right-hand-traffic layout with `count` lanes each way.
+    ///
+    /// Backward lanes are on the left (-X), forward lanes on the right (+X).
+    pub fn two_way(count: usize, width: f32) -> Vec<Self> {
+        std::iter::repeat_n(Self::backward(width), count)
+            .chain(std::iter::repeat_n(Self::forward(width), count))
+            .collect()
+    }
+}
+
+/// Lateral offset of each lane's centerline from the road spline, left to right.
+pub fn lane_offsets(lanes: &[RoadLane]) -> Vec<f32> {
+    let total: f32 = lanes.iter().map(|lane| lane.width).sum();
+    let mut left = -total / 2.0;
+    lanes
+        .iter()
+        .map(|lane| {
+            let center = left + lane.width / 2.0;
+            left += lane.width;
+            center
+        })
+        .collect()
+}
+
+/// Marker component for a generated lane marking decal.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GeneratedLaneMarkings {
+    /// The SplineRoad entity these markings belong to.
+    pub road: Entity,
+}
+
+/// Texture projected by lane marking decals.
+///
+/// Inserted as plain white the first time markings are generated; insert
+/// your own beforehand to customize it.
+#[derive(Resource, Debug, Clone)]
+pub struct LaneMarkingTexture(pub Handle<Image>);
+
+/// Line painted on a lane boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MarkingStyle {
+    Solid,
+    Dashed,
+}
+
+/// Offset and style of every painted line, left to right.
+///
+/// Road edges get solid lines, lanes travelling the same way are separated
+/// by dashed lines, and opposing lanes by a solid center line.
+fn lane_boundaries(lanes: &[RoadLane]) -> Vec<(f32, MarkingStyle)> {
+    let total: f32 = lanes.iter().map(|lane| lane.width).sum();
+    let inset = MARKING_WIDTH / 2.0;
+
+    let mut boundaries = vec![(-total / 2.0 + inset, MarkingStyle::Solid)];
+    let mut x = -total / 2.0;
+    for pair in lanes.windows(2) {
+        x += pair[0].width;
+        let style = if pair[0].direction == pair[1].direction {
+            MarkingStyle::Dashed
+        } else {
+            MarkingStyle::Solid
+        };
+        boundaries.push((x, style));
+    }
+    boundaries.push((total / 2.0 - inset, MarkingStyle::Solid));
+    boundaries
+}
+
+/// Transforms of the decals painting lane lines along a spline.
+///
+/// Each decal is one straight piece of line: a dash, or one of `segments`
+/// equal stretches of a solid line so it follows curves. Decals project along
+/// their local Z, with Y running along the road and X across it.
+pub fn lane_marking_decals(
+    spline: &Spline,
+    lanes: &[RoadLane],
+    segments: usize,
+) -> Vec<Transform> {
+    if lanes.is_empty() {
+        return Vec::new();
+    }
+
+    let table = ArcLengthTable::compute(spline, DEFAULT_ARC_LENGTH_SAMPLES);
+    let total_length = table.total_length();
+    if total_length <= 0.0 {
+        return Vec::new();
+    }
+    let segments = segments.max(1);
+
+    let mut decals = Vec::new();
+
+    // Add a piece between two arc lengths, centered on a lateral offset
+    let mut add_piece = |start: f32, end: f32, offset: f32| {
+        let (Some(a), Some(b)) = (
+            spline.evaluate(table.length_to_t(start)),
+            spline.evaluate(table.length_to_t(end)),
+        ) else {
+            return;
+        };
+        let length = a.distance(b);
+        if length <= f32::EPSILON {
+            return;
+        }
+        let frame = CoordinateFrame::from_tangent(b - a);
+        decals.push(Transform {
+            translation: (a + b) / 2.0 + frame.transform_profile_point(offset, 0.0),
+            rotation: Quat::from_mat3(&Mat3::from_cols(frame.right, frame.tangent, frame.up)),
+            scale: Vec3::new(MARKING_WIDTH, length, MARKING_DEPTH),
+        });
+    };
+
+    for (offset, style) in lane_boundaries(lanes) {
+        match style {
+            MarkingStyle::Solid => {
+                for i in 0..segments {
+                    let start = total_length * i as f32 / segments as f32;
+                    let end = total_length * (i + 1) as f32 / segments as f32;
+                    add_piece(start, end, offset);
+                }
+            }
+            MarkingStyle::Dashed => {
+                let mut start = 0.0;
+                while start < total_length {
+                    let end = (start + MARKING_DASH_LENGTH).min(total_length);
+                    add_piece(start, end, offset);
+                    start += MARKING_DASH_LENGTH + MARKING_DASH_GAP;
+                }
+            }
+        }
+    }
+
+    decals
+}
+
+/// System to update lane markings when roads or their splines change.
+#[allow(clippy::too_many_arguments)]
+pub fn update_lane_markings(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    marking_texture: Option<Res<LaneMarkingTexture>>,
+    roads: Query<(Entity, &SplineRoad)>,
+    changed_roads: Query<(), Changed<SplineRoad>>,
+    splines: Query<&Spline>,
+    changed_splines: Query<(), Changed<Spline>>,
+    existing_markings: Query<(), With<GeneratedLaneMarkings>>,
+    children_query: Query<&Children>,
+) {
+    let mut texture = marking_texture.map(|t| t.0.clone());
+
+    for (road_entity, road) in &roads {
+        let needs_update = changed_roads.contains(road_entity)
+            || (road.auto_update && changed_splines.contains(road.spline));
+        if !needs_update {
+            continue;
+        }
+
+        // The number of decals depends on the road length; respawn them all
+        if let Ok(children) = children_query.get(road_entity) {
+            for child in children.iter().filter(|child| existing_markings.contains(*child)) {
+                commands.entity(child).despawn();
+            }
+        }
+
+        if !road.lane_markings {
+            continue;
+        }
+        let decals = splines
+            .get(road.spline)
+            .ok()
+            .filter(|spline| spline.is_valid())
+            .map(|spline| lane_marking_decals(spline, &road.lanes, road.segments_per_curve))
+            .unwrap_or_default();
+        if decals.is_empty() {
+            continue;
+        }
+
+        let texture = texture
+            .get_or_insert_with(|| {
+                let handle = images.add(Image::new_fill(
+                    Extent3d {
+                        width: 1,
+                        height: 1,
+                        depth_or_array_layers: 1,
+                    },
+                    TextureDimension::D2,
+                    &[255, 255, 255, 255],
+                    TextureFormat::Rgba8UnormSrgb,
+                    RenderAssetUsages::RENDER_WORLD,
+                ));
+                commands.insert_resource(LaneMarkingTexture(handle.clone()));
+                handle
+            })
+            .clone();
+
+        for transform in decals {
+            let marking = commands
+                .spawn((
+                    ClusteredDecal {
+                        base_color_texture: Some(texture.clone()),
+                        ..default()
+                    },
+                    transform,
+                    Visibility::default(),
+                    GeneratedLaneMarkings { road: road_entity },
+                ))
+                .id();
+            commands.entity(road_entity).add_child(marking);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    #[test]
+    fn test_lane_offsets_centered() {
+        let offsets = lane_offsets(&RoadLane::two_way(2, 3.0));
+        assert_eq!(offsets, vec![-4.5, -1.5, 1.5, 4.5]);
+    }
+
+    #[test]
+    fn test_center_line_is_solid() {
+        let lanes = vec![
+            RoadLane::backward(3.0),
+            RoadLane::forward(3.0),
+            RoadLane::forward(3.0),
+        ];
+        let styles: Vec<MarkingStyle> = lane_boundaries(&lanes)
+            .into_iter()
+            .map(|(_, style)| style)
+            .collect();
+        assert_eq!(
+            styles,
+            vec![
+                MarkingStyle::Solid,
+                MarkingStyle::Solid,
+                MarkingStyle::Dashed,
+                MarkingStyle::Solid,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_marking_decals_lie_on_boundaries() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, -10.0),
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 30.0),
+                Vec3::new(0.0, 0.0, 40.0),
+            ],
+        );
+        let lanes = vec![RoadLane::forward(3.0), RoadLane::forward(3.0)];
+        let decals = lane_marking_decals(&spline, &lanes, 10);
+
+        // Two solid edges in 10 pieces each, and a dash every 9 units of 30
+        assert_eq!(decals.len(), 24);
+        for decal in &decals {
+            let x = decal.translation.x.abs();
+            assert!(x < 0.001 || (x - (3.0 - MARKING_WIDTH / 2.0)).abs() < 0.001);
+            // Lines run along the road and project straight down onto it
+            assert!((decal.rotation * Vec3::Y).dot(Vec3::Z).abs() > 0.999);
+            assert!((decal.rotation * Vec3::Z).dot(Vec3::Y).abs() > 0.999);
+        }
+        let dashes = decals.iter().filter(|d| d.translation.x.abs() < 0.001);
+        assert!(dashes.clone().all(|d| (d.scale.y - MARKING_DASH_LENGTH).abs() < 0.01));
+        assert_eq!(dashes.count(), 4);
+    }
+}
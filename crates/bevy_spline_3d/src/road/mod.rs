@@ -1,8 +1,12 @@
+mod graph;
 mod intersection;
+mod lanes;
 mod mesh_gen;
 mod projection;
 
+pub use graph::*;
 pub use intersection::*;
+pub use lanes::*;
 pub use mesh_gen::*;
 pub use projection::NeedsProjection;
 
@@ -100,15 +104,28 @@ impl Plugin for SplineRoadPlugin {
             .register_type::<RoadIntersection>()
             .register_type::<RoadEnd>()
             .register_type::<RoadConnection>()
+            .register_type::<RoadLane>()
+            .register_type::<LaneDirection>()
+            .init_resource::<RoadGraph>()
+            .add_message::<ExportRoadGraph>()
             .add_systems(
                 Update,
                 (
                     mesh_gen::update_road_meshes,
+                    lanes::update_lane_markings,
                     intersection::update_intersection_meshes,
                     intersection::cleanup_intersection_meshes,
                 ),
             );
 
+        // The graph is built in world space, so wait for transforms
+        app.add_systems(
+            PostUpdate,
+            (graph::update_road_graph, graph::handle_export_road_graph)
+                .chain()
+                .after(TransformSystems::Propagate),
+        );
+
         // Run projection in PostUpdate after transform propagation
         // to ensure GlobalTransform is up to date.
         // Only runs when avian3d physics is available.
@@ -138,6 +155,11 @@ pub struct SplineRoad {
     /// UV tiling factor along the road length.
     /// Higher values = more texture repeats.
     pub uv_tile_length: f32,
+    /// Lanes from left (-X) to right (+X), centered on the spline.
+    /// Empty for roads without lane data.
+    pub lanes: Vec<RoadLane>,
+    /// Whether to generate lane marking decals for `lanes`.
+    pub lane_markings: bool,
 }
 
 impl Default for SplineRoad {
@@ -148,6 +170,8 @@ impl Default for SplineRoad {
             segments_per_curve: 32,
             auto_update: true,
             uv_tile_length: 1.0,
+            lanes: Vec::new(),
+            lane_markings: true,
         }
     }
 }
@@ -173,6 +197,12 @@ impl SplineRoad {
         self.uv_tile_length = tile;
         self
     }
+
+    /// Set the lanes, left to right.
+    pub fn with_lanes(mut self, lanes: Vec<RoadLane>) -> Self {
+        self.lanes = lanes;
+        self
+    }
 }
 
 /// Marker component for the generated road mesh entity.
@@ -0,0 +1,962 @@
+//! Deterministic CPU reference simulation of GPU emitters.
+//!
+//! Mirrors the spawn and update compute shaders step for step: emitters are
+//! packed into the same `GpuEmitterParams` the GPU receives, and the PCG
+//! random sequence matches `common.wgsl`. Every render mode is simulated, so
+//! presets can be checked headless (no GPU, no render app). On devices
+//! without compute shaders, [`crate::render::fallback`] draws these
+//! simulations in place of the GPU pipeline.
+//!
+//! Where WGSL leaves a result undefined (normalizing a zero vector), the
+//! simulator uses zero instead.
+
+use bevy::color::ColorToComponents;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+
+use crate::data::{
+    CollisionShape, EmitterDef, SubEmitterTrigger, VfxParamOverrides, VfxRestart, VfxSystem,
+};
+use crate::field::{emitter_field_paths, VectorField, VfxFieldCache, FIELD_SLOTS};
+use crate::gpu::buffers::*;
+use crate::gpu::prepare::{apply_field_bounds, emitter_seed, pack_emitter_params};
+
+/// A single simulated particle. Mirrors `Particle` in `common.wgsl`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CpuParticle {
+    /// World-space position.
+    pub position: Vec3,
+    /// World-space velocity.
+    pub velocity: Vec3,
+    /// Seconds since spawn.
+    pub age: f32,
+    /// Total lifetime in seconds.
+    pub lifetime: f32,
+    /// Color before alpha-mode encoding (not premultiplied).
+    pub color: LinearRgba,
+    /// Billboard size.
+    pub size: f32,
+    /// Rotation in radians.
+    pub rotation: f32,
+    /// RNG seed the particle was spawned with.
+    pub seed: u32,
+}
+
+impl CpuParticle {
+    /// Whether the particle is still alive.
+    pub fn is_alive(&self) -> bool {
+        self.lifetime > 0.0 && self.age < self.lifetime
+    }
+}
+
+/// Simulation state for one emitter.
+#[derive(Clone, Debug)]
+pub struct CpuEmitterSim {
+    /// Index of this emitter within the parent VfxSystem.
+    pub emitter_index: usize,
+    /// The emitter definition being simulated.
+    pub emitter: EmitterDef,
+    /// Live particles, oldest first.
+    pub particles: Vec<CpuParticle>,
+    params: GpuEmitterParams,
+}
+
+/// Seeded CPU simulation of every enabled emitter in a [`VfxSystem`].
+///
+/// Use it directly for headless tests:
+///
+/// ```ignore
+/// let mut sim = CpuVfxSim::new(&system, 42);
+/// sim.run(2.0, 1.0 / 60.0);
+/// assert!(sim.alive_count() > 0);
+/// ```
+///
+/// or insert it on a `VfxSystem` entity, where `VfxPlugin` steps it every
+/// frame for inspection. The GPU simulation of that entity keeps running and
+/// remains what is drawn, except under the CPU fallback, which inserts one
+/// on every `VfxSystem` and draws it instead.
+#[derive(Component, Clone, Debug, Default)]
+pub struct CpuVfxSim {
+    emitters: Vec<CpuEmitterSim>,
+    elapsed: f32,
+    seed: u64,
+    transform: GlobalTransform,
+    /// Vector fields by asset path, for `VectorField` / `ConformToSdf` modules.
+    fields: HashMap<String, VectorField>,
+}
+
+impl CpuVfxSim {
+    /// Create a simulation of `system` with the given RNG seed.
+    pub fn new(system: &VfxSystem, seed: u64) -> Self {
+        let mut sim = Self {
+            seed,
+            ..default()
+        };
+        sim.set_system(system);
+        sim
+    }
+
+    /// Builder-style `set_transform`.
+    pub fn with_transform(mut self, transform: GlobalTransform) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Set the emitter's world transform.
+    pub fn set_transform(&mut self, transform: GlobalTransform) {
+        self.transform = transform;
+    }
+
+    /// Builder-style `set_field`.
+    pub fn with_field(mut self, path: impl Into<String>, field: VectorField) -> Self {
+        self.set_field(path, field);
+        self
+    }
+
+    /// Provide the vector field loaded from `path`. `VectorField` and
+    /// `ConformToSdf` modules whose field is missing have no effect, like
+    /// on the GPU before the field texture is uploaded.
+    pub fn set_field(&mut self, path: impl Into<String>, field: VectorField) {
+        self.fields.insert(path.into(), field);
+        for i in 0..self.emitters.len() {
+            let params = self.pack(self.emitters[i].emitter_index, &self.emitters[i].emitter);
+            self.emitters[i].params = params;
+        }
+    }
+
+    /// Whether a field has been provided for `path`.
+    pub fn has_field(&self, path: &str) -> bool {
+        self.fields.contains_key(path)
+    }
+
+    fn pack(&self, index: usize, emitter: &EmitterDef) -> GpuEmitterParams {
+        let seed = emitter_seed(self.seed, index);
+        let mut params = pack_emitter_params(emitter, index, &self.transform, 0.0, self.elapsed, seed);
+        let bounds = emitter_fields(&self.fields, emitter).map(|f| f.map(|f| (f.min, f.max)));
+        apply_field_bounds(&mut params, bounds);
+        params
+    }
+
+    /// Replace the simulated system, keeping live particles of emitters that
+    /// still exist (like editing a running effect on the GPU).
+    pub fn set_system(&mut self, system: &VfxSystem) {
+        let mut previous = std::mem::take(&mut self.emitters);
+        for (index, emitter) in system.emitters.iter().enumerate() {
+            if !emitter.enabled {
+                continue;
+            }
+            let mut particles = previous
+                .iter_mut()
+                .find(|e| e.emitter_index == index)
+                .map(|e| std::mem::take(&mut e.particles))
+                .unwrap_or_default();
+            particles.truncate(emitter.capacity as usize);
+
+            let params = self.pack(index, emitter);
+            self.emitters.push(CpuEmitterSim {
+                emitter_index: index,
+                emitter: emitter.clone(),
+                particles,
+                params,
+            });
+        }
+    }
+
+    /// Kill all particles and reset time to zero.
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+        for emitter in &mut self.emitters {
+            emitter.particles.clear();
+        }
+    }
+
+    /// Advance the simulation by one frame: spawn, update, then spawn
+    /// sub-emitter children from this frame's parent events.
+    pub fn step(&mut self, dt: f32) {
+        let transform = self.transform.to_matrix();
+        let mut events = Vec::with_capacity(self.emitters.len());
+        for emitter in &mut self.emitters {
+            let fields = emitter_fields(&self.fields, &emitter.emitter);
+            emitter.params.dt = dt;
+            emitter.params.time = self.elapsed;
+            emitter.params.emitter_transform = transform.to_cols_array_2d();
+
+            let mut emitter_events = Vec::new();
+            let free = (emitter.emitter.capacity as usize).saturating_sub(emitter.particles.len());
+            let count = (emitter.emitter.spawn.spawn_count(dt, self.elapsed) as usize).min(free);
+            for thread_id in 0..count {
+                let seed = (thread_id as u32)
+                    .wrapping_mul(1973)
+                    .wrapping_add((emitter.params.time * 1000.0).to_bits())
+                    .wrapping_add(emitter.params.emitter_seed);
+                let particle = spawn_particle(&emitter.params, seed);
+                emit_sub_events(
+                    &emitter.params,
+                    SubEmitterTrigger::Birth,
+                    &particle,
+                    0.0,
+                    &mut emitter_events,
+                );
+                emitter.particles.push(particle);
+            }
+
+            for particle in &mut emitter.particles {
+                update_particle(&emitter.params, fields, particle, &mut emitter_events);
+            }
+            emitter.particles.retain(CpuParticle::is_alive);
+            events.push(emitter_events);
+        }
+
+        // Sub-spawn pass (matches `sub_spawn` in spawn.wgsl)
+        for emitter_events in &events {
+            for (event_index, event) in emitter_events.iter().enumerate() {
+                let Some(child) = self
+                    .emitters
+                    .iter_mut()
+                    .find(|e| e.emitter_index == event.child)
+                else {
+                    continue;
+                };
+                let origin = transform.w_axis.truncate();
+                for i in 0..event.count {
+                    if child.particles.len() >= child.emitter.capacity as usize {
+                        break;
+                    }
+                    let seed = pcg_hash((event_index as u32).wrapping_mul(4099).wrapping_add(i))
+                        .wrapping_add((child.params.time * 1000.0).to_bits())
+                        .wrapping_add(child.params.emitter_seed);
+                    let mut particle = spawn_particle(&child.params, seed);
+                    particle.position += event.position - origin;
+                    particle.velocity += event.velocity;
+                    if let Some(color) = event.color {
+                        particle.color = color;
+                    }
+                    child.particles.push(particle);
+                }
+            }
+        }
+        self.elapsed += dt;
+    }
+
+    /// Step with a fixed `dt` until `duration` seconds have been simulated.
+    pub fn run(&mut self, duration: f32, dt: f32) {
+        let steps = (duration / dt).round() as usize;
+        for _ in 0..steps {
+            self.step(dt);
+        }
+    }
+
+    /// Simulated time in seconds.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Simulated emitters (disabled emitters are skipped).
+    pub fn emitters(&self) -> &[CpuEmitterSim] {
+        &self.emitters
+    }
+
+    /// Simulated emitter by its index in the VfxSystem.
+    pub fn emitter(&self, emitter_index: usize) -> Option<&CpuEmitterSim> {
+        self.emitters.iter().find(|e| e.emitter_index == emitter_index)
+    }
+
+    /// Total live particles across all emitters.
+    pub fn alive_count(&self) -> usize {
+        self.emitters.iter().map(|e| e.particles.len()).sum()
+    }
+}
+
+/// Fields sampled by `emitter`, by slot (see `emitter_field_paths`).
+fn emitter_fields<'a>(
+    fields: &'a HashMap<String, VectorField>,
+    emitter: &EmitterDef,
+) -> [Option<&'a VectorField>; FIELD_SLOTS] {
+    emitter_field_paths(emitter)
+        .map(|path| path.and_then(|path| fields.get(path)).filter(|field| field.is_valid()))
+}
+
+/// Step `CpuVfxSim` components, resyncing them when the system, its
+/// parameter overrides or its vector fields change.
+pub fn step_cpu_vfx_sims(
+    time: Res<Time>,
+    mut field_events: MessageReader<AssetEvent<VectorField>>,
+    field_cache: Res<VfxFieldCache>,
+    field_assets: Res<Assets<VectorField>>,
+    mut query: Query<(
+        Ref<VfxSystem>,
+        Option<Ref<VfxParamOverrides>>,
+        &GlobalTransform,
+        Has<VfxRestart>,
+        &mut CpuVfxSim,
+    )>,
+) {
+    let dt = time.delta_secs();
+    let changed_fields: HashSet<AssetId<VectorField>> = field_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (system, overrides, transform, restart, mut sim) in &mut query {
+        let overrides_changed = overrides.as_ref().is_some_and(|o| o.is_changed());
+        if sim.is_added() || system.is_changed() || overrides_changed {
+            sim.set_system(&system.resolved(overrides.as_deref()));
+        }
+        for module in system.emitters.iter().flat_map(|e| &e.update) {
+            let Some(path) = module.field_path() else {
+                continue;
+            };
+            let changed = field_cache
+                .handles
+                .get(path)
+                .is_some_and(|handle| changed_fields.contains(&handle.id()));
+            if !changed && sim.has_field(path) {
+                continue;
+            }
+            if let Some(field) = field_cache.field(path, &field_assets) {
+                sim.set_field(path, field.clone());
+            }
+        }
+        if restart {
+            sim.restart();
+        }
+        sim.set_transform(*transform);
+        sim.step(dt);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// RNG (matches common.wgsl)
+// ---------------------------------------------------------------------------
+
+fn pcg_hash(input: u32) -> u32 {
+    let state = input.wrapping_mul(747796405).wrapping_add(2891336453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+    (word >> 22) ^ word
+}
+
+fn rand_float(seed: &mut u32) -> f32 {
+    *seed = pcg_hash(*seed);
+    *seed as f32 / 4294967296.0
+}
+
+fn rand_range(seed: &mut u32, min: f32, max: f32) -> f32 {
+    min + rand_float(seed) * (max - min)
+}
+
+fn rand_unit_sphere(seed: &mut u32) -> Vec3 {
+    let theta = rand_float(seed) * std::f32::consts::TAU;
+    let z = rand_range(seed, -1.0, 1.0);
+    let r = (1.0 - z * z).sqrt();
+    Vec3::new(r * theta.cos(), r * theta.sin(), z)
+}
+
+fn sample_curve(keys: &[[f32; 4]], t: f32) -> f32 {
+    let Some(last) = keys.last() else {
+        return 1.0;
+    };
+    if keys.len() == 1 {
+        return keys[0][1];
+    }
+    let t = t.clamp(0.0, 1.0);
+    if t <= keys[0][0] {
+        return keys[0][1];
+    }
+    for pair in keys.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if t >= a[0] && t <= b[0] {
+            let span = b[0] - a[0];
+            if span < 0.0001 {
+                return a[1];
+            }
+            return a[1] + (b[1] - a[1]) * (t - a[0]) / span;
+        }
+    }
+    last[1]
+}
+
+fn sample_gradient(keys: &[[f32; 4]], count: usize, t: f32) -> LinearRgba {
+    let key = |i: usize| {
+        let trgb = keys[i * 2];
+        (trgb[0], Vec4::new(trgb[1], trgb[2], trgb[3], keys[i * 2 + 1][0]))
+    };
+    let color = if count == 1 {
+        key(0).1
+    } else {
+        (0..count - 1)
+            .find_map(|i| {
+                let ((ta, a), (tb, b)) = (key(i), key(i + 1));
+                (t >= ta && t <= tb).then(|| {
+                    let span = tb - ta;
+                    let frac = if span > 0.0001 { (t - ta) / span } else { 0.0 };
+                    a.lerp(b, frac)
+                })
+            })
+            .unwrap_or_else(|| key(count - 1).1)
+    };
+    LinearRgba::from_vec4(color)
+}
+
+// ---------------------------------------------------------------------------
+// Sub-emitter events (matches emit_sub_events in common.wgsl)
+// ---------------------------------------------------------------------------
+
+/// A parent particle event queued for a child emitter.
+struct SubEmitterEvent {
+    position: Vec3,
+    child: usize,
+    velocity: Vec3,
+    count: u32,
+    color: Option<LinearRgba>,
+}
+
+fn emit_sub_events(
+    params: &GpuEmitterParams,
+    trigger: SubEmitterTrigger,
+    p: &CpuParticle,
+    prev_age: f32,
+    events: &mut Vec<SubEmitterEvent>,
+) {
+    let count = (params.sub_emitter_count as usize).min(MAX_SUB_EMITTERS);
+    for i in 0..count {
+        let link = params.sub_emitters[i * 2];
+        let inherit = params.sub_emitters[i * 2 + 1];
+        if link[0] as usize != trigger.variant_index() {
+            continue;
+        }
+        if matches!(trigger, SubEmitterTrigger::Interval(_))
+            && !SubEmitterTrigger::interval_crossed(link[3], prev_age, p.age)
+        {
+            continue;
+        }
+        if events.len() >= MAX_SUB_EMITTER_EVENTS as usize {
+            return;
+        }
+        events.push(SubEmitterEvent {
+            position: p.position,
+            child: link[1] as usize,
+            velocity: p.velocity * inherit[0],
+            count: link[2] as u32,
+            color: (inherit[1] != 0.0).then_some(p.color),
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Spawn pass (matches spawn.wgsl)
+// ---------------------------------------------------------------------------
+
+fn spawn_particle(params: &GpuEmitterParams, spawn_seed: u32) -> CpuParticle {
+    let mut seed = spawn_seed;
+    let particle_seed = seed;
+
+    let lifetime = rand_range(&mut seed, params.lifetime_min, params.lifetime_max);
+
+    let param0 = Vec3::from_array(params.shape_param0);
+    let param1 = Vec3::from_array(params.shape_param1);
+    let mut position = match params.shape_type {
+        // Point
+        0 => param0,
+        // Sphere
+        1 => {
+            let dir = rand_unit_sphere(&mut seed);
+            let r = rand_range(&mut seed, params.shape_radius_min, params.shape_radius_max);
+            param0 + dir * r
+        }
+        // Box
+        2 => {
+            let x = rand_range(&mut seed, -param1.x, param1.x);
+            let y = rand_range(&mut seed, -param1.y, param1.y);
+            let z = rand_range(&mut seed, -param1.z, param1.z);
+            param0 + Vec3::new(x, y, z)
+        }
+        // Cone
+        3 => {
+            let (radius, height) = (param0.y, param0.z);
+            let theta = rand_float(&mut seed) * std::f32::consts::TAU;
+            let h = rand_float(&mut seed) * height;
+            let r = radius * (1.0 - h / height.max(0.001)) * rand_float(&mut seed);
+            Vec3::new(r * theta.cos(), h, r * theta.sin())
+        }
+        // Circle
+        4 => {
+            let axis = param1.normalize_or_zero();
+            let r = rand_range(&mut seed, params.shape_radius_min, params.shape_radius_max);
+            let theta = rand_float(&mut seed) * std::f32::consts::TAU;
+            let tangent = if axis.y.abs() < 0.99 {
+                axis.cross(Vec3::Y).normalize_or_zero()
+            } else {
+                axis.cross(Vec3::X).normalize_or_zero()
+            };
+            let bitangent = axis.cross(tangent);
+            param0 + (tangent * theta.cos() + bitangent * theta.sin()) * r
+        }
+        // Edge
+        5 => param0.lerp(param1, rand_float(&mut seed)),
+        _ => Vec3::ZERO,
+    };
+
+    let velocity_param = Vec3::from_array(params.velocity_param0);
+    let mut velocity = match params.velocity_mode {
+        // Radial
+        0 => {
+            let dir = (position - velocity_param).normalize_or_zero();
+            dir * rand_range(&mut seed, params.velocity_speed_min, params.velocity_speed_max)
+        }
+        // Directional
+        1 => {
+            let dir = velocity_param.normalize_or_zero();
+            dir * rand_range(&mut seed, params.velocity_speed_min, params.velocity_speed_max)
+        }
+        // Tangent
+        2 => {
+            let axis = velocity_param.normalize_or_zero();
+            let dir = axis.cross(position).normalize_or_zero();
+            dir * rand_range(&mut seed, params.velocity_speed_min, params.velocity_speed_max)
+        }
+        // Cone
+        3 => {
+            let dir = velocity_param.normalize_or_zero();
+            let rand_dir = rand_unit_sphere(&mut seed);
+            let blended = dir
+                .lerp(rand_dir, params.velocity_cone_angle / 3.14159)
+                .normalize_or_zero();
+            blended * rand_range(&mut seed, params.velocity_speed_min, params.velocity_speed_max)
+        }
+        // Random
+        4 => {
+            let dir = rand_unit_sphere(&mut seed);
+            dir * rand_range(&mut seed, params.velocity_speed_min, params.velocity_speed_max)
+        }
+        _ => Vec3::ZERO,
+    };
+
+    let transform = Mat4::from_cols_array_2d(&params.emitter_transform);
+    if params.sim_space == 1 {
+        // Local: transform position and velocity into world space
+        position = transform.transform_point3(position);
+        velocity = transform.transform_vector3(velocity);
+    } else {
+        // World: offset spawn position by emitter location
+        position += transform.w_axis.truncate();
+    }
+
+    let color = LinearRgba::from_f32_array(params.init_color);
+    let size = rand_range(&mut seed, params.init_size_min, params.init_size_max);
+    let rotation = rand_range(&mut seed, params.init_rotation_min, params.init_rotation_max);
+
+    CpuParticle {
+        position,
+        velocity,
+        age: 0.0,
+        lifetime,
+        color,
+        size,
+        rotation,
+        seed: particle_seed,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Update pass (matches update.wgsl)
+// ---------------------------------------------------------------------------
+
+fn update_particle(
+    params: &GpuEmitterParams,
+    fields: [Option<&VectorField>; FIELD_SLOTS],
+    p: &mut CpuParticle,
+    events: &mut Vec<SubEmitterEvent>,
+) {
+    if !p.is_alive() {
+        return;
+    }
+
+    let dt = params.dt;
+    p.age += dt;
+    if p.age >= p.lifetime {
+        emit_sub_events(params, SubEmitterTrigger::Death, p, 0.0, events);
+        return;
+    }
+
+    let normalized_life = p.age / p.lifetime;
+    let flags = params.flags;
+    let transform = Mat4::from_cols_array_2d(&params.emitter_transform);
+    let local = params.sim_space == 1;
+
+    // Position-relative modifiers are relative to the emitter in Local mode
+    let ref_origin = if local {
+        transform.w_axis.truncate()
+    } else {
+        Vec3::ZERO
+    };
+    let local_axis = |axis: [f32; 3]| {
+        let axis = Vec3::from_array(axis).normalize_or_zero();
+        if local {
+            transform.transform_vector3(axis).normalize_or_zero()
+        } else {
+            axis
+        }
+    };
+
+    if flags & FLAG_GRAVITY != 0 {
+        p.velocity += Vec3::from_array(params.gravity) * dt;
+    }
+
+    if flags & FLAG_CONSTANT_FORCE != 0 {
+        p.velocity += Vec3::from_array(params.constant_force) * dt;
+    }
+
+    if flags & FLAG_DRAG != 0 {
+        let speed = p.velocity.length();
+        if speed > 0.001 {
+            let decel = (params.drag * speed * dt).min(speed);
+            p.velocity -= p.velocity / speed * decel;
+        }
+    }
+
+    if flags & FLAG_RADIAL_ACCEL != 0 {
+        let to_particle = p.position - (Vec3::from_array(params.radial_accel_origin) + ref_origin);
+        let dist = to_particle.length();
+        if dist > 0.001 {
+            p.velocity += to_particle / dist * params.radial_accel_value * dt;
+        }
+    }
+
+    if flags & FLAG_TANGENT_ACCEL != 0 {
+        let to_particle = p.position - (Vec3::from_array(params.tangent_accel_origin) + ref_origin);
+        let tangent = local_axis(params.tangent_accel_axis).cross(to_particle);
+        let tangent_len = tangent.length();
+        if tangent_len > 0.001 {
+            p.velocity += tangent / tangent_len * params.tangent_accel_value * dt;
+        }
+    }
+
+    if flags & FLAG_ORBIT != 0 {
+        let axis = local_axis(params.orbit_axis);
+        let angle = params.orbit_speed * dt;
+        let (sin_a, cos_a) = angle.sin_cos();
+        // Rodrigues' rotation around ref_origin
+        let rel = p.position - ref_origin;
+        let rotated = rel * cos_a + axis.cross(rel) * sin_a + axis * axis.dot(rel) * (1.0 - cos_a);
+        p.position = ref_origin + rotated;
+        if params.orbit_radius_decay > 0.0 && (p.position - ref_origin).length() > 0.001 {
+            p.position = ref_origin + (p.position - ref_origin) * (1.0 - params.orbit_radius_decay * dt);
+        }
+    }
+
+    if flags & FLAG_ATTRACT != 0 {
+        let to_target = Vec3::from_array(params.attract_target) + ref_origin - p.position;
+        let dist = to_target.length();
+        if dist > 0.001 {
+            let strength = params.attract_strength / dist.powf(params.attract_falloff).max(0.001);
+            p.velocity += to_target / dist * strength * dt;
+        }
+    }
+
+    if flags & FLAG_NOISE != 0 {
+        let mut seed = p.seed.wrapping_add((params.time * 100.0).to_bits());
+        let x = rand_float(&mut seed) * 2.0 - 1.0;
+        let y = rand_float(&mut seed) * 2.0 - 1.0;
+        let z = rand_float(&mut seed) * 2.0 - 1.0;
+        p.velocity += Vec3::new(x, y, z) * params.noise_strength * dt;
+    }
+
+    if let Some(field) = fields[0].filter(|_| flags & FLAG_VECTOR_FIELD != 0) {
+        p.velocity = field.advect(
+            p.position - ref_origin,
+            p.velocity,
+            params.field_strength,
+            params.field_tightness,
+            dt,
+        );
+    }
+
+    if let Some(field) = fields[1].filter(|_| flags & FLAG_CONFORM_SDF != 0) {
+        p.velocity = field.conform(
+            p.position - ref_origin,
+            p.velocity,
+            params.sdf_surface,
+            params.sdf_attraction,
+            params.sdf_damping,
+            dt,
+        );
+    }
+
+    p.position += p.velocity * dt;
+
+    if flags & FLAG_COLLIDE != 0 {
+        let count = (params.collision_count as usize).min(MAX_COLLISION_SHAPES);
+        for i in 0..count {
+            let shape = CollisionShape::from_packed([
+                params.collision_shapes[i * 2],
+                params.collision_shapes[i * 2 + 1],
+            ]);
+            let (distance, n) = shape.signed_distance(p.position - ref_origin);
+            let penetration = distance - params.collision_radius;
+            if penetration >= 0.0 {
+                continue;
+            }
+            if params.collision_kill != 0 {
+                emit_sub_events(params, SubEmitterTrigger::Collision, p, 0.0, events);
+                emit_sub_events(params, SubEmitterTrigger::Death, p, 0.0, events);
+                p.age = p.lifetime;
+                return;
+            }
+            p.position -= n * penetration;
+            let vn = p.velocity.dot(n);
+            if vn < 0.0 {
+                emit_sub_events(params, SubEmitterTrigger::Collision, p, 0.0, events);
+                let tangential = p.velocity - n * vn;
+                p.velocity = tangential * (1.0 - params.collision_friction)
+                    - n * vn * params.collision_bounce;
+            }
+        }
+    }
+
+    if flags & FLAG_KILL_ZONE != 0 {
+        let center = Vec3::from_array(params.kill_zone_center) + ref_origin;
+        let extent = Vec3::from_array(params.kill_zone_param);
+        let inside = match params.kill_zone_type {
+            1 => (p.position - center).length() < extent.x,
+            2 => (p.position - center).abs().cmplt(extent).all(),
+            _ => false,
+        };
+        if inside != (params.kill_zone_invert != 0) {
+            emit_sub_events(params, SubEmitterTrigger::Death, p, 0.0, events);
+            p.age = p.lifetime;
+            return;
+        }
+    }
+
+    emit_sub_events(params, SubEmitterTrigger::Interval(0.0), p, p.age - dt, events);
+
+    if flags & FLAG_SIZE_BY_LIFE != 0 {
+        let count = (params.size_curve_count as usize).min(MAX_CURVE_KEYS);
+        p.size = sample_curve(&params.size_curve[..count], normalized_life);
+    }
+
+    if flags & FLAG_SIZE_BY_SPEED != 0 {
+        let speed_range = (params.size_by_speed_max_speed - params.size_by_speed_min_speed).max(0.001);
+        let t = ((p.velocity.length() - params.size_by_speed_min_speed) / speed_range).clamp(0.0, 1.0);
+        let (min_size, max_size) = (params.size_by_speed_min_size, params.size_by_speed_max_size);
+        p.size = min_size + (max_size - min_size) * t;
+    }
+
+    if flags & FLAG_ROTATE_BY_VELOCITY != 0 && Vec2::new(p.velocity.x, p.velocity.z).length() > 0.001 {
+        p.rotation = p.velocity.x.atan2(p.velocity.z);
+    }
+
+    if flags & FLAG_COLOR_BY_LIFE != 0 {
+        let count = (params.color_gradient_count as usize).min(MAX_GRADIENT_KEYS);
+        if count > 0 {
+            p.color = sample_gradient(&params.color_gradient, count, normalized_life);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::*;
+
+    fn falling_emitter() -> VfxSystem {
+        VfxSystem {
+            emitters: vec![EmitterDef {
+                spawn: SpawnModule::Once { offset: 0.0, count: 10 },
+                init: vec![
+                    InitModule::SetLifetime(ScalarRange::Constant(1.0)),
+                    InitModule::SetPosition(ShapeEmitter::Point(Vec3::ZERO)),
+                    InitModule::SetVelocity(VelocityMode::Directional {
+                        direction: Vec3::X,
+                        speed: ScalarRange::Constant(2.0),
+                    }),
+                ],
+                update: vec![UpdateModule::Gravity(Vec3::new(0.0, -10.0, 0.0))],
+                sim_space: SimSpace::World,
+                ..default()
+            }],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn test_gravity_integration() {
+        let dt = 0.01;
+        let mut sim = CpuVfxSim::new(&falling_emitter(), 1);
+        sim.run(0.5, dt);
+
+        let particles = &sim.emitter(0).unwrap().particles;
+        assert_eq!(particles.len(), 10);
+        for p in particles {
+            assert!((p.velocity.y + 5.0).abs() < 1e-3);
+            assert!((p.position.x - 1.0).abs() < 1e-3);
+            // Semi-implicit Euler falls slightly further than the analytic 1.25
+            assert!(p.position.y < -1.2 && p.position.y > -1.35);
+        }
+    }
+
+    #[test]
+    fn test_particles_expire() {
+        let mut sim = CpuVfxSim::new(&falling_emitter(), 1);
+        sim.run(0.9, 0.1);
+        assert_eq!(sim.alive_count(), 10);
+        sim.run(0.2, 0.1);
+        assert_eq!(sim.alive_count(), 0);
+    }
+
+    #[test]
+    fn test_world_space_spawns_at_emitter() {
+        let offset = Vec3::new(5.0, 0.0, -3.0);
+        let mut sim = CpuVfxSim::new(&falling_emitter(), 1)
+            .with_transform(GlobalTransform::from_translation(offset));
+        sim.step(0.01);
+
+        let particles = &sim.emitter(0).unwrap().particles;
+        assert_eq!(particles.len(), 10);
+        for p in particles {
+            // One step of velocity (2, -0.1, 0) after spawning at the emitter
+            assert!((p.position - (offset + Vec3::new(0.02, -0.001, 0.0))).length() < 1e-4);
+        }
+    }
+
+    fn with_ground(kill_on_hit: bool) -> VfxSystem {
+        let mut system = falling_emitter();
+        system.emitters[0].update.push(UpdateModule::Collide {
+            shapes: vec![CollisionShape::Plane {
+                point: Vec3::new(0.0, -0.5, 0.0),
+                normal: Vec3::Y,
+            }],
+            scene_colliders: false,
+            radius: 0.05,
+            bounce: 0.0,
+            friction: 0.0,
+            kill_on_hit,
+        });
+        system
+    }
+
+    #[test]
+    fn test_collide_rests_on_plane() {
+        let mut sim = CpuVfxSim::new(&with_ground(false), 1);
+        sim.run(0.9, 0.01);
+
+        let particles = &sim.emitter(0).unwrap().particles;
+        assert_eq!(particles.len(), 10);
+        for p in particles {
+            assert!((p.position.y + 0.45).abs() < 1e-3);
+            // Sliding without friction keeps the horizontal speed
+            assert!((p.velocity.x - 2.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_collide_kill_on_hit() {
+        let mut sim = CpuVfxSim::new(&with_ground(true), 1);
+        sim.run(0.9, 0.01);
+        assert_eq!(sim.alive_count(), 0);
+    }
+
+    fn with_death_burst() -> VfxSystem {
+        let mut system = falling_emitter();
+        let parent = &mut system.emitters[0];
+        // Dies on the tenth 0.1s step, away from float rounding at 1.0
+        parent.init[0] = InitModule::SetLifetime(ScalarRange::Constant(0.95));
+        parent.init.push(InitModule::SetColor(ColorSource::Constant(LinearRgba::RED)));
+        parent.sub_emitters.push(SubEmitter {
+            emitter: 1,
+            trigger: SubEmitterTrigger::Death,
+            count: 3,
+            inherit_velocity: 1.0,
+            inherit_color: true,
+        });
+        system.emitters.push(EmitterDef {
+            name: "Burst".to_string(),
+            spawn: SpawnModule::Rate(0.0),
+            init: vec![InitModule::SetLifetime(ScalarRange::Constant(5.0))],
+            update: Vec::new(),
+            sim_space: SimSpace::World,
+            ..default()
+        });
+        system
+    }
+
+    #[test]
+    fn test_sub_emitter_on_death() {
+        let mut sim = CpuVfxSim::new(&with_death_burst(), 1);
+        sim.run(0.9, 0.1);
+        assert!(sim.emitter(1).unwrap().particles.is_empty());
+
+        sim.step(0.1);
+        assert_eq!(sim.emitter(0).unwrap().particles.len(), 0);
+        let children = &sim.emitter(1).unwrap().particles;
+        assert_eq!(children.len(), 30);
+        for p in children {
+            // Spawned at the parent's last position with its velocity and color
+            assert!((p.position.x - 1.8).abs() < 1e-3);
+            assert!((p.velocity.x - 2.0).abs() < 1e-3);
+            assert_eq!(p.color, LinearRgba::RED);
+        }
+    }
+
+    #[test]
+    fn test_vector_field_drives_velocity() {
+        let mut system = falling_emitter();
+        system.emitters[0].update = vec![UpdateModule::VectorField {
+            path: "wind.vfield".to_string(),
+            strength: 2.0,
+            tightness: 1.0,
+        }];
+
+        // The module does nothing until its field is loaded
+        let mut sim = CpuVfxSim::new(&system, 1);
+        sim.step(0.1);
+        for p in &sim.emitter(0).unwrap().particles {
+            assert_eq!(p.velocity, Vec3::new(2.0, 0.0, 0.0));
+        }
+
+        let mut wind = VectorField::new(Vec3::splat(-10.0), Vec3::splat(10.0), UVec3::splat(2));
+        wind.vectors.fill(Vec3::Z);
+        sim.set_field("wind.vfield", wind);
+        sim.step(0.1);
+        for p in &sim.emitter(0).unwrap().particles {
+            assert!(p.velocity.distance(Vec3::new(0.0, 0.0, 2.0)) < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_conform_to_sdf_settles_on_surface() {
+        let mut system = falling_emitter();
+        let emitter = &mut system.emitters[0];
+        emitter.init = vec![
+            InitModule::SetLifetime(ScalarRange::Constant(5.0)),
+            InitModule::SetPosition(ShapeEmitter::Point(Vec3::new(0.0, 1.0, 0.0))),
+        ];
+        emitter.update = vec![UpdateModule::ConformToSdf {
+            path: "ground.vfield".to_string(),
+            surface: 0.0,
+            attraction: 20.0,
+            damping: 5.0,
+        }];
+        // Ground slab whose top face is y = 0
+        let ground = VectorField::bake_from_boxes(
+            Vec3::splat(-2.0),
+            Vec3::splat(2.0),
+            UVec3::splat(17),
+            &[(Vec3::new(-5.0, -5.0, -5.0), Vec3::new(5.0, 0.0, 5.0))],
+            Vec3::ZERO,
+            1.0,
+        );
+
+        let mut sim = CpuVfxSim::new(&system, 1).with_field("ground.vfield", ground);
+        sim.run(3.0, 0.01);
+
+        let particles = &sim.emitter(0).unwrap().particles;
+        assert_eq!(particles.len(), 10);
+        for p in particles {
+            assert!(p.position.y.abs() < 0.02, "settled at {}", p.position.y);
+        }
+    }
+}
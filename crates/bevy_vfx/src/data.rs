@@ -4,6 +4,9 @@
 //! The GPU compute pipeline reads these definitions and uploads packed parameter
 //! buffers — the CPU never touches individual particle data.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -20,7 +23,8 @@ use crate::curve::{Curve, Gradient};
 pub struct VfxSystem {
     /// Ordered list of emitter definitions.
     pub emitters: Vec<EmitterDef>,
-    /// Exposed parameters (future: bindable from game code).
+    /// Exposed parameters with their default values. Emitters reference
+    /// them by name through `EmitterDef::bindings`.
     pub params: Vec<VfxParam>,
     /// Total duration in seconds. 0.0 = infinite.
     pub duration: f32,
@@ -39,6 +43,63 @@ impl Default for VfxSystem {
     }
 }
 
+impl VfxSystem {
+    /// Default value of a named parameter.
+    pub fn param(&self, name: &str) -> Option<&VfxParamValue> {
+        self.params.iter().find(|p| p.name == name).map(|p| &p.value)
+    }
+
+    /// Insert an emitter, keeping sub-emitter links pointed at the same emitters.
+    pub fn insert_emitter(&mut self, index: usize, emitter: EmitterDef) {
+        self.emitters.insert(index, emitter);
+        for link in self.emitters.iter_mut().flat_map(|e| &mut e.sub_emitters) {
+            if link.emitter >= index {
+                link.emitter += 1;
+            }
+        }
+    }
+
+    /// Remove an emitter, dropping sub-emitter links to it and re-pointing
+    /// links to emitters after it.
+    pub fn remove_emitter(&mut self, index: usize) -> EmitterDef {
+        let removed = self.emitters.remove(index);
+        for emitter in &mut self.emitters {
+            emitter.sub_emitters.retain(|link| link.emitter != index);
+            for link in &mut emitter.sub_emitters {
+                if link.emitter > index {
+                    link.emitter -= 1;
+                }
+            }
+        }
+        removed
+    }
+
+    /// The system with every emitter binding applied, using values from
+    /// `overrides` first and the defaults in `params` otherwise.
+    ///
+    /// Borrows `self` unchanged when no emitter has bindings.
+    pub fn resolved(&self, overrides: Option<&VfxParamOverrides>) -> Cow<'_, VfxSystem> {
+        if self.emitters.iter().all(|e| e.bindings.is_empty()) {
+            return Cow::Borrowed(self);
+        }
+
+        let mut system = self.clone();
+        for emitter in &mut system.emitters {
+            let bindings = std::mem::take(&mut emitter.bindings);
+            for binding in &bindings {
+                let value = overrides
+                    .and_then(|o| o.get(&binding.param))
+                    .or_else(|| self.param(&binding.param));
+                if let Some(value) = value {
+                    binding.target.apply(emitter, value);
+                }
+            }
+            emitter.bindings = bindings;
+        }
+        Cow::Owned(system)
+    }
+}
+
 /// Per-system start time (seconds since app start). Auto-inserted when missing.
 /// Used to compute per-system elapsed time for burst offsets, Once mode, etc.
 #[derive(Component, Clone, Copy, Debug)]
@@ -74,6 +135,14 @@ pub struct EmitterDef {
     pub sim_space: SimSpace,
     /// Alpha / blending mode.
     pub alpha_mode: VfxAlphaMode,
+    /// Module fields driven by named system parameters.
+    #[serde(default)]
+    #[reflect(default)]
+    pub bindings: Vec<ParamBinding>,
+    /// Other emitters in the system spawned from this emitter's particles.
+    #[serde(default)]
+    #[reflect(default)]
+    pub sub_emitters: Vec<SubEmitter>,
 }
 
 impl Default for EmitterDef {
@@ -98,6 +167,8 @@ impl Default for EmitterDef {
             render: RenderModule::Billboard(BillboardConfig::default()),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Blend,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }
     }
 }
@@ -258,6 +329,66 @@ impl SpawnModule {
             Self::Distance { .. } => "Distance",
         }
     }
+
+    /// Number of particles to spawn this frame, given the frame delta and the
+    /// system's local elapsed time. Shared by the GPU prepare pass and the CPU
+    /// reference simulator.
+    pub fn spawn_count(&self, dt: f32, elapsed: f32) -> u32 {
+        match self {
+            Self::Rate(rate) => {
+                let exact = rate * dt;
+                let base = exact as u32;
+                let frac = exact - base as f32;
+                if (elapsed * 1000.0).fract() < frac {
+                    base + 1
+                } else {
+                    base
+                }
+            }
+            Self::Burst {
+                count,
+                interval,
+                max_cycles,
+                offset,
+            } => {
+                if *interval <= 0.0 {
+                    return 0;
+                }
+                let local = elapsed - offset;
+                if local < 0.0 {
+                    return 0;
+                }
+                // Shift so first burst fires at local=0 (i.e. elapsed=offset),
+                // then every interval after that
+                let adjusted = local + *interval;
+                let cycle = (adjusted / interval) as u32;
+                let prev_adjusted = adjusted - dt;
+                let prev_cycle =
+                    if prev_adjusted <= 0.0 { 0 } else { (prev_adjusted / interval) as u32 };
+                if cycle > prev_cycle {
+                    if let Some(max) = max_cycles {
+                        if cycle > *max {
+                            return 0;
+                        }
+                    }
+                    *count
+                } else {
+                    0
+                }
+            }
+            Self::Once { count, offset } => {
+                // Fire only on the frame whose (elapsed - dt, elapsed] window
+                // holds the offset. Consecutive windows don't overlap, so the
+                // burst spawns exactly once, like CPU mesh emitters.
+                if elapsed >= *offset && elapsed - dt < *offset {
+                    *count
+                } else {
+                    0
+                }
+            }
+            Self::Distance { .. } => 0,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -563,6 +694,45 @@ pub enum UpdateModule {
     },
     /// Emissive color over normalized lifetime (mesh particles only).
     EmissiveOverLife(Gradient),
+    /// Collide against analytic shapes and, optionally, nearby static scene
+    /// colliders. Physics-driven mesh particles collide through Avian instead.
+    Collide {
+        shapes: Vec<CollisionShape>,
+        /// Also collide against the bounding boxes of the nearest static
+        /// colliders in the scene (GPU emitters only).
+        scene_colliders: bool,
+        /// Particle collision radius.
+        radius: f32,
+        /// Fraction of normal velocity kept after a bounce (0 = no bounce).
+        bounce: f32,
+        /// Fraction of tangential velocity lost on contact.
+        friction: f32,
+        /// Kill particles on their first contact instead of bouncing.
+        kill_on_hit: bool,
+    },
+    /// Follow a baked [`VectorField`](crate::field::VectorField) asset.
+    /// Field bounds are relative to the emitter in Local sim space.
+    VectorField {
+        /// Asset path of the field (`.vfield` or `.vfield.ron`).
+        path: String,
+        /// Multiplier on the sampled flow vectors.
+        strength: f32,
+        /// 0 = flow is applied as an acceleration, 1 = particles move
+        /// exactly with the flow.
+        tightness: f32,
+    },
+    /// Pull particles onto a level set of a field's signed distances and
+    /// damp their motion across it, so they slide along the surface.
+    ConformToSdf {
+        /// Asset path of the field (must contain distances).
+        path: String,
+        /// Signed distance particles settle at (0 = on the surface).
+        surface: f32,
+        /// Acceleration toward the surface.
+        attraction: f32,
+        /// How quickly velocity along the surface normal is removed.
+        damping: f32,
+    },
 }
 
 impl UpdateModule {
@@ -586,6 +756,9 @@ impl UpdateModule {
             Self::Scale3dByLife { .. } => "Scale 3D Over Life",
             Self::OffsetByLife { .. } => "Offset Over Life",
             Self::EmissiveOverLife(_) => "Emissive Over Life",
+            Self::Collide { .. } => "Collide",
+            Self::VectorField { .. } => "Vector Field",
+            Self::ConformToSdf { .. } => "Conform To SDF",
         }
     }
 
@@ -651,7 +824,36 @@ impl UpdateModule {
             z: Curve::constant(0.0),
         }),
         ("Emissive Over Life", || Self::EmissiveOverLife(Gradient::constant(LinearRgba::BLACK))),
+        ("Collide", || Self::Collide {
+            shapes: vec![CollisionShape::default()],
+            scene_colliders: false,
+            radius: 0.05,
+            bounce: 0.5,
+            friction: 0.1,
+            kill_on_hit: false,
+        }),
+        ("Vector Field", || Self::VectorField {
+            path: String::new(),
+            strength: 1.0,
+            tightness: 0.0,
+        }),
+        ("Conform To SDF", || Self::ConformToSdf {
+            path: String::new(),
+            surface: 0.0,
+            attraction: 5.0,
+            damping: 2.0,
+        }),
     ];
+
+    /// Asset path of the vector field this module samples, if any.
+    pub fn field_path(&self) -> Option<&str> {
+        match self {
+            Self::VectorField { path, .. } | Self::ConformToSdf { path, .. } => {
+                (!path.is_empty()).then_some(path.as_str())
+            }
+            _ => None,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -665,6 +867,239 @@ pub enum KillShape {
     Box { center: Vec3, half_extents: Vec3 },
 }
 
+/// Solid shape used by `UpdateModule::Collide`.
+///
+/// Positions are relative to the emitter in Local sim space, like kill zones.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Reflect)]
+pub enum CollisionShape {
+    /// Infinite floor: particles stay on the side `normal` points to.
+    Plane { point: Vec3, normal: Vec3 },
+    Sphere { center: Vec3, radius: f32 },
+    Box { center: Vec3, half_extents: Vec3 },
+}
+
+impl Default for CollisionShape {
+    fn default() -> Self {
+        Self::Plane {
+            point: Vec3::ZERO,
+            normal: Vec3::Y,
+        }
+    }
+}
+
+impl CollisionShape {
+    pub const VARIANT_LABELS: &[&str] = &["Plane", "Sphere", "Box"];
+
+    pub fn variant_index(&self) -> usize {
+        match self {
+            Self::Plane { .. } => 0,
+            Self::Sphere { .. } => 1,
+            Self::Box { .. } => 2,
+        }
+    }
+
+    pub fn from_variant_index(idx: usize) -> Self {
+        match idx {
+            1 => Self::Sphere {
+                center: Vec3::ZERO,
+                radius: 1.0,
+            },
+            2 => Self::Box {
+                center: Vec3::ZERO,
+                half_extents: Vec3::splat(0.5),
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Signed distance from `point` to the surface (negative inside) and the
+    /// outward surface normal. Must match `collision_sdf` in `common.wgsl`.
+    pub fn signed_distance(&self, point: Vec3) -> (f32, Vec3) {
+        match self {
+            Self::Plane { point: origin, normal } => {
+                let n = normal.normalize_or(Vec3::Y);
+                ((point - *origin).dot(n), n)
+            }
+            Self::Sphere { center, radius } => {
+                let rel = point - *center;
+                let len = rel.length();
+                let n = if len > 0.0001 { rel / len } else { Vec3::Y };
+                (len - radius, n)
+            }
+            Self::Box {
+                center,
+                half_extents,
+            } => {
+                let rel = point - *center;
+                let q = rel.abs() - *half_extents;
+                if q.cmpgt(Vec3::ZERO).any() {
+                    let outside = q.max(Vec3::ZERO);
+                    (outside.length(), (outside * rel.signum()).normalize_or(Vec3::Y))
+                } else {
+                    // Inside: push out through the nearest face
+                    let sign = |v: f32| if v < 0.0 { -1.0 } else { 1.0 };
+                    let n = if q.x >= q.y && q.x >= q.z {
+                        Vec3::X * sign(rel.x)
+                    } else if q.y >= q.z {
+                        Vec3::Y * sign(rel.y)
+                    } else {
+                        Vec3::Z * sign(rel.z)
+                    };
+                    (q.max_element(), n)
+                }
+            }
+        }
+    }
+
+    /// Pack into two vec4s: `[type, position]` and `[normal / radius / half extents]`.
+    pub fn pack_for_gpu(&self) -> [[f32; 4]; 2] {
+        match self {
+            Self::Plane { point, normal } => [
+                [0.0, point.x, point.y, point.z],
+                [normal.x, normal.y, normal.z, 0.0],
+            ],
+            Self::Sphere { center, radius } => [
+                [1.0, center.x, center.y, center.z],
+                [*radius, 0.0, 0.0, 0.0],
+            ],
+            Self::Box {
+                center,
+                half_extents,
+            } => [
+                [2.0, center.x, center.y, center.z],
+                [half_extents.x, half_extents.y, half_extents.z, 0.0],
+            ],
+        }
+    }
+
+    /// Inverse of [`Self::pack_for_gpu`].
+    pub fn from_packed(packed: [[f32; 4]; 2]) -> Self {
+        let [a, b] = packed;
+        let position = Vec3::new(a[1], a[2], a[3]);
+        match a[0] as u32 {
+            1 => Self::Sphere {
+                center: position,
+                radius: b[0],
+            },
+            2 => Self::Box {
+                center: position,
+                half_extents: Vec3::new(b[0], b[1], b[2]),
+            },
+            _ => Self::Plane {
+                point: position,
+                normal: Vec3::new(b[0], b[1], b[2]),
+            },
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sub-emitters
+// ---------------------------------------------------------------------------
+
+/// Particle event that fires a sub-emitter.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub enum SubEmitterTrigger {
+    /// When a particle spawns (not for particles spawned by another sub-emitter).
+    Birth,
+    /// When a particle expires or is killed.
+    #[default]
+    Death,
+    /// When a particle hits a `UpdateModule::Collide` shape.
+    Collision,
+    /// Every N seconds of a particle's life (trails).
+    Interval(f32),
+}
+
+impl SubEmitterTrigger {
+    pub const VARIANT_LABELS: &[&str] = &["Birth", "Death", "Collision", "Interval"];
+
+    pub fn label(&self) -> &'static str {
+        Self::VARIANT_LABELS[self.variant_index()]
+    }
+
+    /// Also the trigger code used by the GPU (`SUB_TRIGGER_*` in `common.wgsl`).
+    pub fn variant_index(&self) -> usize {
+        match self {
+            Self::Birth => 0,
+            Self::Death => 1,
+            Self::Collision => 2,
+            Self::Interval(_) => 3,
+        }
+    }
+
+    pub fn from_variant_index(idx: usize) -> Self {
+        match idx {
+            0 => Self::Birth,
+            2 => Self::Collision,
+            3 => Self::Interval(0.1),
+            _ => Self::Death,
+        }
+    }
+
+    /// Whether a particle aged from `prev_age` to `age` crosses an interval boundary.
+    pub fn interval_crossed(interval: f32, prev_age: f32, age: f32) -> bool {
+        interval > 0.0 && (age / interval).floor() != (prev_age / interval).floor()
+    }
+}
+
+/// Link from a parent emitter to another emitter in the same system. Each
+/// time a parent particle fires `trigger`, `count` child particles spawn at
+/// its position.
+///
+/// Parent and child must run on the same path: billboard/ribbon emitters
+/// link to billboard/ribbon emitters (GPU), mesh emitters to mesh emitters.
+/// Children usually use `SpawnModule::Rate(0.0)` so they only spawn from links.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Reflect)]
+pub struct SubEmitter {
+    /// Index of the child emitter in `VfxSystem::emitters`.
+    pub emitter: usize,
+    pub trigger: SubEmitterTrigger,
+    /// Child particles spawned per event.
+    pub count: u32,
+    /// Fraction of the parent particle's velocity added to each child.
+    pub inherit_velocity: f32,
+    /// Children take the parent particle's current color.
+    pub inherit_color: bool,
+}
+
+impl Default for SubEmitter {
+    fn default() -> Self {
+        Self {
+            emitter: 0,
+            trigger: SubEmitterTrigger::Death,
+            count: 10,
+            inherit_velocity: 0.0,
+            inherit_color: false,
+        }
+    }
+}
+
+impl SubEmitter {
+    /// Pack into two vec4s: `[trigger, child, count, interval]` and
+    /// `[inherit velocity, inherit color, 0, 0]`.
+    pub fn pack_for_gpu(&self) -> [[f32; 4]; 2] {
+        let interval = match self.trigger {
+            SubEmitterTrigger::Interval(interval) => interval,
+            _ => 0.0,
+        };
+        [
+            [
+                self.trigger.variant_index() as f32,
+                self.emitter as f32,
+                self.count as f32,
+                interval,
+            ],
+            [
+                self.inherit_velocity,
+                if self.inherit_color { 1.0 } else { 0.0 },
+                0.0,
+                0.0,
+            ],
+        ]
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Render modules
 // ---------------------------------------------------------------------------
@@ -846,7 +1281,7 @@ impl Default for MeshParticleConfig {
 }
 
 // ---------------------------------------------------------------------------
-// Exposed parameters (future use)
+// Exposed parameters
 // ---------------------------------------------------------------------------
 
 /// An exposed parameter that can be bound from game code or the effect sequencer.
@@ -865,6 +1300,206 @@ pub enum VfxParamValue {
     Curve(Curve<f32>),
 }
 
+impl Default for VfxParamValue {
+    fn default() -> Self {
+        Self::Float(1.0)
+    }
+}
+
+impl VfxParamValue {
+    pub const VARIANT_LABELS: &[&str] = &["Float", "Vec3", "Color", "Curve"];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Float(_) => "Float",
+            Self::Vec3(_) => "Vec3",
+            Self::Color(_) => "Color",
+            Self::Curve(_) => "Curve",
+        }
+    }
+
+    pub fn variant_index(&self) -> usize {
+        match self {
+            Self::Float(_) => 0,
+            Self::Vec3(_) => 1,
+            Self::Color(_) => 2,
+            Self::Curve(_) => 3,
+        }
+    }
+
+    pub fn from_variant_index(idx: usize) -> Self {
+        match idx {
+            1 => Self::Vec3(Vec3::ZERO),
+            2 => Self::Color(LinearRgba::WHITE),
+            3 => Self::Curve(Curve::constant(1.0)),
+            _ => Self::Float(1.0),
+        }
+    }
+}
+
+/// Emitter field that a parameter can drive.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum ParamTarget {
+    /// `SpawnModule::Rate` particles per second (Float).
+    #[default]
+    SpawnRate,
+    /// `SpawnModule::Burst` / `Once` particle count (Float, rounded).
+    SpawnCount,
+    /// `InitModule::SetLifetime` (Float).
+    Lifetime,
+    /// Speed of `InitModule::SetVelocity` (Float).
+    Speed,
+    /// `InitModule::SetSize` (Float).
+    Size,
+    /// `InitModule::SetColor` (Color).
+    Color,
+    /// `UpdateModule::Gravity` acceleration (Vec3).
+    Gravity,
+    /// `UpdateModule::SizeByLife` curve (Curve).
+    SizeByLife,
+}
+
+impl ParamTarget {
+    pub const ALL: [Self; 8] = [
+        Self::SpawnRate,
+        Self::SpawnCount,
+        Self::Lifetime,
+        Self::Speed,
+        Self::Size,
+        Self::Color,
+        Self::Gravity,
+        Self::SizeByLife,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::SpawnRate => "Spawn Rate",
+            Self::SpawnCount => "Spawn Count",
+            Self::Lifetime => "Lifetime",
+            Self::Speed => "Speed",
+            Self::Size => "Size",
+            Self::Color => "Color",
+            Self::Gravity => "Gravity",
+            Self::SizeByLife => "Size Over Life",
+        }
+    }
+
+    /// Write `value` into every matching module of `emitter`.
+    /// Values of the wrong type, or targets with no matching module, are ignored.
+    pub fn apply(&self, emitter: &mut EmitterDef, value: &VfxParamValue) {
+        match (self, value) {
+            (Self::SpawnRate, VfxParamValue::Float(v)) => {
+                if let SpawnModule::Rate(rate) = &mut emitter.spawn {
+                    *rate = v.max(0.0);
+                }
+            }
+            (Self::SpawnCount, VfxParamValue::Float(v)) => {
+                if let SpawnModule::Burst { count, .. } | SpawnModule::Once { count, .. } =
+                    &mut emitter.spawn
+                {
+                    *count = v.max(0.0).round() as u32;
+                }
+            }
+            (Self::Lifetime, VfxParamValue::Float(v)) => {
+                for module in &mut emitter.init {
+                    if let InitModule::SetLifetime(range) = module {
+                        *range = ScalarRange::Constant(*v);
+                    }
+                }
+            }
+            (Self::Speed, VfxParamValue::Float(v)) => {
+                for module in &mut emitter.init {
+                    if let InitModule::SetVelocity(
+                        VelocityMode::Radial { speed, .. }
+                        | VelocityMode::Directional { speed, .. }
+                        | VelocityMode::Tangent { speed, .. }
+                        | VelocityMode::Cone { speed, .. }
+                        | VelocityMode::Random { speed },
+                    ) = module
+                    {
+                        *speed = ScalarRange::Constant(*v);
+                    }
+                }
+            }
+            (Self::Size, VfxParamValue::Float(v)) => {
+                for module in &mut emitter.init {
+                    if let InitModule::SetSize(range) = module {
+                        *range = ScalarRange::Constant(*v);
+                    }
+                }
+            }
+            (Self::Color, VfxParamValue::Color(c)) => {
+                for module in &mut emitter.init {
+                    if let InitModule::SetColor(source) = module {
+                        *source = ColorSource::Constant(*c);
+                    }
+                }
+            }
+            (Self::Gravity, VfxParamValue::Vec3(g)) => {
+                for module in &mut emitter.update {
+                    if let UpdateModule::Gravity(gravity) = module {
+                        *gravity = *g;
+                    }
+                }
+            }
+            (Self::SizeByLife, VfxParamValue::Curve(c)) => {
+                for module in &mut emitter.update {
+                    if let UpdateModule::SizeByLife(curve) = module {
+                        *curve = c.clone();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Binds a named system parameter to an emitter field.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Reflect)]
+pub struct ParamBinding {
+    /// Name of the parameter in `VfxSystem::params`.
+    pub param: String,
+    /// Field the parameter drives.
+    pub target: ParamTarget,
+}
+
+/// Per-entity parameter values set at runtime by game code or effect sequences.
+/// Takes precedence over the defaults in `VfxSystem::params`.
+///
+/// ```ignore
+/// commands.entity(explosion).insert(
+///     VfxParamOverrides::default().with("debris", VfxParamValue::Float(damage * 4.0)),
+/// );
+/// ```
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct VfxParamOverrides {
+    pub values: HashMap<String, VfxParamValue>,
+}
+
+impl VfxParamOverrides {
+    /// Builder-style `set`.
+    pub fn with(mut self, name: impl Into<String>, value: VfxParamValue) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Set a parameter value.
+    pub fn set(&mut self, name: impl Into<String>, value: VfxParamValue) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Clear a parameter so the system default applies again.
+    pub fn clear(&mut self, name: &str) {
+        self.values.remove(name);
+    }
+
+    /// Current override for a parameter, if any.
+    pub fn get(&self, name: &str) -> Option<&VfxParamValue> {
+        self.values.get(name)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // VFX library (preset storage)
 // ---------------------------------------------------------------------------
@@ -872,5 +1507,42 @@ pub enum VfxParamValue {
 /// Library of named VFX presets. No disk I/O — that's the editor's job.
 #[derive(Resource, Default)]
 pub struct VfxLibrary {
-    pub effects: std::collections::HashMap<String, VfxSystem>,
+    pub effects: HashMap<String, VfxSystem>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Total spawned by `spawn` over frames with the given deltas. Like
+    /// `Time`, each frame's elapsed time already includes its delta, and the
+    /// first frame starts at zero.
+    fn total_spawned(spawn: &SpawnModule, deltas: &[f32]) -> u32 {
+        let mut elapsed = 0.0;
+        let mut total = 0;
+        for (frame, &dt) in deltas.iter().enumerate() {
+            if frame > 0 {
+                elapsed += dt;
+            }
+            total += spawn.spawn_count(dt, elapsed);
+        }
+        total
+    }
+
+    #[test]
+    fn test_once_spawns_exactly_once() {
+        let steady = [1.0 / 60.0; 30];
+        let uneven = [0.016, 0.033, 0.008, 0.05, 0.016, 0.02, 0.1, 0.016, 0.004, 0.03];
+        for offset in [0.0, 0.05, 0.2] {
+            let spawn = SpawnModule::Once { count: 5, offset };
+            assert_eq!(total_spawned(&spawn, &steady), 5, "offset {offset}, steady frames");
+            assert_eq!(total_spawned(&spawn, &uneven), 5, "offset {offset}, uneven frames");
+        }
+    }
+
+    #[test]
+    fn test_once_waits_for_offset() {
+        let spawn = SpawnModule::Once { count: 5, offset: 1.0 };
+        assert_eq!(total_spawned(&spawn, &[0.1; 5]), 0);
+    }
 }
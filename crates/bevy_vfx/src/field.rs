@@ -0,0 +1,562 @@
+//! Baked 3D vector fields for the `VectorField` and `ConformToSdf` update modules.
+//!
+//! A [`VectorField`] is a regular grid of flow vectors with an optional signed
+//! distance per cell. Fields load from a small binary format (`.vfield`) or
+//! RON (`.vfield.ron`), or are baked from scene collider bounds with
+//! [`VectorField::bake_from_boxes`]. Loaded fields are uploaded as 3D
+//! textures (rgb = flow, a = distance) for the GPU update pass.
+
+use std::fmt;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, RenderAssetUsages};
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::data::{CollisionShape, EmitterDef, UpdateModule, VfxSystem};
+
+/// Header of the binary `.vfield` format.
+const BINARY_MAGIC: &[u8; 4] = b"VFLD";
+const BINARY_VERSION: u32 = 1;
+/// Header flag: the file stores a distance per cell after the vectors.
+const BINARY_FLAG_DISTANCES: u32 = 1;
+
+/// Field slots per emitter: `VectorField` flow and `ConformToSdf` distances.
+pub const FIELD_SLOTS: usize = 2;
+
+// ---------------------------------------------------------------------------
+// Asset
+// ---------------------------------------------------------------------------
+
+/// A 3D grid of flow vectors with an optional signed distance per cell.
+///
+/// Cells are stored x-fastest, then y, then z. Cell centers sit on the grid
+/// corners, so the first and last cell along each axis lie on `min` and `max`.
+/// Bounds are relative to the emitter in Local sim space, like kill zones.
+#[derive(Asset, TypePath, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VectorField {
+    pub min: Vec3,
+    pub max: Vec3,
+    /// Cells along each axis.
+    pub resolution: UVec3,
+    /// Flow vector per cell.
+    pub vectors: Vec<Vec3>,
+    /// Signed distance to the nearest surface per cell (negative inside).
+    /// Empty when the field carries no distances.
+    #[serde(default)]
+    pub distances: Vec<f32>,
+}
+
+impl Default for VectorField {
+    fn default() -> Self {
+        Self::new(Vec3::splat(-1.0), Vec3::splat(1.0), UVec3::splat(2))
+    }
+}
+
+impl VectorField {
+    /// A zero field without distances.
+    pub fn new(min: Vec3, max: Vec3, resolution: UVec3) -> Self {
+        let resolution = resolution.max(UVec3::ONE);
+        Self {
+            min,
+            max,
+            resolution,
+            vectors: vec![Vec3::ZERO; resolution.element_product() as usize],
+            distances: Vec::new(),
+        }
+    }
+
+    /// Number of cells in the grid.
+    pub fn cell_count(&self) -> usize {
+        self.resolution.element_product() as usize
+    }
+
+    /// Whether the cell data matches the resolution.
+    pub fn is_valid(&self) -> bool {
+        self.resolution.min_element() > 0
+            && self.vectors.len() == self.cell_count()
+            && (self.distances.is_empty() || self.distances.len() == self.cell_count())
+    }
+
+    /// Whether the field carries signed distances (needed by `ConformToSdf`).
+    pub fn has_distances(&self) -> bool {
+        !self.distances.is_empty()
+    }
+
+    /// Flat index of a cell.
+    pub fn index(&self, cell: UVec3) -> usize {
+        let res = self.resolution;
+        (cell.x + res.x * (cell.y + res.y * cell.z)) as usize
+    }
+
+    /// Position of a cell center.
+    pub fn cell_position(&self, cell: UVec3) -> Vec3 {
+        let steps = (self.resolution.max(UVec3::splat(2)) - 1).as_vec3();
+        self.min + (self.max - self.min) * cell.as_vec3() / steps
+    }
+
+    /// Spacing between neighbouring cells along each axis.
+    pub fn cell_size(&self) -> Vec3 {
+        (self.max - self.min) / (self.resolution.max(UVec3::splat(2)) - 1).as_vec3()
+    }
+
+    fn texel(&self, cell: IVec3) -> Vec4 {
+        let i = self.index(cell.as_uvec3());
+        let vector = self.vectors.get(i).copied().unwrap_or(Vec3::ZERO);
+        vector.extend(self.distances.get(i).copied().unwrap_or(0.0))
+    }
+
+    /// Trilinear sample: xyz = flow, w = signed distance. Points outside the
+    /// bounds use the nearest edge. Must match `sample_field` in `update.wgsl`.
+    pub fn sample(&self, point: Vec3) -> Vec4 {
+        let dims = self.resolution.max(UVec3::ONE).as_ivec3();
+        let extent = (self.max - self.min).max(Vec3::splat(0.0001));
+        let g = ((point - self.min) / extent).clamp(Vec3::ZERO, Vec3::ONE) * (dims - 1).as_vec3();
+        let base = g.floor().as_ivec3().min((dims - 2).max(IVec3::ZERO));
+        let f = g - base.as_vec3();
+        let hi = (base + 1).min(dims - 1);
+
+        let row = |y: i32, z: i32| {
+            self.texel(IVec3::new(base.x, y, z))
+                .lerp(self.texel(IVec3::new(hi.x, y, z)), f.x)
+        };
+        let near = row(base.y, base.z).lerp(row(hi.y, base.z), f.y);
+        let far = row(base.y, hi.z).lerp(row(hi.y, hi.z), f.y);
+        near.lerp(far, f.z)
+    }
+
+    /// Direction of increasing distance (the outward surface normal near
+    /// geometry), by central differences one cell apart. Must match
+    /// `sdf_gradient` in `update.wgsl`.
+    pub fn distance_gradient(&self, point: Vec3) -> Vec3 {
+        let h = self.cell_size().max(Vec3::splat(0.0001));
+        let diff = |offset: Vec3| self.sample(point + offset).w - self.sample(point - offset).w;
+        Vec3::new(
+            diff(Vec3::X * h.x) / h.x,
+            diff(Vec3::Y * h.y) / h.y,
+            diff(Vec3::Z * h.z) / h.z,
+        )
+        .normalize_or_zero()
+    }
+
+    /// Velocity after one `VectorField` module step.
+    pub fn advect(
+        &self,
+        position: Vec3,
+        velocity: Vec3,
+        strength: f32,
+        tightness: f32,
+        dt: f32,
+    ) -> Vec3 {
+        let flow = self.sample(position).truncate() * strength;
+        (velocity + flow * dt).lerp(flow, tightness.clamp(0.0, 1.0))
+    }
+
+    /// Velocity after one `ConformToSdf` module step: accelerate toward the
+    /// `surface` level set, then damp motion along the surface normal.
+    pub fn conform(
+        &self,
+        position: Vec3,
+        velocity: Vec3,
+        surface: f32,
+        attraction: f32,
+        damping: f32,
+        dt: f32,
+    ) -> Vec3 {
+        let distance = self.sample(position).w - surface;
+        let n = self.distance_gradient(position);
+        let velocity = velocity - n * distance.clamp(-1.0, 1.0) * attraction * dt;
+        velocity - n * velocity.dot(n) * (damping * dt).min(1.0)
+    }
+
+    /// Bake a field around axis-aligned boxes given as `(min, max)`, e.g.
+    /// static collider bounds.
+    ///
+    /// Distances are to the nearest box. Vectors are `flow` with the part
+    /// heading into nearby geometry removed, fading out over `falloff`, so
+    /// particles slide around obstacles; inside geometry they point outward.
+    pub fn bake_from_boxes(
+        min: Vec3,
+        max: Vec3,
+        resolution: UVec3,
+        boxes: &[(Vec3, Vec3)],
+        flow: Vec3,
+        falloff: f32,
+    ) -> Self {
+        let mut field = Self::new(min, max, resolution);
+        field.distances = vec![0.0; field.cell_count()];
+        let shapes: Vec<CollisionShape> = boxes
+            .iter()
+            .map(|(lo, hi)| CollisionShape::Box {
+                center: (*lo + *hi) * 0.5,
+                half_extents: (*hi - *lo) * 0.5,
+            })
+            .collect();
+        let far = (max - min).length();
+
+        let res = field.resolution;
+        for z in 0..res.z {
+            for y in 0..res.y {
+                for x in 0..res.x {
+                    let cell = UVec3::new(x, y, z);
+                    let point = field.cell_position(cell);
+                    let (distance, normal) = shapes
+                        .iter()
+                        .map(|shape| shape.signed_distance(point))
+                        .min_by(|a, b| a.0.total_cmp(&b.0))
+                        .unwrap_or((far, Vec3::ZERO));
+
+                    let i = field.index(cell);
+                    field.distances[i] = distance;
+                    field.vectors[i] = if distance < 0.0 {
+                        normal * flow.length().max(1.0)
+                    } else {
+                        let blend = (1.0 - distance / falloff.max(0.0001)).clamp(0.0, 1.0);
+                        flow - normal * flow.dot(normal).min(0.0) * blend
+                    };
+                }
+            }
+        }
+        field
+    }
+
+    /// Upload layout: one Rgba32Float texel per cell (rgb = flow, a = distance).
+    pub fn to_image(&self) -> Image {
+        let texels: Vec<[f32; 4]> = (0..self.cell_count())
+            .map(|i| {
+                let v = self.vectors.get(i).copied().unwrap_or(Vec3::ZERO);
+                [v.x, v.y, v.z, self.distances.get(i).copied().unwrap_or(0.0)]
+            })
+            .collect();
+        Image::new(
+            Extent3d {
+                width: self.resolution.x,
+                height: self.resolution.y,
+                depth_or_array_layers: self.resolution.z,
+            },
+            TextureDimension::D3,
+            bytemuck::cast_slice(&texels).to_vec(),
+            TextureFormat::Rgba32Float,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+    }
+
+    /// Encode in the binary `.vfield` format (little-endian).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(48 + self.cell_count() * 16);
+        bytes.extend_from_slice(BINARY_MAGIC);
+        let flags = if self.has_distances() {
+            BINARY_FLAG_DISTANCES
+        } else {
+            0
+        };
+        for word in [
+            BINARY_VERSION,
+            self.resolution.x,
+            self.resolution.y,
+            self.resolution.z,
+            flags,
+        ] {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        let floats = self
+            .min
+            .to_array()
+            .into_iter()
+            .chain(self.max.to_array())
+            .chain(self.vectors.iter().flat_map(|v| v.to_array()))
+            .chain(self.distances.iter().copied());
+        for value in floats {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decode a binary `.vfield` file, or RON when the magic header is absent.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VectorFieldError> {
+        let field = match bytes.strip_prefix(BINARY_MAGIC) {
+            Some(body) => Self::decode_binary(body)?,
+            None => ron::de::from_bytes(bytes)?,
+        };
+        if !field.is_valid() {
+            return Err(VectorFieldError::Invalid(format!(
+                "{} cells expected for resolution {}",
+                field.cell_count(),
+                field.resolution
+            )));
+        }
+        Ok(field)
+    }
+
+    fn decode_binary(body: &[u8]) -> Result<Self, VectorFieldError> {
+        let mut words = body
+            .chunks_exact(4)
+            .map(|chunk| [chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let mut next = || {
+            words
+                .next()
+                .ok_or_else(|| VectorFieldError::Invalid("unexpected end of file".into()))
+        };
+
+        let version = u32::from_le_bytes(next()?);
+        if version != BINARY_VERSION {
+            return Err(VectorFieldError::Invalid(format!(
+                "unsupported version {version}"
+            )));
+        }
+        let mut uvec3 = || -> Result<UVec3, VectorFieldError> {
+            Ok(UVec3::new(
+                u32::from_le_bytes(next()?),
+                u32::from_le_bytes(next()?),
+                u32::from_le_bytes(next()?),
+            ))
+        };
+        let resolution = uvec3()?;
+        let flags = u32::from_le_bytes(next()?);
+        let mut float = || next().map(f32::from_le_bytes);
+        let mut vec3 =
+            || -> Result<Vec3, VectorFieldError> { Ok(Vec3::new(float()?, float()?, float()?)) };
+        let min = vec3()?;
+        let max = vec3()?;
+
+        let cells = resolution.element_product() as usize;
+        let vectors = (0..cells).map(|_| vec3()).collect::<Result<_, _>>()?;
+        let distances = if flags & BINARY_FLAG_DISTANCES != 0 {
+            (0..cells).map(|_| float()).collect::<Result<_, _>>()?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            min,
+            max,
+            resolution,
+            vectors,
+            distances,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Loading
+// ---------------------------------------------------------------------------
+
+/// Errors from decoding a vector field file.
+#[derive(Debug)]
+pub enum VectorFieldError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Invalid(String),
+}
+
+impl fmt::Display for VectorFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read vector field: {e}"),
+            Self::Ron(e) => write!(f, "failed to parse vector field RON: {e}"),
+            Self::Invalid(reason) => write!(f, "invalid vector field: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for VectorFieldError {}
+
+impl From<std::io::Error> for VectorFieldError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for VectorFieldError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        Self::Ron(e)
+    }
+}
+
+/// Loads `.vfield` (binary) and `.vfield.ron` files.
+#[derive(Default, TypePath)]
+pub struct VectorFieldLoader;
+
+impl AssetLoader for VectorFieldLoader {
+    type Asset = VectorField;
+    type Settings = ();
+    type Error = VectorFieldError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        VectorField::from_bytes(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["vfield", "vfield.ron"]
+    }
+}
+
+/// Field paths sampled by an emitter, by slot: the `VectorField` module's
+/// and the `ConformToSdf` module's. Later modules of the same kind win, as
+/// when packing GPU params.
+pub fn emitter_field_paths(emitter: &EmitterDef) -> [Option<&str>; FIELD_SLOTS] {
+    let mut paths = [None; FIELD_SLOTS];
+    for module in &emitter.update {
+        let slot = match module {
+            UpdateModule::VectorField { .. } => 0,
+            UpdateModule::ConformToSdf { .. } => 1,
+            _ => continue,
+        };
+        paths[slot] = module.field_path();
+    }
+    paths
+}
+
+/// Main-world resource holding strong handles to vector fields referenced by
+/// VFX systems, plus their GPU texture copies.
+#[derive(Resource, Default)]
+pub struct VfxFieldCache {
+    pub handles: HashMap<String, Handle<VectorField>>,
+    /// Loaded fields as 3D textures (rgb = flow, a = distance).
+    pub images: HashMap<AssetId<VectorField>, Handle<Image>>,
+}
+
+impl VfxFieldCache {
+    /// The loaded, valid field at `path`.
+    pub fn field<'a>(
+        &self,
+        path: &str,
+        fields: &'a Assets<VectorField>,
+    ) -> Option<&'a VectorField> {
+        self.handles
+            .get(path)
+            .and_then(|handle| fields.get(handle))
+            .filter(|field| field.is_valid())
+    }
+
+    /// The texture uploaded for the field at `path`.
+    pub fn image(&self, path: &str) -> Option<AssetId<Image>> {
+        let handle = self.handles.get(path)?;
+        self.images.get(&handle.id()).map(Handle::id)
+    }
+}
+
+/// Main-world system: loads vector fields referenced by VfxSystem emitters.
+pub fn load_vfx_fields(
+    mut cache: ResMut<VfxFieldCache>,
+    asset_server: Res<AssetServer>,
+    query: Query<&VfxSystem>,
+) {
+    let mut active_paths = HashSet::new();
+    for system in &query {
+        for module in system.emitters.iter().flat_map(|e| &e.update) {
+            if let Some(path) = module.field_path() {
+                active_paths.insert(path.to_string());
+            }
+        }
+    }
+    for path in &active_paths {
+        if !cache.handles.contains_key(path) {
+            let handle = asset_server.load::<VectorField>(path);
+            cache.handles.insert(path.clone(), handle);
+        }
+    }
+    // Drop handles for fields no longer referenced by any emitter
+    cache.handles.retain(|path, _| active_paths.contains(path));
+}
+
+/// Main-world system: (re)builds the 3D texture of every loaded or modified field.
+pub fn upload_vfx_field_images(
+    mut events: MessageReader<AssetEvent<VectorField>>,
+    fields: Res<Assets<VectorField>>,
+    mut images: ResMut<Assets<Image>>,
+    mut cache: ResMut<VfxFieldCache>,
+) {
+    for event in events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                match fields.get(*id).filter(|field| field.is_valid()) {
+                    Some(field) => {
+                        let image = images.add(field.to_image());
+                        cache.images.insert(*id, image);
+                    }
+                    None => {
+                        cache.images.remove(id);
+                    }
+                }
+            }
+            AssetEvent::Removed { id } | AssetEvent::Unused { id } => {
+                cache.images.remove(id);
+            }
+            AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flow pointing into a ground slab whose top face is y = 0.
+    fn baked_ground() -> VectorField {
+        VectorField::bake_from_boxes(
+            Vec3::new(-2.0, 0.0, -2.0),
+            Vec3::new(2.0, 2.0, 2.0),
+            UVec3::splat(5),
+            &[(Vec3::new(-4.0, -4.0, -4.0), Vec3::new(4.0, 0.0, 4.0))],
+            Vec3::NEG_Y,
+            1.0,
+        )
+    }
+
+    #[test]
+    fn test_bake_deflects_flow_at_surface() {
+        let field = baked_ground();
+        assert!(field.is_valid() && field.has_distances());
+
+        // The into-surface component is removed at the surface, untouched past the falloff
+        assert!(field.sample(Vec3::ZERO).truncate().length() < 1e-5);
+        assert!(
+            field
+                .sample(Vec3::new(0.0, 2.0, 0.0))
+                .truncate()
+                .distance(Vec3::NEG_Y)
+                < 1e-5
+        );
+
+        assert!((field.sample(Vec3::new(0.3, 1.0, -0.7)).w - 1.0).abs() < 1e-5);
+        assert!(
+            field
+                .distance_gradient(Vec3::new(0.0, 1.0, 0.0))
+                .distance(Vec3::Y)
+                < 1e-5
+        );
+    }
+
+    #[test]
+    fn test_sample_clamps_outside_bounds() {
+        let field = baked_ground();
+        assert_eq!(
+            field.sample(Vec3::new(0.0, 50.0, 0.0)),
+            field.sample(Vec3::new(0.0, 2.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_binary_and_ron_roundtrip() {
+        let field = baked_ground();
+        assert_eq!(VectorField::from_bytes(&field.to_bytes()).unwrap(), field);
+
+        let ron = ron::to_string(&field).unwrap();
+        assert_eq!(VectorField::from_bytes(ron.as_bytes()).unwrap(), field);
+    }
+
+    #[test]
+    fn test_truncated_binary_is_rejected() {
+        let bytes = baked_ground().to_bytes();
+        assert!(VectorField::from_bytes(&bytes[..bytes.len() - 4]).is_err());
+    }
+}
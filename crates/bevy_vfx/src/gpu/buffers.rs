@@ -9,13 +9,22 @@ use bytemuck::{Pod, Zeroable};
 
 use bevy::asset::AssetId;
 use bevy::image::Image;
+use bevy::platform::collections::HashMap;
 
-use crate::data::EmitterDef;
+use super::extract::ExtractedField;
+use crate::data::{CollisionShape, EmitterDef};
+use crate::field::FIELD_SLOTS;
 
 /// Maximum number of curve keyframes packed into the GPU params buffer.
 pub const MAX_CURVE_KEYS: usize = 8;
 /// Maximum number of gradient keys packed into the GPU params buffer.
 pub const MAX_GRADIENT_KEYS: usize = 8;
+/// Maximum number of collision shapes packed into the GPU params buffer.
+pub const MAX_COLLISION_SHAPES: usize = 8;
+/// Maximum number of sub-emitter links packed into the GPU params buffer.
+pub const MAX_SUB_EMITTERS: usize = 4;
+/// Sub-emitter events each emitter can queue per frame; extra events are dropped.
+pub const MAX_SUB_EMITTER_EVENTS: u32 = 512;
 
 // ---------------------------------------------------------------------------
 // GPU-side particle struct (matches common.wgsl)
@@ -200,10 +209,37 @@ pub struct GpuEmitterParams {
     /// Per-emitter RNG salt so identically-configured emitters don't produce
     /// identical particle sequences. Derived from entity ID + emitter index.
     pub emitter_seed: u32,        // 880
-    pub _pad_tail: [f32; 7],     // 884: padding to match WGSL struct size (912)
+
+    // -- Update: collide -- (offset 884)
+    pub collision_count: u32,      // 884
+    pub collision_bounce: f32,     // 888
+    pub collision_friction: f32,   // 892
+    pub collision_radius: f32,     // 896
+    pub collision_kill: u32,       // 900
+    pub _pad14: [f32; 2],         // 904: align to 16 for array
+    pub collision_shapes: [[f32; 4]; MAX_COLLISION_SHAPES * 2], // 912 (256 bytes)
+
+    // -- Sub-emitters -- (offset 1168)
+    /// Index of this emitter in its VfxSystem, matched against event targets.
+    pub emitter_index: u32,        // 1168
+    pub sub_emitter_count: u32,    // 1172
+    pub _pad15: [f32; 2],         // 1176: align to 16 for array
+    pub sub_emitters: [[f32; 4]; MAX_SUB_EMITTERS * 2], // 1184 (128 bytes)
+
+    // -- Update: vector field / conform to SDF -- (offset 1312)
+    pub field_min: [f32; 3],       // 1312
+    pub field_strength: f32,       // 1324
+    pub field_max: [f32; 3],       // 1328
+    pub field_tightness: f32,      // 1340
+    pub sdf_min: [f32; 3],         // 1344
+    pub sdf_surface: f32,          // 1356
+    pub sdf_max: [f32; 3],         // 1360
+    pub sdf_attraction: f32,       // 1372
+    pub sdf_damping: f32,          // 1376
+    pub _pad16: [f32; 3],         // 1380: align struct to 16
 }
 
-const _: () = assert!(std::mem::size_of::<GpuEmitterParams>() == 912);
+const _: () = assert!(std::mem::size_of::<GpuEmitterParams>() == 1392);
 
 /// Dynamic subset of GpuEmitterParams that changes every frame.
 /// Written via partial buffer upload at DYNAMIC_PARAMS_OFFSET.
@@ -237,10 +273,37 @@ pub const FLAG_SIZE_BY_SPEED: u32 = 1 << 9;
 pub const FLAG_ROTATE_BY_VELOCITY: u32 = 1 << 10;
 pub const FLAG_TANGENT_ACCEL: u32 = 1 << 11;
 pub const FLAG_RADIAL_ACCEL: u32 = 1 << 12;
+pub const FLAG_COLLIDE: u32 = 1 << 13;
+pub const FLAG_VECTOR_FIELD: u32 = 1 << 14;
+pub const FLAG_CONFORM_SDF: u32 = 1 << 15;
 
 /// Billboard counter flag: particle has a texture bound (vs procedural circle).
 pub const BILLBOARD_FLAG_HAS_TEXTURE: u32 = 1;
 
+// ---------------------------------------------------------------------------
+// Sub-emitter events
+// ---------------------------------------------------------------------------
+
+/// A parent particle event queued for a child emitter.
+/// Must match `SubEmitterEvent` in `common.wgsl`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct GpuSubEmitterEvent {
+    /// World-space position of the parent particle.
+    pub position: [f32; 3],
+    /// Index of the child emitter.
+    pub child: u32,
+    /// Parent velocity, already scaled by the inherit ratio.
+    pub velocity: [f32; 3],
+    /// Child particles to spawn.
+    pub count: u32,
+    /// Parent color, or alpha < 0 when the child keeps its own.
+    pub color: [f32; 4],
+}
+
+/// Byte size of the event buffer header (atomic count + padding).
+pub const SUB_EMITTER_EVENTS_HEADER: u64 = 16;
+
 // ---------------------------------------------------------------------------
 // Per-emitter GPU resource set
 // ---------------------------------------------------------------------------
@@ -267,12 +330,22 @@ pub struct EmitterBuffers {
     /// When the EmitterDef changes, we re-upload the full params buffer.
     pub static_params_hash: u64,
     /// Cached EmitterDef for fast change detection (PartialEq comparison
-    /// avoids re-packing 912-byte GPU params struct every frame).
+    /// avoids re-packing 1392-byte GPU params struct every frame).
     pub last_emitter_def: Option<EmitterDef>,
+    /// Scene collider boxes packed alongside `last_emitter_def`.
+    pub last_scene_colliders: Vec<CollisionShape>,
+    /// Field textures bound in the compute bind group and packed alongside
+    /// `last_emitter_def` (None = fallback, module inactive).
+    pub last_fields: [Option<ExtractedField>; FIELD_SLOTS],
     /// The texture AssetId currently bound in the billboard bind group (None = fallback).
     pub bound_texture: Option<AssetId<Image>>,
     /// Whether the bound texture is the real GpuImage (true) or fallback (false, still loading).
     pub bound_texture_resolved: bool,
+    /// Sub-emitter events written by this emitter's particles, read by its children.
+    pub event_buffer: Buffer,
+    /// Cached sub-spawn bind groups (this emitter's buffers plus a parent's
+    /// event buffer), keyed by parent emitter index.
+    pub sub_spawn_bind_groups: HashMap<usize, (BufferId, BindGroup)>,
 }
 
 /// Resource collecting all active emitter bind group data for the render graph node.
@@ -280,6 +353,8 @@ pub struct EmitterBuffers {
 #[derive(Resource, Default)]
 pub struct ActiveEmitterBuffers {
     pub entries: Vec<ActiveEmitterEntry>,
+    /// One sub-spawn dispatch per (parent, child) sub-emitter link.
+    pub sub_spawns: Vec<BindGroup>,
 }
 
 /// One entry per active emitter — pre-cached bind groups and draw metadata.
@@ -287,6 +362,8 @@ pub struct ActiveEmitterEntry {
     pub capacity: u32,
     /// Counter buffer reference (needed for clear_buffer between compute passes).
     pub counter_buffer: Buffer,
+    /// Sub-emitter event buffer (its count is cleared after the sub-spawn dispatches).
+    pub event_buffer: Buffer,
     /// Pre-cached compute bind group (avoids per-frame creation).
     pub compute_bind_group: BindGroup,
     /// Pre-cached billboard bind group (avoids per-frame creation).
@@ -352,6 +429,14 @@ impl EmitterBuffers {
             mapped_at_creation: false,
         });
 
+        let event_size = std::mem::size_of::<GpuSubEmitterEvent>() as u64;
+        let event_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("vfx_sub_emitter_event_buffer"),
+            size: SUB_EMITTER_EVENTS_HEADER + event_size * MAX_SUB_EMITTER_EVENTS as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         Self {
             particle_buffer,
             alive_buffer,
@@ -363,8 +448,12 @@ impl EmitterBuffers {
             billboard_bind_group: None,
             static_params_hash: 0,
             last_emitter_def: None,
+            last_scene_colliders: Vec::new(),
+            last_fields: [None; FIELD_SLOTS],
             bound_texture: None,
             bound_texture_resolved: false,
+            event_buffer,
+            sub_spawn_bind_groups: HashMap::new(),
         }
     }
 }
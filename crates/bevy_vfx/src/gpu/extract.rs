@@ -1,12 +1,18 @@
 //! Extract VfxSystem + Transform data from the main world into the render world.
 
+use avian3d::prelude::*;
 use bevy::asset::AssetId;
 use bevy::image::Image;
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
 use bevy::render::Extract;
 
-use crate::data::{EmitterDef, RenderModule, VfxRestart, VfxStartTime, VfxSystem};
+use super::buffers::MAX_COLLISION_SHAPES;
+use crate::data::{
+    CollisionShape, EmitterDef, RenderModule, SimSpace, UpdateModule, VfxParamOverrides,
+    VfxRestart, VfxStartTime, VfxSystem,
+};
+use crate::field::{emitter_field_paths, VectorField, VfxFieldCache, FIELD_SLOTS};
 
 /// Extracted data for a single emitter, stored in a resource (not per-entity).
 pub struct ExtractedEmitterInfo {
@@ -24,6 +30,19 @@ pub struct ExtractedEmitterInfo {
     pub start_time: f32,
     /// If true, this system should be restarted (evict GPU caches).
     pub restart: bool,
+    /// Bounding boxes of the nearest static scene colliders, for `Collide`
+    /// modules with `scene_colliders` enabled. Relative to the emitter in Local mode.
+    pub scene_colliders: Vec<CollisionShape>,
+    /// Loaded field textures for the `VectorField` and `ConformToSdf` modules.
+    pub fields: [Option<ExtractedField>; FIELD_SLOTS],
+}
+
+/// A vector field texture and the bounds it spans.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExtractedField {
+    pub image: AssetId<Image>,
+    pub min: Vec3,
+    pub max: Vec3,
 }
 
 /// Resource holding all extracted emitter data for the current frame.
@@ -79,13 +98,24 @@ pub fn extract_vfx_systems(
             &GlobalTransform,
             Option<&VfxStartTime>,
             Has<VfxRestart>,
+            Option<&VfxParamOverrides>,
         )>,
     >,
     texture_cache: Extract<Res<VfxTextureCache>>,
+    field_cache: Extract<Res<VfxFieldCache>>,
+    field_assets: Extract<Res<Assets<VectorField>>>,
+    colliders: Extract<Query<(&ColliderAabb, &ColliderOf), SceneColliderFilter>>,
+    bodies: Extract<Query<&RigidBody>>,
 ) {
     extracted.emitters.clear();
 
-    for (entity, system, transform, start_time, restart) in &query {
+    // Only static geometry is worth colliding against; dynamic bodies
+    // (including physics mesh particles) move too much to cache as boxes
+    let mut static_aabbs: Option<Vec<(Vec3, Vec3)>> = None;
+
+    for (entity, system, transform, start_time, restart, overrides) in &query {
+        // Apply param bindings so prepare sees plain emitter values
+        let system = system.resolved(overrides);
         let st = start_time.map(|s| s.0).unwrap_or(0.0);
         for (idx, emitter) in system.emitters.iter().enumerate() {
             if !emitter.enabled {
@@ -101,6 +131,23 @@ pub fn extract_vfx_systems(
                 }),
                 _ => None,
             };
+            let scene_colliders = scene_collision_budget(emitter)
+                .map(|budget| {
+                    let aabbs = static_aabbs.get_or_insert_with(|| {
+                        static_collider_boxes(colliders.iter(), |body| bodies.get(body).ok().copied())
+                    });
+                    nearest_collider_boxes(aabbs, emitter, transform, budget)
+                })
+                .unwrap_or_default();
+            let fields = emitter_field_paths(emitter).map(|path| {
+                let path = path?;
+                let field = field_cache.field(path, &field_assets)?;
+                Some(ExtractedField {
+                    image: field_cache.image(path)?,
+                    min: field.min,
+                    max: field.max,
+                })
+            });
             extracted.emitters.push(ExtractedEmitterInfo {
                 source_entity: entity,
                 emitter_index: idx,
@@ -109,7 +156,71 @@ pub fn extract_vfx_systems(
                 texture,
                 start_time: st,
                 restart,
+                scene_colliders,
+                fields,
             });
         }
     }
 }
+
+/// Query filter for scene colliders particles may hit. Sensors (e.g. trigger
+/// volumes) and VFX entities are left out; pair it with [`static_collider_boxes`].
+pub type SceneColliderFilter = (With<Collider>, Without<Sensor>, Without<VfxSystem>);
+
+/// World-space bounds of the colliders attached to a static rigid body.
+/// Colliders without a body, like editor pick volumes, are skipped.
+/// `body_of` looks up the rigid body of a collider's [`ColliderOf::body`].
+pub fn static_collider_boxes<'a>(
+    colliders: impl IntoIterator<Item = (&'a ColliderAabb, &'a ColliderOf)>,
+    body_of: impl Fn(Entity) -> Option<RigidBody>,
+) -> Vec<(Vec3, Vec3)> {
+    colliders
+        .into_iter()
+        .filter(|(_, collider_of)| body_of(collider_of.body).is_some_and(|body| body.is_static()))
+        .map(|(aabb, _)| (aabb.min, aabb.max))
+        .collect()
+}
+
+/// Number of free collision slots for scene colliders, if the emitter wants them.
+fn scene_collision_budget(emitter: &EmitterDef) -> Option<usize> {
+    emitter.update.iter().find_map(|module| match module {
+        UpdateModule::Collide {
+            shapes,
+            scene_colliders: true,
+            ..
+        } => Some(MAX_COLLISION_SHAPES.saturating_sub(shapes.len())),
+        _ => None,
+    })
+}
+
+/// The `budget` static collider boxes closest to the emitter, as collision shapes.
+fn nearest_collider_boxes(
+    aabbs: &[(Vec3, Vec3)],
+    emitter: &EmitterDef,
+    transform: &GlobalTransform,
+    budget: usize,
+) -> Vec<CollisionShape> {
+    let origin = transform.translation();
+    let mut nearest: Vec<(f32, Vec3, Vec3)> = aabbs
+        .iter()
+        .map(|(min, max)| {
+            let distance = origin.clamp(*min, *max).distance_squared(origin);
+            (distance, *min, *max)
+        })
+        .collect();
+    nearest.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    // Shapes are emitter-relative in Local mode, like kill zones
+    let offset = match emitter.sim_space {
+        SimSpace::Local => origin,
+        SimSpace::World => Vec3::ZERO,
+    };
+    nearest
+        .into_iter()
+        .take(budget)
+        .map(|(_, min, max)| CollisionShape::Box {
+            center: (min + max) * 0.5 - offset,
+            half_extents: (max - min) * 0.5,
+        })
+        .collect()
+}
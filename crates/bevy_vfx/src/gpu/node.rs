@@ -1,11 +1,11 @@
-//! Render graph node that dispatches the spawn → update → compact compute passes.
+//! Render graph node that dispatches the spawn → update → sub-spawn → compact compute passes.
 
 use bevy::prelude::*;
 use bevy::render::render_graph::{self, RenderLabel};
 use bevy::render::render_resource::*;
 use bevy::render::renderer::RenderContext;
 
-use super::buffers::ActiveEmitterBuffers;
+use super::buffers::{ActiveEmitterBuffers, MAX_SUB_EMITTER_EVENTS};
 use super::pipeline::VfxComputePipelines;
 
 /// Render graph label for the VFX compute node.
@@ -53,6 +53,11 @@ impl render_graph::Node for VfxComputeNode {
         else {
             return Ok(());
         };
+        let Some(sub_spawn_pipeline) =
+            pipeline_cache.get_compute_pipeline(pipelines.sub_spawn_pipeline)
+        else {
+            return Ok(());
+        };
 
         let encoder = render_context.command_encoder();
 
@@ -80,12 +85,24 @@ impl render_graph::Node for VfxComputeNode {
                 pass.set_bind_group(0, &entry.compute_bind_group, &[]);
                 pass.dispatch_workgroups(workgroups, 1, 1);
             }
+
+            // Batch: sub-emitter spawns from this frame's parent events
+            if !active_buffers.sub_spawns.is_empty() {
+                let workgroups = MAX_SUB_EMITTER_EVENTS.div_ceil(WORKGROUP_SIZE);
+                pass.set_pipeline(sub_spawn_pipeline);
+                for bind_group in &active_buffers.sub_spawns {
+                    pass.set_bind_group(0, bind_group, &[]);
+                    pass.dispatch_workgroups(workgroups, 1, 1);
+                }
+            }
         }
 
         // Between passes: clear alive_count (offset 4) and dead_count (offset 16).
         // Zeroes bytes 4..20, preserving vertex_count (6) and spawn_count.
+        // Also reset each sub-emitter event count for the next frame.
         for entry in &active_buffers.entries {
             encoder.clear_buffer(&entry.counter_buffer, 4, Some(16));
+            encoder.clear_buffer(&entry.event_buffer, 0, Some(4));
         }
 
         // Pass 2: ALL compact dispatches (single pipeline set).
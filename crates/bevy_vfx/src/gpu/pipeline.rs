@@ -2,7 +2,9 @@
 
 use bevy::prelude::*;
 use bevy::render::render_resource::*;
-use bevy::render::render_resource::binding_types::{storage_buffer_sized, uniform_buffer_sized};
+use bevy::render::render_resource::binding_types::{
+    storage_buffer_sized, texture_3d, uniform_buffer_sized,
+};
 use bevy::render::renderer::RenderDevice;
 
 /// Cached compute pipelines and bind group layout for VFX simulation.
@@ -12,6 +14,10 @@ pub struct VfxComputePipelines {
     pub spawn_pipeline: CachedComputePipelineId,
     pub update_pipeline: CachedComputePipelineId,
     pub compact_pipeline: CachedComputePipelineId,
+    /// Spawn shader's `sub_spawn` entry point, dispatched once per sub-emitter link.
+    pub sub_spawn_pipeline: CachedComputePipelineId,
+    /// Empty 1x1x1 field texture bound for emitters without a vector field.
+    pub fallback_field_view: TextureView,
 }
 
 impl VfxComputePipelines {
@@ -22,7 +28,7 @@ impl VfxComputePipelines {
         update_shader: Handle<Shader>,
         compact_shader: Handle<Shader>,
     ) -> Self {
-        // All five bindings visible to compute stage
+        // All eight bindings visible to compute stage
         let layout_entries = BindGroupLayoutEntries::sequential(
             ShaderStages::COMPUTE,
             (
@@ -36,6 +42,12 @@ impl VfxComputePipelines {
                 storage_buffer_sized(false, None),
                 // @binding(4) EmitterParams (uniform)
                 uniform_buffer_sized(false, None),
+                // @binding(5) SubEmitterEvents (storage, read_write)
+                storage_buffer_sized(false, None),
+                // @binding(6) VectorField flow texture (Rgba32Float, textureLoad only)
+                texture_3d(TextureSampleType::Float { filterable: false }),
+                // @binding(7) ConformToSdf distance texture
+                texture_3d(TextureSampleType::Float { filterable: false }),
             ),
         );
 
@@ -53,12 +65,22 @@ impl VfxComputePipelines {
             label: Some("vfx_spawn_pipeline".into()),
             layout: vec![layout_desc.clone()],
             push_constant_ranges: vec![],
-            shader: spawn_shader,
+            shader: spawn_shader.clone(),
             shader_defs: vec![],
             entry_point: Some("main".into()),
             zero_initialize_workgroup_memory: true,
         });
 
+        let sub_spawn_pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("vfx_sub_spawn_pipeline".into()),
+            layout: vec![layout_desc.clone()],
+            push_constant_ranges: vec![],
+            shader: spawn_shader,
+            shader_defs: vec![],
+            entry_point: Some("sub_spawn".into()),
+            zero_initialize_workgroup_memory: true,
+        });
+
         let update_pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
             label: Some("vfx_update_pipeline".into()),
             layout: vec![layout_desc.clone()],
@@ -79,11 +101,30 @@ impl VfxComputePipelines {
             zero_initialize_workgroup_memory: true,
         });
 
+        // wgpu zero-initializes new textures, so the fallback reads as no flow
+        let fallback_field = device.create_texture(&TextureDescriptor {
+            label: Some("vfx_fallback_field_texture"),
+            size: Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D3,
+            format: TextureFormat::Rgba32Float,
+            usage: TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let fallback_field_view = fallback_field.create_view(&TextureViewDescriptor::default());
+
         Self {
             bind_group_layout,
             spawn_pipeline,
             update_pipeline,
             compact_pipeline,
+            sub_spawn_pipeline,
+            fallback_field_view,
         }
     }
 }
@@ -14,6 +14,7 @@ use super::buffers::*;
 use super::extract::ExtractedVfxData;
 use super::pipeline::VfxComputePipelines;
 use crate::data::*;
+use crate::field::FIELD_SLOTS;
 use crate::render::billboard::VfxBillboardPipeline;
 
 /// Persistent buffer cache keyed by (main-world entity, emitter index).
@@ -46,6 +47,7 @@ pub fn prepare_vfx_buffers(
     gpu_images: Res<RenderAssets<GpuImage>>,
 ) {
     active_buffers.entries.clear();
+    active_buffers.sub_spawns.clear();
     let dt = time.delta_secs();
     let elapsed = time.elapsed_secs();
 
@@ -100,21 +102,44 @@ pub fn prepare_vfx_buffers(
         let local_elapsed = elapsed - info.start_time;
 
         // Compute spawn count for this frame
-        let spawn_count = compute_spawn_count(&emitter_def.spawn, dt, local_elapsed);
+        let spawn_count = emitter_def.spawn.spawn_count(dt, local_elapsed);
+
+        // Field textures only count once their GpuImage exists; until then the
+        // module is packed as inactive instead of reading the empty fallback
+        let fields = info
+            .fields
+            .map(|field| field.filter(|f| gpu_images.get(f.image).is_some()));
+        if buffers.last_fields != fields {
+            buffers.compute_bind_group = None;
+        }
 
         // Fast change detection: compare EmitterDef by value to skip expensive
-        // pack_emitter_params() (builds 912-byte struct) when nothing changed.
+        // pack_emitter_params() (builds 1392-byte struct) when nothing changed.
         let def_changed = buffers
             .last_emitter_def
             .as_ref()
-            .map_or(true, |prev| prev != emitter_def);
+            .map_or(true, |prev| prev != emitter_def)
+            || buffers.last_scene_colliders != info.scene_colliders
+            || buffers.last_fields != fields;
 
         if def_changed {
-            // EmitterDef changed (or first frame) — full pack + upload 912 bytes
-            let params = pack_emitter_params(emitter_def, &info.transform, dt, local_elapsed, key);
+            // EmitterDef, scene colliders or fields changed (or first frame) — full pack + upload
+            let seed = emitter_seed(key.0.to_bits(), key.1);
+            let mut params = pack_emitter_params(
+                emitter_def,
+                info.emitter_index,
+                &info.transform,
+                dt,
+                local_elapsed,
+                seed,
+            );
+            append_collision_shapes(&mut params, &info.scene_colliders);
+            apply_field_bounds(&mut params, fields.map(|f| f.map(|f| (f.min, f.max))));
             queue.write_buffer(&buffers.params_buffer, 0, bytemuck::bytes_of(&params));
             buffers.static_params_hash = hash_static_params(&params);
             buffers.last_emitter_def = Some(emitter_def.clone());
+            buffers.last_scene_colliders.clone_from(&info.scene_colliders);
+            buffers.last_fields = fields;
         } else {
             // Only dynamic params changed (dt, time, transform) — upload 84 bytes
             let dynamic = GpuDynamicParams {
@@ -163,6 +188,11 @@ pub fn prepare_vfx_buffers(
             let billboard_pl = billboard_pipeline.as_ref().unwrap();
 
             if buffers.compute_bind_group.is_none() {
+                let field_view = |slot: usize| {
+                    fields[slot]
+                        .and_then(|f| gpu_images.get(f.image))
+                        .map_or(&compute_pl.fallback_field_view, |image| &image.texture_view)
+                };
                 buffers.compute_bind_group = Some(device.create_bind_group(
                     "vfx_compute_bind_group",
                     &compute_pl.bind_group_layout,
@@ -187,6 +217,18 @@ pub fn prepare_vfx_buffers(
                             binding: 4,
                             resource: buffers.params_buffer.as_entire_binding(),
                         },
+                        BindGroupEntry {
+                            binding: 5,
+                            resource: buffers.event_buffer.as_entire_binding(),
+                        },
+                        BindGroupEntry {
+                            binding: 6,
+                            resource: BindingResource::TextureView(field_view(0)),
+                        },
+                        BindGroupEntry {
+                            binding: 7,
+                            resource: BindingResource::TextureView(field_view(1)),
+                        },
                     ],
                 ));
             }
@@ -239,6 +281,7 @@ pub fn prepare_vfx_buffers(
                 active_buffers.entries.push(ActiveEmitterEntry {
                     capacity: buffers.capacity,
                     counter_buffer: buffers.counter_buffer.clone(),
+                    event_buffer: buffers.event_buffer.clone(),
                     compute_bind_group: compute_bg.clone(),
                     billboard_bind_group: billboard_bg.clone(),
                 });
@@ -248,80 +291,81 @@ pub fn prepare_vfx_buffers(
 
     // Evict stale buffers for emitters that no longer exist (O(n) with HashSet)
     cache.buffers.retain(|k, _| active_keys.contains(k));
-}
 
-/// Compute how many particles to spawn this frame.
-fn compute_spawn_count(spawn: &SpawnModule, dt: f32, elapsed: f32) -> u32 {
-    match spawn {
-        SpawnModule::Rate(rate) => {
-            let exact = rate * dt;
-            let base = exact as u32;
-            let frac = exact - base as f32;
-            if (elapsed * 1000.0).fract() < frac {
-                base + 1
-            } else {
-                base
-            }
-        }
-        SpawnModule::Burst {
-            count,
-            interval,
-            max_cycles,
-            offset,
-        } => {
-            if *interval <= 0.0 {
-                return 0;
-            }
-            let local = elapsed - offset;
-            if local < 0.0 {
-                return 0;
-            }
-            // Shift so first burst fires at local=0 (i.e. elapsed=offset),
-            // then every interval after that
-            let adjusted = local + *interval;
-            let cycle = (adjusted / interval) as u32;
-            let prev_adjusted = adjusted - dt;
-            let prev_cycle =
-                if prev_adjusted <= 0.0 { 0 } else { (prev_adjusted / interval) as u32 };
-            if cycle > prev_cycle {
-                if let Some(max) = max_cycles {
-                    if cycle > *max {
-                        return 0;
+    // Sub-emitter links: each child gets a bind group with its parent's event buffer
+    if let Some(compute_pl) = compute_pipelines.filter(|_| pipelines_ready) {
+        for info in &extracted.emitters {
+            let parent_key = (info.source_entity, info.emitter_index);
+            let Some(parent_events) = cache.buffers.get(&parent_key).map(|b| b.event_buffer.clone())
+            else {
+                continue;
+            };
+            let mut children: Vec<usize> = info
+                .emitter
+                .sub_emitters
+                .iter()
+                .map(|link| link.emitter)
+                .filter(|child| *child != info.emitter_index)
+                .take(MAX_SUB_EMITTERS)
+                .collect();
+            children.sort_unstable();
+            children.dedup();
+
+            for child in children {
+                let Some(buffers) = cache.buffers.get_mut(&(info.source_entity, child)) else {
+                    continue;
+                };
+                let cached = buffers
+                    .sub_spawn_bind_groups
+                    .get(&info.emitter_index)
+                    .filter(|(id, _)| *id == parent_events.id());
+                let bind_group = match cached {
+                    Some((_, bind_group)) => bind_group.clone(),
+                    None => {
+                        let bind_group = device.create_bind_group(
+                            "vfx_sub_spawn_bind_group",
+                            &compute_pl.bind_group_layout,
+                            &BindGroupEntries::sequential((
+                                buffers.particle_buffer.as_entire_binding(),
+                                buffers.alive_buffer.as_entire_binding(),
+                                buffers.dead_buffer.as_entire_binding(),
+                                buffers.counter_buffer.as_entire_binding(),
+                                buffers.params_buffer.as_entire_binding(),
+                                parent_events.as_entire_binding(),
+                                // The sub-spawn pass never samples fields
+                                &compute_pl.fallback_field_view,
+                                &compute_pl.fallback_field_view,
+                            )),
+                        );
+                        buffers
+                            .sub_spawn_bind_groups
+                            .insert(info.emitter_index, (parent_events.id(), bind_group.clone()));
+                        bind_group
                     }
-                }
-                *count
-            } else {
-                0
-            }
-        }
-        SpawnModule::Once { count, offset } => {
-            let local = elapsed - offset;
-            if local >= 0.0 && local < dt * 2.0 {
-                *count
-            } else {
-                0
+                };
+                active_buffers.sub_spawns.push(bind_group);
             }
         }
-        SpawnModule::Distance { .. } => 0,
     }
 }
 
+/// Derive a per-emitter RNG salt from entity bits + emitter index so
+/// identically-configured emitters at the same time don't stack particles.
+pub(crate) fn emitter_seed(entity_bits: u64, emitter_index: usize) -> u32 {
+    entity_bits
+        .wrapping_mul(2654435761) // Knuth multiplicative hash
+        .wrapping_add(emitter_index as u64 * 1073741827) as u32
+}
+
 /// Pack an EmitterDef into the GPU-side GpuEmitterParams struct.
-fn pack_emitter_params(
+pub(crate) fn pack_emitter_params(
     emitter: &EmitterDef,
+    emitter_index: usize,
     transform: &GlobalTransform,
     dt: f32,
     time: f32,
-    key: (Entity, usize),
+    emitter_seed: u32,
 ) -> GpuEmitterParams {
-    // Derive a per-emitter seed from entity bits + emitter index so
-    // identically-configured emitters at the same time don't stack particles.
-    let entity_bits = key.0.to_bits();
-    let emitter_seed = entity_bits
-        .wrapping_mul(2654435761) // Knuth multiplicative hash
-        .wrapping_add(key.1 as u64 * 1073741827)
-        as u32;
-
     let mut params = GpuEmitterParams {
         shape_type: 0,
         _pad0: [0; 3],
@@ -392,9 +436,41 @@ fn pack_emitter_params(
         _pad13: [0.0; 3],
         emitter_transform: transform.to_matrix().to_cols_array_2d(),
         emitter_seed,
-        _pad_tail: [0.0; 7],
+        collision_count: 0,
+        collision_bounce: 0.0,
+        collision_friction: 0.0,
+        collision_radius: 0.0,
+        collision_kill: 0,
+        _pad14: [0.0; 2],
+        collision_shapes: [[0.0; 4]; MAX_COLLISION_SHAPES * 2],
+        emitter_index: emitter_index as u32,
+        sub_emitter_count: 0,
+        _pad15: [0.0; 2],
+        sub_emitters: [[0.0; 4]; MAX_SUB_EMITTERS * 2],
+        field_min: [0.0; 3],
+        field_strength: 0.0,
+        field_max: [0.0; 3],
+        field_tightness: 0.0,
+        sdf_min: [0.0; 3],
+        sdf_surface: 0.0,
+        sdf_max: [0.0; 3],
+        sdf_attraction: 0.0,
+        sdf_damping: 0.0,
+        _pad16: [0.0; 3],
     };
 
+    // Pack sub-emitter links (self-links are ignored)
+    for link in emitter.sub_emitters.iter().filter(|l| l.emitter != emitter_index) {
+        let i = params.sub_emitter_count as usize;
+        if i >= MAX_SUB_EMITTERS {
+            break;
+        }
+        let [a, b] = link.pack_for_gpu();
+        params.sub_emitters[i * 2] = a;
+        params.sub_emitters[i * 2 + 1] = b;
+        params.sub_emitter_count += 1;
+    }
+
     // Pack init modules
     for module in &emitter.init {
         match module {
@@ -547,6 +623,42 @@ fn pack_emitter_params(
                 params.radial_accel_value = *accel;
                 params.flags |= FLAG_RADIAL_ACCEL;
             }
+            UpdateModule::Collide {
+                shapes,
+                radius,
+                bounce,
+                friction,
+                kill_on_hit,
+                ..
+            } => {
+                params.collision_radius = *radius;
+                params.collision_bounce = *bounce;
+                params.collision_friction = *friction;
+                params.collision_kill = if *kill_on_hit { 1 } else { 0 };
+                append_collision_shapes(&mut params, shapes);
+                params.flags |= FLAG_COLLIDE;
+            }
+            // Bounds come from the loaded field (see apply_field_bounds)
+            UpdateModule::VectorField {
+                strength,
+                tightness,
+                ..
+            } => {
+                params.field_strength = *strength;
+                params.field_tightness = *tightness;
+                params.flags |= FLAG_VECTOR_FIELD;
+            }
+            UpdateModule::ConformToSdf {
+                surface,
+                attraction,
+                damping,
+                ..
+            } => {
+                params.sdf_surface = *surface;
+                params.sdf_attraction = *attraction;
+                params.sdf_damping = *damping;
+                params.flags |= FLAG_CONFORM_SDF;
+            }
             // CPU-only modules (mesh particles)
             UpdateModule::Spin { .. }
             | UpdateModule::UvScroll { .. }
@@ -584,6 +696,43 @@ fn pack_emitter_params(
     params
 }
 
+/// Append collision shapes after any already packed, dropping those past
+/// `MAX_COLLISION_SHAPES`.
+pub(crate) fn append_collision_shapes(params: &mut GpuEmitterParams, shapes: &[CollisionShape]) {
+    for shape in shapes {
+        let i = params.collision_count as usize;
+        if i >= MAX_COLLISION_SHAPES {
+            break;
+        }
+        let [a, b] = shape.pack_for_gpu();
+        params.collision_shapes[i * 2] = a;
+        params.collision_shapes[i * 2 + 1] = b;
+        params.collision_count += 1;
+    }
+}
+
+/// Set the bounds of the `VectorField` / `ConformToSdf` fields, by slot
+/// (see `emitter_field_paths`). Modules whose field isn't loaded are disabled.
+pub(crate) fn apply_field_bounds(
+    params: &mut GpuEmitterParams,
+    bounds: [Option<(Vec3, Vec3)>; FIELD_SLOTS],
+) {
+    match bounds[0] {
+        Some((min, max)) => {
+            params.field_min = min.to_array();
+            params.field_max = max.to_array();
+        }
+        None => params.flags &= !FLAG_VECTOR_FIELD,
+    }
+    match bounds[1] {
+        Some((min, max)) => {
+            params.sdf_min = min.to_array();
+            params.sdf_max = max.to_array();
+        }
+        None => params.flags &= !FLAG_CONFORM_SDF,
+    }
+}
+
 fn pack_shape_emitter(params: &mut GpuEmitterParams, shape: &ShapeEmitter) {
     match shape {
         ShapeEmitter::Point(p) => {
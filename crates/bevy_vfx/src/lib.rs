@@ -5,6 +5,9 @@
 //! All particle simulation runs on the GPU via compute shaders. The CPU only
 //! uploads emitter parameters and spawn counts — no particle data is ever
 //! read back. This design scales to hundreds of thousands of particles.
+//! [`cpu_sim::CpuVfxSim`] is a seeded CPU reference of the same simulation
+//! for headless tests, and drives the CPU fallback
+//! ([`render::fallback`]) on devices without compute shaders.
 //!
 //! ## Quick Start
 //!
@@ -27,9 +30,31 @@
 //!     ));
 //! }
 //! ```
+//!
+//! ## Parameters
+//!
+//! Emitter fields can be bound to named [`VfxParam`]s on the system. Game code
+//! sets per-entity values with [`VfxParamOverrides`] instead of cloning presets:
+//!
+//! ```ignore
+//! commands.spawn((
+//!     library.effects["Explosion"].clone(),
+//!     VfxParamOverrides::default().with("debris", VfxParamValue::Float(damage * 2.0)),
+//!     Transform::from_translation(hit_point),
+//! ));
+//! ```
+//!
+//! ## Vector fields
+//!
+//! `UpdateModule::VectorField` and `UpdateModule::ConformToSdf` sample a
+//! [`VectorField`] asset by path (`.vfield` binary or `.vfield.ron`), so
+//! smoke can flow around level geometry baked with
+//! [`VectorField::bake_from_boxes`].
 
+pub mod cpu_sim;
 pub mod curve;
 pub mod data;
+pub mod field;
 pub mod gpu;
 pub mod mesh_particles;
 pub mod presets;
@@ -38,6 +63,8 @@ pub mod render;
 // Re-export core types
 pub use curve::{Curve, CurveKey, Gradient, GradientKey, Interp};
 pub use data::*;
+pub use field::{VectorField, VfxFieldCache};
+pub use gpu::extract::{static_collider_boxes, SceneColliderFilter};
 
 use bevy::core_pipeline::core_3d::graph::{Core3d, Node3d};
 use bevy::prelude::*;
@@ -45,12 +72,14 @@ use bevy::render::render_graph::{RenderGraph, RenderGraphExt, ViewNodeRunner};
 use bevy::render::render_resource::PipelineCache;
 use bevy::render::renderer::{RenderDevice, RenderQueue};
 use bevy::render::{Render, RenderApp, RenderSystems};
+use bevy::transform::TransformSystems;
 
 use gpu::extract::{extract_vfx_systems, load_vfx_textures, VfxTextureCache};
 use gpu::node::{VfxComputeLabel, VfxComputeNode};
 use gpu::pipeline::VfxComputePipelines;
 use gpu::prepare::prepare_vfx_buffers;
 use render::billboard::{VfxBillboardLabel, VfxBillboardNode, VfxBillboardPipeline};
+use render::fallback::VfxCpuFallback;
 
 /// Embedded shader handles.
 const COMMON_SHADER: &str = include_str!("shaders/common.wgsl");
@@ -117,6 +146,9 @@ impl Plugin for VfxPlugin {
             .register_type::<VelocityMode>()
             .register_type::<ColorSource>()
             .register_type::<KillShape>()
+            .register_type::<CollisionShape>()
+            .register_type::<SubEmitter>()
+            .register_type::<SubEmitterTrigger>()
             .register_type::<BillboardConfig>()
             .register_type::<BillboardOrient>()
             .register_type::<FlipbookConfig>()
@@ -127,6 +159,9 @@ impl Plugin for VfxPlugin {
             .register_type::<OrientMode>()
             .register_type::<VfxParam>()
             .register_type::<VfxParamValue>()
+            .register_type::<ParamBinding>()
+            .register_type::<ParamTarget>()
+            .register_type::<VfxParamOverrides>()
             .register_type::<Curve<f32>>()
             .register_type::<CurveKey<f32>>()
             .register_type::<Gradient>()
@@ -134,12 +169,23 @@ impl Plugin for VfxPlugin {
             .register_type::<Interp>()
             .init_resource::<VfxLibrary>()
             .init_resource::<VfxTextureCache>()
+            .init_resource::<VfxFieldCache>()
+            .init_asset::<VectorField>()
+            .init_asset_loader::<field::VectorFieldLoader>()
             .init_resource::<mesh_particles::MeshParticleAssets>()
-            .add_systems(PostUpdate, load_vfx_textures)
+            .add_systems(
+                PostUpdate,
+                (
+                    load_vfx_textures,
+                    field::load_vfx_fields,
+                    field::upload_vfx_field_images,
+                ),
+            )
             .add_systems(
                 Update,
                 (
                     vfx_init_start_time,
+                    cpu_sim::step_cpu_vfx_sims,
                     vfx_handle_restart,
                     mesh_particles::auto_insert_mesh_particle_state,
                     mesh_particles::cpu_mesh_particle_spawn,
@@ -150,6 +196,18 @@ impl Plugin for VfxPlugin {
                     mesh_particles::cpu_mesh_particle_cleanup,
                 )
                     .chain(),
+            )
+            .add_systems(
+                Update,
+                render::fallback::insert_fallback_sims
+                    .before(cpu_sim::step_cpu_vfx_sims)
+                    .run_if(resource_exists::<VfxCpuFallback>),
+            )
+            .add_systems(
+                PostUpdate,
+                render::fallback::draw_fallback_particles
+                    .after(TransformSystems::Propagate)
+                    .run_if(resource_exists::<VfxCpuFallback>),
             );
 
         // Set up render app if available
@@ -158,18 +216,31 @@ impl Plugin for VfxPlugin {
                 .init_resource::<gpu::buffers::ActiveEmitterBuffers>()
                 .init_resource::<gpu::extract::ExtractedVfxData>()
                 .init_resource::<gpu::prepare::EmitterBufferCache>();
+            // Pipelines are only created when the device supports compute shaders
             render_app.add_systems(
                 bevy::render::ExtractSchedule,
-                extract_vfx_systems,
+                extract_vfx_systems.run_if(resource_exists::<VfxComputePipelines>),
             );
             render_app.add_systems(
                 Render,
-                prepare_vfx_buffers.in_set(RenderSystems::Prepare),
+                prepare_vfx_buffers
+                    .in_set(RenderSystems::Prepare)
+                    .run_if(resource_exists::<VfxComputePipelines>),
             );
         }
     }
 
     fn finish(&self, app: &mut App) {
+        // Without compute shaders (e.g. WebGL2) particles are simulated and drawn on the CPU
+        let needs_fallback = app.get_sub_app(RenderApp).is_some_and(|render_app| {
+            render::fallback::needs_cpu_fallback(render_app.world().resource::<RenderDevice>())
+        });
+        if needs_fallback {
+            warn!("Compute shaders are unavailable, VFX particles fall back to the CPU");
+            app.init_resource::<VfxCpuFallback>();
+            return;
+        }
+
         // Load shaders into main world's Assets<Shader> (render world doesn't have this resource).
         // Compute shaders prepend common.wgsl; billboard shader is self-contained (uses
         // plain u32 counters instead of atomic<u32>, compatible with var<storage, read>).
@@ -10,6 +10,7 @@ use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
 
 use crate::data::*;
+use crate::field::{VectorField, VfxFieldCache};
 
 // ---------------------------------------------------------------------------
 // Components & resources
@@ -40,6 +41,21 @@ pub struct MeshParticleState {
     pub material_handle: Option<Handle<StandardMaterial>>,
     /// Per-emitter mesh handle.
     pub mesh_handle: Option<Handle<Mesh>>,
+    /// Child spawns queued by parent emitters' sub-emitter links.
+    pub pending_sub_spawns: Vec<PendingSubSpawn>,
+}
+
+/// A parent particle event waiting to spawn children on this emitter.
+#[derive(Clone, Debug)]
+pub struct PendingSubSpawn {
+    /// World-space position of the parent particle.
+    pub position: Vec3,
+    /// World-space parent velocity, already scaled by the inherit ratio.
+    pub velocity: Vec3,
+    /// Parent color, if the link inherits it.
+    pub color: Option<LinearRgba>,
+    /// Child particles to spawn.
+    pub count: u32,
 }
 
 /// A single CPU-simulated particle.
@@ -139,6 +155,7 @@ pub fn auto_insert_mesh_particle_state(
                         once_fired: false,
                         material_handle: None,
                         mesh_handle: None,
+                        pending_sub_spawns: Vec::new(),
                     });
                 }
             }
@@ -154,6 +171,7 @@ pub fn auto_insert_mesh_particle_state(
                     once_fired: false,
                     material_handle: None,
                     mesh_handle: None,
+                    pending_sub_spawns: Vec::new(),
                 })
                 .collect();
             commands
@@ -170,11 +188,20 @@ pub fn cpu_mesh_particle_spawn(
     mut assets: ResMut<MeshParticleAssets>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    mut query: Query<(Entity, &VfxSystem, &GlobalTransform, &mut MeshParticleStates)>,
+    mut query: Query<(
+        Entity,
+        &VfxSystem,
+        &GlobalTransform,
+        &mut MeshParticleStates,
+        Option<&VfxParamOverrides>,
+    )>,
 ) {
     let dt = time.delta_secs();
 
-    for (parent_entity, system, global_transform, mut states) in &mut query {
+    for (parent_entity, system, global_transform, mut states, overrides) in &mut query {
+        let system = system.resolved(overrides);
+        let to_local = global_transform.affine().inverse();
+        let mut queued = Vec::new();
         for state in &mut states.entries {
             let Some(emitter) = system.emitters.get(state.emitter_index) else {
                 continue;
@@ -188,7 +215,8 @@ pub fn cpu_mesh_particle_spawn(
 
             // Compute how many to spawn this frame
             let spawn_count = compute_spawn_count(&emitter.spawn, state, dt);
-            if spawn_count == 0 {
+            let pending = std::mem::take(&mut state.pending_sub_spawns);
+            if spawn_count == 0 && pending.is_empty() {
                 continue;
             }
 
@@ -259,7 +287,13 @@ pub fn cpu_mesh_particle_spawn(
 
             let emitter_pos = global_transform.translation();
 
-            for _ in 0..spawn_count {
+            // Regular spawns first, then children of queued parent events
+            let origins = std::iter::repeat_n(None, spawn_count as usize).chain(
+                pending
+                    .iter()
+                    .flat_map(|event| std::iter::repeat_n(Some(event), event.count as usize)),
+            );
+            for origin in origins {
                 if state.particles.len() >= emitter.capacity as usize {
                     break;
                 }
@@ -315,6 +349,15 @@ pub fn cpu_mesh_particle_spawn(
                     }
                 }
 
+                if let Some(event) = origin {
+                    velocity += if emitter.sim_space == SimSpace::Local {
+                        to_local.transform_vector3(event.velocity)
+                    } else {
+                        event.velocity
+                    };
+                    color = event.color.unwrap_or(color);
+                }
+
                 // Deferred: align to velocity after velocity is sampled
                 if orient_mode == Some(OrientMode::AlignVelocity) {
                     let dir = velocity.normalize_or_zero();
@@ -323,11 +366,14 @@ pub fn cpu_mesh_particle_spawn(
                     }
                 }
 
-                // World-space position
-                let world_pos = if emitter.sim_space == SimSpace::World {
-                    emitter_pos + position
-                } else {
-                    position
+                // World-space position (emitter-relative in Local mode)
+                let world_pos = match (origin, emitter.sim_space) {
+                    (Some(event), SimSpace::World) => event.position + position,
+                    (Some(event), SimSpace::Local) => {
+                        to_local.transform_point3(event.position) + position
+                    }
+                    (None, SimSpace::World) => emitter_pos + position,
+                    (None, SimSpace::Local) => position,
                 };
 
                 // Spawn child entity
@@ -391,8 +437,65 @@ pub fn cpu_mesh_particle_spawn(
                     orientation,
                     physics: use_physics,
                 });
+
+                // Children of sub-spawned particles don't fire birth links
+                if origin.is_none() {
+                    let (position, velocity) = match emitter.sim_space {
+                        SimSpace::World => (world_pos, velocity),
+                        SimSpace::Local => (
+                            global_transform.transform_point(world_pos),
+                            global_transform.affine().transform_vector3(velocity),
+                        ),
+                    };
+                    queue_sub_spawns(
+                        emitter,
+                        state.emitter_index,
+                        &|trigger| trigger == SubEmitterTrigger::Birth,
+                        position,
+                        velocity,
+                        color,
+                        &mut queued,
+                    );
+                }
             }
         }
+        deliver_sub_spawns(&mut states, queued);
+    }
+}
+
+/// Queue child spawns for every link on `emitter` whose trigger `fired`.
+/// `position` and `velocity` are world-space.
+fn queue_sub_spawns(
+    emitter: &EmitterDef,
+    emitter_index: usize,
+    fired: &dyn Fn(SubEmitterTrigger) -> bool,
+    position: Vec3,
+    velocity: Vec3,
+    color: LinearRgba,
+    queued: &mut Vec<(usize, PendingSubSpawn)>,
+) {
+    for link in &emitter.sub_emitters {
+        if link.emitter == emitter_index || !fired(link.trigger) {
+            continue;
+        }
+        queued.push((
+            link.emitter,
+            PendingSubSpawn {
+                position,
+                velocity: velocity * link.inherit_velocity,
+                color: link.inherit_color.then_some(color),
+                count: link.count,
+            },
+        ));
+    }
+}
+
+/// Hand queued child spawns to their emitters; links to non-mesh emitters are dropped.
+fn deliver_sub_spawns(states: &mut MeshParticleStates, queued: Vec<(usize, PendingSubSpawn)>) {
+    for (child, spawn) in queued {
+        if let Some(state) = states.entries.iter_mut().find(|s| s.emitter_index == child) {
+            state.pending_sub_spawns.push(spawn);
+        }
     }
 }
 
@@ -400,15 +503,48 @@ pub fn cpu_mesh_particle_spawn(
 pub fn cpu_mesh_particle_update(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(&VfxSystem, &mut MeshParticleStates)>,
+    mut query: Query<(
+        &VfxSystem,
+        &GlobalTransform,
+        &mut MeshParticleStates,
+        Option<&VfxParamOverrides>,
+    )>,
+    field_cache: Res<VfxFieldCache>,
+    field_assets: Res<Assets<VectorField>>,
 ) {
     let dt = time.delta_secs();
 
-    for (system, mut states) in &mut query {
+    for (system, global_transform, mut states, overrides) in &mut query {
+        let system = system.resolved(overrides);
+        let mut queued = Vec::new();
         for state in &mut states.entries {
             let Some(emitter) = system.emitters.get(state.emitter_index) else {
                 continue;
             };
+            let emitter_index = state.emitter_index;
+            let mut fire = |fired: &dyn Fn(SubEmitterTrigger) -> bool, p: &CpuParticle| {
+                if emitter.sub_emitters.is_empty() {
+                    return;
+                }
+                let (position, velocity) = match emitter.sim_space {
+                    SimSpace::World => (p.position, p.velocity),
+                    SimSpace::Local => (
+                        global_transform.transform_point(p.position),
+                        global_transform.affine().transform_vector3(p.velocity),
+                    ),
+                };
+                queue_sub_spawns(
+                    emitter,
+                    emitter_index,
+                    fired,
+                    position,
+                    velocity,
+                    p.color,
+                    &mut queued,
+                );
+            };
+            let is_death = |t: SubEmitterTrigger| t == SubEmitterTrigger::Death;
+            let is_collision = |t: SubEmitterTrigger| t == SubEmitterTrigger::Collision;
 
             let mut dead = Vec::new();
 
@@ -416,10 +552,23 @@ pub fn cpu_mesh_particle_update(
                 p.age += dt;
 
                 if p.age >= p.lifetime {
+                    fire(&is_death, p);
                     dead.push(i);
                     continue;
                 }
 
+                let prev_age = p.age - dt;
+                let age = p.age;
+                fire(
+                    &|t| match t {
+                        SubEmitterTrigger::Interval(interval) => {
+                            SubEmitterTrigger::interval_crossed(interval, prev_age, age)
+                        }
+                        _ => false,
+                    },
+                    p,
+                );
+
                 let t = p.age / p.lifetime;
 
                 for update in &emitter.update {
@@ -487,6 +636,38 @@ pub fn cpu_mesh_particle_update(
                             let dir = (p.position - *origin).normalize_or_zero();
                             p.velocity += dir * *accel * dt;
                         }
+                        UpdateModule::VectorField {
+                            path,
+                            strength,
+                            tightness,
+                        } if !p.physics => {
+                            if let Some(field) = field_cache.field(path, &field_assets) {
+                                p.velocity = field.advect(
+                                    p.position,
+                                    p.velocity,
+                                    *strength,
+                                    *tightness,
+                                    dt,
+                                );
+                            }
+                        }
+                        UpdateModule::ConformToSdf {
+                            path,
+                            surface,
+                            attraction,
+                            damping,
+                        } if !p.physics => {
+                            if let Some(field) = field_cache.field(path, &field_assets) {
+                                p.velocity = field.conform(
+                                    p.position,
+                                    p.velocity,
+                                    *surface,
+                                    *attraction,
+                                    *damping,
+                                    dt,
+                                );
+                            }
+                        }
                         UpdateModule::KillZone { shape, invert } => {
                             let inside = match shape {
                                 KillShape::Sphere { center, radius } => {
@@ -504,6 +685,7 @@ pub fn cpu_mesh_particle_update(
                             };
                             let should_kill = if *invert { !inside } else { inside };
                             if should_kill {
+                                fire(&is_death, p);
                                 p.age = p.lifetime;
                                 dead.push(i);
                             }
@@ -556,6 +738,44 @@ pub fn cpu_mesh_particle_update(
 
                 if !p.physics {
                     p.position += p.velocity * dt;
+
+                    // Physics particles collide through Avian instead
+                    for update in &emitter.update {
+                        if let UpdateModule::Collide {
+                            shapes,
+                            radius,
+                            bounce,
+                            friction,
+                            kill_on_hit,
+                            ..
+                        } = update
+                        {
+                            for shape in shapes {
+                                let (distance, n) = shape.signed_distance(p.position);
+                                let penetration = distance - radius;
+                                if penetration >= 0.0 {
+                                    continue;
+                                }
+                                if *kill_on_hit {
+                                    if p.age < p.lifetime {
+                                        fire(&is_collision, p);
+                                        fire(&is_death, p);
+                                        p.age = p.lifetime;
+                                        dead.push(i);
+                                    }
+                                    break;
+                                }
+                                p.position -= n * penetration;
+                                let vn = p.velocity.dot(n);
+                                if vn < 0.0 {
+                                    fire(&is_collision, p);
+                                    let tangential = p.velocity - n * vn;
+                                    p.velocity =
+                                        tangential * (1.0 - friction) - n * vn * *bounce;
+                                }
+                            }
+                        }
+                    }
                 }
             }
 
@@ -566,18 +786,20 @@ pub fn cpu_mesh_particle_update(
                 commands.entity(p.entity).try_despawn();
             }
         }
+        deliver_sub_spawns(&mut states, queued);
     }
 }
 
 /// Sync particle state to Transform and material on child entities.
 pub fn cpu_mesh_particle_sync(
-    mut query: Query<(&VfxSystem, &mut MeshParticleStates)>,
+    mut query: Query<(&VfxSystem, &mut MeshParticleStates, Option<&VfxParamOverrides>)>,
     mut transforms: Query<&mut Transform>,
     camera: Query<&GlobalTransform, With<Camera3d>>,
 ) {
     let camera_pos = camera.iter().next().map(|gt| gt.translation());
 
-    for (system, mut states) in &mut query {
+    for (system, mut states, overrides) in &mut query {
+        let system = system.resolved(overrides);
         for state in &mut states.entries {
             let Some(emitter) = system.emitters.get(state.emitter_index) else {
                 continue;
@@ -629,11 +851,12 @@ pub fn cpu_mesh_particle_sync(
 /// Animate UV scrolling on mesh particle materials (non-library StandardMaterial).
 /// Each particle's UV offset is based on its age (time since spawn).
 pub fn cpu_mesh_particle_uv_scroll(
-    query: Query<(&VfxSystem, &MeshParticleStates)>,
+    query: Query<(&VfxSystem, &MeshParticleStates, Option<&VfxParamOverrides>)>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     particle_mats: Query<&MeshMaterial3d<StandardMaterial>, With<MeshParticleChild>>,
 ) {
-    for (system, states) in &query {
+    for (system, states, overrides) in &query {
+        let system = system.resolved(overrides);
         for state in &states.entries {
             let Some(emitter) = system.emitters.get(state.emitter_index) else {
                 continue;
@@ -683,11 +906,12 @@ pub fn cpu_mesh_particle_uv_scroll(
 /// Sync particle color and emissive to mesh particle materials.
 /// Applies ColorByLife → base_color and EmissiveOverLife → emissive on each particle's material.
 pub fn cpu_mesh_particle_color_sync(
-    query: Query<(&VfxSystem, &MeshParticleStates)>,
+    query: Query<(&VfxSystem, &MeshParticleStates, Option<&VfxParamOverrides>)>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     particle_mats: Query<&MeshMaterial3d<StandardMaterial>, With<MeshParticleChild>>,
 ) {
-    for (system, states) in &query {
+    for (system, states, overrides) in &query {
+        let system = system.resolved(overrides);
         for state in &states.entries {
             let Some(emitter) = system.emitters.get(state.emitter_index) else {
                 continue;
@@ -25,6 +25,7 @@ pub fn default_presets() -> Vec<(&'static str, VfxSystem)> {
         ("Waterfall", waterfall()),
         ("Campfire", campfire()),
         ("Rock Debris", rock_debris()),
+        ("Fireworks", fireworks()),
     ]
 }
 
@@ -74,6 +75,8 @@ fn fire() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Flame body — main visible fire shapes
             EmitterDef {
@@ -124,6 +127,8 @@ fn fire() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Flame tips — tall thin tongues that lick upward
             EmitterDef {
@@ -173,6 +178,8 @@ fn fire() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
         ],
         params: Vec::new(),
@@ -235,6 +242,8 @@ fn smoke() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Blend,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -296,6 +305,8 @@ fn sparks() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Additive,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -354,6 +365,8 @@ fn fountain() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Blend,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -418,6 +431,8 @@ fn magic_orb() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Additive,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -472,6 +487,8 @@ fn fireflies() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Additive,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -518,6 +535,8 @@ fn explosion() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Fireball
             EmitterDef {
@@ -560,6 +579,8 @@ fn explosion() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Debris sparks
             EmitterDef {
@@ -602,6 +623,11 @@ fn explosion() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: vec![ParamBinding {
+                    param: "debris".to_string(),
+                    target: ParamTarget::SpawnCount,
+                }],
+                sub_emitters: Vec::new(),
             },
             // Smoke cloud
             EmitterDef {
@@ -644,9 +670,14 @@ fn explosion() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Blend,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
         ],
-        params: Vec::new(),
+        params: vec![VfxParam {
+            name: "debris".to_string(),
+            value: VfxParamValue::Float(80.0),
+        }],
         duration: 3.0,
         looping: false,
     }
@@ -701,6 +732,8 @@ fn rain() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Blend,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -757,6 +790,8 @@ fn portal() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Center glow
             EmitterDef {
@@ -787,6 +822,8 @@ fn portal() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
         ],
         params: Vec::new(),
@@ -843,6 +880,8 @@ fn embers() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Additive,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -889,6 +928,8 @@ fn dust_motes() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Blend,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -946,6 +987,8 @@ fn flamethrower() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Additive,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -1003,6 +1046,8 @@ fn heal_aura() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Ground ring
             EmitterDef {
@@ -1044,6 +1089,8 @@ fn heal_aura() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
         ],
         params: Vec::new(),
@@ -1107,6 +1154,8 @@ fn waterfall() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Blend,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Mist at base
             EmitterDef {
@@ -1149,6 +1198,8 @@ fn waterfall() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Blend,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
         ],
         params: Vec::new(),
@@ -1203,6 +1254,8 @@ fn campfire() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Flame body — main billowy fire shapes
             EmitterDef {
@@ -1253,6 +1306,8 @@ fn campfire() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Flame wisps — thin licking flame tongues
             EmitterDef {
@@ -1302,6 +1357,8 @@ fn campfire() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Embers — small glowing particles drifting upward
             EmitterDef {
@@ -1349,6 +1406,8 @@ fn campfire() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
             // Smoke — soft billowing smoke above the flames
             EmitterDef {
@@ -1397,6 +1456,8 @@ fn campfire() -> VfxSystem {
                 }),
                 sim_space: SimSpace::Local,
                 alpha_mode: VfxAlphaMode::Blend,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
             },
         ],
         params: Vec::new(),
@@ -1452,6 +1513,8 @@ fn rock_debris() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Opaque,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
@@ -1513,9 +1576,226 @@ fn snow() -> VfxSystem {
             }),
             sim_space: SimSpace::Local,
             alpha_mode: VfxAlphaMode::Blend,
+            bindings: Vec::new(),
+            sub_emitters: Vec::new(),
         }],
         params: Vec::new(),
         duration: 0.0,
         looping: true,
     }
 }
+
+/// Firework shells that leave a spark trail and burst on death. The trail
+/// and burst emitters never spawn on their own — they are driven entirely by
+/// the shell's sub-emitter links.
+fn fireworks() -> VfxSystem {
+    VfxSystem {
+        emitters: vec![
+            // Shell — one rising particle per launch
+            EmitterDef {
+                name: "Shell".to_string(),
+                enabled: true,
+                capacity: 8,
+                spawn: SpawnModule::Burst {
+                    count: 1,
+                    interval: 1.5,
+                    max_cycles: None,
+                    offset: 0.0,
+                },
+                init: vec![
+                    InitModule::SetLifetime(ScalarRange::Random(0.9, 1.2)),
+                    InitModule::SetPosition(ShapeEmitter::Point(Vec3::ZERO)),
+                    InitModule::SetVelocity(VelocityMode::Cone {
+                        direction: Vec3::Y,
+                        angle: 0.2,
+                        speed: ScalarRange::Random(10.0, 13.0),
+                    }),
+                    InitModule::SetSize(ScalarRange::Constant(0.12)),
+                ],
+                update: vec![
+                    UpdateModule::Gravity(Vec3::new(0.0, -6.0, 0.0)),
+                    UpdateModule::ColorByLife(Gradient {
+                        keys: vec![
+                            GradientKey { time: 0.0, color: LinearRgba::new(3.0, 2.2, 1.2, 1.0) },
+                            GradientKey { time: 1.0, color: LinearRgba::new(2.0, 1.2, 0.5, 1.0) },
+                        ],
+                    }),
+                ],
+                render: RenderModule::Billboard(BillboardConfig {
+                    texture: Some("textures/particles/light_02.png".into()),
+                    ..default()
+                }),
+                sim_space: SimSpace::World,
+                alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: vec![
+                    SubEmitter {
+                        emitter: 1,
+                        trigger: SubEmitterTrigger::Interval(0.03),
+                        count: 1,
+                        inherit_velocity: 0.0,
+                        inherit_color: false,
+                    },
+                    SubEmitter {
+                        emitter: 2,
+                        trigger: SubEmitterTrigger::Death,
+                        count: 80,
+                        inherit_velocity: 0.2,
+                        inherit_color: false,
+                    },
+                ],
+            },
+            // Trail — short-lived sparks dropped behind the shell
+            EmitterDef {
+                name: "Trail".to_string(),
+                enabled: true,
+                capacity: 256,
+                spawn: SpawnModule::Rate(0.0),
+                init: vec![
+                    InitModule::SetLifetime(ScalarRange::Random(0.3, 0.5)),
+                    InitModule::SetPosition(ShapeEmitter::Point(Vec3::ZERO)),
+                    InitModule::SetVelocity(VelocityMode::Random {
+                        speed: ScalarRange::Random(0.0, 0.3),
+                    }),
+                    InitModule::SetSize(ScalarRange::Random(0.04, 0.07)),
+                ],
+                update: vec![
+                    UpdateModule::Gravity(Vec3::new(0.0, -1.0, 0.0)),
+                    UpdateModule::ColorByLife(Gradient {
+                        keys: vec![
+                            GradientKey { time: 0.0, color: LinearRgba::new(2.0, 1.2, 0.4, 1.0) },
+                            GradientKey { time: 1.0, color: LinearRgba::new(0.8, 0.2, 0.0, 0.0) },
+                        ],
+                    }),
+                ],
+                render: RenderModule::Billboard(BillboardConfig {
+                    texture: Some("textures/particles/light_02.png".into()),
+                    ..default()
+                }),
+                sim_space: SimSpace::World,
+                alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
+            },
+            // Burst — the star shell, spawned where the shell dies
+            EmitterDef {
+                name: "Burst".to_string(),
+                enabled: true,
+                capacity: 512,
+                spawn: SpawnModule::Rate(0.0),
+                init: vec![
+                    InitModule::SetLifetime(ScalarRange::Random(1.0, 1.6)),
+                    InitModule::SetPosition(ShapeEmitter::Point(Vec3::ZERO)),
+                    InitModule::SetVelocity(VelocityMode::Random {
+                        speed: ScalarRange::Random(4.0, 7.0),
+                    }),
+                    InitModule::SetSize(ScalarRange::Random(0.06, 0.1)),
+                ],
+                update: vec![
+                    UpdateModule::Gravity(Vec3::new(0.0, -3.0, 0.0)),
+                    UpdateModule::Drag(1.2),
+                    UpdateModule::ColorByLife(Gradient {
+                        keys: vec![
+                            GradientKey { time: 0.0, color: LinearRgba::new(4.0, 3.0, 3.5, 1.0) },
+                            GradientKey { time: 0.3, color: LinearRgba::new(3.0, 0.6, 1.8, 1.0) },
+                            GradientKey { time: 1.0, color: LinearRgba::new(1.0, 0.3, 0.1, 0.0) },
+                        ],
+                    }),
+                ],
+                render: RenderModule::Billboard(BillboardConfig {
+                    texture: Some("textures/particles/star_01.png".into()),
+                    ..default()
+                }),
+                sim_space: SimSpace::World,
+                alpha_mode: VfxAlphaMode::Additive,
+                bindings: Vec::new(),
+                sub_emitters: Vec::new(),
+            },
+        ],
+        params: Vec::new(),
+        duration: 0.0,
+        looping: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu_sim::CpuVfxSim;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn preset(name: &str) -> VfxSystem {
+        default_presets()
+            .into_iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, system)| system)
+            .expect("preset exists")
+    }
+
+    #[test]
+    fn test_presets_simulate_cleanly() {
+        for (name, system) in default_presets() {
+            let mut sim = CpuVfxSim::new(&system, 7);
+            let mut emitted = vec![false; system.emitters.len()];
+
+            for _ in 0..120 {
+                sim.step(DT);
+                for emitter in sim.emitters() {
+                    let label = format!("{name}/{}", emitter.emitter.name);
+                    assert!(
+                        emitter.particles.len() <= emitter.emitter.capacity as usize,
+                        "{label} exceeded its capacity"
+                    );
+                    for p in &emitter.particles {
+                        assert!(
+                            p.position.is_finite() && p.velocity.is_finite() && p.size.is_finite(),
+                            "{label} produced a non-finite particle"
+                        );
+                    }
+                    emitted[emitter.emitter_index] |= !emitter.particles.is_empty();
+                }
+            }
+
+            for (emitter, emitted) in system.emitters.iter().zip(emitted) {
+                assert!(
+                    !emitter.enabled || emitted,
+                    "{name}/{} never emitted",
+                    emitter.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_simulation_is_deterministic() {
+        let system = preset("Fire");
+        let mut a = CpuVfxSim::new(&system, 3);
+        let mut b = CpuVfxSim::new(&system, 3);
+        a.run(1.0, DT);
+        b.run(1.0, DT);
+
+        for (ea, eb) in a.emitters().iter().zip(b.emitters()) {
+            assert_eq!(ea.particles, eb.particles);
+        }
+    }
+
+    #[test]
+    fn test_explosion_debris_param() {
+        let system = preset("Explosion");
+        let debris = system
+            .emitters
+            .iter()
+            .position(|e| e.name == "Debris")
+            .expect("debris emitter");
+
+        let mut sim = CpuVfxSim::new(&system, 1);
+        sim.run(0.25, DT);
+        assert_eq!(sim.emitter(debris).unwrap().particles.len(), 80);
+
+        let overrides = VfxParamOverrides::default().with("debris", VfxParamValue::Float(20.0));
+        let mut sim = CpuVfxSim::new(&system.resolved(Some(&overrides)), 1);
+        sim.run(0.25, DT);
+        assert_eq!(sim.emitter(debris).unwrap().particles.len(), 20);
+    }
+}
@@ -0,0 +1,283 @@
+//! CPU fallback for devices without compute shaders (e.g. WebGL2).
+//!
+//! When the render device can't run the VFX compute passes, `VfxPlugin`
+//! skips the GPU pipeline and inserts [`VfxCpuFallback`]. Every `VfxSystem`
+//! then gets a [`CpuVfxSim`], and each billboard or ribbon emitter is drawn
+//! as one mesh of camera-facing quads rebuilt every frame, laid out like
+//! `billboard.wgsl`. Mesh emitters are CPU-driven either way.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::color::ColorToComponents;
+use bevy::camera::visibility::NoFrustumCulling;
+use bevy::light::NotShadowCaster;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::renderer::RenderDevice;
+
+use crate::cpu_sim::{CpuEmitterSim, CpuVfxSim};
+use crate::data::{RenderModule, VfxAlphaMode, VfxSystem};
+use crate::gpu::extract::VfxTextureCache;
+
+/// Quad corners and UVs, in the order of `QUAD_POSITIONS` / `QUAD_UVS` in
+/// `billboard.wgsl` (deduplicated to four vertices).
+const QUAD_CORNERS: [(Vec2, Vec2); 4] = [
+    (Vec2::new(-0.5, -0.5), Vec2::new(0.0, 1.0)),
+    (Vec2::new(0.5, -0.5), Vec2::new(1.0, 1.0)),
+    (Vec2::new(0.5, 0.5), Vec2::new(1.0, 0.0)),
+    (Vec2::new(-0.5, 0.5), Vec2::new(0.0, 0.0)),
+];
+
+/// Resolution of the soft circle texture used for untextured particles.
+const CIRCLE_SIZE: u32 = 64;
+
+/// Present when particles are simulated and drawn on the CPU because the
+/// render device has no compute shader support.
+#[derive(Resource, Default)]
+pub struct VfxCpuFallback {
+    /// Soft circle for untextured particles (the billboard shader's mask).
+    circle: Option<Handle<Image>>,
+}
+
+/// Mesh drawing the particles of one emitter of a `VfxSystem` entity.
+#[derive(Component)]
+pub struct VfxFallbackMesh {
+    pub system: Entity,
+    pub emitter_index: usize,
+}
+
+/// Whether `device` lacks the compute shaders the GPU simulation needs.
+pub fn needs_cpu_fallback(device: &RenderDevice) -> bool {
+    device.limits().max_compute_workgroups_per_dimension == 0
+}
+
+/// Give every `VfxSystem` a `CpuVfxSim` to draw from.
+pub fn insert_fallback_sims(
+    mut commands: Commands,
+    query: Query<(Entity, &VfxSystem), Without<CpuVfxSim>>,
+) {
+    for (entity, system) in &query {
+        commands
+            .entity(entity)
+            .insert(CpuVfxSim::new(system, entity.to_bits()));
+    }
+}
+
+/// Rebuild the quad mesh of every simulated billboard and ribbon emitter,
+/// facing the active 3D camera.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_fallback_particles(
+    mut commands: Commands,
+    mut fallback: ResMut<VfxCpuFallback>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    textures: Res<VfxTextureCache>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    sims: Query<(Entity, &CpuVfxSim)>,
+    mut drawn: Query<(
+        Entity,
+        &VfxFallbackMesh,
+        &Mesh3d,
+        &MeshMaterial3d<StandardMaterial>,
+        &mut Visibility,
+    )>,
+) {
+    let Some((_, camera)) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order)
+    else {
+        return;
+    };
+    let circle = fallback
+        .circle
+        .get_or_insert_with(|| images.add(soft_circle_image()))
+        .clone();
+
+    let mut existing: HashMap<(Entity, usize), Entity> = drawn
+        .iter()
+        .map(|(entity, target, ..)| ((target.system, target.emitter_index), entity))
+        .collect();
+
+    for (system, sim) in &sims {
+        for emitter in sim.emitters() {
+            let texture_path = match &emitter.emitter.render {
+                RenderModule::Billboard(config) => config.texture.as_ref(),
+                RenderModule::Ribbon(_) => None,
+                RenderModule::Mesh(_) => continue,
+            };
+            let texture = texture_path
+                .and_then(|path| textures.handles.get(path).cloned())
+                .unwrap_or_else(|| circle.clone());
+            let alpha_mode = alpha_mode(emitter.emitter.alpha_mode);
+
+            let Some(entity) = existing.remove(&(system, emitter.emitter_index)) else {
+                // An empty mesh can't be drawn; wait for the first particles
+                if emitter.particles.is_empty() {
+                    continue;
+                }
+                let mut mesh =
+                    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default());
+                write_quads(&mut mesh, emitter, camera);
+                commands.spawn((
+                    VfxFallbackMesh {
+                        system,
+                        emitter_index: emitter.emitter_index,
+                    },
+                    Mesh3d(meshes.add(mesh)),
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color_texture: Some(texture),
+                        alpha_mode,
+                        unlit: true,
+                        cull_mode: None,
+                        ..default()
+                    })),
+                    Transform::IDENTITY,
+                    Visibility::default(),
+                    NoFrustumCulling,
+                    NotShadowCaster,
+                ));
+                continue;
+            };
+
+            let Ok((_, _, mesh, material, mut visibility)) = drawn.get_mut(entity) else {
+                continue;
+            };
+            // Hide the last frame's quads rather than uploading an empty mesh
+            if emitter.particles.is_empty() {
+                visibility.set_if_neq(Visibility::Hidden);
+                continue;
+            }
+            visibility.set_if_neq(Visibility::Inherited);
+            if let Some(mesh) = meshes.get_mut(&mesh.0) {
+                write_quads(mesh, emitter, camera);
+            }
+            let outdated = materials.get(&material.0).is_some_and(|m| {
+                m.alpha_mode != alpha_mode || m.base_color_texture.as_ref() != Some(&texture)
+            });
+            if outdated {
+                if let Some(m) = materials.get_mut(&material.0) {
+                    m.alpha_mode = alpha_mode;
+                    m.base_color_texture = Some(texture);
+                }
+            }
+        }
+    }
+
+    // Emitters that were removed, disabled or lost their system
+    for entity in existing.into_values() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Fill `mesh` with one camera-facing quad per live particle.
+fn write_quads(mesh: &mut Mesh, emitter: &CpuEmitterSim, camera: &GlobalTransform) {
+    let (right, up) = (camera.right().as_vec3(), camera.up().as_vec3());
+    let normal = camera.back().as_vec3();
+    let count = emitter.particles.len();
+
+    let mut positions = Vec::with_capacity(count * 4);
+    let mut uvs = Vec::with_capacity(count * 4);
+    let mut colors = Vec::with_capacity(count * 4);
+    let mut indices = Vec::with_capacity(count * 6);
+    for particle in &emitter.particles {
+        let base = positions.len() as u32;
+        let (sin, cos) = particle.rotation.sin_cos();
+        for (corner, uv) in QUAD_CORNERS {
+            let local = corner * particle.size;
+            let rotated = Vec2::new(local.x * cos - local.y * sin, local.x * sin + local.y * cos);
+            positions.push((particle.position + right * rotated.x + up * rotated.y).to_array());
+            uvs.push(uv.to_array());
+            colors.push(particle.color.to_f32_array());
+        }
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![normal.to_array(); count * 4]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(indices));
+}
+
+fn alpha_mode(mode: VfxAlphaMode) -> AlphaMode {
+    match mode {
+        VfxAlphaMode::Blend => AlphaMode::Blend,
+        VfxAlphaMode::Additive => AlphaMode::Add,
+        VfxAlphaMode::Premultiply => AlphaMode::Premultiplied,
+        VfxAlphaMode::Multiply => AlphaMode::Multiply,
+        VfxAlphaMode::Opaque => AlphaMode::Opaque,
+    }
+}
+
+/// White circle with a soft edge, matching the untextured mask in `billboard.wgsl`.
+fn soft_circle_image() -> Image {
+    let mut data = Vec::with_capacity((CIRCLE_SIZE * CIRCLE_SIZE * 4) as usize);
+    for y in 0..CIRCLE_SIZE {
+        for x in 0..CIRCLE_SIZE {
+            let uv = (Vec2::new(x as f32, y as f32) + 0.5) / CIRCLE_SIZE as f32;
+            let t = ((uv.distance(Vec2::splat(0.5)) - 0.4) / 0.1).clamp(0.0, 1.0);
+            let mask = 1.0 - t * t * (3.0 - 2.0 * t);
+            data.extend_from_slice(&[255, 255, 255, (mask * 255.0).round() as u8]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: CIRCLE_SIZE,
+            height: CIRCLE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::*;
+    use bevy::mesh::VertexAttributeValues;
+
+    #[test]
+    fn test_quads_center_on_particles() {
+        let system = VfxSystem {
+            emitters: vec![EmitterDef {
+                spawn: SpawnModule::Once { offset: 0.0, count: 3 },
+                init: vec![InitModule::SetPosition(ShapeEmitter::Point(Vec3::new(1.0, 2.0, 3.0)))],
+                sim_space: SimSpace::World,
+                ..default()
+            }],
+            ..default()
+        };
+        let mut sim = CpuVfxSim::new(&system, 7);
+        sim.step(0.01);
+        let emitter = &sim.emitters()[0];
+        assert_eq!(emitter.particles.len(), 3);
+
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(0.0, 0.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default());
+        write_quads(&mut mesh, emitter, &camera);
+
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("quad positions missing");
+        };
+        assert_eq!(positions.len(), 12);
+        assert_eq!(mesh.indices().map(|i| i.len()), Some(18));
+        for (particle, corners) in emitter.particles.iter().zip(positions.chunks(4)) {
+            let corners: Vec<Vec3> = corners.iter().map(|&p| Vec3::from_array(p)).collect();
+            let center = corners.iter().sum::<Vec3>() / 4.0;
+            assert!(center.abs_diff_eq(particle.position, 1e-4));
+            assert!((corners[0].distance(corners[1]) - particle.size).abs() < 1e-4);
+            // Quads face the camera
+            assert!((corners[1] - corners[0]).dot(Vec3::Z).abs() < 1e-4);
+        }
+    }
+}
@@ -1,3 +1,4 @@
 //! VFX rendering pipelines.
 
 pub mod billboard;
+pub mod fallback;
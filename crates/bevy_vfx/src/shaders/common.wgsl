@@ -29,6 +29,11 @@ struct EmitterCounters {
 // Maximum curve/gradient keys (must match MAX_CURVE_KEYS / MAX_GRADIENT_KEYS)
 const MAX_CURVE_KEYS: u32 = 8u;
 const MAX_GRADIENT_KEYS: u32 = 8u;
+// Must match MAX_COLLISION_SHAPES
+const MAX_COLLISION_SHAPES: u32 = 8u;
+// Must match MAX_SUB_EMITTERS / MAX_SUB_EMITTER_EVENTS
+const MAX_SUB_EMITTERS: u32 = 4u;
+const MAX_SUB_EMITTER_EVENTS: u32 = 512u;
 
 // Must match GpuEmitterParams in buffers.rs
 struct EmitterParams {
@@ -109,7 +114,44 @@ struct EmitterParams {
     emitter_transform: mat4x4<f32>,
 
     emitter_seed: u32,
-    _pad: vec2<f32>,
+
+    collision_count: u32,
+    collision_bounce: f32,
+    collision_friction: f32,
+    collision_radius: f32,
+    collision_kill: u32,
+    collision_shapes: array<vec4<f32>, 16>, // 2 vec4 per shape: [type,x,y,z], [param]
+
+    emitter_index: u32,
+    sub_emitter_count: u32,
+    sub_emitters: array<vec4<f32>, 8>, // 2 vec4 per link: [trigger,child,count,interval], [inherit vel,inherit color,0,0]
+
+    field_min: vec3<f32>,
+    field_strength: f32,
+    field_max: vec3<f32>,
+    field_tightness: f32,
+    sdf_min: vec3<f32>,
+    sdf_surface: f32,
+    sdf_max: vec3<f32>,
+    sdf_attraction: f32,
+    sdf_damping: f32,
+};
+
+// Must match GpuSubEmitterEvent in buffers.rs
+struct SubEmitterEvent {
+    position: vec3<f32>,
+    child: u32,
+    velocity: vec3<f32>,
+    count: u32,
+    color: vec4<f32>, // alpha < 0 = child keeps its own color
+};
+
+struct SubEmitterEvents {
+    count: atomic<u32>,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+    events: array<SubEmitterEvent>,
 };
 
 // Bit flags for active update modules
@@ -126,6 +168,15 @@ const FLAG_SIZE_BY_SPEED: u32   = 512u;
 const FLAG_ROTATE_BY_VELOCITY: u32 = 1024u;
 const FLAG_TANGENT_ACCEL: u32   = 2048u;
 const FLAG_RADIAL_ACCEL: u32    = 4096u;
+const FLAG_COLLIDE: u32         = 8192u;
+const FLAG_VECTOR_FIELD: u32    = 16384u;
+const FLAG_CONFORM_SDF: u32     = 32768u;
+
+// Sub-emitter triggers (must match SubEmitterTrigger::variant_index)
+const SUB_TRIGGER_BIRTH: u32     = 0u;
+const SUB_TRIGGER_DEATH: u32     = 1u;
+const SUB_TRIGGER_COLLISION: u32 = 2u;
+const SUB_TRIGGER_INTERVAL: u32  = 3u;
 
 // ---------------------------------------------------------------------------
 // PCG random number generator
@@ -200,3 +251,91 @@ fn sample_curve(keys: array<vec4<f32>, 8>, count: u32, t: f32) -> f32 {
 
     return keys[count - 1u].y;
 }
+
+// ---------------------------------------------------------------------------
+// Collision shapes (must match CollisionShape::signed_distance)
+// ---------------------------------------------------------------------------
+
+// a = [type, position], b = [normal | radius | half extents]
+// Returns xyz = outward surface normal, w = signed distance (negative inside)
+fn collision_sdf(a: vec4<f32>, b: vec4<f32>, pos: vec3<f32>) -> vec4<f32> {
+    let shape_type = u32(a.x);
+    let rel = pos - a.yzw;
+
+    if shape_type == 1u {
+        // Sphere
+        let len = length(rel);
+        var n = vec3<f32>(0.0, 1.0, 0.0);
+        if len > 0.0001 {
+            n = rel / len;
+        }
+        return vec4<f32>(n, len - b.x);
+    }
+
+    if shape_type == 2u {
+        // Box
+        let q = abs(rel) - b.xyz;
+        if any(q > vec3<f32>(0.0)) {
+            let outside = max(q, vec3<f32>(0.0));
+            var n = vec3<f32>(0.0, 1.0, 0.0);
+            let dir = outside * sign(rel);
+            if length(dir) > 0.0 {
+                n = normalize(dir);
+            }
+            return vec4<f32>(n, length(outside));
+        }
+        // Inside: push out through the nearest face
+        var n = vec3<f32>(0.0, 0.0, select(1.0, -1.0, rel.z < 0.0));
+        if q.x >= q.y && q.x >= q.z {
+            n = vec3<f32>(select(1.0, -1.0, rel.x < 0.0), 0.0, 0.0);
+        } else if q.y >= q.z {
+            n = vec3<f32>(0.0, select(1.0, -1.0, rel.y < 0.0), 0.0);
+        }
+        return vec4<f32>(n, max(q.x, max(q.y, q.z)));
+    }
+
+    // Plane
+    var n = vec3<f32>(0.0, 1.0, 0.0);
+    if length(b.xyz) > 0.0 {
+        n = normalize(b.xyz);
+    }
+    return vec4<f32>(n, dot(rel, n));
+}
+
+// ---------------------------------------------------------------------------
+// Sub-emitter events
+// ---------------------------------------------------------------------------
+
+// Bound to the emitter's own event buffer in the spawn/update passes and to
+// the parent's event buffer in the sub-spawn pass.
+@group(0) @binding(5) var<storage, read_write> events: SubEmitterEvents;
+
+// Queue an event for every link with `trigger`. `prev_age` is only used by
+// interval links. Events past MAX_SUB_EMITTER_EVENTS are dropped.
+fn emit_sub_events(trigger: u32, p: Particle, prev_age: f32) {
+    let count = min(params.sub_emitter_count, MAX_SUB_EMITTERS);
+    for (var i = 0u; i < count; i++) {
+        let link = params.sub_emitters[i * 2u];
+        let inherit = params.sub_emitters[i * 2u + 1u];
+        if u32(link.x) != trigger {
+            continue;
+        }
+        if trigger == SUB_TRIGGER_INTERVAL {
+            let interval = link.w;
+            if interval <= 0.0 || floor(p.age / interval) == floor(prev_age / interval) {
+                continue;
+            }
+        }
+        let slot = atomicAdd(&events.count, 1u);
+        if slot >= MAX_SUB_EMITTER_EVENTS {
+            return;
+        }
+        var e: SubEmitterEvent;
+        e.position = p.position;
+        e.child = u32(link.y);
+        e.velocity = p.velocity * inherit.x;
+        e.count = u32(link.z);
+        e.color = select(vec4<f32>(0.0, 0.0, 0.0, -1.0), p.color, inherit.y != 0.0);
+        events.events[slot] = e;
+    }
+}
@@ -2,6 +2,9 @@
 //
 // Each thread pops one index from DeadBuffer (atomically), then initializes
 // the particle at that index using InitModule parameters from EmitterParams.
+//
+// The `sub_spawn` entry point runs after the update pass with a parent
+// emitter's event buffer bound, spawning child particles at each event.
 
 @group(0) @binding(0) var<storage, read_write> particles: array<Particle>;
 @group(0) @binding(1) var<storage, read_write> alive: array<u32>;
@@ -18,17 +21,65 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
         return;
     }
 
+    // Initialize RNG seed from thread_id + time + per-emitter salt
+    let seed = thread_id * 1973u + bitcast<u32>(params.time * 1000.0) + params.emitter_seed;
+    let p = init_particle(seed);
+    if store_particle(p) {
+        emit_sub_events(SUB_TRIGGER_BIRTH, p, 0.0);
+    }
+}
+
+// One thread per parent event; spawns the event's children around it.
+@compute @workgroup_size(64)
+fn sub_spawn(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let event_idx = gid.x;
+    if event_idx >= min(atomicLoad(&events.count), MAX_SUB_EMITTER_EVENTS) {
+        return;
+    }
+    let e = events.events[event_idx];
+    if e.child != params.emitter_index {
+        return;
+    }
+
+    // Move the spawn shape from the emitter to the parent particle
+    let offset = e.position - params.emitter_transform[3].xyz;
+    for (var i = 0u; i < e.count; i++) {
+        let seed = pcg_hash(event_idx * 4099u + i) + bitcast<u32>(params.time * 1000.0) + params.emitter_seed;
+        var p = init_particle(seed);
+        p.position += offset;
+        p.velocity += e.velocity;
+        if e.color.a >= 0.0 {
+            p.color = e.color;
+        }
+        if !store_particle(p) {
+            return;
+        }
+    }
+}
+
+// Pop a free slot and store `p` as alive. Returns false when the pool is empty.
+fn store_particle(p: Particle) -> bool {
     // Atomically decrement dead count and get the index
     let dead_idx = atomicSub(&counters.dead_count, 1u);
     if dead_idx == 0u {
         // No more free slots
         atomicAdd(&counters.dead_count, 1u);
-        return;
+        return false;
     }
     let slot = dead[dead_idx - 1u];
 
-    // Initialize RNG seed from thread_id + time + per-emitter salt
-    var seed = thread_id * 1973u + bitcast<u32>(params.time * 1000.0) + params.emitter_seed;
+    // Store the particle
+    particles[slot] = p;
+
+    // Add to alive list
+    let alive_idx = atomicAdd(&counters.alive_count, 1u);
+    alive[alive_idx] = slot;
+    return true;
+}
+
+// Initialize a particle from the emitter's init modules.
+fn init_particle(spawn_seed: u32) -> Particle {
+    var seed = spawn_seed;
 
     // Initialize particle
     var p: Particle;
@@ -148,10 +199,5 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
     // Rotation
     p.rotation = rand_range(&seed, params.init_rotation_min, params.init_rotation_max);
 
-    // Store the particle
-    particles[slot] = p;
-
-    // Add to alive list
-    let alive_idx = atomicAdd(&counters.alive_count, 1u);
-    alive[alive_idx] = slot;
+    return p;
 }
@@ -7,6 +7,46 @@
 @group(0) @binding(2) var<storage, read_write> dead: array<u32>;
 @group(0) @binding(3) var<storage, read_write> counters: EmitterCounters;
 @group(0) @binding(4) var<uniform> params: EmitterParams;
+// Rgba32Float fields: rgb = flow, a = signed distance (see VectorField::to_image)
+@group(0) @binding(6) var vector_field: texture_3d<f32>;
+@group(0) @binding(7) var sdf_field: texture_3d<f32>;
+
+// Trilinear sample with cells on the grid corners of [bounds_min, bounds_max],
+// clamped to the edges. Must match VectorField::sample.
+fn sample_field(field: texture_3d<f32>, bounds_min: vec3<f32>, bounds_max: vec3<f32>, pos: vec3<f32>) -> vec4<f32> {
+    let dims = max(vec3<i32>(textureDimensions(field)), vec3<i32>(1));
+    let extent = max(bounds_max - bounds_min, vec3<f32>(0.0001));
+    let g = clamp((pos - bounds_min) / extent, vec3<f32>(0.0), vec3<f32>(1.0)) * vec3<f32>(dims - 1);
+    let base = min(vec3<i32>(floor(g)), max(dims - 2, vec3<i32>(0)));
+    let f = g - vec3<f32>(base);
+    let hi = min(base + 1, dims - 1);
+
+    let c00 = mix(textureLoad(field, vec3<i32>(base.x, base.y, base.z), 0), textureLoad(field, vec3<i32>(hi.x, base.y, base.z), 0), f.x);
+    let c10 = mix(textureLoad(field, vec3<i32>(base.x, hi.y, base.z), 0), textureLoad(field, vec3<i32>(hi.x, hi.y, base.z), 0), f.x);
+    let c01 = mix(textureLoad(field, vec3<i32>(base.x, base.y, hi.z), 0), textureLoad(field, vec3<i32>(hi.x, base.y, hi.z), 0), f.x);
+    let c11 = mix(textureLoad(field, vec3<i32>(base.x, hi.y, hi.z), 0), textureLoad(field, vec3<i32>(hi.x, hi.y, hi.z), 0), f.x);
+    return mix(mix(c00, c10, f.y), mix(c01, c11, f.y), f.z);
+}
+
+// Outward normal of the SDF by central differences one cell apart.
+// Must match VectorField::distance_gradient.
+fn sdf_gradient(pos: vec3<f32>) -> vec3<f32> {
+    let steps = vec3<f32>(max(vec3<i32>(textureDimensions(sdf_field)), vec3<i32>(2)) - 1);
+    let h = max((params.sdf_max - params.sdf_min) / steps, vec3<f32>(0.0001));
+    let dx = vec3<f32>(h.x, 0.0, 0.0);
+    let dy = vec3<f32>(0.0, h.y, 0.0);
+    let dz = vec3<f32>(0.0, 0.0, h.z);
+    let grad = vec3<f32>(
+        sample_field(sdf_field, params.sdf_min, params.sdf_max, pos + dx).w - sample_field(sdf_field, params.sdf_min, params.sdf_max, pos - dx).w,
+        sample_field(sdf_field, params.sdf_min, params.sdf_max, pos + dy).w - sample_field(sdf_field, params.sdf_min, params.sdf_max, pos - dy).w,
+        sample_field(sdf_field, params.sdf_min, params.sdf_max, pos + dz).w - sample_field(sdf_field, params.sdf_min, params.sdf_max, pos - dz).w,
+    ) / h;
+    let len = length(grad);
+    if len > 0.0 {
+        return grad / len;
+    }
+    return vec3<f32>(0.0);
+}
 
 @compute @workgroup_size(64)
 fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
@@ -34,6 +74,7 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
 
     // Kill expired
     if p.age >= p.lifetime {
+        emit_sub_events(SUB_TRIGGER_DEATH, p, 0.0);
         // Push to dead buffer
         let dead_idx = atomicAdd(&counters.dead_count, 1u);
         dead[dead_idx] = idx;
@@ -143,9 +184,58 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
         p.velocity += noise_offset * params.noise_strength * dt;
     }
 
+    // Vector field (bounds offset by emitter position in Local mode)
+    if (params.flags & FLAG_VECTOR_FIELD) != 0u {
+        let flow = sample_field(vector_field, params.field_min, params.field_max, p.position - ref_origin).xyz * params.field_strength;
+        p.velocity = mix(p.velocity + flow * dt, flow, clamp(params.field_tightness, 0.0, 1.0));
+    }
+
+    // Conform to SDF: pull toward the surface level set, damp motion across it
+    if (params.flags & FLAG_CONFORM_SDF) != 0u {
+        let local_pos = p.position - ref_origin;
+        let distance = sample_field(sdf_field, params.sdf_min, params.sdf_max, local_pos).w - params.sdf_surface;
+        let n = sdf_gradient(local_pos);
+        p.velocity -= n * clamp(distance, -1.0, 1.0) * params.sdf_attraction * dt;
+        p.velocity -= n * dot(p.velocity, n) * min(params.sdf_damping * dt, 1.0);
+    }
+
     // Integrate position
     p.position += p.velocity * dt;
 
+    // Collide (shapes offset by emitter position in Local mode)
+    if (params.flags & FLAG_COLLIDE) != 0u {
+        let count = min(params.collision_count, MAX_COLLISION_SHAPES);
+        for (var i = 0u; i < count; i++) {
+            let hit = collision_sdf(
+                params.collision_shapes[i * 2u],
+                params.collision_shapes[i * 2u + 1u],
+                p.position - ref_origin,
+            );
+            let penetration = hit.w - params.collision_radius;
+            if penetration >= 0.0 {
+                continue;
+            }
+            if params.collision_kill != 0u {
+                emit_sub_events(SUB_TRIGGER_COLLISION, p, 0.0);
+                emit_sub_events(SUB_TRIGGER_DEATH, p, 0.0);
+                p.age = p.lifetime; // Mark as dead
+                let dead_idx = atomicAdd(&counters.dead_count, 1u);
+                dead[dead_idx] = idx;
+                particles[idx] = p;
+                return;
+            }
+            let n = hit.xyz;
+            p.position -= n * penetration;
+            let vn = dot(p.velocity, n);
+            if vn < 0.0 {
+                // Impact (resting contacts don't re-fire collision events)
+                emit_sub_events(SUB_TRIGGER_COLLISION, p, 0.0);
+                let tangential = p.velocity - n * vn;
+                p.velocity = tangential * (1.0 - params.collision_friction) - n * vn * params.collision_bounce;
+            }
+        }
+    }
+
     // Kill zone (center offset by emitter position in Local mode)
     if (params.flags & FLAG_KILL_ZONE) != 0u {
         let kz_center = params.kill_zone_center + ref_origin;
@@ -163,6 +253,7 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
         }
         let should_kill = select(inside, !inside, params.kill_zone_invert != 0u);
         if should_kill {
+            emit_sub_events(SUB_TRIGGER_DEATH, p, 0.0);
             p.age = p.lifetime; // Mark as dead
             let dead_idx = atomicAdd(&counters.dead_count, 1u);
             dead[dead_idx] = idx;
@@ -171,6 +262,9 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
         }
     }
 
+    // Interval sub-emitters (trails)
+    emit_sub_events(SUB_TRIGGER_INTERVAL, p, p.age - dt);
+
     // Size over life
     if (params.flags & FLAG_SIZE_BY_LIFE) != 0u {
         p.size = sample_curve(params.size_curve, params.size_curve_count, normalized_life);
@@ -0,0 +1,192 @@
+//! Scene sound emitters and their playback.
+//!
+//! `SoundEmitterMarker` is the serialized description of an emitter. Playback
+//! lives on a separate voice entity so scene snapshots never capture audio
+//! state. Emitters play while the game runs, and while editing only when
+//! sound preview is enabled.
+
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use bevy_editor_game::{GameCamera, GameState};
+use serde::{Deserialize, Serialize};
+
+use crate::editor::EditorCamera;
+
+/// Serializable marker component for sound emitter entities.
+#[derive(Component, Serialize, Deserialize, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct SoundEmitterMarker {
+    /// Audio asset path relative to `assets/`.
+    pub path: String,
+    /// Linear volume at the emitter.
+    pub volume: f32,
+    /// Loop the sound (ambience) instead of playing it once.
+    pub looping: bool,
+    /// Distance at which the sound has faded out; 0 disables falloff.
+    pub radius: f32,
+}
+
+impl Default for SoundEmitterMarker {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            volume: 1.0,
+            looping: true,
+            radius: 10.0,
+        }
+    }
+}
+
+/// Whether sound emitters play while editing.
+#[derive(Resource, Default)]
+pub struct SoundPreview {
+    pub enabled: bool,
+}
+
+/// Event to toggle editor sound preview
+#[derive(Message)]
+pub struct ToggleSoundPreviewEvent;
+
+/// Runtime entity playing a sound emitter (never serialized).
+#[derive(Component)]
+struct SoundVoice {
+    emitter: Entity,
+    path: String,
+    looping: bool,
+}
+
+/// Link from an emitter to the voice currently playing it.
+#[derive(Component)]
+struct SoundEmitterVoice(Entity);
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SoundEmitterMarker>()
+            .init_resource::<SoundPreview>()
+            .add_message::<ToggleSoundPreviewEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_toggle_sound_preview,
+                    sync_sound_voices,
+                    update_sound_voices,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Linear falloff from full volume at the emitter to silence at `radius`.
+pub fn distance_falloff(distance: f32, radius: f32) -> f32 {
+    if radius <= 0.0 {
+        return 1.0;
+    }
+    (1.0 - distance / radius).clamp(0.0, 1.0)
+}
+
+fn handle_toggle_sound_preview(
+    mut events: MessageReader<ToggleSoundPreviewEvent>,
+    mut preview: ResMut<SoundPreview>,
+) {
+    for _ in events.read() {
+        preview.enabled = !preview.enabled;
+        info!(
+            "Sound preview: {}",
+            if preview.enabled { "ON" } else { "OFF" }
+        );
+    }
+}
+
+/// Start, restart and stop voices so every audible emitter has exactly one.
+fn sync_sound_voices(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    preview: Res<SoundPreview>,
+    game_state: Res<State<GameState>>,
+    emitters: Query<(Entity, &SoundEmitterMarker, Option<&SoundEmitterVoice>)>,
+    voices: Query<(Entity, &SoundVoice)>,
+) {
+    let audible = *game_state.get() != GameState::Editing || preview.enabled;
+
+    // Emitters can vanish through deletion, undo or effect cleanup
+    for (voice_entity, voice) in &voices {
+        if !emitters.contains(voice.emitter) {
+            commands.entity(voice_entity).despawn();
+        }
+    }
+
+    for (entity, marker, link) in &emitters {
+        let wanted = audible && !marker.path.is_empty();
+        let current = link.and_then(|link| voices.get(link.0).ok());
+        let up_to_date = current
+            .is_some_and(|(_, voice)| voice.path == marker.path && voice.looping == marker.looping);
+        if wanted && up_to_date {
+            continue;
+        }
+
+        if let Some((voice_entity, _)) = current {
+            commands.entity(voice_entity).despawn();
+        }
+        if link.is_some() {
+            commands.entity(entity).remove::<SoundEmitterVoice>();
+        }
+        if !wanted {
+            continue;
+        }
+
+        // Start silent; `update_sound_voices` applies volume and falloff
+        let settings = if marker.looping {
+            PlaybackSettings::LOOP
+        } else {
+            PlaybackSettings::ONCE
+        };
+        let voice = commands
+            .spawn((
+                SoundVoice {
+                    emitter: entity,
+                    path: marker.path.clone(),
+                    looping: marker.looping,
+                },
+                AudioPlayer::new(asset_server.load(marker.path.clone())),
+                settings.with_volume(Volume::Linear(0.0)),
+            ))
+            .id();
+        commands.entity(entity).insert(SoundEmitterVoice(voice));
+    }
+}
+
+/// Attenuate voices by distance to the active camera and follow game pause.
+fn update_sound_voices(
+    game_state: Res<State<GameState>>,
+    emitters: Query<(&SoundEmitterMarker, &GlobalTransform)>,
+    mut voices: Query<(&SoundVoice, &mut AudioSink)>,
+    cameras: Query<
+        (&Camera, &GlobalTransform, Has<GameCamera>),
+        Or<(With<GameCamera>, With<EditorCamera>)>,
+    >,
+) {
+    let listener = cameras
+        .iter()
+        .filter(|(camera, _, _)| camera.is_active)
+        .max_by_key(|(_, _, is_game)| *is_game)
+        .map(|(_, transform, _)| transform.translation());
+    let paused = *game_state.get() == GameState::Paused;
+
+    for (voice, mut sink) in &mut voices {
+        let Ok((marker, transform)) = emitters.get(voice.emitter) else {
+            continue;
+        };
+        let distance = listener.map_or(0.0, |position| position.distance(transform.translation()));
+        sink.set_volume(Volume::Linear(
+            marker.volume.max(0.0) * distance_falloff(distance, marker.radius),
+        ));
+
+        if paused && !sink.is_paused() {
+            sink.pause();
+        } else if !paused && sink.is_paused() {
+            sink.play();
+        }
+    }
+}
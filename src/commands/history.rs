@@ -1,34 +1,133 @@
 use avian3d::prelude::Physics;
 use avian3d::schedule::PhysicsTime;
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
 use bevy::prelude::*;
+use bevy::scene::DynamicEntity;
 use bevy_egui::EguiContexts;
+use std::any::TypeId;
 use std::collections::VecDeque;
 
 use bevy_editor_game::GameState;
 
 use crate::editor::EditorState;
-use crate::scene::{build_editor_scene, restore_scene_from_data, SceneEntity};
+use crate::scene::{
+    build_editor_scene, clear_derived_components, despawn_scene_entities,
+    regenerate_runtime_components, resolve_entity_references, restore_scene_from_data,
+    visit_reflected_entities, SceneEntity,
+};
 use crate::ui::Settings;
 use crate::utils::should_process_input;
 
-/// A snapshot of the scene state for undo/redo
-#[derive(Clone)]
-struct SceneSnapshot {
-    /// Serialized scene data (RON format)
-    data: String,
-    /// Description of what action this snapshot is for
+/// A reversible operation stored in the history.
+///
+/// Each variant holds the state to restore. Applying an operation returns its
+/// inverse (the state that was replaced), which goes onto the opposite stack.
+enum HistoryOp {
+    /// Full scene snapshot (RON), used as a fallback for operations that can't express a delta
+    Snapshot(String),
+    /// Previous transforms of a set of entities
+    Transforms(Vec<(Entity, Transform)>),
+    /// Previous parent and transform of a set of entities
+    Reparent(Vec<(Entity, Option<Entity>, Transform)>),
+    /// Previous editor components of a set of entities. `None` means the entity did not exist.
+    Entities(Vec<(Entity, Option<DynamicEntity>)>),
+    /// Scene entities spawned since the spawn was recorded, collected by
+    /// `collect_spawned_entities`. Closed into an `Entities` op once the spawn has happened.
+    PendingSpawn(Vec<Entity>),
+}
+
+impl HistoryOp {
+    /// Call `f` with every entity this operation refers to, including entity
+    /// references inside recorded components
+    fn visit_entities(&mut self, f: &mut dyn FnMut(&mut Entity)) {
+        match self {
+            HistoryOp::Snapshot(_) => {}
+            HistoryOp::Transforms(transforms) => {
+                for (entity, _) in transforms {
+                    f(entity);
+                }
+            }
+            HistoryOp::Reparent(parents) => {
+                for (entity, parent, _) in parents {
+                    f(entity);
+                    if let Some(parent) = parent {
+                        f(parent);
+                    }
+                }
+            }
+            HistoryOp::Entities(states) => {
+                for (entity, state) in states {
+                    f(entity);
+                    if let Some(state) = state {
+                        f(&mut state.entity);
+                        for component in &mut state.components {
+                            visit_reflected_entities(component.as_mut(), f);
+                        }
+                    }
+                }
+            }
+            HistoryOp::PendingSpawn(spawned) => {
+                for entity in spawned {
+                    f(entity);
+                }
+            }
+        }
+    }
+}
+
+/// The op undoing a spawn: despawn each spawned entity
+fn despawn_op(spawned: Vec<Entity>) -> HistoryOp {
+    HistoryOp::Entities(spawned.into_iter().map(|e| (e, None)).collect())
+}
+
+/// A single undo/redo step
+pub struct HistoryEntry {
+    /// Description of what action this entry is for
     description: String,
+    /// When the action was recorded, in seconds since startup (`Time<Real>`)
+    timestamp: f64,
+    op: HistoryOp,
 }
 
-/// Resource to manage undo/redo history using scene snapshots
+impl HistoryEntry {
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn timestamp(&self) -> f64 {
+        self.timestamp
+    }
+
+    /// Entities this step touches, as recorded. `None` for full scene snapshots.
+    ///
+    /// Use [`SnapshotHistory::resolve`] to find the entity currently standing in for each.
+    pub fn touched_entities(&self) -> Option<Vec<Entity>> {
+        match &self.op {
+            HistoryOp::Snapshot(_) => None,
+            HistoryOp::Transforms(transforms) => Some(transforms.iter().map(|(e, _)| *e).collect()),
+            HistoryOp::Reparent(parents) => Some(parents.iter().map(|(e, _, _)| *e).collect()),
+            HistoryOp::Entities(states) => Some(states.iter().map(|(e, _)| *e).collect()),
+            HistoryOp::PendingSpawn(spawned) => Some(spawned.clone()),
+        }
+    }
+}
+
+/// Resource to manage undo/redo history.
+///
+/// Most operations record per-entity deltas; full scene snapshots are only
+/// taken by [`TakeSnapshotCommand`].
 #[derive(Resource)]
 pub struct SnapshotHistory {
     /// Stack of previous states (for undo)
-    undo_stack: VecDeque<SceneSnapshot>,
+    undo_stack: VecDeque<HistoryEntry>,
     /// Stack of future states (for redo)
-    redo_stack: VecDeque<SceneSnapshot>,
+    redo_stack: VecDeque<HistoryEntry>,
     /// Whether we're currently restoring (to avoid taking snapshots during restore)
     restoring: bool,
+    /// Entities that were re-created by undo/redo or scene restores, mapped to their replacement
+    remap: EntityHashMap<Entity>,
+    /// Whether old undo entries were dropped to stay within the history size
+    trimmed: bool,
 }
 
 impl Default for SnapshotHistory {
@@ -37,6 +136,8 @@ impl Default for SnapshotHistory {
             undo_stack: VecDeque::with_capacity(50),
             redo_stack: VecDeque::with_capacity(50),
             restoring: false,
+            remap: EntityHashMap::default(),
+            trimmed: false,
         }
     }
 }
@@ -57,6 +158,79 @@ impl SnapshotHistory {
     pub fn redo_count(&self) -> usize {
         self.redo_stack.len()
     }
+
+    /// Whether the oldest undo entry is no longer the initial state
+    pub fn is_trimmed(&self) -> bool {
+        self.trimmed
+    }
+
+    /// Entries that can be undone, oldest first
+    pub fn undo_entries(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        self.undo_stack.iter()
+    }
+
+    /// Entries that can be redone, in the order they would be redone
+    pub fn redo_entries(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        self.redo_stack.iter().rev()
+    }
+
+    /// Follow the remap chain to the entity currently standing in for `entity`
+    pub fn resolve(&self, entity: Entity) -> Entity {
+        let mut current = entity;
+        while let Some(&next) = self.remap.get(&current) {
+            current = next;
+        }
+        current
+    }
+
+    /// Record that entities were re-created, e.g. after restoring a full scene.
+    /// `entity_map` maps the entity IDs stored in the restored data to the new entities.
+    pub fn remap_entities(&mut self, entity_map: &EntityHashMap<Entity>) {
+        let resolved: Vec<(Entity, Entity, Entity)> = entity_map
+            .iter()
+            .map(|(&old, &new)| (old, self.resolve(old), new))
+            .collect();
+        for (old, current, new) in resolved {
+            if old != new {
+                self.remap.insert(old, new);
+            }
+            if current != new {
+                self.remap.insert(current, new);
+            }
+        }
+    }
+
+    /// Drop remap entries that no remaining entry refers to, pointing the rest
+    /// straight at the entity currently standing in for them
+    fn prune_remap(&mut self) {
+        if self.remap.is_empty() {
+            return;
+        }
+        let mut referenced = EntityHashSet::default();
+        for entry in self.undo_stack.iter_mut().chain(self.redo_stack.iter_mut()) {
+            entry.op.visit_entities(&mut |entity| {
+                referenced.insert(*entity);
+            });
+        }
+        let remap = referenced
+            .into_iter()
+            .filter_map(|entity| {
+                let current = self.resolve(entity);
+                (current != entity).then_some((entity, current))
+            })
+            .collect();
+        self.remap = remap;
+    }
+
+    /// Stop collecting into a pending spawn on top of the undo stack
+    fn close_pending_spawn(&mut self) {
+        if let Some(entry) = self.undo_stack.back_mut() {
+            if let HistoryOp::PendingSpawn(spawned) = &mut entry.op {
+                let spawned = std::mem::take(spawned);
+                entry.op = despawn_op(spawned);
+            }
+        }
+    }
 }
 
 /// Event to trigger undo
@@ -67,6 +241,13 @@ pub struct UndoEvent;
 #[derive(Message)]
 pub struct RedoEvent;
 
+/// Event to undo or redo until `position` entries remain on the undo stack.
+/// Position 0 is the oldest state still in the history.
+#[derive(Message)]
+pub struct JumpToHistoryEvent {
+    pub position: usize,
+}
+
 pub struct HistoryPlugin;
 
 impl Plugin for HistoryPlugin {
@@ -74,12 +255,16 @@ impl Plugin for HistoryPlugin {
         app.init_resource::<SnapshotHistory>()
             .add_message::<UndoEvent>()
             .add_message::<RedoEvent>()
+            .add_message::<JumpToHistoryEvent>()
+            .add_observer(collect_spawned_entities)
+            .add_systems(Last, close_pending_spawns)
             .add_systems(
                 Update,
                 (
                     handle_undo_redo_input,
                     handle_undo,
                     handle_redo,
+                    handle_jump_to_history,
                 ),
             );
     }
@@ -110,76 +295,217 @@ fn handle_undo_redo_input(
     }
 }
 
-/// Command to take a snapshot with exclusive world access
+/// Check whether a history entry may be recorded right now
+fn can_record(world: &World, description: &str) -> bool {
+    // Check if we're currently restoring - don't take snapshots during restore
+    let restoring = world
+        .get_resource::<SnapshotHistory>()
+        .map(|h| h.restoring)
+        .unwrap_or(false);
+
+    if restoring {
+        info!("Skipping snapshot (restoring): {}", description);
+        return false;
+    }
+
+    // Don't take snapshots unless in Editing state — physics/pause changes shouldn't pollute undo history
+    if let Some(state) = world.get_resource::<State<GameState>>() {
+        if *state.get() != GameState::Editing {
+            info!("Skipping snapshot (not editing): {}", description);
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Create a history entry stamped with the current time
+fn new_entry(world: &World, description: String, op: HistoryOp) -> HistoryEntry {
+    let timestamp = world
+        .get_resource::<Time<Real>>()
+        .map(|t| t.elapsed_secs_f64())
+        .unwrap_or_default();
+    HistoryEntry {
+        description,
+        timestamp,
+        op,
+    }
+}
+
+/// Push a new entry onto the undo stack, clearing the redo stack
+fn push_undo_entry(world: &mut World, entry: HistoryEntry) {
+    // Get max history size from settings
+    let max_history = world
+        .get_resource::<Settings>()
+        .map(|s| s.undo_history_size)
+        .unwrap_or(50);
+
+    if let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() {
+        // A pending spawn on top of the stack has happened by now
+        history.close_pending_spawn();
+
+        // Clear redo stack when new action is taken
+        history.redo_stack.clear();
+
+        while history.undo_stack.len() >= max_history {
+            history.undo_stack.pop_front();
+            history.trimmed = true;
+        }
+
+        history.undo_stack.push_back(entry);
+        history.prune_remap();
+    }
+}
+
+/// Command to take a full scene snapshot with exclusive world access.
+///
+/// Prefer the delta commands ([`RecordTransformsCommand`], [`RecordEntitiesCommand`],
+/// [`RecordSpawnCommand`], [`RecordReparentCommand`]) where the affected entities are known.
 pub struct TakeSnapshotCommand {
     pub description: String,
 }
 
 impl Command for TakeSnapshotCommand {
     fn apply(self, world: &mut World) {
-        // Check if we're currently restoring - don't take snapshots during restore
-        let restoring = world
-            .get_resource::<SnapshotHistory>()
-            .map(|h| h.restoring)
-            .unwrap_or(false);
-
-        if restoring {
-            info!("Skipping snapshot (restoring): {}", self.description);
+        if !can_record(world, &self.description) {
             return;
         }
 
-        // Don't take snapshots unless in Editing state — physics/pause changes shouldn't pollute undo history
-        let game_state = world.get_resource::<State<GameState>>();
-        if let Some(state) = game_state {
-            if *state.get() != GameState::Editing {
-                info!("Skipping snapshot (not editing): {}", self.description);
-                return;
-            }
+        let Some(data) = serialize_scene(world) else {
+            warn!("Failed to serialize scene for snapshot");
+            return;
+        };
+
+        info!("Taking snapshot '{}'", self.description);
+        let entry = new_entry(world, self.description, HistoryOp::Snapshot(data));
+        push_undo_entry(world, entry);
+    }
+}
+
+/// Command to record the transforms of entities before they are changed
+pub struct RecordTransformsCommand {
+    pub entities: Vec<Entity>,
+    pub description: String,
+}
+
+impl Command for RecordTransformsCommand {
+    fn apply(self, world: &mut World) {
+        if !can_record(world, &self.description) {
+            return;
         }
 
-        // Collect scene entity IDs
-        let scene_entity_ids: Vec<Entity> = {
-            let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
-            query.iter(world).collect()
+        let transforms: Vec<(Entity, Transform)> = self
+            .entities
+            .iter()
+            .filter_map(|&e| world.get::<Transform>(e).map(|t| (e, *t)))
+            .collect();
+        if transforms.is_empty() {
+            return;
+        }
+
+        let entry = new_entry(world, self.description, HistoryOp::Transforms(transforms));
+        push_undo_entry(world, entry);
+    }
+}
+
+/// Command to record the editor components of entities before they are edited or deleted.
+///
+/// Set `recursive` when the operation also affects descendants (e.g. despawning).
+pub struct RecordEntitiesCommand {
+    pub entities: Vec<Entity>,
+    pub recursive: bool,
+    pub description: String,
+}
+
+impl Command for RecordEntitiesCommand {
+    fn apply(self, world: &mut World) {
+        if !can_record(world, &self.description) {
+            return;
+        }
+
+        let entities = if self.recursive {
+            with_scene_descendants(world, &self.entities)
+        } else {
+            let mut seen = EntityHashSet::default();
+            self.entities.into_iter().filter(|e| seen.insert(*e)).collect()
         };
 
-        info!("Taking snapshot '{}' with {} entities", self.description, scene_entity_ids.len());
+        let states = capture_entity_states(world, &entities);
+        if states.is_empty() {
+            return;
+        }
 
-        // Build the scene using shared helper
-        let scene = build_editor_scene(world, scene_entity_ids.into_iter());
+        let entry = new_entry(world, self.description, HistoryOp::Entities(states));
+        push_undo_entry(world, entry);
+    }
+}
 
-        // Serialize the scene
-        let type_registry = world.resource::<AppTypeRegistry>().clone();
-        let type_registry = type_registry.read();
+/// Command to record that scene entities are about to be spawned.
+///
+/// Every `SceneEntity` spawned after this command runs, until the end of the
+/// frame in which the first one appears, is recorded. Undo despawns exactly those.
+pub struct RecordSpawnCommand {
+    pub description: String,
+}
 
-        let Ok(serialized) = scene.serialize(&type_registry) else {
-            warn!("Failed to serialize scene for snapshot");
+impl Command for RecordSpawnCommand {
+    fn apply(self, world: &mut World) {
+        if !can_record(world, &self.description) {
             return;
-        };
+        }
 
-        drop(type_registry);
+        let entry = new_entry(world, self.description, HistoryOp::PendingSpawn(Vec::new()));
+        push_undo_entry(world, entry);
+    }
+}
 
-        // Get max history size from settings
-        let max_history = world
-            .get_resource::<Settings>()
-            .map(|s| s.undo_history_size)
-            .unwrap_or(50);
+/// Add newly spawned scene entities to a pending spawn on top of the undo stack
+fn collect_spawned_entities(trigger: On<Add, SceneEntity>, mut history: ResMut<SnapshotHistory>) {
+    if history.restoring {
+        return;
+    }
+    if let Some(HistoryEntry {
+        op: HistoryOp::PendingSpawn(spawned),
+        ..
+    }) = history.undo_stack.back_mut()
+    {
+        spawned.push(trigger.event().entity);
+    }
+}
 
-        // Add to history
-        if let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() {
-            // Clear redo stack when new action is taken
-            history.redo_stack.clear();
+/// Close a pending spawn at the end of the frame its first entity was spawned in
+fn close_pending_spawns(mut history: ResMut<SnapshotHistory>) {
+    let spawned = matches!(
+        history.undo_stack.back(),
+        Some(HistoryEntry {
+            op: HistoryOp::PendingSpawn(spawned),
+            ..
+        }) if !spawned.is_empty()
+    );
+    if spawned {
+        history.close_pending_spawn();
+    }
+}
 
-            // Add to undo stack
-            while history.undo_stack.len() >= max_history {
-                history.undo_stack.pop_front();
-            }
+/// Command to record the parent and transform of entities before they are reparented
+pub struct RecordReparentCommand {
+    pub entities: Vec<Entity>,
+    pub description: String,
+}
 
-            history.undo_stack.push_back(SceneSnapshot {
-                data: serialized,
-                description: self.description,
-            });
+impl Command for RecordReparentCommand {
+    fn apply(self, world: &mut World) {
+        if !can_record(world, &self.description) {
+            return;
         }
+
+        let parents = capture_parents(world, &self.entities);
+        if parents.is_empty() {
+            return;
+        }
+
+        let entry = new_entry(world, self.description, HistoryOp::Reparent(parents));
+        push_undo_entry(world, entry);
     }
 }
 
@@ -198,54 +524,16 @@ impl Command for UndoCommand {
     fn apply(self, world: &mut World) {
         info!("UndoCommand::apply running");
 
-        // First, take a snapshot of current state for redo
-        let current_snapshot = take_current_snapshot(world, "redo");
-
-        // Get max history size from settings
-        let max_history = world
-            .get_resource::<Settings>()
-            .map(|s| s.undo_history_size)
-            .unwrap_or(50);
-
-        // Pop from undo stack
-        let snapshot = {
-            let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() else {
-                info!("No SnapshotHistory resource found!");
-                return;
-            };
-
-            info!(
-                "Undo stack has {} items, redo stack has {} items",
-                history.undo_stack.len(),
-                history.redo_stack.len()
-            );
-
-            let Some(snapshot) = history.undo_stack.pop_back() else {
-                info!("Nothing to undo");
-                return;
-            };
-
-            // Push current state to redo stack
-            if let Some(current) = current_snapshot {
-                while history.redo_stack.len() >= max_history {
-                    history.redo_stack.pop_front();
-                }
-                history.redo_stack.push_back(current);
-            }
-
-            history.restoring = true;
-            snapshot
+        let Some(entry) = pop_entry(world, true) else {
+            info!("Nothing to undo");
+            return;
         };
 
-        // Restore the snapshot
-        restore_snapshot(world, &snapshot);
-
-        // Clear restoring flag
-        if let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() {
-            history.restoring = false;
-        }
+        let description = entry.description.clone();
+        let inverse = apply_entry(world, entry);
+        push_inverse(world, inverse, false);
 
-        info!("Undo: {}", snapshot.description);
+        info!("Undo: {}", description);
     }
 }
 
@@ -261,52 +549,131 @@ struct RedoCommand;
 
 impl Command for RedoCommand {
     fn apply(self, world: &mut World) {
-        // First, take a snapshot of current state for undo
-        let current_snapshot = take_current_snapshot(world, "undo");
-
-        // Get max history size from settings
-        let max_history = world
-            .get_resource::<Settings>()
-            .map(|s| s.undo_history_size)
-            .unwrap_or(50);
-
-        // Pop from redo stack
-        let snapshot = {
-            let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() else {
-                return;
+        let Some(entry) = pop_entry(world, false) else {
+            info!("Nothing to redo");
+            return;
+        };
+
+        let description = entry.description.clone();
+        let inverse = apply_entry(world, entry);
+        push_inverse(world, inverse, true);
+
+        info!("Redo: {}", description);
+    }
+}
+
+/// Handle jump-to-history events
+fn handle_jump_to_history(mut events: MessageReader<JumpToHistoryEvent>, mut commands: Commands) {
+    for event in events.read() {
+        commands.queue(JumpToHistoryCommand {
+            position: event.position,
+        });
+    }
+}
+
+/// Command to step through undo/redo until the undo stack has `position` entries
+struct JumpToHistoryCommand {
+    position: usize,
+}
+
+impl Command for JumpToHistoryCommand {
+    fn apply(self, world: &mut World) {
+        loop {
+            let undo_count = world
+                .get_resource::<SnapshotHistory>()
+                .map(|h| h.undo_count())
+                .unwrap_or(0);
+
+            let (entry, undo) = if undo_count > self.position {
+                (pop_entry(world, true), true)
+            } else if undo_count < self.position {
+                (pop_entry(world, false), false)
+            } else {
+                break;
             };
 
-            let Some(snapshot) = history.redo_stack.pop_back() else {
-                info!("Nothing to redo");
-                return;
+            let Some(entry) = entry else {
+                break;
             };
+            let inverse = apply_entry(world, entry);
+            push_inverse(world, inverse, !undo);
+        }
 
-            // Push current state to undo stack
-            if let Some(current) = current_snapshot {
-                while history.undo_stack.len() >= max_history {
-                    history.undo_stack.pop_front();
-                }
-                history.undo_stack.push_back(current);
-            }
+        info!("Jumped to history position {}", self.position);
+    }
+}
 
-            history.restoring = true;
-            snapshot
-        };
+/// Pop the top entry from the undo (or redo) stack
+fn pop_entry(world: &mut World, undo: bool) -> Option<HistoryEntry> {
+    let mut history = world.get_resource_mut::<SnapshotHistory>()?;
+    info!(
+        "Undo stack has {} items, redo stack has {} items",
+        history.undo_stack.len(),
+        history.redo_stack.len()
+    );
+    if undo {
+        history.undo_stack.pop_back()
+    } else {
+        history.redo_stack.pop_back()
+    }
+}
 
-        // Restore the snapshot
-        restore_snapshot(world, &snapshot);
+/// Push the inverse of an applied entry onto the undo (or redo) stack
+fn push_inverse(world: &mut World, entry: HistoryEntry, undo: bool) {
+    // Get max history size from settings
+    let max_history = world
+        .get_resource::<Settings>()
+        .map(|s| s.undo_history_size)
+        .unwrap_or(50);
 
-        // Clear restoring flag
-        if let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() {
-            history.restoring = false;
+    if let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() {
+        let history = &mut *history;
+        let stack = if undo {
+            &mut history.undo_stack
+        } else {
+            &mut history.redo_stack
+        };
+        while stack.len() >= max_history {
+            stack.pop_front();
+            history.trimmed |= undo;
         }
+        stack.push_back(entry);
+        history.prune_remap();
+    }
+}
 
-        info!("Redo: {}", snapshot.description);
+/// Apply a history entry and return its inverse
+fn apply_entry(world: &mut World, entry: HistoryEntry) -> HistoryEntry {
+    if let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() {
+        history.restoring = true;
+    }
+
+    let op = match entry.op {
+        HistoryOp::Snapshot(data) => apply_snapshot(world, &data),
+        HistoryOp::Transforms(transforms) => apply_transforms(world, transforms),
+        HistoryOp::Reparent(parents) => apply_reparent(world, parents),
+        HistoryOp::Entities(states) => apply_entity_states(world, states),
+        HistoryOp::PendingSpawn(spawned) => apply_entity_states(world, despawn_op(spawned)),
+    };
+
+    // Keep physics paused after undo/redo
+    if let Some(mut physics_time) = world.get_resource_mut::<Time<Physics>>() {
+        physics_time.set_relative_speed(0.0);
+    }
+
+    if let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() {
+        history.restoring = false;
+    }
+
+    HistoryEntry {
+        description: entry.description,
+        timestamp: entry.timestamp,
+        op,
     }
 }
 
-/// Take a snapshot of the current scene state
-fn take_current_snapshot(world: &mut World, description: &str) -> Option<SceneSnapshot> {
+/// Serialize the whole editable scene to RON
+fn serialize_scene(world: &mut World) -> Option<String> {
     let scene_entity_ids: Vec<Entity> = {
         let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
         query.iter(world).collect()
@@ -318,22 +685,463 @@ fn take_current_snapshot(world: &mut World, description: &str) -> Option<SceneSn
     let type_registry = world.resource::<AppTypeRegistry>().clone();
     let type_registry = type_registry.read();
 
-    scene.serialize(&type_registry).ok().map(|data| {
-        SceneSnapshot {
-            data,
-            description: description.to_string(),
+    scene.serialize(&type_registry).ok()
+}
+
+/// Restore a full snapshot, returning a snapshot of the replaced state
+fn apply_snapshot(world: &mut World, data: &str) -> HistoryOp {
+    let current = serialize_scene(world).unwrap_or_default();
+
+    info!("Restoring full scene snapshot");
+    let entity_map = restore_scene_from_data(world, data);
+
+    if let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() {
+        history.remap_entities(&entity_map);
+    }
+
+    HistoryOp::Snapshot(current)
+}
+
+/// Restore entity transforms, returning the replaced transforms
+fn apply_transforms(world: &mut World, transforms: Vec<(Entity, Transform)>) -> HistoryOp {
+    let mut inverse = Vec::with_capacity(transforms.len());
+    for (entity, transform) in transforms {
+        let entity = world.resource::<SnapshotHistory>().resolve(entity);
+        if let Some(mut current) = world.get_mut::<Transform>(entity) {
+            inverse.push((entity, *current));
+            *current = transform;
+        }
+    }
+    HistoryOp::Transforms(inverse)
+}
+
+/// Capture the parent and transform of each entity
+fn capture_parents(world: &World, entities: &[Entity]) -> Vec<(Entity, Option<Entity>, Transform)> {
+    entities
+        .iter()
+        .filter_map(|&e| {
+            let transform = *world.get::<Transform>(e)?;
+            let parent = world.get::<ChildOf>(e).map(|c| c.parent());
+            Some((e, parent, transform))
+        })
+        .collect()
+}
+
+/// Restore entity parents, returning the replaced parents
+fn apply_reparent(world: &mut World, parents: Vec<(Entity, Option<Entity>, Transform)>) -> HistoryOp {
+    let (entities, targets): (Vec<Entity>, Vec<(Option<Entity>, Transform)>) = {
+        let history = world.resource::<SnapshotHistory>();
+        parents
+            .into_iter()
+            .map(|(e, p, t)| (history.resolve(e), (p.map(|p| history.resolve(p)), t)))
+            .unzip()
+    };
+
+    let inverse = capture_parents(world, &entities);
+
+    for (entity, (parent, transform)) in entities.into_iter().zip(targets) {
+        let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        match parent {
+            Some(parent) => {
+                entity_mut.insert(ChildOf(parent));
+            }
+            None => {
+                entity_mut.remove::<ChildOf>();
+            }
         }
-    })
+        entity_mut.insert(transform);
+    }
+
+    HistoryOp::Reparent(inverse)
 }
 
-/// Restore the scene from a snapshot
-fn restore_snapshot(world: &mut World, snapshot: &SceneSnapshot) {
-    info!("Restoring snapshot: {}", snapshot.description);
+/// Expand a list of entities with all of their `SceneEntity` descendants.
+/// Listed entities are kept even if they aren't scene entities (yet), so an
+/// entity that is about to be spawned is recorded as absent.
+pub(crate) fn with_scene_descendants(world: &World, entities: &[Entity]) -> Vec<Entity> {
+    let mut result: Vec<Entity> = Vec::with_capacity(entities.len());
+    let mut seen = EntityHashSet::default();
+    let mut stack: Vec<(Entity, bool)> = entities.iter().map(|&e| (e, true)).collect();
+    while let Some((entity, listed)) = stack.pop() {
+        if !seen.insert(entity) {
+            continue;
+        }
+        if !listed && !is_scene_entity(world, entity) {
+            continue;
+        }
+        result.push(entity);
+        if let Some(children) = world.get::<Children>(entity) {
+            let children: &[Entity] = children;
+            stack.extend(children.iter().map(|&child| (child, false)));
+        }
+    }
+    result
+}
 
-    restore_scene_from_data(world, &snapshot.data);
+/// Capture the editor components of each entity. Entities that are not
+/// (or no longer) scene entities are captured as `None`.
+fn capture_entity_states(world: &World, entities: &[Entity]) -> Vec<(Entity, Option<DynamicEntity>)> {
+    let alive: Vec<Entity> = entities
+        .iter()
+        .copied()
+        .filter(|&e| is_scene_entity(world, e))
+        .collect();
 
-    // Keep physics paused after undo/redo
-    if let Some(mut physics_time) = world.get_resource_mut::<Time<Physics>>() {
-        physics_time.set_relative_speed(0.0);
+    let mut extracted: EntityHashMap<DynamicEntity> = build_editor_scene(world, alive.into_iter())
+        .entities
+        .into_iter()
+        .map(|mut dyn_entity| {
+            // Children is maintained from ChildOf; restoring it directly would fight the relationship hooks
+            dyn_entity.components.retain(|c| {
+                c.get_represented_type_info().map(|info| info.type_id())
+                    != Some(TypeId::of::<Children>())
+            });
+            (dyn_entity.entity, dyn_entity)
+        })
+        .collect();
+
+    entities
+        .iter()
+        .map(|&e| (e, extracted.remove(&e)))
+        .collect()
+}
+
+fn is_scene_entity(world: &World, entity: Entity) -> bool {
+    world.get::<SceneEntity>(entity).is_some()
+}
+
+/// Restore entity component states, returning the replaced states.
+///
+/// Entities whose state is `None` are despawned; entities that no longer exist are
+/// re-created and recorded in the remap so older entries can still find them.
+fn apply_entity_states(world: &mut World, states: Vec<(Entity, Option<DynamicEntity>)>) -> HistoryOp {
+    let resolved: Vec<Entity> = {
+        let history = world.resource::<SnapshotHistory>();
+        states.iter().map(|(e, _)| history.resolve(*e)).collect()
+    };
+
+    let inverse = capture_entity_states(world, &resolved);
+
+    // Despawn entities that did not exist in the restored state
+    let to_despawn: Vec<Entity> = states
+        .iter()
+        .zip(&resolved)
+        .filter(|((_, state), e)| state.is_none() && is_scene_entity(world, **e))
+        .map(|(_, e)| *e)
+        .collect();
+    despawn_scene_entities(world, &to_despawn);
+
+    // Remove editor components that were added after the state was recorded
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    for (((_, state), &entity), (_, current)) in states.iter().zip(&resolved).zip(&inverse) {
+        let (Some(state), Some(current)) = (state, current) else {
+            continue;
+        };
+        let keep: Vec<TypeId> = state
+            .components
+            .iter()
+            .filter_map(|c| c.get_represented_type_info().map(|i| i.type_id()))
+            .collect();
+        let stale: Vec<TypeId> = current
+            .components
+            .iter()
+            .filter_map(|c| c.get_represented_type_info().map(|i| i.type_id()))
+            .filter(|t| !keep.contains(t))
+            .collect();
+        {
+            let registry = type_registry.read();
+            let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+                continue;
+            };
+            for type_id in stale {
+                if let Some(reflect_component) = registry
+                    .get(type_id)
+                    .and_then(|r| r.data::<ReflectComponent>())
+                {
+                    reflect_component.remove(&mut entity_mut);
+                }
+            }
+        }
+        clear_derived_components(world, entity);
+    }
+
+    // Write the recorded states back, re-creating entities that no longer exist
+    let mut entity_map = EntityHashMap::default();
+    {
+        let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
+        for entity in query.iter(world) {
+            entity_map.insert(entity, entity);
+        }
+    }
+    let history = world.resource::<SnapshotHistory>();
+    let remapped: Vec<(Entity, Entity)> = history
+        .remap
+        .keys()
+        .map(|&old| (old, history.resolve(old)))
+        .collect();
+    for (old, new) in remapped {
+        if is_scene_entity(world, new) {
+            entity_map.insert(old, new);
+        }
+    }
+
+    let mut respawned: Vec<(Entity, Entity)> = Vec::new();
+    let mut entities: Vec<DynamicEntity> = Vec::new();
+    for ((_, state), &entity) in states.into_iter().zip(&resolved) {
+        let Some(state) = state else {
+            continue;
+        };
+        if is_scene_entity(world, entity) {
+            entity_map.insert(state.entity, entity);
+        } else {
+            entity_map.remove(&state.entity);
+            respawned.push((state.entity, entity));
+        }
+        entities.push(state);
+    }
+
+    if !entities.is_empty() {
+        let scene = DynamicScene {
+            resources: Vec::new(),
+            entities,
+        };
+        if let Err(e) = scene.write_to_world(world, &mut entity_map) {
+            warn!("Failed to restore entity states: {:?}", e);
+        }
+
+        let mut remap = EntityHashMap::default();
+        for (recorded, resolved) in respawned {
+            if let Some(&new) = entity_map.get(&recorded) {
+                remap.insert(recorded, new);
+                remap.insert(resolved, new);
+            }
+        }
+        world.resource_mut::<SnapshotHistory>().remap_entities(&remap);
+
+        regenerate_runtime_components(world);
+        resolve_entity_references(world);
+    }
+
+    HistoryOp::Entities(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::headless_app;
+
+    fn history_app() -> App {
+        let mut app = headless_app();
+        app.init_resource::<SnapshotHistory>()
+            .add_observer(collect_spawned_entities);
+        app
+    }
+
+    fn spawn_named(world: &mut World, name: &str) -> Entity {
+        world
+            .spawn((
+                SceneEntity,
+                Name::new(name.to_string()),
+                Transform::default(),
+            ))
+            .id()
+    }
+
+    fn find(world: &mut World, name: &str) -> Option<Entity> {
+        let mut query = world.query_filtered::<(Entity, &Name), With<SceneEntity>>();
+        query
+            .iter(world)
+            .find(|(_, n)| n.as_str() == name)
+            .map(|(e, _)| e)
+    }
+
+    fn parent_of(world: &World, entity: Entity) -> Option<Entity> {
+        world.get::<ChildOf>(entity).map(|c| c.parent())
+    }
+
+    #[test]
+    fn transform_undo_redo_round_trip() {
+        let mut app = history_app();
+        let world = app.world_mut();
+        let entity = spawn_named(world, "Cube");
+
+        RecordTransformsCommand {
+            entities: vec![entity],
+            description: "Move".to_string(),
+        }
+        .apply(world);
+        world.get_mut::<Transform>(entity).unwrap().translation = Vec3::X;
+
+        UndoCommand.apply(world);
+        assert_eq!(
+            world.get::<Transform>(entity).unwrap().translation,
+            Vec3::ZERO
+        );
+
+        RedoCommand.apply(world);
+        assert_eq!(world.get::<Transform>(entity).unwrap().translation, Vec3::X);
+    }
+
+    #[test]
+    fn reparent_undo_redo_round_trip() {
+        let mut app = history_app();
+        let world = app.world_mut();
+        let group = spawn_named(world, "Group");
+        let child = spawn_named(world, "Child");
+
+        RecordReparentCommand {
+            entities: vec![child],
+            description: "Parent to group".to_string(),
+        }
+        .apply(world);
+        world.entity_mut(child).insert(ChildOf(group));
+
+        UndoCommand.apply(world);
+        assert_eq!(parent_of(world, child), None);
+
+        RedoCommand.apply(world);
+        assert_eq!(parent_of(world, child), Some(group));
+    }
+
+    #[test]
+    fn entity_spawned_after_record_is_despawned_on_undo() {
+        let mut app = history_app();
+        let world = app.world_mut();
+        let child = spawn_named(world, "Child");
+        let group = world.spawn_empty().id();
+
+        RecordEntitiesCommand {
+            entities: vec![child, group],
+            recursive: true,
+            description: "Group selected entities".to_string(),
+        }
+        .apply(world);
+        world
+            .entity_mut(group)
+            .insert((SceneEntity, Name::new("Group"), Transform::default()));
+        world.entity_mut(child).insert(ChildOf(group));
+
+        UndoCommand.apply(world);
+        assert_eq!(find(world, "Group"), None);
+        let child = find(world, "Child").expect("child should be restored");
+        assert_eq!(parent_of(world, child), None);
+
+        RedoCommand.apply(world);
+        let group = find(world, "Group").expect("group should be re-created");
+        let child = find(world, "Child").expect("child should still exist");
+        assert_eq!(parent_of(world, child), Some(group));
+    }
+
+    #[test]
+    fn spawn_undo_redo_round_trip() {
+        let mut app = history_app();
+        let world = app.world_mut();
+        let existing = spawn_named(world, "Existing");
+
+        RecordSpawnCommand {
+            description: "Insert Cube".to_string(),
+        }
+        .apply(world);
+        let spawned = spawn_named(world, "Cube");
+
+        let history = world.resource::<SnapshotHistory>();
+        let touched = history.undo_entries().last().unwrap().touched_entities();
+        assert_eq!(touched, Some(vec![spawned]));
+
+        UndoCommand.apply(world);
+        assert_eq!(find(world, "Cube"), None);
+        assert_eq!(find(world, "Existing"), Some(existing));
+
+        RedoCommand.apply(world);
+        let respawned = find(world, "Cube").expect("redo re-creates the spawned entity");
+        assert_eq!(
+            world.resource::<SnapshotHistory>().resolve(spawned),
+            respawned
+        );
+
+        UndoCommand.apply(world);
+        assert_eq!(find(world, "Cube"), None);
+        assert_eq!(find(world, "Existing"), Some(existing));
+    }
+
+    #[test]
+    fn delete_undo_redo_round_trip() {
+        let mut app = history_app();
+        let world = app.world_mut();
+        let group = spawn_named(world, "Group");
+        let child = spawn_named(world, "Child");
+        world.entity_mut(child).insert(ChildOf(group));
+
+        RecordEntitiesCommand {
+            entities: vec![group],
+            recursive: true,
+            description: "Delete 1 entities".to_string(),
+        }
+        .apply(world);
+        despawn_scene_entities(world, &[group]);
+
+        UndoCommand.apply(world);
+        let restored_group = find(world, "Group").expect("undo restores the group");
+        let restored_child = find(world, "Child").expect("undo restores the child");
+        assert_eq!(parent_of(world, restored_child), Some(restored_group));
+
+        RedoCommand.apply(world);
+        assert_eq!(find(world, "Group"), None);
+        assert_eq!(find(world, "Child"), None);
+
+        UndoCommand.apply(world);
+        let group = find(world, "Group").expect("second undo restores the group");
+        let child = find(world, "Child").expect("second undo restores the child");
+        assert_eq!(parent_of(world, child), Some(group));
+    }
+
+    #[test]
+    fn remap_is_pruned_with_its_entries() {
+        let mut app = history_app();
+        let world = app.world_mut();
+        let deleted = spawn_named(world, "Deleted");
+        let other = spawn_named(world, "Other");
+
+        RecordEntitiesCommand {
+            entities: vec![deleted],
+            recursive: true,
+            description: "Delete 1 entities".to_string(),
+        }
+        .apply(world);
+        despawn_scene_entities(world, &[deleted]);
+        UndoCommand.apply(world);
+        assert!(!world.resource::<SnapshotHistory>().remap.is_empty());
+
+        // A new action clears the redo entry, the last one referring to the deleted entity
+        RecordTransformsCommand {
+            entities: vec![other],
+            description: "Move".to_string(),
+        }
+        .apply(world);
+        assert!(world.resource::<SnapshotHistory>().remap.is_empty());
+    }
+
+    #[test]
+    fn trimming_marks_history_as_trimmed() {
+        let mut app = history_app();
+        let world = app.world_mut();
+        world.insert_resource(Settings {
+            undo_history_size: 2,
+            ..default()
+        });
+        let entity = spawn_named(world, "Cube");
+
+        for step in 0..3 {
+            RecordTransformsCommand {
+                entities: vec![entity],
+                description: "Move".to_string(),
+            }
+            .apply(world);
+            let history = world.resource::<SnapshotHistory>();
+            assert_eq!(history.is_trimmed(), step == 2);
+        }
+        assert_eq!(world.resource::<SnapshotHistory>().undo_count(), 2);
     }
 }
@@ -1,13 +1,16 @@
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
 use bevy::prelude::*;
 use bevy_egui::EguiContexts;
 use bevy_spline_3d::prelude::SplineType;
 
-use super::TakeSnapshotCommand;
+use super::history::with_scene_descendants;
+use super::{RecordEntitiesCommand, RecordSpawnCommand, RecordTransformsCommand};
 use crate::editor::{EditorMode, EditorState};
 use crate::scene::blockout::{ArchMarker, LShapeMarker, RampMarker, StairsMarker};
 use crate::scene::{
-    DirectionalLightMarker, FogVolumeMarker, GroupMarker, PrimitiveMarker, SceneEntity,
-    SceneLightMarker, SpawnEntityEvent, SpawnEntityKind, SplineMarker,
+    build_editor_scene, regenerate_runtime_components, resolve_entity_references,
+    DirectionalLightMarker, FogVolumeMarker, GroupMarker, PersistentId, PrimitiveMarker,
+    SceneEntity, SceneLightMarker, SpawnEntityEvent, SpawnEntityKind, SplineMarker,
 };
 use crate::selection::Selected;
 use crate::utils::should_process_input;
@@ -34,17 +37,30 @@ pub struct CopySelectedEvent;
 #[derive(Message)]
 pub struct PasteEntitiesEvent;
 
-/// Data for a copied entity
-#[derive(Clone)]
-pub struct CopiedEntityData {
-    pub kind: SpawnEntityKind,
-    pub position: Vec3,
-    pub rotation: Quat,
-}
+/// Event to copy selected entities to clipboard and then delete them
+#[derive(Message)]
+pub struct CutSelectedEvent;
 
-/// Resource storing copied entities for paste operations
+/// Clipboard holding the copied entities, their descendants and all of their
+/// editor components as a scene fragment. Holds no live entity references, so
+/// it survives switching scene tabs.
 #[derive(Resource, Default)]
-pub struct CopiedEntities(pub Vec<CopiedEntityData>);
+pub struct CopiedEntities {
+    scene: Option<DynamicScene>,
+    /// Copied entities that were not below another copied entity (scene-local IDs)
+    roots: Vec<Entity>,
+}
+
+impl CopiedEntities {
+    /// Number of top-level entities on the clipboard
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+}
 
 pub struct OperationsPlugin;
 
@@ -56,16 +72,17 @@ impl Plugin for OperationsPlugin {
             .add_message::<NudgeSelectedEvent>()
             .add_message::<CopySelectedEvent>()
             .add_message::<PasteEntitiesEvent>()
+            .add_message::<CutSelectedEvent>()
             .add_systems(
                 Update,
                 (
                     handle_delete_input,
                     handle_nudge_input,
                     handle_copy_paste_input,
-                    handle_delete_selected,
+                    // Cut copies before the delete despawns the selection
+                    (handle_cut_selected, handle_copy_selected, handle_delete_selected).chain(),
                     handle_duplicate_selected,
                     handle_nudge_selected,
-                    handle_copy_selected,
                     handle_paste_entities,
                 ),
             );
@@ -78,22 +95,27 @@ fn handle_delete_input(
     editor_mode: Res<State<EditorMode>>,
     mut delete_events: MessageWriter<DeleteSelectedEvent>,
     mut duplicate_events: MessageWriter<DuplicateSelectedEvent>,
+    mut cut_events: MessageWriter<CutSelectedEvent>,
     mut contexts: EguiContexts,
 ) {
     if !should_process_input(&editor_state, &mut contexts) {
         return;
     }
 
+    let ctrl = keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
+
     // Delete key always deletes, X only deletes outside ObjectInspector mode
-    // (in ObjectInspector mode, X opens the remove component palette instead)
+    // (in ObjectInspector mode, X opens the remove component palette instead).
+    // Ctrl+X cuts instead.
     let x_pressed =
         keyboard.just_pressed(KeyCode::KeyX) && *editor_mode.get() != EditorMode::ObjectInspector;
-    if keyboard.just_pressed(KeyCode::Delete) || x_pressed {
+    if x_pressed && ctrl {
+        cut_events.write(CutSelectedEvent);
+    } else if keyboard.just_pressed(KeyCode::Delete) || x_pressed {
         delete_events.write(DeleteSelectedEvent);
     }
 
     // Ctrl+D to duplicate
-    let ctrl = keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
     if ctrl && keyboard.just_pressed(KeyCode::KeyD) {
         duplicate_events.write(DuplicateSelectedEvent);
     }
@@ -154,8 +176,10 @@ fn handle_delete_selected(
     for _ in events.read() {
         let count = selected.iter().count();
         if count > 0 {
-            // Queue snapshot command first, then despawn
-            commands.queue(TakeSnapshotCommand {
+            // Record the entities (and their children) first, then despawn
+            commands.queue(RecordEntitiesCommand {
+                entities: selected.iter().collect(),
+                recursive: true,
                 description: format!("Delete {} entities", count),
             });
 
@@ -167,6 +191,18 @@ fn handle_delete_selected(
     }
 }
 
+/// Handle cut by copying and deleting the selection in the same frame
+fn handle_cut_selected(
+    mut events: MessageReader<CutSelectedEvent>,
+    mut copy_events: MessageWriter<CopySelectedEvent>,
+    mut delete_events: MessageWriter<DeleteSelectedEvent>,
+) {
+    for _ in events.read() {
+        copy_events.write(CopySelectedEvent);
+        delete_events.write(DeleteSelectedEvent);
+    }
+}
+
 /// Query for all duplicatable entity types
 #[allow(clippy::type_complexity)]
 fn handle_duplicate_selected(
@@ -198,8 +234,8 @@ fn handle_duplicate_selected(
             continue;
         }
 
-        // Queue snapshot command before spawning duplicates
-        commands.queue(TakeSnapshotCommand {
+        // Record before spawning duplicates
+        commands.queue(RecordSpawnCommand {
             description: format!("Duplicate {} entities", count),
         });
 
@@ -262,21 +298,22 @@ fn handle_duplicate_selected(
 /// Handle nudging selected entities
 fn handle_nudge_selected(
     mut events: MessageReader<NudgeSelectedEvent>,
-    mut selected_query: Query<&mut Transform, (With<Selected>, With<SceneEntity>)>,
+    mut selected_query: Query<(Entity, &mut Transform), (With<Selected>, With<SceneEntity>)>,
     mut commands: Commands,
 ) {
     for event in events.read() {
-        let count = selected_query.iter().count();
-        if count == 0 {
+        let entities: Vec<Entity> = selected_query.iter().map(|(e, _)| e).collect();
+        if entities.is_empty() {
             continue;
         }
 
-        // Take snapshot for undo
-        commands.queue(TakeSnapshotCommand {
-            description: format!("Nudge {} entities", count),
+        // Record transforms for undo
+        commands.queue(RecordTransformsCommand {
+            description: format!("Nudge {} entities", entities.len()),
+            entities,
         });
 
-        for mut transform in selected_query.iter_mut() {
+        for (_, mut transform) in selected_query.iter_mut() {
             transform.translation += event.direction;
         }
     }
@@ -312,82 +349,60 @@ fn handle_copy_paste_input(
 }
 
 /// Handle copying selected entities to clipboard
-#[allow(clippy::type_complexity)]
 fn handle_copy_selected(
     mut events: MessageReader<CopySelectedEvent>,
-    selected_query: Query<
-        (
-            &Transform,
-            Option<&PrimitiveMarker>,
-            Option<&GroupMarker>,
-            Option<&SceneLightMarker>,
-            Option<&DirectionalLightMarker>,
-            Option<&SplineMarker>,
-            Option<&FogVolumeMarker>,
-            Option<&StairsMarker>,
-            Option<&RampMarker>,
-            Option<&ArchMarker>,
-            Option<&LShapeMarker>,
-        ),
-        With<Selected>,
-    >,
-    mut copied_entities: ResMut<CopiedEntities>,
+    selected: Query<Entity, (With<Selected>, With<SceneEntity>)>,
+    mut commands: Commands,
 ) {
     for _ in events.read() {
-        let mut copied = Vec::new();
+        let entities: Vec<Entity> = selected.iter().collect();
+        if !entities.is_empty() {
+            commands.queue(CopyEntitiesCommand { entities });
+        }
+    }
+}
 
-        for (
-            transform,
-            primitive,
-            group,
-            point_light,
-            dir_light,
-            spline,
-            fog,
-            stairs,
-            ramp,
-            arch,
-            lshape,
-        ) in selected_query.iter()
-        {
-            // Determine what kind of entity this is
-            let kind = if let Some(prim) = primitive {
-                Some(SpawnEntityKind::Primitive(prim.shape))
-            } else if group.is_some() {
-                Some(SpawnEntityKind::Group)
-            } else if point_light.is_some() {
-                Some(SpawnEntityKind::PointLight)
-            } else if dir_light.is_some() {
-                Some(SpawnEntityKind::DirectionalLight)
-            } else if spline.is_some() {
-                Some(SpawnEntityKind::Spline(SplineType::CatmullRom))
-            } else if fog.is_some() {
-                Some(SpawnEntityKind::FogVolume)
-            } else if stairs.is_some() {
-                Some(SpawnEntityKind::Stairs)
-            } else if ramp.is_some() {
-                Some(SpawnEntityKind::Ramp)
-            } else if arch.is_some() {
-                Some(SpawnEntityKind::Arch)
-            } else if lshape.is_some() {
-                Some(SpawnEntityKind::LShape)
-            } else {
-                None
-            };
+/// Command to copy entities and their scene descendants to the clipboard
+struct CopyEntitiesCommand {
+    entities: Vec<Entity>,
+}
 
-            if let Some(kind) = kind {
-                copied.push(CopiedEntityData {
-                    kind,
-                    position: transform.translation,
-                    rotation: transform.rotation,
-                });
-            }
+impl Command for CopyEntitiesCommand {
+    fn apply(self, world: &mut World) {
+        // Selected descendants are copied along with their selected ancestor
+        let selected: EntityHashSet = self.entities.iter().copied().collect();
+        let roots: Vec<Entity> = self
+            .entities
+            .into_iter()
+            .filter(|&entity| {
+                let mut current = entity;
+                while let Some(child_of) = world.get::<ChildOf>(current) {
+                    current = child_of.parent();
+                    if selected.contains(&current) {
+                        return false;
+                    }
+                }
+                true
+            })
+            .collect();
+
+        let entities = with_scene_descendants(world, &roots);
+        let mut scene = build_editor_scene(world, entities.into_iter());
+        for dynamic_entity in &mut scene.entities {
+            // Children is rebuilt from ChildOf, pasted roots go to the top level
+            // and pasted entities get fresh stable IDs
+            let is_root = roots.contains(&dynamic_entity.entity);
+            dynamic_entity.components.retain(|component| {
+                !component.represents::<Children>()
+                    && !component.represents::<PersistentId>()
+                    && !(is_root && component.represents::<ChildOf>())
+            });
         }
 
-        if !copied.is_empty() {
-            info!("Copied {} entities", copied.len());
-            copied_entities.0 = copied;
-        }
+        info!("Copied {} entities", roots.len());
+        let mut copied = world.resource_mut::<CopiedEntities>();
+        copied.scene = Some(scene);
+        copied.roots = roots;
     }
 }
 
@@ -396,11 +411,10 @@ fn handle_paste_entities(
     mut events: MessageReader<PasteEntitiesEvent>,
     copied_entities: Res<CopiedEntities>,
     selected: Query<Entity, With<Selected>>,
-    mut spawn_events: MessageWriter<SpawnEntityEvent>,
     mut commands: Commands,
 ) {
     for _ in events.read() {
-        if copied_entities.0.is_empty() {
+        if copied_entities.is_empty() {
             info!("No entities copied");
             continue;
         }
@@ -410,20 +424,106 @@ fn handle_paste_entities(
             commands.entity(entity).remove::<Selected>();
         }
 
-        // Queue snapshot command before spawning
-        commands.queue(TakeSnapshotCommand {
-            description: format!("Paste {} entities", copied_entities.0.len()),
+        // Record before spawning
+        commands.queue(RecordSpawnCommand {
+            description: format!("Paste {} entities", copied_entities.len()),
         });
+        commands.queue(PasteEntitiesCommand);
+    }
+}
 
-        // Spawn copied entities (they will be auto-selected by SpawnEntityEvent handler)
-        for data in &copied_entities.0 {
-            spawn_events.write(SpawnEntityEvent {
-                kind: data.kind.clone(),
-                position: data.position,
-                rotation: data.rotation,
-            });
+/// Command to spawn a fresh copy of the clipboard and select its roots
+struct PasteEntitiesCommand;
+
+impl Command for PasteEntitiesCommand {
+    fn apply(self, world: &mut World) {
+        let pasted = world.resource_scope(|world, copied: Mut<CopiedEntities>| {
+            let scene = copied.scene.as_ref()?;
+            let mut entity_map = EntityHashMap::default();
+            if let Err(e) = scene.write_to_world(world, &mut entity_map) {
+                warn!("Failed to paste entities: {:?}", e);
+                return None;
+            }
+            Some(
+                copied
+                    .roots
+                    .iter()
+                    .filter_map(|root| entity_map.get(root).copied())
+                    .collect::<Vec<Entity>>(),
+            )
+        });
+        let Some(pasted) = pasted else {
+            return;
+        };
+
+        regenerate_runtime_components(world);
+        resolve_entity_references(world);
+
+        for &entity in &pasted {
+            world.entity_mut(entity).insert(Selected);
         }
+        info!("Pasted {} entities", pasted.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::headless_app;
+    use bevy_editor_game::MaterialRef;
+
+    fn find(world: &mut World, name: &str) -> Vec<Entity> {
+        let mut query = world.query_filtered::<(Entity, &Name), With<SceneEntity>>();
+        query
+            .iter(world)
+            .filter(|(_, n)| n.as_str() == name)
+            .map(|(e, _)| e)
+            .collect()
+    }
 
-        info!("Pasted {} entities", copied_entities.0.len());
+    #[test]
+    fn cut_and_paste_keeps_children_and_materials() {
+        let mut app = headless_app();
+        app.init_resource::<CopiedEntities>();
+        let world = app.world_mut();
+        let group = world
+            .spawn((SceneEntity, Name::new("Group"), Transform::default()))
+            .id();
+        let child = world
+            .spawn((
+                SceneEntity,
+                Name::new("Child"),
+                Transform::from_xyz(1.0, 0.0, 0.0),
+                MaterialRef::Library("Brick".to_string()),
+                ChildOf(group),
+            ))
+            .id();
+
+        CopyEntitiesCommand {
+            entities: vec![group, child],
+        }
+        .apply(world);
+        assert_eq!(world.resource::<CopiedEntities>().len(), 1);
+        world.entity_mut(group).despawn();
+
+        PasteEntitiesCommand.apply(world);
+        PasteEntitiesCommand.apply(world);
+
+        let groups = find(world, "Group");
+        let children = find(world, "Child");
+        assert_eq!(groups.len(), 2);
+        assert_eq!(children.len(), 2);
+        for child in children {
+            let parent = world.get::<ChildOf>(child).unwrap().parent();
+            assert!(groups.contains(&parent));
+            assert!(matches!(
+                world.get::<MaterialRef>(child),
+                Some(MaterialRef::Library(name)) if name == "Brick"
+            ));
+        }
+        for group in groups {
+            assert!(world.get::<Selected>(group).is_some());
+            assert!(world.get::<ChildOf>(group).is_none());
+        }
     }
 }
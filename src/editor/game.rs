@@ -10,6 +10,7 @@ use bevy_editor_game::{
 use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
 use bevy_infinite_grid::InfiniteGridSettings;
 
+use crate::commands::SnapshotHistory;
 use crate::editor::{EditorMode, EditorState, TransformOperation};
 use crate::scene::{build_editor_scene, restore_scene_from_data, SceneEntity};
 use crate::selection::Selected;
@@ -349,7 +350,11 @@ impl Command for ResetCommand {
             .and_then(|s| s.data.clone());
 
         if let Some(data) = data {
-            restore_scene_from_data(world, &data);
+            let entity_map = restore_scene_from_data(world, &data);
+            // Keep undo history pointing at the restored entities
+            if let Some(mut history) = world.get_resource_mut::<SnapshotHistory>() {
+                history.remap_entities(&entity_map);
+            }
             rebuild_spatial_query_pipeline(world);
             info!("Scene restored from game snapshot");
         } else {
@@ -4,7 +4,7 @@ use bevy_egui::EguiContexts;
 use bevy_editor_game::GameState;
 
 use super::state::{AxisConstraint, ControlPointSnapState, CycleShadingModeEvent, EditorMode, EditorState, PinnedWindows, ToggleEditorEvent, TogglePreviewModeEvent, TransformOperation};
-use crate::commands::TakeSnapshotCommand;
+use crate::commands::RecordTransformsCommand;
 use crate::scene::GroupSelectedEvent;
 use crate::selection::Selected;
 use crate::ui::{open_add_component_palette, CommandPaletteState, ComponentEditorState};
@@ -251,9 +251,10 @@ fn handle_mode_input(
             *transform_op = TransformOperation::Rotate;
             *axis_constraint = AxisConstraint::None;
         } else if keyboard.just_pressed(KeyCode::KeyR) {
-            // Take snapshot before entering place mode
+            // Record transforms before entering place mode
             if !selected.is_empty() {
-                commands.queue(TakeSnapshotCommand {
+                commands.queue(RecordTransformsCommand {
+                    entities: selected.iter().collect(),
                     description: "Place entities".to_string(),
                 });
             }
@@ -265,9 +266,10 @@ fn handle_mode_input(
             let spline_has_selected_point = !selected_splines.is_empty()
                 && control_point_selection.0.is_some();
             if !spline_has_selected_point {
-                // Take snapshot before entering snap to object mode
+                // Record transforms before entering snap to object mode
                 if !selected.is_empty() {
-                    commands.queue(TakeSnapshotCommand {
+                    commands.queue(RecordTransformsCommand {
+                        entities: selected.iter().collect(),
                         description: "Snap to object".to_string(),
                     });
                 }
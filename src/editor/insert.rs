@@ -6,7 +6,7 @@ use bevy_egui::EguiContexts;
 
 use super::camera::EditorCamera;
 use super::state::{EditorMode, EditorState, InsertObjectType, InsertPreview, InsertState, SnapSubMode, StartInsertEvent};
-use crate::commands::TakeSnapshotCommand;
+use crate::commands::RecordSpawnCommand;
 use bevy_spline_3d::prelude::SplineType;
 
 use crate::constants::physics;
@@ -263,7 +263,7 @@ pub fn spawn_preview_entity(
                     .id()
             }
         }
-        InsertObjectType::Spline(_spline_type) => {
+        InsertObjectType::Spline(_) | InsertObjectType::DeformSpline => {
             // For splines, show a simple line indicator as preview
             // The actual spline gizmos will be handled by the library
             commands
@@ -394,6 +394,36 @@ pub fn spawn_preview_entity(
                 ))
                 .id()
         }
+        InsertObjectType::SoundEmitter => {
+            // Show a small translucent sphere at the emitter position
+            commands
+                .spawn((
+                    InsertPreview,
+                    Mesh3d(meshes.add(Sphere::new(0.3))),
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color: Color::srgba(0.3, 0.7, 1.0, 0.5),
+                        alpha_mode: AlphaMode::Blend,
+                        ..default()
+                    })),
+                    Transform::from_translation(Vec3::ZERO),
+                ))
+                .id()
+        }
+        InsertObjectType::TriggerVolume => {
+            // Show a translucent cube matching the default box volume
+            commands
+                .spawn((
+                    InsertPreview,
+                    Mesh3d(meshes.add(Cuboid::new(2.0, 2.0, 2.0))),
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color: Color::srgba(0.2, 0.9, 0.5, 0.25),
+                        alpha_mode: AlphaMode::Blend,
+                        ..default()
+                    })),
+                    Transform::from_translation(Vec3::ZERO),
+                ))
+                .id()
+        }
         InsertObjectType::Prefab => {
             // Load the prefab's scene file as the preview (scene_path is set by the palette)
             if let Some(path) = scene_path {
@@ -652,6 +682,7 @@ fn handle_insert_click(
             SplineType::CatmullRom => "Catmull-Rom Spline".to_string(),
             SplineType::BSpline => "B-Spline".to_string(),
         },
+        InsertObjectType::DeformSpline => "Mesh Deform Spline".to_string(),
         InsertObjectType::FogVolume => "Fog Volume".to_string(),
         InsertObjectType::Stairs => "Stairs".to_string(),
         InsertObjectType::Ramp => "Ramp".to_string(),
@@ -659,13 +690,15 @@ fn handle_insert_click(
         InsertObjectType::LShape => "L-Shape".to_string(),
         InsertObjectType::ParticleEffect => "Particle Effect".to_string(),
         InsertObjectType::Decal => "Decal".to_string(),
+        InsertObjectType::SoundEmitter => "Sound Emitter".to_string(),
+        InsertObjectType::TriggerVolume => "Trigger Volume".to_string(),
         InsertObjectType::Prefab => {
             insert_state.prefab_name.as_ref()
                 .map(|n| format!("Prefab: {}", n))
                 .unwrap_or_else(|| "Prefab".to_string())
         }
     };
-    commands.queue(TakeSnapshotCommand {
+    commands.queue(RecordSpawnCommand {
         description: format!("Insert {}", object_name),
     });
 
@@ -724,6 +757,13 @@ fn handle_insert_click(
                 rotation,
             });
         }
+        InsertObjectType::DeformSpline => {
+            spawn_entity_events.write(SpawnEntityEvent {
+                kind: SpawnEntityKind::DeformSpline,
+                position,
+                rotation,
+            });
+        }
         InsertObjectType::FogVolume => {
             spawn_entity_events.write(SpawnEntityEvent {
                 kind: SpawnEntityKind::FogVolume,
@@ -773,6 +813,20 @@ fn handle_insert_click(
                 rotation,
             });
         }
+        InsertObjectType::SoundEmitter => {
+            spawn_entity_events.write(SpawnEntityEvent {
+                kind: SpawnEntityKind::SoundEmitter,
+                position,
+                rotation,
+            });
+        }
+        InsertObjectType::TriggerVolume => {
+            spawn_entity_events.write(SpawnEntityEvent {
+                kind: SpawnEntityKind::TriggerVolume,
+                position,
+                rotation,
+            });
+        }
         InsertObjectType::Prefab => {
             if let Some(prefab_name) = insert_state.prefab_name.clone() {
                 spawn_prefab_events.write(SpawnPrefabEvent {
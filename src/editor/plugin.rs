@@ -12,6 +12,7 @@ use bevy_gaussian_splatting::GaussianSplattingPlugin;
 use bevy_grid_shader::GridMaterialPlugin;
 use bevy_outliner::prelude::*;
 use bevy_procedural::ProceduralPlugin;
+use bevy_spline_3d::deform::SplineMeshDeformPlugin;
 use bevy_spline_3d::path_follow::SplineFollowPlugin;
 
 use super::state::ViewportShadingMode;
@@ -23,6 +24,7 @@ use super::marks::CameraMarksPlugin;
 use super::scene_loading::SceneLoadingPlugin;
 use super::spline_edit::SplineEditPlugin;
 use super::state::EditorStatePlugin;
+use crate::audio::SoundPlugin;
 use crate::commands::CommandsPlugin;
 use crate::gizmos::EditorGizmosPlugin;
 use crate::navigation::NavigationPlugin;
@@ -226,6 +228,7 @@ impl Plugin for EditorPlugin {
             .add_plugins(SplineEditPlugin)
             .add_plugins(SceneLoadingPlugin)
             .add_plugins(SplineFollowPlugin)
+            .add_plugins(SplineMeshDeformPlugin)
             .add_plugins(ProceduralPlugin)
             // Editor systems
             .add_plugins(SelectionPlugin)
@@ -237,6 +240,8 @@ impl Plugin for EditorPlugin {
             .add_plugins(VfxEditorPlugin)
             // Effects
             .add_plugins(EffectPlugin)
+            // Audio
+            .add_plugins(SoundPlugin)
             // Navigation (navmesh + pathfinding)
             .add_plugins(NavigationPlugin)
             // UI
@@ -9,15 +9,15 @@ use bevy_egui::EguiContexts;
 use bevy_procedural::ProceduralEntity;
 use bevy_spline_3d::prelude::*;
 use bevy_spline_3d::editor::selection::pick_control_points;
-use bevy_spline_3d::road::{GeneratedRoadMesh, GeneratedIntersectionMesh};
+use bevy_spline_3d::road::{GeneratedRoadMesh, GeneratedIntersectionMesh, GeneratedLaneMarkings};
 
 use avian3d::prelude::{SpatialQuery, SpatialQueryFilter};
 
 use super::state::{ControlPointSnapState, EditorMode, EditorState, SelectedControlPointIndex};
-use crate::commands::TakeSnapshotCommand;
+use crate::commands::RecordEntitiesCommand;
 use crate::constants::physics;
 use crate::editor::EditorCamera;
-use crate::scene::{SceneEntity, SceneProceduralObject, SplineMarker};
+use crate::scene::{PersistentId, SceneEntity, SceneProceduralObject, SplineMarker};
 use crate::selection::Selected;
 use crate::utils::{should_process_input, snap_to_grid};
 
@@ -51,6 +51,7 @@ impl Plugin for SplineEditPlugin {
                 handle_control_point_snap_confirm.run_if(in_state(EditorMode::Edit)),
                 // Tag procedural objects spawned by bevy_spline_3d
                 tag_procedural_objects,
+                sync_road_graph_ids,
             )
                 .chain(),
         );
@@ -184,10 +185,13 @@ fn handle_spline_hotkeys(
     if splines.is_empty() {
         return;
     }
+    let spline_entities: Vec<Entity> = splines.iter().map(|(e, _)| e).collect();
 
     // A - Add control point after selection
     if keyboard.just_pressed(KeyCode::KeyA) {
-        commands.queue(TakeSnapshotCommand {
+        commands.queue(RecordEntitiesCommand {
+            entities: spline_entities.clone(),
+            recursive: false,
             description: "Add spline control point".to_string(),
         });
         for (entity, mut spline) in &mut splines {
@@ -217,7 +221,9 @@ fn handle_spline_hotkeys(
 
     // X - Delete selected control point
     if keyboard.just_pressed(KeyCode::KeyX) {
-        commands.queue(TakeSnapshotCommand {
+        commands.queue(RecordEntitiesCommand {
+            entities: spline_entities.clone(),
+            recursive: false,
             description: "Delete spline control point".to_string(),
         });
         if let Some(selected_index) = control_point_selection.0 {
@@ -262,9 +268,12 @@ fn handle_spline_hotkeys(
     }
 
     // Tab - Cycle spline type (note: this conflicts with mode switching in View mode,
-    // but we're in Edit mode here so it's safe)
-    if keyboard.just_pressed(KeyCode::Tab) {
-        commands.queue(TakeSnapshotCommand {
+    // but we're in Edit mode here so it's safe). Ctrl+Tab switches scene tabs.
+    let ctrl = keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
+    if keyboard.just_pressed(KeyCode::Tab) && !ctrl {
+        commands.queue(RecordEntitiesCommand {
+            entities: spline_entities.clone(),
+            recursive: false,
             description: "Cycle spline type".to_string(),
         });
         for (entity, mut spline) in &mut splines {
@@ -275,7 +284,9 @@ fn handle_spline_hotkeys(
 
     // S - Toggle closed/open
     if keyboard.just_pressed(KeyCode::KeyS) {
-        commands.queue(TakeSnapshotCommand {
+        commands.queue(RecordEntitiesCommand {
+            entities: spline_entities.clone(),
+            recursive: false,
             description: "Toggle spline closed".to_string(),
         });
         for (entity, mut spline) in &mut splines {
@@ -298,7 +309,9 @@ fn handle_spline_hotkeys(
         if let Some(point_index) = selected_index {
             if let Some((entity, spline)) = splines.iter().next() {
                 if point_index < spline.control_points.len() {
-                    commands.queue(TakeSnapshotCommand {
+                    commands.queue(RecordEntitiesCommand {
+                        entities: vec![entity],
+                        recursive: false,
                         description: "Snap control point to object".to_string(),
                     });
                     snap_state.active = true;
@@ -346,9 +359,11 @@ fn handle_control_point_drag(
     selection_state: Res<SelectionState>,
     mut was_dragging: Local<bool>,
 ) {
-    // Detect drag start: take snapshot of the pre-drag state for undo
+    // Detect drag start: record the pre-drag spline state for undo
     if !*was_dragging && selection_state.dragging {
-        commands.queue(TakeSnapshotCommand {
+        commands.queue(RecordEntitiesCommand {
+            entities: selected_points.iter().map(|m| m.spline_entity).collect(),
+            recursive: false,
             description: "Move spline control point".to_string(),
         });
     }
@@ -539,10 +554,27 @@ fn handle_control_point_snap_confirm(
     }
 }
 
+/// Mirror scene `PersistentId`s onto roads and intersections so exported
+/// road graphs refer to them by an id that survives reloads.
+fn sync_road_graph_ids(
+    mut commands: Commands,
+    changed: Query<
+        (Entity, &PersistentId),
+        (
+            Or<(With<SplineRoad>, With<RoadIntersection>)>,
+            Or<(Changed<PersistentId>, Without<RoadGraphId>)>,
+        ),
+    >,
+) {
+    for (entity, id) in &changed {
+        commands.entity(entity).insert(RoadGraphId(u64::from(id.0)));
+    }
+}
+
 /// Tag procedurally generated spline entities with `SceneProceduralObject`.
 ///
 /// Reactively inserts the marker on entities spawned by bevy_spline_3d
-/// (road meshes, intersection meshes, distributed instances) so the editor
+/// (road meshes, intersection meshes, deformed meshes, distributed instances) so the editor
 /// can identify them for a future baking process.
 fn tag_procedural_objects(
     mut commands: Commands,
@@ -551,7 +583,9 @@ fn tag_procedural_objects(
         (
             Or<(
                 With<GeneratedRoadMesh>,
+                With<GeneratedLaneMarkings>,
                 With<GeneratedIntersectionMesh>,
+                With<GeneratedDeformMesh>,
                 With<ProceduralEntity>,
             )>,
             Without<SceneProceduralObject>,
@@ -240,6 +240,8 @@ pub enum InsertObjectType {
     Scene,
     /// Spline with the specified type
     Spline(SplineType),
+    /// Spline bending a mesh along its length
+    DeformSpline,
     /// Volumetric fog volume
     FogVolume,
     /// Parametric stairs
@@ -254,6 +256,10 @@ pub enum InsertObjectType {
     ParticleEffect,
     /// Clustered decal (projected texture)
     Decal,
+    /// Spatial sound emitter
+    SoundEmitter,
+    /// Sensor volume reporting enters and exits
+    TriggerVolume,
     /// Prefab instance (name stored in InsertState.prefab_name)
     Prefab,
 }
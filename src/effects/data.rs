@@ -1,13 +1,17 @@
 //! Data model for the effect sequencer system.
 //!
 //! An effect is a list of **steps**, each with a **trigger** and one or more
-//! **actions**. Triggers fire based on time, collision, or internal events.
-//! Actions spawn entities, apply physics, emit events, etc.
+//! **actions**. Triggers fire based on time, collision, trigger volumes,
+//! proximity, internal effect events, or events and state changes from the game.
+//! Actions spawn entities, apply physics, emit events, etc. Steps can be gated
+//! on per-effect **variables** that actions set and increment.
 
 use std::collections::{HashMap, HashSet};
 
 use avian3d::prelude::RigidBody;
 use bevy::prelude::*;
+use bevy_editor_game::GameState;
+use bevy_vfx::VfxParamValue;
 use serde::{Deserialize, Serialize};
 
 use crate::scene::PrimitiveShape;
@@ -21,14 +25,88 @@ use crate::scene::PrimitiveShape;
 /// Runtime playback state (`EffectPlayback`) is regenerated from this marker
 /// after scene restore, following the same pattern as other marker components.
 #[derive(Component, Serialize, Deserialize, Clone, Debug, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, Default)]
 pub struct EffectMarker {
     pub steps: Vec<EffectStep>,
+    /// Variables declared on this effect, with their starting values.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[reflect(default)]
+    pub variables: Vec<EffectVariable>,
 }
 
 impl Default for EffectMarker {
     fn default() -> Self {
-        Self { steps: Vec::new() }
+        Self {
+            steps: Vec::new(),
+            variables: Vec::new(),
+        }
+    }
+}
+
+impl EffectMarker {
+    /// Time each step first fires when only the clock drives the effect.
+    ///
+    /// `None` for steps that wait on collisions, events or idle timeouts, and
+    /// for `AfterRule` chains that never reach a timed step.
+    pub fn scheduled_times(&self) -> Vec<Option<f32>> {
+        let mut times = vec![None; self.steps.len()];
+        // Each pass resolves one more link of AfterRule chains; cycles stay None
+        for _ in 0..=self.steps.len() {
+            let mut resolved_any = false;
+            for (idx, step) in self.steps.iter().enumerate() {
+                if times[idx].is_some() {
+                    continue;
+                }
+                let time = match &step.trigger {
+                    EffectTrigger::AtTime(t) => Some(t.max(0.0)),
+                    EffectTrigger::OnSpawn => Some(0.0),
+                    EffectTrigger::RepeatingInterval { interval, max_count } => {
+                        (*max_count != Some(0)).then_some(*interval)
+                    }
+                    EffectTrigger::AfterRule { source_rule, delay } => self
+                        .steps
+                        .iter()
+                        .zip(&times)
+                        .filter(|(s, _)| s.name == *source_rule)
+                        .filter_map(|(_, t)| *t)
+                        .reduce(f32::min)
+                        .map(|t| t + delay.max(0.0)),
+                    _ => None,
+                };
+                if time.is_some() {
+                    times[idx] = time;
+                    resolved_any = true;
+                }
+            }
+            if !resolved_any {
+                break;
+            }
+        }
+        times
+    }
+
+    /// Move a timed step so it first fires at `time`, adjusting whichever
+    /// value its trigger is keyed on. Returns false for untimed steps.
+    pub fn retime_step(&mut self, step_idx: usize, time: f32) -> bool {
+        let time = time.max(0.0);
+        let times = self.scheduled_times();
+        let Some(step) = self.steps.get_mut(step_idx) else {
+            return false;
+        };
+        match &mut step.trigger {
+            EffectTrigger::AtTime(t) => *t = time,
+            EffectTrigger::RepeatingInterval { interval, .. } => *interval = time.max(0.01),
+            EffectTrigger::AfterRule { delay, .. } => {
+                // The delay is measured from when the source rule fires
+                let Some(step_time) = times[step_idx] else {
+                    return false;
+                };
+                let source_time = step_time - delay.max(0.0);
+                *delay = (time - source_time).max(0.0);
+            }
+            _ => return false,
+        }
+        true
     }
 }
 
@@ -45,6 +123,168 @@ pub struct EffectStep {
     pub trigger: EffectTrigger,
     /// What happens when this step fires.
     pub actions: Vec<EffectAction>,
+    /// Only fire while this holds; the trigger is otherwise ignored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[reflect(default)]
+    pub condition: Option<EffectCondition>,
+}
+
+// ---------------------------------------------------------------------------
+// Variables and conditions
+// ---------------------------------------------------------------------------
+
+/// Value of an effect variable.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum EffectValue {
+    Int(i32),
+    Float(f32),
+    Bool(bool),
+}
+
+impl Default for EffectValue {
+    fn default() -> Self {
+        Self::Int(0)
+    }
+}
+
+impl EffectValue {
+    pub fn variant_index(&self) -> usize {
+        match self {
+            Self::Int(_) => 0,
+            Self::Float(_) => 1,
+            Self::Bool(_) => 2,
+        }
+    }
+
+    pub const VARIANT_LABELS: &[&str] = &["Int", "Float", "Bool"];
+
+    /// Convert to the variant at `idx`, carrying the value across.
+    pub fn convert_to(&self, idx: usize) -> Self {
+        match idx {
+            1 => Self::Float(self.as_f32()),
+            2 => Self::Bool(self.as_f32() != 0.0),
+            _ => Self::Int(self.as_f32().round() as i32),
+        }
+    }
+
+    /// Numeric view used by comparisons; booleans read as 0 or 1.
+    pub fn as_f32(&self) -> f32 {
+        match self {
+            Self::Int(v) => *v as f32,
+            Self::Float(v) => *v,
+            Self::Bool(v) => {
+                if *v {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Add `amount`, rounding it for ints. Booleans flip on any nonzero amount.
+    pub fn increment(&self, amount: f32) -> Self {
+        match self {
+            Self::Int(v) => Self::Int(v.saturating_add(amount.round() as i32)),
+            Self::Float(v) => Self::Float(v + amount),
+            Self::Bool(v) => Self::Bool(if amount != 0.0 { !v } else { *v }),
+        }
+    }
+}
+
+impl std::fmt::Display for EffectValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(v) => write!(f, "{}", v),
+            Self::Float(v) => write!(f, "{:.2}", v),
+            Self::Bool(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// A named variable declared on an effect.
+#[derive(Serialize, Deserialize, Clone, Debug, Reflect)]
+pub struct EffectVariable {
+    pub name: String,
+    /// Value at effect start and after a reset.
+    pub initial: EffectValue,
+}
+
+/// Comparison operator for step conditions.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum CompareOp {
+    #[default]
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl CompareOp {
+    pub const ALL: [CompareOp; 6] = [
+        Self::Equal,
+        Self::NotEqual,
+        Self::Less,
+        Self::LessOrEqual,
+        Self::Greater,
+        Self::GreaterOrEqual,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+        }
+    }
+}
+
+/// Gate on a step, e.g. `hits >= 3`.
+#[derive(Serialize, Deserialize, Clone, Debug, Reflect)]
+pub struct EffectCondition {
+    pub variable: String,
+    pub op: CompareOp,
+    pub value: EffectValue,
+}
+
+impl Default for EffectCondition {
+    fn default() -> Self {
+        Self {
+            variable: String::new(),
+            op: CompareOp::GreaterOrEqual,
+            value: EffectValue::Int(1),
+        }
+    }
+}
+
+impl EffectCondition {
+    /// Compare the variable's current value numerically. Unset variables
+    /// never pass.
+    pub fn evaluate(&self, variables: &HashMap<String, EffectValue>) -> bool {
+        let Some(current) = variables.get(&self.variable) else {
+            return false;
+        };
+        let (lhs, rhs) = (current.as_f32(), self.value.as_f32());
+        match self.op {
+            CompareOp::Equal => lhs == rhs,
+            CompareOp::NotEqual => lhs != rhs,
+            CompareOp::Less => lhs < rhs,
+            CompareOp::LessOrEqual => lhs <= rhs,
+            CompareOp::Greater => lhs > rhs,
+            CompareOp::GreaterOrEqual => lhs >= rhs,
+        }
+    }
+}
+
+impl std::fmt::Display for EffectCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.variable, self.op.symbol(), self.value)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -68,6 +308,18 @@ pub enum EffectTrigger {
     OnSpawn,
     /// Fire after no other rule has fired for the given duration.
     AfterIdleTimeout { timeout: f32 },
+    /// Fire when an entity matching `tag` enters the sensor collider named
+    /// `volume`. An empty `tag` matches any entity.
+    OnEnterVolume { volume: String, tag: String },
+    /// Fire when an entity matching `tag` leaves the sensor collider named
+    /// `volume`. An empty `tag` matches any entity.
+    OnExitVolume { volume: String, tag: String },
+    /// Fire when an entity matching `tag` comes within `radius` of the effect.
+    OnProximity { tag: String, radius: f32 },
+    /// Fire when game code writes a `GameEffectEvent` with this name.
+    OnGameEvent(String),
+    /// Fire when the game enters this state.
+    OnGameStateChange(GameState),
 }
 
 impl Default for EffectTrigger {
@@ -86,6 +338,11 @@ impl EffectTrigger {
             Self::RepeatingInterval { .. } => "Repeating Interval",
             Self::OnSpawn => "On Spawn",
             Self::AfterIdleTimeout { .. } => "After Idle Timeout",
+            Self::OnEnterVolume { .. } => "On Enter Volume",
+            Self::OnExitVolume { .. } => "On Exit Volume",
+            Self::OnProximity { .. } => "On Proximity",
+            Self::OnGameEvent(_) => "On Game Event",
+            Self::OnGameStateChange(_) => "On Game State Change",
         }
     }
 
@@ -98,9 +355,28 @@ impl EffectTrigger {
             Self::RepeatingInterval { .. } => 4,
             Self::OnSpawn => 5,
             Self::AfterIdleTimeout { .. } => 6,
+            Self::OnEnterVolume { .. } => 7,
+            Self::OnExitVolume { .. } => 8,
+            Self::OnProximity { .. } => 9,
+            Self::OnGameEvent(_) => 10,
+            Self::OnGameStateChange(_) => 11,
         }
     }
 
+    /// Whether this trigger reacts to a one-tick stimulus (an event, contact start,
+    /// volume crossing or game event) and fires again on every new one.
+    pub fn is_stimulus(&self) -> bool {
+        matches!(
+            self,
+            Self::OnCollision { .. }
+                | Self::OnEffectEvent(_)
+                | Self::OnEnterVolume { .. }
+                | Self::OnExitVolume { .. }
+                | Self::OnGameEvent(_)
+                | Self::OnGameStateChange(_)
+        )
+    }
+
     pub fn from_variant_index(idx: usize) -> Self {
         match idx {
             0 => Self::AtTime(0.0),
@@ -118,6 +394,20 @@ impl EffectTrigger {
             },
             5 => Self::OnSpawn,
             6 => Self::AfterIdleTimeout { timeout: 2.0 },
+            7 => Self::OnEnterVolume {
+                volume: String::new(),
+                tag: String::new(),
+            },
+            8 => Self::OnExitVolume {
+                volume: String::new(),
+                tag: String::new(),
+            },
+            9 => Self::OnProximity {
+                tag: String::new(),
+                radius: 3.0,
+            },
+            10 => Self::OnGameEvent(String::new()),
+            11 => Self::OnGameStateChange(GameState::Playing),
             _ => Self::AtTime(0.0),
         }
     }
@@ -130,6 +420,11 @@ impl EffectTrigger {
         "Repeating Interval",
         "On Spawn",
         "After Idle Timeout",
+        "On Enter Volume",
+        "On Exit Volume",
+        "On Proximity",
+        "On Game Event",
+        "On Game State Change",
     ];
 }
 
@@ -206,6 +501,24 @@ pub enum EffectAction {
         duration: f32,
         easing: EasingType,
     },
+    /// Set a parameter on a tagged particle effect.
+    SetVfxParam {
+        tag: String,
+        name: String,
+        value: VfxParamValue,
+    },
+    /// Assign an effect variable.
+    SetVariable { name: String, value: EffectValue },
+    /// Add to an effect variable; undeclared variables start at `Int(0)`.
+    IncrementVariable { name: String, amount: f32 },
+    /// Play a one-shot sound at a location; silent while scrubbing.
+    PlaySound {
+        tag: String,
+        path: String,
+        volume: f32,
+        radius: f32,
+        at: SpawnLocation,
+    },
 }
 
 impl EffectAction {
@@ -224,6 +537,10 @@ impl EffectAction {
             Self::InsertComponent { .. } => "Insert Component",
             Self::RemoveComponent { .. } => "Remove Component",
             Self::TweenValue { .. } => "Tween Value",
+            Self::SetVfxParam { .. } => "Set VFX Param",
+            Self::SetVariable { .. } => "Set Variable",
+            Self::IncrementVariable { .. } => "Increment Variable",
+            Self::PlaySound { .. } => "Play Sound",
         }
     }
 
@@ -242,6 +559,10 @@ impl EffectAction {
             Self::InsertComponent { .. } => 10,
             Self::RemoveComponent { .. } => 11,
             Self::TweenValue { .. } => 12,
+            Self::SetVfxParam { .. } => 13,
+            Self::SetVariable { .. } => 14,
+            Self::IncrementVariable { .. } => 15,
+            Self::PlaySound { .. } => 16,
         }
     }
 
@@ -259,6 +580,10 @@ impl EffectAction {
         "Insert Component",
         "Remove Component",
         "Tween Value",
+        "Set VFX Param",
+        "Set Variable",
+        "Increment Variable",
+        "Play Sound",
     ];
 
     pub fn from_variant_index(idx: usize) -> Self {
@@ -327,6 +652,26 @@ impl EffectAction {
                 duration: 1.0,
                 easing: EasingType::Linear,
             },
+            13 => Self::SetVfxParam {
+                tag: String::new(),
+                name: String::new(),
+                value: VfxParamValue::Float(1.0),
+            },
+            14 => Self::SetVariable {
+                name: String::new(),
+                value: EffectValue::Int(0),
+            },
+            15 => Self::IncrementVariable {
+                name: String::new(),
+                amount: 1.0,
+            },
+            16 => Self::PlaySound {
+                tag: String::new(),
+                path: String::new(),
+                volume: 1.0,
+                radius: 10.0,
+                at: SpawnLocation::Offset(Vec3::ZERO),
+            },
             _ => Self::EmitEvent(String::new()),
         }
     }
@@ -492,8 +837,11 @@ pub struct EffectPlayback {
     pub spawned: HashMap<String, Entity>,
     /// Events emitted this frame (cleared each tick).
     pub pending_events: Vec<String>,
-    /// Tags whose spawned entities collided this frame (cleared each tick).
+    /// Tags whose spawned entities started touching something this frame
+    /// (cleared each tick).
     pub collision_tags: HashSet<String>,
+    /// Spawned entities touching something, for contact-start detection.
+    pub touching_children: HashSet<Entity>,
     /// Last known collision point (for SpawnLocation::CollisionPoint).
     pub last_collision_point: Option<Vec3>,
     /// Rule name → elapsed time when that rule fired.
@@ -504,6 +852,27 @@ pub struct EffectPlayback {
     pub active_tweens: Vec<ActiveTween>,
     /// Fire count per rule name (for repeating triggers).
     pub repeat_counts: HashMap<String, u32>,
+    /// Pending scrub target; the effect is reset and replayed up to this time.
+    pub seek_to: Option<f32>,
+    /// While playing, jump back to the start of this `(start, end)` region
+    /// whenever `elapsed` passes its end.
+    pub loop_region: Option<(f32, f32)>,
+    /// `(volume, tag)` pairs entered this frame (cleared each tick). Each
+    /// entering entity is recorded under its name, its effect tag and `""`.
+    pub volume_entered: HashSet<(String, String)>,
+    /// `(volume, tag)` pairs exited this frame (cleared each tick).
+    pub volume_exited: HashSet<(String, String)>,
+    /// Entities inside each watched volume, for enter/exit detection.
+    pub volume_occupants: HashMap<String, HashSet<Entity>>,
+    /// Distance to the nearest matching entity per proximity tag (cleared
+    /// each tick).
+    pub nearest_tagged: HashMap<String, f32>,
+    /// `GameEffectEvent` names received this frame (cleared each tick).
+    pub game_events: Vec<String>,
+    /// Game states entered this frame (cleared each tick).
+    pub game_states_entered: Vec<GameState>,
+    /// Current variable values, seeded from the marker's declarations.
+    pub variables: HashMap<String, EffectValue>,
 }
 
 impl Default for EffectPlayback {
@@ -515,15 +884,52 @@ impl Default for EffectPlayback {
             spawned: HashMap::new(),
             pending_events: Vec::new(),
             collision_tags: HashSet::new(),
+            touching_children: HashSet::new(),
             last_collision_point: None,
             rule_fire_times: HashMap::new(),
             last_fire_time: 0.0,
             active_tweens: Vec::new(),
             repeat_counts: HashMap::new(),
+            seek_to: None,
+            loop_region: None,
+            volume_entered: HashSet::new(),
+            volume_exited: HashSet::new(),
+            volume_occupants: HashMap::new(),
+            nearest_tagged: HashMap::new(),
+            game_events: Vec::new(),
+            game_states_entered: Vec::new(),
+            variables: HashMap::new(),
         }
     }
 }
 
+impl EffectPlayback {
+    /// Inject the stimulus an event-driven trigger waits for, so it fires on
+    /// the next tick. Used to preview triggers in the editor. Returns false
+    /// for triggers driven by the clock.
+    pub fn simulate_trigger(&mut self, trigger: &EffectTrigger) -> bool {
+        match trigger {
+            EffectTrigger::OnCollision { tag } => {
+                self.collision_tags.insert(tag.clone());
+            }
+            EffectTrigger::OnEffectEvent(name) => self.pending_events.push(name.clone()),
+            EffectTrigger::OnEnterVolume { volume, tag } => {
+                self.volume_entered.insert((volume.clone(), tag.clone()));
+            }
+            EffectTrigger::OnExitVolume { volume, tag } => {
+                self.volume_exited.insert((volume.clone(), tag.clone()));
+            }
+            EffectTrigger::OnProximity { tag, .. } => {
+                self.nearest_tagged.insert(tag.clone(), 0.0);
+            }
+            EffectTrigger::OnGameEvent(name) => self.game_events.push(name.clone()),
+            EffectTrigger::OnGameStateChange(state) => self.game_states_entered.push(*state),
+            _ => return false,
+        }
+        true
+    }
+}
+
 /// Current playback state of an effect.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum PlaybackState {
@@ -539,3 +945,150 @@ pub struct EffectChild {
     pub effect_entity: Entity,
     pub tag: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(name: &str, trigger: EffectTrigger) -> EffectStep {
+        EffectStep {
+            name: name.to_string(),
+            trigger,
+            actions: Vec::new(),
+            condition: None,
+        }
+    }
+
+    fn chained_marker() -> EffectMarker {
+        EffectMarker {
+            steps: vec![
+                step(
+                    "land",
+                    EffectTrigger::AfterRule {
+                        source_rule: "drop".into(),
+                        delay: 0.5,
+                    },
+                ),
+                step("drop", EffectTrigger::AtTime(1.0)),
+                step("hit", EffectTrigger::OnCollision { tag: "rock".into() }),
+                step(
+                    "loop_a",
+                    EffectTrigger::AfterRule {
+                        source_rule: "loop_b".into(),
+                        delay: 0.0,
+                    },
+                ),
+                step(
+                    "loop_b",
+                    EffectTrigger::AfterRule {
+                        source_rule: "loop_a".into(),
+                        delay: 0.0,
+                    },
+                ),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_scheduled_times_resolve_rule_chains() {
+        let times = chained_marker().scheduled_times();
+        assert_eq!(times, vec![Some(1.5), Some(1.0), None, None, None]);
+    }
+
+    #[test]
+    fn test_retime_after_rule_keeps_source() {
+        let mut marker = chained_marker();
+        assert!(marker.retime_step(0, 3.0));
+        assert!(matches!(
+            marker.steps[0].trigger,
+            EffectTrigger::AfterRule { delay, .. } if (delay - 2.0).abs() < 1e-5
+        ));
+        assert_eq!(marker.scheduled_times()[1], Some(1.0));
+        assert!(!marker.retime_step(2, 1.0));
+    }
+
+    #[test]
+    fn test_new_triggers_roundtrip_ron() {
+        let marker = EffectMarker {
+            steps: vec![
+                step(
+                    "enter",
+                    EffectTrigger::OnEnterVolume {
+                        volume: "Gate".into(),
+                        tag: "player".into(),
+                    },
+                ),
+                step(
+                    "near",
+                    EffectTrigger::OnProximity {
+                        tag: "player".into(),
+                        radius: 2.5,
+                    },
+                ),
+                step("boss", EffectTrigger::OnGameEvent("boss_spawned".into())),
+                step("paused", EffectTrigger::OnGameStateChange(GameState::Paused)),
+            ],
+            ..Default::default()
+        };
+        let ron_str = ron::to_string(&marker).unwrap();
+        let parsed: EffectMarker = ron::from_str(&ron_str).unwrap();
+        assert_eq!(ron::to_string(&parsed).unwrap(), ron_str);
+    }
+
+    #[test]
+    fn test_condition_compares_numerically() {
+        let condition = EffectCondition {
+            variable: "hits".into(),
+            op: CompareOp::GreaterOrEqual,
+            value: EffectValue::Int(3),
+        };
+        let mut variables = HashMap::new();
+        assert!(!condition.evaluate(&variables));
+        variables.insert("hits".to_string(), EffectValue::Int(2));
+        assert!(!condition.evaluate(&variables));
+        variables.insert("hits".to_string(), EffectValue::Int(2).increment(1.0));
+        assert!(condition.evaluate(&variables));
+        variables.insert("hits".to_string(), EffectValue::Float(3.5));
+        assert!(condition.evaluate(&variables));
+        assert_eq!(condition.to_string(), "hits >= 3");
+    }
+
+    #[test]
+    fn test_marker_without_variables_deserializes() {
+        let ron_str = r#"(steps: [(name: "a", trigger: OnSpawn, actions: [])])"#;
+        let marker: EffectMarker = ron::from_str(ron_str).unwrap();
+        assert!(marker.variables.is_empty());
+        assert!(marker.steps[0].condition.is_none());
+        let saved = ron::to_string(&marker).unwrap();
+        assert!(!saved.contains("variables") && !saved.contains("condition"));
+    }
+
+    #[test]
+    fn test_simulate_trigger_injects_stimulus() {
+        let mut playback = EffectPlayback::default();
+        assert!(!playback.simulate_trigger(&EffectTrigger::AtTime(1.0)));
+        assert!(playback.simulate_trigger(&EffectTrigger::OnGameEvent("door".into())));
+        assert!(playback.simulate_trigger(&EffectTrigger::OnExitVolume {
+            volume: "Gate".into(),
+            tag: String::new(),
+        }));
+        assert_eq!(playback.game_events, vec!["door".to_string()]);
+        assert!(playback
+            .volume_exited
+            .contains(&("Gate".to_string(), String::new())));
+    }
+
+    #[test]
+    fn test_action_variant_indices_match_labels() {
+        for (idx, label) in EffectAction::VARIANT_LABELS.iter().enumerate() {
+            let action = EffectAction::from_variant_index(idx);
+            assert_eq!(action.variant_index(), idx);
+            assert_eq!(action.label(), *label);
+        }
+        assert!(matches!(
+            EffectAction::from_variant_index(16),
+            EffectAction::PlaySound { volume, .. } if volume == 1.0
+        ));
+    }
+}
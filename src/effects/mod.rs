@@ -3,15 +3,18 @@ pub mod presets;
 
 pub use data::*;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use avian3d::prelude::*;
 use bevy::light::ClusteredDecal;
 use bevy::prelude::*;
 
+use crate::audio::SoundEmitterMarker;
 use crate::constants::physics;
-use bevy_vfx::VfxLibrary;
+use crate::scene::TriggerVolumeMarker;
+use bevy_editor_game::{GameEffectEvent, GameState, TriggerEnter, TriggerExit};
+use bevy_vfx::{VfxLibrary, VfxParamOverrides, VfxParamValue};
 
 /// Library of named effect presets.
 #[derive(Resource, Default)]
@@ -33,6 +36,12 @@ impl Plugin for EffectPlugin {
             .register_type::<SpawnLocation>()
             .register_type::<TweenProperty>()
             .register_type::<EasingType>()
+            .register_type::<EffectValue>()
+            .register_type::<EffectVariable>()
+            .register_type::<EffectCondition>()
+            .register_type::<CompareOp>()
+            .register_type::<GameState>()
+            .add_message::<GameEffectEvent>()
             .init_resource::<EffectLibrary>()
             .add_systems(PreStartup, init_effect_library)
             .add_systems(
@@ -42,6 +51,18 @@ impl Plugin for EffectPlugin {
                     detect_effect_collisions
                         .before(advance_effects)
                         .run_if(any_with_component::<EffectPlayback>),
+                    seek_effects
+                        .before(advance_effects)
+                        .run_if(any_with_component::<EffectPlayback>),
+                    (
+                        detect_effect_volumes,
+                        detect_effect_proximity,
+                        receive_game_effect_events,
+                        detect_game_state_changes,
+                    )
+                        .after(seek_effects)
+                        .before(advance_effects)
+                        .run_if(any_with_component::<EffectPlayback>),
                     advance_effects.run_if(any_with_component::<EffectPlayback>),
                     advance_tweens
                         .after(advance_effects)
@@ -187,6 +208,13 @@ fn detect_effect_collisions(
     effect_children: Query<(Entity, &EffectChild)>,
     mut effects: Query<&mut EffectPlayback>,
 ) {
+    // Forget children that were despawned while touching
+    for mut playback in &mut effects {
+        playback
+            .touching_children
+            .retain(|entity| effect_children.contains(*entity));
+    }
+
     for (child_entity, child) in &effect_children {
         // Check if this child is currently colliding with anything
         let mut hit_point = None;
@@ -206,19 +234,233 @@ fn detect_effect_collisions(
             break;
         }
 
-        if hit_point.is_some() {
-            if let Ok(mut playback) = effects.get_mut(child.effect_entity) {
-                if playback.state == PlaybackState::Playing {
-                    playback.collision_tags.insert(child.tag.clone());
-                    if let Some(pt) = hit_point {
-                        playback.last_collision_point = Some(pt);
+        if let Ok(mut playback) = effects.get_mut(child.effect_entity) {
+            if playback.state == PlaybackState::Playing {
+                record_child_contact(&mut playback, child_entity, &child.tag, hit_point);
+            }
+        }
+    }
+}
+
+/// Update the contact state of an effect child. `Collisions` lists a pair on
+/// every frame it touches, so the tag is only reported when contact begins.
+fn record_child_contact(
+    playback: &mut EffectPlayback,
+    child_entity: Entity,
+    tag: &str,
+    hit_point: Option<Vec3>,
+) {
+    let Some(point) = hit_point else {
+        playback.touching_children.remove(&child_entity);
+        return;
+    };
+    if playback.touching_children.insert(child_entity) {
+        playback.collision_tags.insert(tag.to_string());
+        playback.last_collision_point = Some(point);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Scene and game stimuli for effect triggers
+// ---------------------------------------------------------------------------
+
+/// Tags `entity` can be matched by: `""` (any), its `Name`, and the tag it
+/// was spawned under if it belongs to `effect_entity`.
+fn entity_trigger_tags(
+    entity: Entity,
+    effect_entity: Entity,
+    names: &Query<&Name>,
+    effect_children: &Query<&EffectChild>,
+) -> Vec<String> {
+    let mut tags = vec![String::new()];
+    if let Ok(name) = names.get(entity) {
+        tags.push(name.as_str().to_string());
+    }
+    if let Ok(child) = effect_children.get(entity) {
+        if child.effect_entity == effect_entity {
+            tags.push(child.tag.clone());
+        }
+    }
+    tags
+}
+
+/// Record enters and exits of the sensor volumes named by `OnEnterVolume` and
+/// `OnExitVolume` steps on the effect.
+///
+/// Trigger volumes report through `TriggerEnter` / `TriggerExit`, so their
+/// filter applies; other sensors are tracked from their contacts.
+fn detect_effect_volumes(
+    mut commands: Commands,
+    mut effects: Query<(Entity, &EffectMarker, &mut EffectPlayback)>,
+    volumes: Query<
+        (
+            Entity,
+            &Name,
+            Option<&CollidingEntities>,
+            Has<TriggerVolumeMarker>,
+        ),
+        With<Sensor>,
+    >,
+    names: Query<&Name>,
+    effect_children: Query<&EffectChild>,
+    mut trigger_enters: MessageReader<TriggerEnter>,
+    mut trigger_exits: MessageReader<TriggerExit>,
+) {
+    let entered: Vec<TriggerEnter> = trigger_enters.read().copied().collect();
+    let exited: Vec<TriggerExit> = trigger_exits.read().copied().collect();
+
+    for (effect_entity, marker, mut playback) in &mut effects {
+        if playback.state != PlaybackState::Playing {
+            continue;
+        }
+
+        let watched: HashSet<&str> = marker
+            .steps
+            .iter()
+            .filter_map(|step| match &step.trigger {
+                EffectTrigger::OnEnterVolume { volume, .. }
+                | EffectTrigger::OnExitVolume { volume, .. } => Some(volume.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        for volume_name in watched {
+            let Some((volume_entity, _, colliding, is_trigger_volume)) = volumes
+                .iter()
+                .find(|(_, name, _, _)| name.as_str() == volume_name)
+            else {
+                continue;
+            };
+
+            if is_trigger_volume {
+                for message in entered.iter().filter(|m| m.volume == volume_entity) {
+                    for tag in
+                        entity_trigger_tags(message.entity, effect_entity, &names, &effect_children)
+                    {
+                        playback
+                            .volume_entered
+                            .insert((volume_name.to_string(), tag));
                     }
                 }
+                for message in exited.iter().filter(|m| m.volume == volume_entity) {
+                    for tag in
+                        entity_trigger_tags(message.entity, effect_entity, &names, &effect_children)
+                    {
+                        playback
+                            .volume_exited
+                            .insert((volume_name.to_string(), tag));
+                    }
+                }
+                continue;
+            }
+
+            let Some(colliding) = colliding else {
+                // Start tracking contacts; occupants show up from next frame
+                commands
+                    .entity(volume_entity)
+                    .insert(CollidingEntities::default());
+                continue;
+            };
+
+            let current: HashSet<Entity> = colliding.iter().copied().collect();
+            let previous = playback
+                .volume_occupants
+                .insert(volume_name.to_string(), current.clone())
+                .unwrap_or_default();
+
+            for entity in current.difference(&previous) {
+                for tag in entity_trigger_tags(*entity, effect_entity, &names, &effect_children) {
+                    playback.volume_entered.insert((volume_name.to_string(), tag));
+                }
+            }
+            for entity in previous.difference(&current) {
+                for tag in entity_trigger_tags(*entity, effect_entity, &names, &effect_children) {
+                    playback.volume_exited.insert((volume_name.to_string(), tag));
+                }
             }
         }
     }
 }
 
+/// Record the distance to the nearest entity matching each `OnProximity` tag,
+/// by `Name` or by the tag of a child this effect spawned.
+fn detect_effect_proximity(
+    mut effects: Query<(Entity, &EffectMarker, &mut EffectPlayback, &GlobalTransform)>,
+    named: Query<(Entity, &Name, &GlobalTransform)>,
+    transforms: Query<&GlobalTransform>,
+) {
+    for (effect_entity, marker, mut playback, effect_transform) in &mut effects {
+        if playback.state != PlaybackState::Playing {
+            continue;
+        }
+
+        let origin = effect_transform.translation();
+        for step in &marker.steps {
+            let EffectTrigger::OnProximity { tag, .. } = &step.trigger else {
+                continue;
+            };
+            if tag.is_empty() {
+                continue;
+            }
+
+            let child = playback
+                .spawned
+                .get(tag)
+                .and_then(|entity| transforms.get(*entity).ok())
+                .map(|t| t.translation());
+            let nearest = named
+                .iter()
+                .filter(|(entity, name, _)| *entity != effect_entity && name.as_str() == tag)
+                .map(|(_, _, t)| t.translation())
+                .chain(child)
+                .map(|position| position.distance(origin))
+                .reduce(f32::min);
+
+            if let Some(distance) = nearest {
+                let entry = playback
+                    .nearest_tagged
+                    .entry(tag.clone())
+                    .or_insert(f32::MAX);
+                *entry = entry.min(distance);
+            }
+        }
+    }
+}
+
+/// Deliver `GameEffectEvent`s written by game code to playing effects.
+fn receive_game_effect_events(
+    mut events: MessageReader<GameEffectEvent>,
+    mut effects: Query<(Entity, &mut EffectPlayback)>,
+) {
+    for event in events.read() {
+        for (entity, mut playback) in &mut effects {
+            if playback.state == PlaybackState::Playing
+                && event.target.is_none_or(|target| target == entity)
+            {
+                playback.game_events.push(event.name.clone());
+            }
+        }
+    }
+}
+
+/// Record `GameState` transitions on playing effects.
+fn detect_game_state_changes(
+    game_state: Res<State<GameState>>,
+    mut last_state: Local<Option<GameState>>,
+    mut effects: Query<&mut EffectPlayback>,
+) {
+    let current = *game_state.get();
+    let previous = last_state.replace(current);
+    if previous.is_none_or(|previous| previous == current) {
+        return;
+    }
+    for mut playback in &mut effects {
+        if playback.state == PlaybackState::Playing {
+            playback.game_states_entered.push(current);
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Spawn location helper
 // ---------------------------------------------------------------------------
@@ -240,6 +482,9 @@ fn resolve_spawn_location(
 // Playback advance
 // ---------------------------------------------------------------------------
 
+/// Fixed step used when replaying an effect up to a scrub target.
+const SEEK_STEP: f32 = 1.0 / 60.0;
+
 /// Main playback tick: advance time, check triggers, execute actions.
 fn advance_effects(
     mut commands: Commands,
@@ -256,96 +501,218 @@ fn advance_effects(
             continue;
         }
 
-        let dt = time.delta_secs();
-        playback.elapsed += dt;
+        tick_effect(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &vfx_library,
+            &effect_library,
+            &asset_server,
+            effect_entity,
+            marker,
+            &mut playback,
+            effect_transform,
+            time.delta_secs(),
+        );
 
-        // Collect events emitted this frame for cross-step triggering
-        let mut new_events: Vec<String> = Vec::new();
+        // Wrap around a looped region by replaying from its start
+        if let Some((start, end)) = playback.loop_region {
+            if end > start && playback.elapsed >= end {
+                playback.seek_to = Some(start);
+            }
+        }
+    }
+}
 
-        for (step_idx, step) in marker.steps.iter().enumerate() {
-            // Repeatable triggers can fire multiple times
-            let is_repeatable = matches!(
-                &step.trigger,
-                EffectTrigger::RepeatingInterval { .. } | EffectTrigger::AfterIdleTimeout { .. }
+/// Reset effects with a pending `seek_to` and replay them from the start in
+/// fixed steps, so scrubbing lands on the same state regardless of frame rate.
+///
+/// Only the clock is replayed: collisions, volumes, proximity and game events
+/// from the live world are not recorded, so those steps stay unfired until
+/// playback resumes.
+fn seek_effects(
+    mut commands: Commands,
+    mut effects: Query<(Entity, &EffectMarker, &mut EffectPlayback, &GlobalTransform)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    vfx_library: Res<VfxLibrary>,
+    effect_library: Res<EffectLibrary>,
+    asset_server: Res<AssetServer>,
+) {
+    for (effect_entity, marker, mut playback, effect_transform) in &mut effects {
+        let Some(target) = playback.seek_to.take() else {
+            continue;
+        };
+
+        let state = playback.state;
+        let loop_region = playback.loop_region;
+        cleanup_effect(&mut commands, &mut playback);
+        playback.loop_region = loop_region;
+
+        let mut remaining = target.max(0.0);
+        while remaining > 0.0 {
+            let dt = remaining.min(SEEK_STEP);
+            tick_effect(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                &vfx_library,
+                &effect_library,
+                &asset_server,
+                effect_entity,
+                marker,
+                &mut playback,
+                effect_transform,
+                dt,
             );
-            if !is_repeatable && playback.fired_steps.contains(&step_idx) {
-                continue;
-            }
+            remaining -= dt;
+        }
 
-            let should_fire = match &step.trigger {
-                EffectTrigger::AtTime(t) => playback.elapsed >= *t,
-                EffectTrigger::OnCollision { tag } => playback.collision_tags.contains(tag),
-                EffectTrigger::OnEffectEvent(name) => playback.pending_events.contains(name),
-                EffectTrigger::AfterRule { source_rule, delay } => {
-                    playback
-                        .rule_fire_times
-                        .get(source_rule)
-                        .map(|t| playback.elapsed >= t + delay)
-                        .unwrap_or(false)
-                }
-                EffectTrigger::RepeatingInterval { interval, max_count } => {
-                    let count = playback.repeat_counts.get(&step.name).copied().unwrap_or(0);
-                    let within_max = max_count.map_or(true, |max| count < max);
-                    within_max && playback.elapsed >= (count + 1) as f32 * interval
-                }
-                EffectTrigger::OnSpawn => {
-                    !playback.fired_steps.contains(&step_idx) && playback.elapsed < dt * 2.0
-                }
-                EffectTrigger::AfterIdleTimeout { timeout } => {
-                    playback.last_fire_time > 0.0
-                        && playback.elapsed - playback.last_fire_time >= *timeout
-                }
-            };
+        // Scrubbing a stopped effect leaves it paused at the new time
+        playback.state = match state {
+            PlaybackState::Stopped => PlaybackState::Paused,
+            other => other,
+        };
+    }
+}
 
-            if !should_fire {
-                continue;
-            }
+/// Advance one effect by `dt`: check triggers and execute the actions of
+/// every step that fires.
+#[allow(clippy::too_many_arguments)]
+fn tick_effect(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    vfx_library: &VfxLibrary,
+    effect_library: &EffectLibrary,
+    asset_server: &AssetServer,
+    effect_entity: Entity,
+    marker: &EffectMarker,
+    playback: &mut EffectPlayback,
+    effect_transform: &GlobalTransform,
+    dt: f32,
+) {
+    playback.elapsed += dt;
+
+    // Seed declared variables; values persist until the effect is reset
+    for variable in &marker.variables {
+        playback
+            .variables
+            .entry(variable.name.clone())
+            .or_insert(variable.initial);
+    }
 
-            playback.fired_steps.insert(step_idx);
+    // Collect events emitted this tick for cross-step triggering
+    let mut new_events: Vec<String> = Vec::new();
 
-            // Record fire time for rule chaining
-            let current_elapsed = playback.elapsed;
-            playback
-                .rule_fire_times
-                .insert(step.name.clone(), current_elapsed);
-            playback.last_fire_time = current_elapsed;
-
-            // Increment repeat count for repeating triggers
-            if is_repeatable {
-                *playback
-                    .repeat_counts
-                    .entry(step.name.clone())
-                    .or_insert(0) += 1;
-            }
-
-            for action in &step.actions {
-                execute_action(
-                    &mut commands,
-                    &mut meshes,
-                    &mut materials,
-                    &vfx_library,
-                    &effect_library,
-                    &asset_server,
-                    effect_entity,
-                    effect_transform,
-                    &mut playback,
-                    action,
-                    &mut new_events,
-                );
+    for (step_idx, step) in marker.steps.iter().enumerate() {
+        // Repeatable triggers can fire multiple times, and stimulus triggers
+        // fire again on every new stimulus
+        let is_repeatable = matches!(
+            &step.trigger,
+            EffectTrigger::RepeatingInterval { .. } | EffectTrigger::AfterIdleTimeout { .. }
+        );
+        if !is_repeatable
+            && !step.trigger.is_stimulus()
+            && playback.fired_steps.contains(&step_idx)
+        {
+            continue;
+        }
+
+        let should_fire = match &step.trigger {
+            EffectTrigger::AtTime(t) => playback.elapsed >= *t,
+            EffectTrigger::OnCollision { tag } => playback.collision_tags.contains(tag),
+            EffectTrigger::OnEffectEvent(name) => playback.pending_events.contains(name),
+            EffectTrigger::AfterRule { source_rule, delay } => {
+                playback
+                    .rule_fire_times
+                    .get(source_rule)
+                    .map(|t| playback.elapsed >= t + delay)
+                    .unwrap_or(false)
+            }
+            EffectTrigger::RepeatingInterval { interval, max_count } => {
+                let count = playback.repeat_counts.get(&step.name).copied().unwrap_or(0);
+                let within_max = max_count.map_or(true, |max| count < max);
+                within_max && playback.elapsed >= (count + 1) as f32 * interval
+            }
+            EffectTrigger::OnSpawn => {
+                !playback.fired_steps.contains(&step_idx) && playback.elapsed < dt * 2.0
             }
+            EffectTrigger::AfterIdleTimeout { timeout } => {
+                playback.last_fire_time > 0.0
+                    && playback.elapsed - playback.last_fire_time >= *timeout
+            }
+            EffectTrigger::OnEnterVolume { volume, tag } => playback
+                .volume_entered
+                .contains(&(volume.clone(), tag.clone())),
+            EffectTrigger::OnExitVolume { volume, tag } => playback
+                .volume_exited
+                .contains(&(volume.clone(), tag.clone())),
+            EffectTrigger::OnProximity { tag, radius } => playback
+                .nearest_tagged
+                .get(tag)
+                .is_some_and(|distance| distance <= radius),
+            EffectTrigger::OnGameEvent(name) => playback.game_events.contains(name),
+            EffectTrigger::OnGameStateChange(state) => {
+                playback.game_states_entered.contains(state)
+            }
+        };
+
+        if !should_fire {
+            continue;
         }
 
-        // Merge new events so subsequent steps can see them next frame
-        playback.pending_events.extend(new_events);
-    }
+        // Conditions see variables changed by earlier steps in the same tick
+        let condition_met = step
+            .condition
+            .as_ref()
+            .is_none_or(|condition| condition.evaluate(&playback.variables));
+        if !condition_met {
+            continue;
+        }
 
-    // Clear pending events and collision tags at end of frame (they only live one tick)
-    for (_, _, mut playback, _) in &mut effects {
-        if playback.state == PlaybackState::Playing {
-            playback.pending_events.clear();
-            playback.collision_tags.clear();
+        playback.fired_steps.insert(step_idx);
+
+        // Record fire time for rule chaining
+        let current_elapsed = playback.elapsed;
+        playback
+            .rule_fire_times
+            .insert(step.name.clone(), current_elapsed);
+        playback.last_fire_time = current_elapsed;
+
+        // Increment repeat count for repeating triggers
+        if is_repeatable {
+            *playback
+                .repeat_counts
+                .entry(step.name.clone())
+                .or_insert(0) += 1;
+        }
+
+        for action in &step.actions {
+            execute_action(
+                commands,
+                meshes,
+                materials,
+                vfx_library,
+                effect_library,
+                asset_server,
+                effect_entity,
+                effect_transform,
+                playback,
+                action,
+                &mut new_events,
+            );
         }
     }
+
+    // Stimuli only live one tick; new events are seen next tick
+    playback.pending_events = new_events;
+    playback.collision_tags.clear();
+    playback.volume_entered.clear();
+    playback.volume_exited.clear();
+    playback.nearest_tagged.clear();
+    playback.game_events.clear();
+    playback.game_states_entered.clear();
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -508,6 +875,37 @@ fn execute_action(
                 .id();
             playback.spawned.insert(tag.clone(), child);
         }
+        EffectAction::PlaySound {
+            tag,
+            path,
+            volume,
+            radius,
+            at,
+        } => {
+            // Seek replays run while stopped; scrubbing stays silent
+            if playback.state != PlaybackState::Playing || path.is_empty() {
+                return;
+            }
+            let pos = resolve_spawn_location(at, effect_transform, playback);
+
+            // The audio plugin gives the emitter a voice and attenuates it
+            let child = commands
+                .spawn((
+                    EffectChild {
+                        effect_entity,
+                        tag: tag.clone(),
+                    },
+                    SoundEmitterMarker {
+                        path: path.clone(),
+                        volume: *volume,
+                        looping: false,
+                        radius: *radius,
+                    },
+                    Transform::from_translation(pos),
+                ))
+                .id();
+            playback.spawned.insert(tag.clone(), child);
+        }
         EffectAction::SpawnEffect {
             tag,
             preset,
@@ -589,6 +987,24 @@ fn execute_action(
                 });
             }
         }
+        EffectAction::SetVfxParam { tag, name, value } => {
+            if let Some(&child_entity) = playback.spawned.get(tag) {
+                commands.queue(SetVfxParamFromEffect {
+                    entity: child_entity,
+                    name: name.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+        EffectAction::SetVariable { name, value } => {
+            playback.variables.insert(name.clone(), *value);
+        }
+        EffectAction::IncrementVariable { name, amount } => {
+            let current = playback.variables.get(name).copied().unwrap_or_default();
+            playback
+                .variables
+                .insert(name.clone(), current.increment(*amount));
+        }
     }
 }
 
@@ -699,6 +1115,29 @@ impl Command for RemoveComponentFromEffect {
     }
 }
 
+// ---------------------------------------------------------------------------
+// VFX parameter commands
+// ---------------------------------------------------------------------------
+
+struct SetVfxParamFromEffect {
+    entity: Entity,
+    name: String,
+    value: VfxParamValue,
+}
+
+impl Command for SetVfxParamFromEffect {
+    fn apply(self, world: &mut World) {
+        let Ok(mut entity_mut) = world.get_entity_mut(self.entity) else {
+            return;
+        };
+        if let Some(mut overrides) = entity_mut.get_mut::<VfxParamOverrides>() {
+            overrides.set(self.name, self.value);
+        } else {
+            entity_mut.insert(VfxParamOverrides::default().with(self.name, self.value));
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tween system
 // ---------------------------------------------------------------------------
@@ -709,7 +1148,8 @@ fn advance_tweens(
     mut point_lights: Query<&mut PointLight>,
 ) {
     for mut playback in &mut effects {
-        if playback.state != PlaybackState::Playing {
+        // Paused effects still apply tweens so scrubbed frames show them
+        if playback.state == PlaybackState::Stopped {
             continue;
         }
 
@@ -749,10 +1189,162 @@ pub fn cleanup_effect(commands: &mut Commands, playback: &mut EffectPlayback) {
     playback.fired_steps.clear();
     playback.pending_events.clear();
     playback.collision_tags.clear();
+    playback.touching_children.clear();
     playback.last_collision_point = None;
     playback.rule_fire_times.clear();
     playback.last_fire_time = 0.0;
     playback.active_tweens.clear();
     playback.repeat_counts.clear();
+    playback.volume_entered.clear();
+    playback.volume_exited.clear();
+    playback.volume_occupants.clear();
+    playback.nearest_tagged.clear();
+    playback.game_events.clear();
+    playback.game_states_entered.clear();
+    playback.variables.clear();
     playback.state = PlaybackState::Stopped;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn effect_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<StandardMaterial>()
+            .init_resource::<VfxLibrary>()
+            .init_resource::<EffectLibrary>();
+        app
+    }
+
+    /// Run one `tick_effect` with `game_events` delivered this frame
+    fn tick(
+        app: &mut App,
+        marker: &EffectMarker,
+        playback: EffectPlayback,
+        game_events: &[&str],
+    ) -> EffectPlayback {
+        let marker = marker.clone();
+        let mut playback = playback;
+        playback.game_events = game_events.iter().map(|e| e.to_string()).collect();
+        app.world_mut()
+            .run_system_once(
+                move |mut commands: Commands,
+                      mut meshes: ResMut<Assets<Mesh>>,
+                      mut materials: ResMut<Assets<StandardMaterial>>,
+                      vfx_library: Res<VfxLibrary>,
+                      effect_library: Res<EffectLibrary>,
+                      asset_server: Res<AssetServer>| {
+                    let mut playback = std::mem::take(&mut playback);
+                    tick_effect(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        &vfx_library,
+                        &effect_library,
+                        &asset_server,
+                        Entity::PLACEHOLDER,
+                        &marker,
+                        &mut playback,
+                        &GlobalTransform::IDENTITY,
+                        0.1,
+                    );
+                    playback
+                },
+            )
+            .expect("tick_effect system should run")
+    }
+
+    #[test]
+    fn game_event_steps_fire_on_every_event() {
+        let mut app = effect_app();
+        let (_, marker) = presets::default_presets()
+            .into_iter()
+            .find(|(name, _)| *name == "Three Switches")
+            .expect("Three Switches preset");
+
+        let mut playback = tick(&mut app, &marker, EffectPlayback::default(), &[]);
+        for expected in 1..=3 {
+            assert!(!playback.rule_fire_times.contains_key("open_door"));
+            playback = tick(&mut app, &marker, playback, &["switch"]);
+            assert_eq!(
+                playback.variables.get("switches"),
+                Some(&EffectValue::Int(expected))
+            );
+        }
+
+        assert!(playback.rule_fire_times.contains_key("open_door"));
+        assert_eq!(
+            playback.variables.get("door_open"),
+            Some(&EffectValue::Bool(true))
+        );
+    }
+
+    #[test]
+    fn one_shot_steps_fire_once() {
+        let mut app = effect_app();
+        let marker = EffectMarker {
+            steps: vec![EffectStep {
+                name: "count".into(),
+                trigger: EffectTrigger::AtTime(0.0),
+                actions: vec![EffectAction::IncrementVariable {
+                    name: "ticks".into(),
+                    amount: 1.0,
+                }],
+                condition: None,
+            }],
+            variables: vec![EffectVariable {
+                name: "ticks".into(),
+                initial: EffectValue::Int(0),
+            }],
+        };
+
+        let mut playback = EffectPlayback::default();
+        for _ in 0..3 {
+            playback = tick(&mut app, &marker, playback, &[]);
+        }
+
+        assert_eq!(playback.variables.get("ticks"), Some(&EffectValue::Int(1)));
+    }
+
+    #[test]
+    fn collision_steps_fire_once_per_contact() {
+        let mut app = effect_app();
+        let marker = EffectMarker {
+            steps: vec![EffectStep {
+                name: "impact".into(),
+                trigger: EffectTrigger::OnCollision {
+                    tag: "cube".into(),
+                },
+                actions: vec![EffectAction::IncrementVariable {
+                    name: "impacts".into(),
+                    amount: 1.0,
+                }],
+                condition: None,
+            }],
+            variables: vec![EffectVariable {
+                name: "impacts".into(),
+                initial: EffectValue::Int(0),
+            }],
+        };
+        let cube = Entity::from_raw_u32(1).unwrap();
+
+        // Resting on the floor for several frames is one collision
+        let mut playback = EffectPlayback::default();
+        for _ in 0..4 {
+            record_child_contact(&mut playback, cube, "cube", Some(Vec3::ZERO));
+            playback = tick(&mut app, &marker, playback, &[]);
+        }
+        assert_eq!(playback.variables.get("impacts"), Some(&EffectValue::Int(1)));
+
+        // Bouncing off and landing again is a new one
+        record_child_contact(&mut playback, cube, "cube", None);
+        playback = tick(&mut app, &marker, playback, &[]);
+        record_child_contact(&mut playback, cube, "cube", Some(Vec3::ZERO));
+        playback = tick(&mut app, &marker, playback, &[]);
+        assert_eq!(playback.variables.get("impacts"), Some(&EffectValue::Int(2)));
+    }
+}
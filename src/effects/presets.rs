@@ -9,6 +9,7 @@ pub fn default_presets() -> Vec<(&'static str, EffectMarker)> {
     vec![
         ("Falling Impact", falling_impact()),
         ("Pulsing Beacon", pulsing_beacon()),
+        ("Three Switches", three_switches()),
     ]
 }
 
@@ -33,6 +34,7 @@ fn falling_impact() -> EffectMarker {
                         velocity: Vec3::new(0.0, -8.0, 0.0),
                     },
                 ],
+                condition: None,
             },
             EffectStep {
                 name: "impact".into(),
@@ -58,8 +60,10 @@ fn falling_impact() -> EffectMarker {
                     },
                     EffectAction::EmitEvent("impact".into()),
                 ],
+                condition: None,
             },
         ],
+        ..Default::default()
     }
 }
 
@@ -88,6 +92,7 @@ fn pulsing_beacon() -> EffectMarker {
                         easing: EasingType::EaseOut,
                     },
                 ],
+                condition: None,
             },
             EffectStep {
                 name: "pulse".into(),
@@ -105,6 +110,7 @@ fn pulsing_beacon() -> EffectMarker {
                         easing: EasingType::EaseInOut,
                     },
                 ],
+                condition: None,
             },
             EffectStep {
                 name: "pulse_back".into(),
@@ -122,6 +128,80 @@ fn pulsing_beacon() -> EffectMarker {
                         easing: EasingType::EaseInOut,
                     },
                 ],
+                condition: None,
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+/// A door block shrinks away once the game has raised three "switch" events.
+/// Demonstrates variables, IncrementVariable, and step conditions.
+fn three_switches() -> EffectMarker {
+    EffectMarker {
+        steps: vec![
+            EffectStep {
+                name: "spawn_door".into(),
+                trigger: EffectTrigger::OnSpawn,
+                actions: vec![EffectAction::SpawnPrimitive {
+                    tag: "door".into(),
+                    shape: crate::scene::PrimitiveShape::Cube,
+                    offset: Vec3::new(0.0, 1.0, 0.0),
+                    material: None,
+                    rigid_body: Some(RigidBodyKind::Static),
+                }],
+                condition: None,
+            },
+            EffectStep {
+                name: "count_switch".into(),
+                trigger: EffectTrigger::OnGameEvent("switch".into()),
+                actions: vec![EffectAction::IncrementVariable {
+                    name: "switches".into(),
+                    amount: 1.0,
+                }],
+                condition: None,
+            },
+            EffectStep {
+                name: "open_door".into(),
+                trigger: EffectTrigger::OnGameEvent("switch".into()),
+                actions: vec![
+                    EffectAction::TweenValue {
+                        target_tag: "door".into(),
+                        property: TweenProperty::Scale,
+                        from: 1.0,
+                        to: 0.0,
+                        duration: 0.6,
+                        easing: EasingType::EaseIn,
+                    },
+                    EffectAction::SetVariable {
+                        name: "door_open".into(),
+                        value: EffectValue::Bool(true),
+                    },
+                ],
+                condition: Some(EffectCondition {
+                    variable: "switches".into(),
+                    op: CompareOp::Equal,
+                    value: EffectValue::Int(3),
+                }),
+            },
+            EffectStep {
+                name: "remove_door".into(),
+                trigger: EffectTrigger::AfterRule {
+                    source_rule: "open_door".into(),
+                    delay: 0.6,
+                },
+                actions: vec![EffectAction::Despawn { tag: "door".into() }],
+                condition: None,
+            },
+        ],
+        variables: vec![
+            EffectVariable {
+                name: "switches".into(),
+                initial: EffectValue::Int(0),
+            },
+            EffectVariable {
+                name: "door_open".into(),
+                initial: EffectValue::Bool(false),
             },
         ],
     }
@@ -11,8 +11,12 @@ use bevy_editor_game::CustomEntityRegistry;
 
 use avian3d::prelude::{Collider, SimpleCollider};
 
+use crate::audio::SoundEmitterMarker;
 use crate::editor::{EditorMode, EditorState};
-use crate::scene::{DecalMarker, DirectionalLightMarker, SceneLightMarker, SplineMarker};
+use crate::scene::{
+    DecalMarker, DirectionalLightMarker, SceneLightMarker, SplineMarker, TriggerOccupants, TriggerShape,
+    TriggerVolumeMarker,
+};
 use crate::selection::Selected;
 use crate::ui::Settings;
 
@@ -38,7 +42,7 @@ impl Plugin for EditorGizmosPlugin {
             .add_plugins(TransformGizmoPlugin)
             .add_plugins(InfiniteGridPlugin)
             .add_systems(PreStartup, (configure_gizmos, spawn_grid))
-            .add_systems(Update, (update_gizmo_settings, draw_directional_light_gizmos, draw_point_light_gizmos, draw_decal_gizmos, draw_custom_entity_gizmos, draw_meshless_selection_gizmos));
+            .add_systems(Update, (update_gizmo_settings, draw_directional_light_gizmos, draw_point_light_gizmos, draw_sound_emitter_gizmos, draw_trigger_volume_gizmos, draw_decal_gizmos, draw_custom_entity_gizmos, draw_meshless_selection_gizmos));
     }
 }
 
@@ -204,6 +208,80 @@ fn draw_point_light_gizmos(
     }
 }
 
+/// Draw gizmos for sound emitters, with the falloff radius when selected
+fn draw_sound_emitter_gizmos(
+    mut gizmos: Gizmos,
+    emitters: Query<(&GlobalTransform, &SoundEmitterMarker, Has<Selected>)>,
+    editor_state: Res<EditorState>,
+    mode: Res<State<EditorMode>>,
+) {
+    if !editor_state.gizmos_visible {
+        return;
+    }
+    let hide_selection = *mode.get() == EditorMode::Particle;
+    let color = Color::srgb(0.3, 0.7, 1.0);
+
+    for (transform, marker, is_selected) in emitters.iter() {
+        let position = transform.translation();
+
+        // Small sphere outline with two horizontal "sound wave" rings around it
+        let radius = 0.25;
+        gizmos.circle(Isometry3d::new(position, Quat::IDENTITY), radius, color);
+        gizmos.circle(Isometry3d::new(position, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)), radius, color);
+        gizmos.circle(Isometry3d::new(position, Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)), radius, color);
+        gizmos.circle(Isometry3d::new(position, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)), radius * 1.6, color.with_alpha(0.6));
+        gizmos.circle(Isometry3d::new(position, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)), radius * 2.2, color.with_alpha(0.3));
+
+        // When selected, draw the falloff sphere (but not in Particle mode)
+        if is_selected && !hide_selection && marker.radius > 0.0 {
+            let range = marker.radius;
+            let range_color = color.with_alpha(0.3);
+            gizmos.circle(Isometry3d::new(position, Quat::IDENTITY), range, range_color);
+            gizmos.circle(Isometry3d::new(position, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)), range, range_color);
+            gizmos.circle(Isometry3d::new(position, Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)), range, range_color);
+        }
+    }
+}
+
+/// Draw trigger volume shapes as translucent wireframes; brighter when
+/// selected or while something is inside during play.
+fn draw_trigger_volume_gizmos(
+    mut gizmos: Gizmos,
+    volumes: Query<(&GlobalTransform, &TriggerVolumeMarker, Has<Selected>, Option<&TriggerOccupants>)>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.gizmos_visible {
+        return;
+    }
+
+    for (transform, marker, is_selected, occupants) in volumes.iter() {
+        let occupied = occupants.is_some_and(|o| !o.0.is_empty());
+        let color = if occupied {
+            Color::srgba(1.0, 0.6, 0.2, 0.8)
+        } else if is_selected {
+            Color::srgba(0.2, 0.9, 0.5, 0.8)
+        } else {
+            Color::srgba(0.2, 0.9, 0.5, 0.3)
+        };
+
+        let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+        let isometry = Isometry3d::new(translation, rotation);
+        match marker.shape {
+            TriggerShape::Box { half_extents } => {
+                let size = half_extents * scale * 2.0;
+                gizmos.primitive_3d(&Cuboid::new(size.x, size.y, size.z), isometry, color);
+            }
+            TriggerShape::Sphere { radius } => {
+                gizmos.sphere(isometry, radius * scale.max_element(), color);
+            }
+            TriggerShape::Capsule { radius, half_height } => {
+                let capsule = Capsule3d::new(radius * scale.x.max(scale.z), half_height * scale.y * 2.0);
+                gizmos.primitive_3d(&capsule, isometry, color);
+            }
+        }
+    }
+}
+
 /// Draw wireframe cube for selected decals showing the projection volume.
 fn draw_decal_gizmos(
     mut gizmos: Gizmos,
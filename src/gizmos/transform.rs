@@ -6,7 +6,7 @@ use bevy_egui::EguiContexts;
 
 use bevy_spline_3d::prelude::Spline;
 
-use crate::commands::TakeSnapshotCommand;
+use crate::commands::RecordTransformsCommand;
 use crate::constants::physics;
 use crate::editor::{ActiveEdgeSnaps, AxisConstraint, DimensionSnapSettings, EditStepAmount, EditorCamera, EditorMode, EditorState, GizmoAxisConstraint, SnapSubMode, TransformOperation};
 use crate::gizmos::{SelectionCircleGizmo, XRayGizmoConfig, XRayGizmoDimmed};
@@ -586,7 +586,7 @@ fn handle_step_keys(
     axis_constraint: Res<AxisConstraint>,
     step_amount: Res<EditStepAmount>,
     editor_state: Res<EditorState>,
-    mut selected: Query<&mut Transform, (With<Selected>, Without<Locked>)>,
+    mut selected: Query<(Entity, &mut Transform), (With<Selected>, Without<Locked>)>,
     mut contexts: EguiContexts,
     mut commands: Commands,
 ) {
@@ -612,7 +612,7 @@ fn handle_step_keys(
         return;
     };
 
-    // Take snapshot before transforming
+    // Record transforms before changing them
     if !selected.is_empty() {
         let op_name = match *transform_op {
             TransformOperation::Translate => "Move",
@@ -620,12 +620,13 @@ fn handle_step_keys(
             TransformOperation::Scale => "Scale",
             _ => "Transform",
         };
-        commands.queue(TakeSnapshotCommand {
+        commands.queue(RecordTransformsCommand {
+            entities: selected.iter().map(|(e, _)| e).collect(),
             description: format!("{} entities (step)", op_name),
         });
     }
 
-    for mut transform in selected.iter_mut() {
+    for (_, mut transform) in selected.iter_mut() {
         match *transform_op {
             TransformOperation::Translate => {
                 let delta = step_amount.translate * direction;
@@ -1145,7 +1146,7 @@ fn manage_editing_sleep_state(
     let should_be_editing = in_edit_mode && has_transform_op && mouse_held && !ui_has_pointer;
     let just_started_editing = in_edit_mode && has_transform_op && mouse_just_pressed && !ui_has_pointer;
 
-    // Take a snapshot when we first start editing
+    // Record transforms when we first start editing
     if just_started_editing && !selected.is_empty() {
         let op_name = match *transform_op {
             TransformOperation::Translate => "Move",
@@ -1153,8 +1154,9 @@ fn manage_editing_sleep_state(
             TransformOperation::Scale => "Scale",
             _ => "Transform",
         };
-        info!("Taking transform snapshot: {} entities", op_name);
-        commands.queue(TakeSnapshotCommand {
+        info!("Recording transforms: {} entities", op_name);
+        commands.queue(RecordTransformsCommand {
+            entities: selected.iter().collect(),
             description: format!("{} entities", op_name),
         });
     }
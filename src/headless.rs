@@ -0,0 +1,489 @@
+//! Headless subcommands for working with scene files without opening a window.
+//!
+//! Commands run against an `App` built with [`headless_app`], which registers the
+//! scene types and the built-in validation rules without opening a window. Games that
+//! register their own validation rules can run these from their own binary by
+//! registering the rules before calling [`HeadlessCommand::run`]:
+//!
+//! ```ignore
+//! let mut app = headless_app();
+//! app.register_validation(ValidationRule { name: "Spawn Point", validate: validate_spawn_points });
+//! let exit = command.run(&mut app);
+//! ```
+
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+use bevy::scene::serde::SceneDeserializer;
+use serde::de::DeserializeSeed;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use bevy_editor_game::{MaterialRef, ValidationMessage, ValidationRegistry, ValidationSeverity};
+
+use crate::scene::{
+    load_scene_material_library, migrate_legacy_materials, serialize_scene_stable,
+    HeadlessEditorPlugin, MaterialType, PrimitiveMaterial, SceneEntity,
+};
+
+/// Usage text printed for `help` and on argument errors
+pub const USAGE: &str = "\
+Usage: bevy_modal_editor [COMMAND]
+
+Without a command the editor window is opened.
+
+Commands:
+  validate <scene>...              Run every validation rule, exit non-zero on errors
+  convert <scene> [-o <output>]    Upgrade legacy PrimitiveMaterial/MaterialType to MaterialRef
+  stats <scene>...                 Print entity, component and material counts
+  help                             Show this message";
+
+/// Exit code for scenes that fail validation
+const EXIT_INVALID: u8 = 1;
+/// Exit code for bad arguments or unreadable files
+pub const EXIT_USAGE: u8 = 2;
+
+/// Build an `App` that can run headless commands: `MinimalPlugins` plus the
+/// editor's scene types and validation rules
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HeadlessEditorPlugin));
+    app
+}
+
+/// A subcommand that runs without a window
+pub enum HeadlessCommand {
+    Validate { paths: Vec<String> },
+    Convert { input: String, output: Option<String> },
+    Stats { paths: Vec<String> },
+    Help,
+}
+
+impl HeadlessCommand {
+    /// Parse command line arguments (without the program name).
+    /// Returns `Ok(None)` when no subcommand was given and the editor should open.
+    pub fn parse(args: &[String]) -> Result<Option<Self>, String> {
+        let Some((command, rest)) = args.split_first() else {
+            return Ok(None);
+        };
+
+        match command.as_str() {
+            "validate" | "stats" => {
+                if rest.is_empty() {
+                    return Err(format!("'{}' needs at least one scene file", command));
+                }
+                let paths = rest.to_vec();
+                Ok(Some(if command == "validate" {
+                    Self::Validate { paths }
+                } else {
+                    Self::Stats { paths }
+                }))
+            }
+            "convert" => {
+                let mut input = None;
+                let mut output = None;
+                let mut iter = rest.iter();
+                while let Some(arg) = iter.next() {
+                    match arg.as_str() {
+                        "-o" | "--output" => {
+                            let Some(path) = iter.next() else {
+                                return Err(format!("'{}' needs a path", arg));
+                            };
+                            output = Some(path.clone());
+                        }
+                        _ if input.is_none() => input = Some(arg.clone()),
+                        _ => return Err(format!("Unexpected argument '{}'", arg)),
+                    }
+                }
+                let Some(input) = input else {
+                    return Err("'convert' needs a scene file".to_string());
+                };
+                Ok(Some(Self::Convert { input, output }))
+            }
+            "help" | "-h" | "--help" => Ok(Some(Self::Help)),
+            other => Err(format!("Unknown command '{}'", other)),
+        }
+    }
+
+    /// Run the command against the app's world and return the process exit status
+    pub fn run(self, app: &mut App) -> AppExit {
+        let world = app.world_mut();
+        match self {
+            Self::Validate { paths } => {
+                let mut status = AppExit::Success;
+                for path in &paths {
+                    let result = run_validate(world, path);
+                    if status.is_success() {
+                        status = result;
+                    }
+                }
+                status
+            }
+            Self::Convert { input, output } => run_convert(world, &input, output.as_deref()),
+            Self::Stats { paths } => {
+                let mut status = AppExit::Success;
+                for path in &paths {
+                    let result = run_stats(world, path);
+                    if status.is_success() {
+                        status = result;
+                    }
+                }
+                status
+            }
+            Self::Help => {
+                println!("{}", USAGE);
+                AppExit::Success
+            }
+        }
+    }
+}
+
+/// Read and deserialize a scene file using the world's type registry
+fn read_scene(world: &World, path: &str) -> Result<DynamicScene, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read scene file: {}", e))?;
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let scene_deserializer = SceneDeserializer {
+        type_registry: &type_registry,
+    };
+
+    let mut ron_deserializer = ron::de::Deserializer::from_str(&content)
+        .map_err(|e| format!("Failed to parse scene file: {}", e))?;
+    scene_deserializer
+        .deserialize(&mut ron_deserializer)
+        .map_err(|e| format!("Failed to deserialize scene: {}", e))
+}
+
+/// Replace the world's scene entities with the scene at `path`
+fn load_scene(world: &mut World, path: &str) -> Result<DynamicScene, String> {
+    let scene = read_scene(world, path)?;
+
+    let existing: Vec<Entity> = {
+        let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
+        query.iter(world).collect()
+    };
+    for entity in existing {
+        if let Ok(entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.despawn();
+        }
+    }
+
+    let mut entity_map = EntityHashMap::default();
+    scene
+        .write_to_world(world, &mut entity_map)
+        .map_err(|e| format!("Failed to instantiate scene: {:?}", e))?;
+    Ok(scene)
+}
+
+fn entity_label(world: &World, entity: Entity) -> String {
+    world
+        .get::<Name>(entity)
+        .map(|name| format!("'{}'", name.as_str()))
+        .unwrap_or_else(|| format!("{:?}", entity))
+}
+
+/// Load a scene and run every registered validation rule on it
+fn run_validate(world: &mut World, path: &str) -> AppExit {
+    // Without the registry nothing would be checked; refuse rather than pass
+    let Some(registry) = world.get_resource::<ValidationRegistry>() else {
+        eprintln!(
+            "{}: error: No validation registry; build the app with `HeadlessEditorPlugin`",
+            path
+        );
+        return AppExit::from_code(EXIT_USAGE);
+    };
+    let rules: Vec<(&'static str, fn(&mut World) -> Vec<ValidationMessage>)> = registry
+        .rules
+        .iter()
+        .map(|r| (r.name, r.validate))
+        .collect();
+    if rules.is_empty() {
+        eprintln!("{}: error: No validation rules registered", path);
+        return AppExit::from_code(EXIT_USAGE);
+    }
+
+    if let Err(e) = load_scene(world, path) {
+        eprintln!("{}: error: {}", path, e);
+        return AppExit::from_code(EXIT_USAGE);
+    }
+    // Library references resolve against the scene's own library
+    world.insert_resource(load_scene_material_library(path).unwrap_or_default());
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    for (name, validate) in rules {
+        for message in validate(world) {
+            let severity = match message.severity {
+                ValidationSeverity::Error => {
+                    errors += 1;
+                    "error"
+                }
+                ValidationSeverity::Warning => {
+                    warnings += 1;
+                    "warning"
+                }
+                ValidationSeverity::Info => "info",
+            };
+            match message.entity {
+                Some(entity) => println!(
+                    "{}: {} [{}] {}: {}",
+                    path,
+                    severity,
+                    name,
+                    entity_label(world, entity),
+                    message.message
+                ),
+                None => println!("{}: {} [{}] {}", path, severity, name, message.message),
+            }
+        }
+    }
+
+    println!("{}: {} errors, {} warnings", path, errors, warnings);
+
+    if errors > 0 {
+        AppExit::from_code(EXIT_INVALID)
+    } else {
+        AppExit::Success
+    }
+}
+
+/// Load a scene, migrate legacy material components to `MaterialRef` and save it
+fn run_convert(world: &mut World, input: &str, output: Option<&str>) -> AppExit {
+    if let Err(e) = load_scene(world, input) {
+        eprintln!("{}: error: {}", input, e);
+        return AppExit::from_code(EXIT_USAGE);
+    }
+
+    let migrated = migrate_legacy_materials(world);
+
+    // The legacy components are only kept around for migration; drop them now
+    let legacy: Vec<Entity> = {
+        let mut query = world.query_filtered::<Entity, (
+            With<MaterialRef>,
+            Or<(With<PrimitiveMaterial>, With<MaterialType>)>,
+        )>();
+        query.iter(world).collect()
+    };
+    for &entity in &legacy {
+        world
+            .entity_mut(entity)
+            .remove::<(PrimitiveMaterial, MaterialType)>();
+    }
+
+    let serialized = match serialize_scene_stable(world) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{}: error: Failed to serialize scene: {}", input, e);
+            return AppExit::from_code(EXIT_USAGE);
+        }
+    };
+
+    let output = output.unwrap_or(input);
+    if let Err(e) = fs::write(output, serialized) {
+        eprintln!("{}: error: Failed to write scene file: {}", output, e);
+        return AppExit::from_code(EXIT_USAGE);
+    }
+
+    // Keep the editor metadata next to the converted scene
+    if output != input {
+        let metadata_path = format!("{}.meta", input);
+        if Path::new(&metadata_path).exists() {
+            let _ = fs::copy(&metadata_path, format!("{}.meta", output));
+        }
+    }
+
+    println!(
+        "{}: migrated {} entities, removed legacy components from {}",
+        output,
+        migrated,
+        legacy.len()
+    );
+    AppExit::Success
+}
+
+/// Print entity, component and material counts for a scene
+fn run_stats(world: &mut World, path: &str) -> AppExit {
+    let scene = match load_scene(world, path) {
+        Ok(scene) => scene,
+        Err(e) => {
+            eprintln!("{}: error: {}", path, e);
+            return AppExit::from_code(EXIT_USAGE);
+        }
+    };
+
+    let mut component_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for entity in &scene.entities {
+        for component in &entity.components {
+            let type_path = component
+                .get_represented_type_info()
+                .map(|info| info.type_path())
+                .unwrap_or_else(|| component.reflect_type_path());
+            *component_counts.entry(type_path).or_default() += 1;
+        }
+    }
+
+    let mut library_refs: BTreeMap<String, usize> = BTreeMap::new();
+    let mut inline_materials = 0;
+    {
+        let mut query = world.query::<&MaterialRef>();
+        for material in query.iter(world) {
+            match material {
+                MaterialRef::Library(name) => *library_refs.entry(name.clone()).or_default() += 1,
+                MaterialRef::Inline(_) => inline_materials += 1,
+            }
+        }
+    }
+    let library = load_scene_material_library(path).unwrap_or_default();
+
+    println!("{}", path);
+    println!("  entities: {}", scene.entities.len());
+    println!("  components:");
+    for (type_path, count) in &component_counts {
+        println!("    {:>6}  {}", count, type_path);
+    }
+    println!("  materials:");
+    println!("    {:>6}  inline", inline_materials);
+    println!("    {:>6}  library definitions", library.materials.len());
+    for (name, count) in &library_refs {
+        let missing = if library.materials.contains_key(name) {
+            ""
+        } else {
+            " (missing from library)"
+        };
+        println!("    {:>6}  {}{}", count, name, missing);
+    }
+
+    AppExit::Success
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_editor_game::{RegisterValidationExt, ValidationRule};
+
+    const TOWER: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/assets/prefabs/tower/tower.scn.ron"
+    );
+
+    /// Flags every scene entity without a name
+    fn require_names(world: &mut World) -> Vec<ValidationMessage> {
+        let mut query = world.query_filtered::<(Entity, Option<&Name>), With<SceneEntity>>();
+        query
+            .iter(world)
+            .filter(|(_, name)| name.is_none())
+            .map(|(entity, _)| ValidationMessage {
+                severity: ValidationSeverity::Error,
+                message: "Scene entity has no name".to_string(),
+                entity: Some(entity),
+            })
+            .collect()
+    }
+
+    fn scene_entity_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
+        query.iter(world).count()
+    }
+
+    #[test]
+    fn validates_and_converts_checked_in_scene() {
+        let mut app = headless_app();
+        app.register_validation(ValidationRule {
+            name: "Names",
+            validate: require_names,
+        });
+
+        let validate = HeadlessCommand::Validate {
+            paths: vec![TOWER.to_string()],
+        };
+        assert_eq!(validate.run(&mut app), AppExit::Success);
+        let loaded = scene_entity_count(&mut app);
+        assert!(loaded > 0);
+
+        let output =
+            std::env::temp_dir().join(format!("headless_convert_{}.scn.ron", std::process::id()));
+        let output = output.to_string_lossy().into_owned();
+        let convert = HeadlessCommand::Convert {
+            input: TOWER.to_string(),
+            output: Some(output.clone()),
+        };
+        assert_eq!(convert.run(&mut app), AppExit::Success);
+
+        // The converted file must load and validate the same way
+        let validate = HeadlessCommand::Validate {
+            paths: vec![output.clone()],
+        };
+        let status = validate.run(&mut app);
+        let _ = fs::remove_file(&output);
+        assert_eq!(status, AppExit::Success);
+        assert_eq!(scene_entity_count(&mut app), loaded);
+    }
+
+    #[test]
+    fn validate_fails_on_rule_errors() {
+        let mut app = headless_app();
+        app.register_validation(ValidationRule {
+            name: "Always",
+            validate: |_| {
+                vec![ValidationMessage {
+                    severity: ValidationSeverity::Error,
+                    message: "broken".to_string(),
+                    entity: None,
+                }]
+            },
+        });
+
+        let validate = HeadlessCommand::Validate {
+            paths: vec![TOWER.to_string()],
+        };
+        assert_eq!(validate.run(&mut app), AppExit::from_code(EXIT_INVALID));
+    }
+
+    #[test]
+    fn validate_fails_on_missing_library_material() {
+        let mut app = headless_app();
+        app.world_mut().spawn((
+            SceneEntity,
+            Name::new("Wall"),
+            Transform::default(),
+            MaterialRef::Library("Missing".to_string()),
+        ));
+        let serialized = serialize_scene_stable(app.world_mut()).unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "headless_missing_material_{}.scn.ron",
+            std::process::id()
+        ));
+        fs::write(&path, serialized).unwrap();
+
+        let validate = HeadlessCommand::Validate {
+            paths: vec![path.to_string_lossy().into_owned()],
+        };
+        let status = validate.run(&mut headless_app());
+        let _ = fs::remove_file(&path);
+        assert_eq!(status, AppExit::from_code(EXIT_INVALID));
+    }
+
+    #[test]
+    fn validate_without_rules_is_an_error() {
+        let mut app = App::new();
+        app.init_resource::<ValidationRegistry>();
+        let validate = HeadlessCommand::Validate {
+            paths: vec![TOWER.to_string()],
+        };
+        assert_eq!(validate.run(&mut app), AppExit::from_code(EXIT_USAGE));
+    }
+
+    #[test]
+    fn validate_without_registry_is_an_error() {
+        let validate = HeadlessCommand::Validate {
+            paths: vec![TOWER.to_string()],
+        };
+        assert_eq!(
+            validate.run(&mut App::new()),
+            AppExit::from_code(EXIT_USAGE)
+        );
+    }
+}
@@ -43,11 +43,13 @@
 //! Press `?` for the full help menu.
 
 pub mod asset_libraries;
+pub mod audio;
 pub mod commands;
 pub mod constants;
 pub mod editor;
 pub mod effects;
 pub mod gizmos;
+pub mod headless;
 pub mod materials;
 pub mod modeling;
 pub mod navigation;
@@ -70,6 +72,9 @@ pub use scene::{
     PrimitiveShape, SceneEntity, SceneLightMarker,
 };
 
+// Re-export sound emitter types
+pub use audio::{SoundEmitterMarker, SoundPreview};
+
 // Re-export selection types
 pub use selection::Selected;
 
@@ -81,7 +86,7 @@ pub use bevy_editor_game::{
     CustomEntityEntry, CustomEntityRegistry, CustomEntityType, RegisterCustomEntityExt,
     InspectorWidgetFn, GizmoDrawFn, RegenerateFn,
     GameCamera, GameEntity, GameState, PauseEvent, PlayEvent, ResetEvent,
-    GameStartedEvent, GameResumedEvent, GamePausedEvent, GameResetEvent,
+    GameStartedEvent, GameResumedEvent, GamePausedEvent, GameResetEvent, GameEffectEvent,
     SceneComponentRegistry, RegisterSceneComponentExt,
     ValidationMessage, ValidationRegistry, ValidationRule, ValidationSeverity,
     RegisterValidationExt,
@@ -95,8 +100,8 @@ pub use bevy_editor_game::{
 
 // Re-export asset library types
 pub use bevy_editor_game::{
-    AnimationLibrary, GltfLibraryConfig, MeshLibrary, MeshRef, RegisterGltfLibraryExt,
-    SceneLibrary,
+    AnimationLibrary, AnimationRef, AnimationSource, GltfLibraryConfig, MeshLibrary, MeshRef,
+    RegisterGltfLibraryExt, SceneLibrary,
 };
 pub use asset_libraries::AssetLibraryState;
 pub use materials::RegisterMaterialTypeExt;
@@ -110,8 +115,12 @@ pub use editor::EditorCamera;
 // Re-export serialization events
 pub use scene::{LoadSceneEvent, SaveSceneEvent};
 
+// Re-export scene tab types
+pub use scene::{CloseSceneTabEvent, OpenSceneTabEvent, SceneWorkspace, SwitchSceneTabEvent};
+
 // Re-export command/history types
 pub use commands::{
-    DeleteSelectedEvent, DuplicateSelectedEvent, RedoEvent, SnapshotHistory, TakeSnapshotCommand,
+    CutSelectedEvent, DeleteSelectedEvent, DuplicateSelectedEvent, JumpToHistoryEvent, RecordEntitiesCommand, RecordReparentCommand,
+    RecordSpawnCommand, RecordTransformsCommand, RedoEvent, SnapshotHistory, TakeSnapshotCommand,
     UndoEvent,
 };
@@ -2,11 +2,25 @@
 //!
 //! For using the editor as a library in your own project,
 //! see the examples directory.
+//!
+//! Run with `validate`, `convert` or `stats` to work on scene files without
+//! opening a window; see `bevy_modal_editor help`.
 
 use bevy::prelude::*;
+use bevy_modal_editor::headless::{headless_app, HeadlessCommand, EXIT_USAGE, USAGE};
 use bevy_modal_editor::{recommended_image_plugin, EditorPlugin};
 
-fn main() {
+fn main() -> AppExit {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match HeadlessCommand::parse(&args) {
+        Ok(Some(command)) => return command.run(&mut headless_app()),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return AppExit::from_code(EXIT_USAGE);
+        }
+    }
+
     App::new()
         .add_plugins(
             DefaultPlugins
@@ -20,5 +34,5 @@ fn main() {
                 .set(recommended_image_plugin()),
         )
         .add_plugins(EditorPlugin::default())
-        .run();
+        .run()
 }
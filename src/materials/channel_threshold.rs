@@ -14,6 +14,9 @@ pub struct ChannelThresholdProps {
     pub threshold: f32,
     pub smoothing: f32,
     pub invert: bool,
+    /// Read the channel from the mesh's vertex colors instead of the base color.
+    #[serde(default)]
+    pub use_vertex_color: bool,
 }
 
 impl Default for ChannelThresholdProps {
@@ -24,6 +27,7 @@ impl Default for ChannelThresholdProps {
             threshold: def.uniform.threshold,
             smoothing: def.uniform.smoothing,
             invert: def.uniform.invert != 0,
+            use_vertex_color: def.uniform.source != 0,
         }
     }
 }
@@ -45,6 +49,7 @@ impl EditorMaterialDef for ChannelThresholdDef {
                 threshold: props.threshold,
                 smoothing: props.smoothing,
                 invert: if props.invert { 1 } else { 0 },
+                source: if props.use_vertex_color { 1 } else { 0 },
             },
         }
     }
@@ -55,6 +60,7 @@ impl EditorMaterialDef for ChannelThresholdDef {
             threshold: ext.uniform.threshold,
             smoothing: ext.uniform.smoothing,
             invert: ext.uniform.invert != 0,
+            use_vertex_color: ext.uniform.source != 0,
         }
     }
 
@@ -78,12 +84,14 @@ impl EditorMaterialDef for ChannelThresholdDef {
                             0 => "Red",
                             1 => "Green",
                             2 => "Blue",
+                            3 => "Alpha",
                             _ => "Red",
                         })
                         .show_ui(ui, |ui| {
                             ui.selectable_value(&mut props.channel, 0, "Red");
                             ui.selectable_value(&mut props.channel, 1, "Green");
                             ui.selectable_value(&mut props.channel, 2, "Blue");
+                            ui.selectable_value(&mut props.channel, 3, "Alpha");
                         });
                     changed |= props.channel != prev;
                     ui.end_row();
@@ -99,6 +107,10 @@ impl EditorMaterialDef for ChannelThresholdDef {
                     grid_label(ui, "Invert");
                     changed |= ui.checkbox(&mut props.invert, "").changed();
                     ui.end_row();
+
+                    grid_label(ui, "Vertex Color");
+                    changed |= ui.checkbox(&mut props.use_vertex_color, "").changed();
+                    ui.end_row();
                 });
         });
 
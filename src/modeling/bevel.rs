@@ -7,6 +7,7 @@
 use bevy::prelude::*;
 use std::collections::HashSet;
 
+use super::edit_mesh::{triangulate_polygon, Edge};
 use super::half_edge::{HalfEdgeMesh, VertexId};
 
 /// Bevel selected edges by the given width.
@@ -52,6 +53,29 @@ pub fn bevel_edges(
         return mesh.clone();
     }
 
+    // Polygons along a bevelled edge are split into triangles; the rest keep
+    // their shape
+    let bevelled: HashSet<Edge> = edges_to_bevel
+        .iter()
+        .map(|&(from, to)| Edge::new(from, to))
+        .collect();
+    let mut faces: Vec<Vec<u32>> = Vec::new();
+    let mut materials: Vec<u32> = Vec::new();
+    for (face, slot) in edit.polygons().into_iter().zip(edit.polygon_materials()) {
+        let touches = (0..face.len())
+            .any(|i| bevelled.contains(&Edge::new(face[i], face[(i + 1) % face.len()])));
+        if touches && face.len() > 3 {
+            faces.extend(
+                triangulate_polygon(&face, &edit.positions)
+                    .into_iter()
+                    .map(|tri| tri.to_vec()),
+            );
+        } else {
+            faces.push(face);
+        }
+        materials.resize(faces.len(), slot);
+    }
+
     // For each edge to bevel, we need to:
     // 1. Create 2 new vertices (one per endpoint, offset along the edge)
     // 2. Split all faces touching this edge to use the new vertices
@@ -81,17 +105,23 @@ pub fn bevel_edges(
         edit.positions.push(p_from + edge_dir * w);
         edit.normals.push(edit.normals[from as usize]);
         edit.uvs.push(edit.uvs[from as usize]);
+        edit.attributes.push_copy(from);
 
         let to_new = edit.positions.len() as u32;
         edit.positions.push(p_to - edge_dir * w);
         edit.normals.push(edit.normals[to as usize]);
         edit.uvs.push(edit.uvs[to as usize]);
+        edit.attributes.push_copy(to);
 
         // Find all triangles that use this edge and split them
         let mut tris_to_remove = Vec::new();
         let mut tris_to_add = Vec::new();
+        let mut materials_to_add = Vec::new();
 
-        for (ti, tri) in edit.triangles.iter().enumerate() {
+        for (ti, tri) in faces.iter().enumerate() {
+            if tri.len() != 3 {
+                continue;
+            }
             let has_from = tri.contains(&from);
             let has_to = tri.contains(&to);
 
@@ -106,7 +136,7 @@ pub fn bevel_edges(
                 // Replace the edge (from, to) with (from_new, to_new)
                 // Original tri: from, to, third (in some order)
                 // New: from_new, to_new, third (preserving winding)
-                let mut new_tri = *tri;
+                let mut new_tri = tri.clone();
                 for v in new_tri.iter_mut() {
                     if *v == from {
                         *v = from_new;
@@ -118,26 +148,30 @@ pub fn bevel_edges(
 
                 // Also add small triangles connecting original vertex to new vertex
                 // from -> from_new -> third
-                tris_to_add.push(order_tri_winding(from, from_new, third, &edit.positions));
+                tris_to_add.push(order_tri_winding(from, from_new, third, &edit.positions).to_vec());
                 // to -> to_new -> third
-                tris_to_add.push(order_tri_winding(to_new, to, third, &edit.positions));
+                tris_to_add.push(order_tri_winding(to_new, to, third, &edit.positions).to_vec());
+                materials_to_add.extend([materials[ti]; 3]);
             }
         }
 
         // Remove old triangles (in reverse order to maintain indices)
         tris_to_remove.sort_unstable();
         for &ti in tris_to_remove.iter().rev() {
-            edit.triangles.swap_remove(ti);
+            faces.swap_remove(ti);
+            materials.swap_remove(ti);
         }
 
-        // Add the bevel face (quad = 2 triangles)
+        // Add the bevel face (in the material of a face it was cut from)
         // from -> from_new -> to_new -> to
-        tris_to_add.push(order_tri_winding(from, from_new, to_new, &edit.positions));
-        tris_to_add.push(order_tri_winding(from, to_new, to, &edit.positions));
+        tris_to_add.push(vec![from, from_new, to_new, to]);
+        materials_to_add.push(materials_to_add.first().copied().unwrap_or(0));
 
-        edit.triangles.extend(tris_to_add);
+        faces.extend(tris_to_add);
+        materials.extend(materials_to_add);
     }
 
+    edit.set_faces(faces, materials);
     edit.recompute_normals();
 
     let mut result = HalfEdgeMesh::from_edit_mesh(&edit);
@@ -4,12 +4,13 @@
 //! using a BSP-tree approach.
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::edit_mesh::EditMesh;
+use super::edit_mesh::{EditMesh, VertexAttributes};
 
 /// Boolean operation type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect)]
 pub enum BooleanOp {
     Union,
     Subtract,
@@ -358,6 +359,9 @@ impl MeshBuilder {
             normals: self.normals,
             uvs: self.uvs,
             triangles: self.triangles,
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
         };
         mesh.recompute_normals();
         mesh
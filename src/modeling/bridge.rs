@@ -69,17 +69,23 @@ pub fn bridge_edge_loops(
     // Find the best rotation of loop_b to minimize total edge length
     let best_offset = find_best_alignment(&edit, loop_a, loop_b);
 
+    let mut faces = edit.polygons();
     for i in 0..n {
         let a0 = loop_a[i % loop_a.len()];
         let a1 = loop_a[(i + 1) % loop_a.len()];
         let b0 = loop_b[(i + best_offset) % loop_b.len()];
         let b1 = loop_b[(i + 1 + best_offset) % loop_b.len()];
 
-        // Quad: a0, a1, b1, b0 — split into 2 triangles
-        edit.triangles.push([a0, a1, b1]);
-        edit.triangles.push([a0, b1, b0]);
+        // Quad: a0, a1, b1, b0 (degenerate when a loop repeats a vertex)
+        let mut quad = vec![a0, a1, b1, b0];
+        quad.dedup();
+        faces.push(quad);
     }
 
+    // Bridge quads go in the base material slot
+    let mut materials = edit.polygon_materials();
+    materials.resize(faces.len(), 0);
+    edit.set_faces(faces, materials);
     edit.recompute_normals();
     HalfEdgeMesh::from_edit_mesh(&edit)
 }
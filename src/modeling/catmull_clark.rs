@@ -2,8 +2,8 @@
 //!
 //! Produces a smooth subdivision by computing face points, edge points, and
 //! updating vertex positions according to the Catmull-Clark rules.
-//! Since our mesh is triangle-based, each triangle is split into 3 quads
-//! (6 triangles).
+//! Works on polygon faces: every N-sided face is split into N quads, so quad
+//! meshes stay all-quad and triangles become three quads each.
 
 use bevy::prelude::*;
 use std::collections::HashMap;
@@ -12,18 +12,32 @@ use super::edit_mesh::{Edge, EditMesh};
 
 /// Apply one level of Catmull-Clark subdivision.
 ///
-/// Each triangle is split into 3 quads (6 triangles). Face points, edge points,
+/// Each polygon is split into one quad per corner. Face points, edge points,
 /// and updated vertex positions are computed per the Catmull-Clark rules.
+/// The result shares vertices between neighboring quads.
 pub fn catmull_clark_subdivide(mesh: &EditMesh) -> EditMesh {
-    let adj = mesh.build_adjacency();
+    let polygons = mesh.polygons();
+
+    // Edge adjacency over polygon edges (triangulation diagonals are not edges)
+    let mut adj: HashMap<Edge, Vec<usize>> = HashMap::new();
+    for (pi, face) in polygons.iter().enumerate() {
+        for i in 0..face.len() {
+            let edge = Edge::new(face[i], face[(i + 1) % face.len()]);
+            adj.entry(edge).or_default().push(pi);
+        }
+    }
 
     // Step 1: Compute face points (centroid of each face)
-    let face_points: Vec<Vec3> = (0..mesh.triangles.len())
-        .map(|fi| mesh.face_center(fi))
+    let face_points: Vec<Vec3> = polygons
+        .iter()
+        .map(|face| {
+            face.iter().map(|&v| mesh.positions[v as usize]).sum::<Vec3>() / face.len() as f32
+        })
         .collect();
 
-    let face_uvs: Vec<Vec2> = (0..mesh.triangles.len())
-        .map(|fi| mesh.face_uv_center(fi))
+    let face_uvs: Vec<Vec2> = polygons
+        .iter()
+        .map(|face| face.iter().map(|&v| mesh.uvs[v as usize]).sum::<Vec2>() / face.len() as f32)
         .collect();
 
     // Step 2: Compute edge points
@@ -78,9 +92,9 @@ pub fn catmull_clark_subdivide(mesh: &EditMesh) -> EditMesh {
 
     // Build vertex-to-faces map
     let mut vertex_faces: HashMap<u32, Vec<usize>> = HashMap::new();
-    for (fi, tri) in mesh.triangles.iter().enumerate() {
-        for &vi in tri {
-            vertex_faces.entry(vi).or_default().push(fi);
+    for (pi, face) in polygons.iter().enumerate() {
+        for &vi in face {
+            vertex_faces.entry(vi).or_default().push(pi);
         }
     }
 
@@ -163,70 +177,53 @@ pub fn catmull_clark_subdivide(mesh: &EditMesh) -> EditMesh {
     }
 
     // Step 4: Build new mesh
-    // Each triangle [a, b, c] with face point FP and edge points EP_ab, EP_bc, EP_ca
-    // becomes 3 quads (6 triangles):
-    //   Quad 1: a', EP_ab, FP, EP_ca
-    //   Quad 2: b', EP_bc, FP, EP_ab
-    //   Quad 3: c', EP_ca, FP, EP_bc
-    let mut new_positions = Vec::new();
-    let mut new_normals = Vec::new();
-    let mut new_uvs = Vec::new();
-    let mut new_triangles = Vec::new();
-
-    // Helper: add a vertex and return its index
-    let mut add_vert = |pos: Vec3, uv: Vec2| -> u32 {
-        let idx = new_positions.len() as u32;
-        new_positions.push(pos);
-        new_normals.push(Vec3::ZERO);
-        new_uvs.push(uv);
-        idx
-    };
-
-    for (fi, tri) in mesh.triangles.iter().enumerate() {
-        let [a, b, c] = *tri;
-        let fp_pos = face_points[fi];
-        let fp_uv = face_uvs[fi];
-
-        let e_ab = Edge::new(a, b);
-        let e_bc = Edge::new(b, c);
-        let e_ca = Edge::new(c, a);
-
-        let va = add_vert(new_vertex_positions[a as usize], new_vertex_uvs[a as usize]);
-        let vb = add_vert(new_vertex_positions[b as usize], new_vertex_uvs[b as usize]);
-        let vc = add_vert(new_vertex_positions[c as usize], new_vertex_uvs[c as usize]);
-        let vfp = add_vert(fp_pos, fp_uv);
-        let vep_ab = add_vert(
-            edge_points.get(&e_ab).copied().unwrap_or((mesh.positions[a as usize] + mesh.positions[b as usize]) * 0.5),
-            edge_uvs.get(&e_ab).copied().unwrap_or((mesh.uvs[a as usize] + mesh.uvs[b as usize]) * 0.5),
-        );
-        let vep_bc = add_vert(
-            edge_points.get(&e_bc).copied().unwrap_or((mesh.positions[b as usize] + mesh.positions[c as usize]) * 0.5),
-            edge_uvs.get(&e_bc).copied().unwrap_or((mesh.uvs[b as usize] + mesh.uvs[c as usize]) * 0.5),
-        );
-        let vep_ca = add_vert(
-            edge_points.get(&e_ca).copied().unwrap_or((mesh.positions[c as usize] + mesh.positions[a as usize]) * 0.5),
-            edge_uvs.get(&e_ca).copied().unwrap_or((mesh.uvs[c as usize] + mesh.uvs[a as usize]) * 0.5),
-        );
-
-        // Quad around vertex a: va, vep_ab, vfp, vep_ca
-        new_triangles.push([va, vep_ab, vfp]);
-        new_triangles.push([va, vfp, vep_ca]);
-
-        // Quad around vertex b: vb, vep_bc, vfp, vep_ab
-        new_triangles.push([vb, vep_bc, vfp]);
-        new_triangles.push([vb, vfp, vep_ab]);
-
-        // Quad around vertex c: vc, vep_ca, vfp, vep_bc
-        new_triangles.push([vc, vep_ca, vfp]);
-        new_triangles.push([vc, vfp, vep_bc]);
+    // Vertex points keep their original indices; face and edge points follow.
+    // Each face corner c (with neighbors prev and next) becomes the quad:
+    //   c', EP_(c,next), FP, EP_(prev,c)
+    let mut new_positions = new_vertex_positions;
+    let mut new_uvs = new_vertex_uvs;
+    let mut new_attributes = mesh.attributes.clone();
+
+    let face_base = new_positions.len() as u32;
+    new_positions.extend(face_points.iter().copied());
+    new_uvs.extend(face_uvs.iter().copied());
+    // Face points take the midpoint of two opposite corners
+    for face in &polygons {
+        new_attributes.push_lerp(face[0], face[face.len() / 2], 0.5);
+    }
+
+    let mut edge_index: HashMap<Edge, u32> = HashMap::new();
+    for (edge, &point) in &edge_points {
+        edge_index.insert(*edge, new_positions.len() as u32);
+        new_positions.push(point);
+        new_uvs.push(edge_uvs[edge]);
+        new_attributes.push_lerp(edge.0, edge.1, 0.5);
+    }
+
+    let materials = mesh.polygon_materials();
+    let mut new_faces = Vec::with_capacity(polygons.iter().map(Vec::len).sum());
+    let mut new_materials = Vec::with_capacity(new_faces.capacity());
+    for (pi, face) in polygons.iter().enumerate() {
+        let n = face.len();
+        let vfp = face_base + pi as u32;
+        for i in 0..n {
+            let prev = face[(i + n - 1) % n];
+            let corner = face[i];
+            let next = face[(i + 1) % n];
+            new_faces.push(vec![
+                corner,
+                edge_index[&Edge::new(corner, next)],
+                vfp,
+                edge_index[&Edge::new(prev, corner)],
+            ]);
+            new_materials.push(materials[pi]);
+        }
     }
 
-    let mut result = EditMesh {
-        positions: new_positions,
-        normals: new_normals,
-        uvs: new_uvs,
-        triangles: new_triangles,
-    };
+    let new_normals = vec![Vec3::ZERO; new_positions.len()];
+    let mut result =
+        EditMesh::from_polygons(new_positions, new_normals, new_uvs, new_faces, new_materials);
+    result.attributes = new_attributes;
     result.recompute_normals();
     result
 }
@@ -6,7 +6,7 @@
 use bevy::prelude::*;
 use std::collections::{HashMap, HashSet};
 
-use super::edit_mesh::{EditMesh, FaceIndex};
+use super::edit_mesh::{EditMesh, FaceIndex, VertexAttributes};
 
 /// Split the mesh into two parts: unselected faces (kept on original entity)
 /// and selected faces (spawned as a new entity).
@@ -24,9 +24,13 @@ pub fn cut_faces(
         return (empty_mesh(), mesh.clone());
     }
 
+    // Cut whole polygons, never half a quad
+    let selected = mesh.expand_to_polygons(selected);
+    let ranges = mesh.polygon_triangle_ranges();
+
     // Build separate meshes by remapping vertices
-    let mut remaining = extract_faces(mesh, |fi| !selected.contains(&fi));
-    let mut cut_out = extract_faces(mesh, |fi| selected.contains(&fi));
+    let mut remaining = extract_faces(mesh, |pi| !selected.contains(&ranges[pi].start));
+    let mut cut_out = extract_faces(mesh, |pi| selected.contains(&ranges[pi].start));
 
     remaining.recompute_normals();
     cut_out.recompute_normals();
@@ -34,39 +38,43 @@ pub fn cut_faces(
     (remaining, cut_out)
 }
 
-/// Extract faces matching a predicate into a new compact mesh.
-fn extract_faces(mesh: &EditMesh, include: impl Fn(FaceIndex) -> bool) -> EditMesh {
+/// Extract polygons matching a predicate (by polygon index) into a new compact mesh.
+fn extract_faces(mesh: &EditMesh, include: impl Fn(usize) -> bool) -> EditMesh {
     let mut new_positions = Vec::new();
     let mut new_normals = Vec::new();
     let mut new_uvs = Vec::new();
-    let mut new_triangles = Vec::new();
+    let mut new_faces = Vec::new();
+    let mut new_materials = Vec::new();
+    let mut origins = Vec::new();
     let mut vertex_map: HashMap<u32, u32> = HashMap::new();
 
-    for (fi, tri) in mesh.triangles.iter().enumerate() {
-        if !include(fi) {
+    let materials = mesh.polygon_materials();
+    for (pi, face) in mesh.polygons().iter().enumerate() {
+        if !include(pi) {
             continue;
         }
 
-        let mut new_tri = [0u32; 3];
-        for (i, &v) in tri.iter().enumerate() {
-            let new_idx = *vertex_map.entry(v).or_insert_with(|| {
-                let idx = new_positions.len() as u32;
-                new_positions.push(mesh.positions[v as usize]);
-                new_normals.push(mesh.normals[v as usize]);
-                new_uvs.push(mesh.uvs[v as usize]);
-                idx
-            });
-            new_tri[i] = new_idx;
-        }
-        new_triangles.push(new_tri);
+        let new_face: Vec<u32> = face
+            .iter()
+            .map(|&v| {
+                *vertex_map.entry(v).or_insert_with(|| {
+                    let idx = new_positions.len() as u32;
+                    new_positions.push(mesh.positions[v as usize]);
+                    new_normals.push(mesh.normals[v as usize]);
+                    new_uvs.push(mesh.uvs[v as usize]);
+                    origins.push(v);
+                    idx
+                })
+            })
+            .collect();
+        new_faces.push(new_face);
+        new_materials.push(materials[pi]);
     }
 
-    EditMesh {
-        positions: new_positions,
-        normals: new_normals,
-        uvs: new_uvs,
-        triangles: new_triangles,
-    }
+    let mut result =
+        EditMesh::from_polygons(new_positions, new_normals, new_uvs, new_faces, new_materials);
+    result.attributes = mesh.attributes.remap(&origins);
+    result
 }
 
 /// Create an empty mesh with no geometry.
@@ -76,5 +84,8 @@ fn empty_mesh() -> EditMesh {
         normals: Vec::new(),
         uvs: Vec::new(),
         triangles: Vec::new(),
+        faces: Vec::new(),
+        face_materials: Vec::new(),
+        attributes: VertexAttributes::default(),
     }
 }
@@ -1,9 +1,9 @@
 //! Element deletion for the mesh modeling tool.
 //!
 //! Deletes selected elements from the mesh:
-//! - Face deletion: removes selected triangles
+//! - Face deletion: removes selected polygons
 //! - Edge dissolution: merges the two faces adjacent to each selected edge
-//! - Vertex dissolution: removes vertex and retriangulates the hole
+//! - Vertex dissolution: removes vertex and merges its faces into one polygon
 
 use bevy::prelude::*;
 use std::collections::HashSet;
@@ -13,35 +13,39 @@ use super::half_edge::{HalfEdgeMesh, HalfEdgeId, VertexId, INVALID};
 
 /// Delete selected faces from the mesh.
 ///
-/// Simply removes the triangles. Leaves the vertices in place (they become
-/// orphaned but don't affect rendering). Recomputes normals.
+/// Removes every polygon touched by the selection. Leaves the vertices in
+/// place (they become orphaned but don't affect rendering). Recomputes normals.
 pub fn delete_faces(mesh: &EditMesh, selected: &HashSet<FaceIndex>) -> EditMesh {
     if selected.is_empty() {
         return mesh.clone();
     }
 
-    let new_triangles: Vec<[u32; 3]> = mesh
-        .triangles
-        .iter()
+    let removed = mesh.selected_polygons(selected);
+    let (new_faces, new_materials): (Vec<Vec<u32>>, Vec<u32>) = mesh
+        .polygons()
+        .into_iter()
+        .zip(mesh.polygon_materials())
         .enumerate()
-        .filter(|(fi, _)| !selected.contains(fi))
-        .map(|(_, tri)| *tri)
-        .collect();
-
-    let mut result = EditMesh {
-        positions: mesh.positions.clone(),
-        normals: mesh.normals.clone(),
-        uvs: mesh.uvs.clone(),
-        triangles: new_triangles,
-    };
+        .filter(|(pi, _)| !removed.contains(pi))
+        .map(|(_, face)| face)
+        .unzip();
+
+    let mut result = EditMesh::from_polygons(
+        mesh.positions.clone(),
+        mesh.normals.clone(),
+        mesh.uvs.clone(),
+        new_faces,
+        new_materials,
+    );
+    result.attributes = mesh.attributes.clone();
     result.recompute_normals();
     result
 }
 
 /// Dissolve selected edges: merge adjacent face pairs across each selected edge.
 ///
-/// For each selected edge with two adjacent triangles, the two triangles are
-/// replaced by a quad (2 new triangles) that skips the dissolved edge.
+/// For each selected edge with two adjacent faces, the two polygons are
+/// replaced by one polygon that skips the dissolved edge.
 /// Boundary edges (only one adjacent face) are simply deleted along with their face.
 pub fn dissolve_edges(
     mesh: &HalfEdgeMesh,
@@ -53,9 +57,15 @@ pub fn dissolve_edges(
 
     let mut edit = mesh.to_edit_mesh();
 
-    // Collect face pairs to merge
-    let mut faces_to_remove: HashSet<usize> = HashSet::new();
-    let mut new_tris: Vec<[u32; 3]> = Vec::new();
+    // Half-edge face i is polygon i; merged faces redirect to their survivor
+    let mut faces: Vec<Option<Vec<u32>>> = edit.polygons().into_iter().map(Some).collect();
+    let mut owner: Vec<usize> = (0..faces.len()).collect();
+    let resolve = |owner: &Vec<usize>, mut face: usize| {
+        while owner[face] != face {
+            face = owner[face];
+        }
+        face
+    };
 
     for &he_id in selected_edges {
         if (he_id as usize) >= mesh.half_edges.len() {
@@ -67,7 +77,7 @@ pub fn dissolve_edges(
         if twin_id == INVALID {
             // Boundary edge — just remove the adjacent face
             if he.face != INVALID {
-                faces_to_remove.insert(he.face as usize);
+                faces[resolve(&owner, he.face as usize)] = None;
             }
             continue;
         }
@@ -79,56 +89,48 @@ pub fn dissolve_edges(
         if face_a == INVALID || face_b == INVALID {
             // One side is boundary — remove the interior face
             if face_a != INVALID {
-                faces_to_remove.insert(face_a as usize);
+                faces[resolve(&owner, face_a as usize)] = None;
             }
             if face_b != INVALID {
-                faces_to_remove.insert(face_b as usize);
+                faces[resolve(&owner, face_b as usize)] = None;
             }
             continue;
         }
 
         // Both faces valid — dissolve the edge
-        // Get the 4 unique vertices of the two triangles
-        let verts_a = mesh.face_vertices(face_a);
-        let verts_b = mesh.face_vertices(face_b);
+        let a = resolve(&owner, face_a as usize);
+        let b = resolve(&owner, face_b as usize);
+        if a == b {
+            continue;
+        }
+        let (Some(loop_a), Some(loop_b)) = (&faces[a], &faces[b]) else {
+            continue;
+        };
 
         let (from, to) = mesh.edge_vertices(he_id);
-
-        // Find the two vertices NOT on the dissolved edge
-        let apex_a = verts_a.iter().find(|&&v| v != from && v != to).copied();
-        let apex_b = verts_b.iter().find(|&&v| v != from && v != to).copied();
-
-        if let (Some(a), Some(b)) = (apex_a, apex_b) {
-            faces_to_remove.insert(face_a as usize);
-            faces_to_remove.insert(face_b as usize);
-
-            // Create new quad (2 triangles) spanning the 4 vertices
-            // from -> a -> b, from -> b -> to
-            new_tris.push([from, a, b]);
-            new_tris.push([from, b, to]);
+        if let Some(merged) = merge_loops(loop_a, loop_b, from, to) {
+            faces[a] = Some(merged);
+            faces[b] = None;
+            owner[b] = a;
         }
     }
 
-    // Remove dissolved faces and add replacements
-    let mut result_tris: Vec<[u32; 3]> = edit
-        .triangles
-        .iter()
-        .enumerate()
-        .filter(|(fi, _)| !faces_to_remove.contains(fi))
-        .map(|(_, tri)| *tri)
-        .collect();
-    result_tris.extend(new_tris);
-
-    edit.triangles = result_tris;
+    // A merged face keeps the material of the face it was merged into
+    let (faces, materials) = faces
+        .into_iter()
+        .zip(edit.polygon_materials())
+        .filter_map(|(face, slot)| face.map(|face| (face, slot)))
+        .unzip();
+    edit.set_faces(faces, materials);
     edit.recompute_normals();
 
     HalfEdgeMesh::from_edit_mesh(&edit)
 }
 
-/// Dissolve selected vertices: remove each vertex and retriangulate the hole.
+/// Dissolve selected vertices: remove each vertex and merge its faces.
 ///
-/// For each selected vertex, all adjacent faces are removed and the resulting
-/// polygon hole is filled with a triangle fan from one of the remaining vertices.
+/// For each selected vertex, all adjacent faces are replaced by a single
+/// polygon made of their outer vertices.
 pub fn dissolve_vertices(
     mesh: &HalfEdgeMesh,
     selected_verts: &HashSet<VertexId>,
@@ -138,51 +140,74 @@ pub fn dissolve_vertices(
     }
 
     let mut edit = mesh.to_edit_mesh();
+    let mut faces: Vec<(Vec<u32>, u32)> = edit
+        .polygons()
+        .into_iter()
+        .zip(edit.polygon_materials())
+        .collect();
 
     for &vert in selected_verts {
-        // Find all faces using this vertex
-        let faces_to_remove: HashSet<usize> = edit
-            .triangles
-            .iter()
-            .enumerate()
-            .filter(|(_, tri)| tri.contains(&vert))
-            .map(|(fi, _)| fi)
-            .collect();
+        // Split faces into those using this vertex and the rest
+        let (around, mut remaining): (Vec<_>, Vec<_>) =
+            faces.into_iter().partition(|(face, _)| face.contains(&vert));
 
-        if faces_to_remove.is_empty() {
+        if around.is_empty() {
+            faces = remaining;
             continue;
         }
 
-        // Collect the ring of vertices around the dissolved vertex (ordered)
-        let mut ring_verts: Vec<u32> = Vec::new();
-        for &fi in &faces_to_remove {
-            for &v in &edit.triangles[fi] {
-                if v != vert && !ring_verts.contains(&v) {
-                    ring_verts.push(v);
-                }
-            }
-        }
-
-        // Remove old faces
-        let mut remaining: Vec<[u32; 3]> = edit
-            .triangles
+        // Each face contributes its loop from the vertex after `vert` to the one before
+        let paths: Vec<Vec<u32>> = around
             .iter()
-            .enumerate()
-            .filter(|(fi, _)| !faces_to_remove.contains(fi))
-            .map(|(_, tri)| *tri)
+            .map(|(face, _)| {
+                let i = face.iter().position(|&v| v == vert).unwrap();
+                (1..face.len()).map(|k| face[(i + k) % face.len()]).collect()
+            })
             .collect();
 
-        // Triangulate the hole with a fan from the first ring vertex
-        if ring_verts.len() >= 3 {
-            let hub = ring_verts[0];
-            for i in 1..ring_verts.len() - 1 {
-                remaining.push([hub, ring_verts[i], ring_verts[i + 1]]);
-            }
+        let ring = chain_paths(paths);
+        if ring.len() >= 3 {
+            remaining.push((ring, around[0].1));
         }
 
-        edit.triangles = remaining;
+        faces = remaining;
     }
 
+    let (faces, materials) = faces.into_iter().unzip();
+    edit.set_faces(faces, materials);
     edit.recompute_normals();
     HalfEdgeMesh::from_edit_mesh(&edit)
 }
+
+/// Merge two polygon loops sharing an edge, where `a` runs `from -> to`
+/// and `b` runs `to -> from`. Returns `None` if the edge isn't shared.
+fn merge_loops(a: &[u32], b: &[u32], from: u32, to: u32) -> Option<Vec<u32>> {
+    let ia = a.iter().position(|&v| v == to)?;
+    let ib = b.iter().position(|&v| v == from)?;
+    if a[(ia + a.len() - 1) % a.len()] != from || b[(ib + b.len() - 1) % b.len()] != to {
+        return None;
+    }
+
+    // a from `to` around to `from`, then b strictly between `from` and `to`
+    let mut merged: Vec<u32> = (0..a.len()).map(|k| a[(ia + k) % a.len()]).collect();
+    merged.extend((1..b.len() - 1).map(|k| b[(ib + k) % b.len()]));
+    Some(merged)
+}
+
+/// Join the outer paths of the faces around a dissolved vertex into one loop.
+fn chain_paths(mut paths: Vec<Vec<u32>>) -> Vec<u32> {
+    // Start from a path nothing leads into (open fan at a boundary vertex)
+    let start = (0..paths.len())
+        .find(|&i| !paths.iter().any(|p| p.last() == paths[i].first()))
+        .unwrap_or(0);
+    let mut ring = paths.swap_remove(start);
+
+    while let Some(next) = paths.iter().position(|p| p.first() == ring.last()) {
+        let path = paths.swap_remove(next);
+        ring.extend_from_slice(&path[1..]);
+    }
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
@@ -2,12 +2,13 @@
 //!
 //! Edge loops are rings of edges that follow a continuous path across quads
 //! (or through triangulated quad pairs). Selection traverses perpendicular
-//! edges via half-edge `next`/`twin`. Insertion splits crossed faces with
-//! new edges.
+//! edges via half-edge `next`/`twin`. Insertion splits each crossed quad into
+//! two quads.
 
 use bevy::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
+use super::edit_mesh::Edge;
 use super::half_edge::{HalfEdgeMesh, HalfEdgeId, INVALID};
 
 /// Select an edge loop starting from the given half-edge.
@@ -17,8 +18,9 @@ use super::half_edge::{HalfEdgeMesh, HalfEdgeId, INVALID};
 /// edge of the triangle (the edge not sharing a vertex with the current one),
 /// then cross to the twin face and repeat.
 ///
-/// For true quads (pairs of triangles sharing a diagonal), this traces the
-/// expected loop. For irregular topology it stops at boundaries or valence != 4.
+/// Quad faces are crossed directly to their opposite edge, and pairs of
+/// triangles sharing a diagonal are crossed as one quad. For irregular
+/// topology it stops at boundaries, n-gons, or valence != 4.
 pub fn select_edge_loop(mesh: &HalfEdgeMesh, start_he: HalfEdgeId) -> Vec<HalfEdgeId> {
     let mut loop_edges = Vec::new();
     let mut visited = HashSet::new();
@@ -79,6 +81,9 @@ fn canonical_edge_he(mesh: &HalfEdgeMesh, he: HalfEdgeId) -> HalfEdgeId {
 
 /// Traverse from a half-edge to the next edge in the loop direction.
 ///
+/// For a quad face: the opposite edge is `next.next` of the current
+/// half-edge; crossing to its twin enters the next quad.
+///
 /// For a triangle face: the current half-edge enters the face. The "opposite"
 /// edge is the one across from the shared vertex — i.e., `next.next` of the
 /// current half-edge. Then cross to its twin face.
@@ -88,6 +93,20 @@ fn next_loop_edge(mesh: &HalfEdgeMesh, he: HalfEdgeId) -> HalfEdgeId {
         return INVALID;
     }
 
+    match mesh.face_half_edges(face).len() {
+        3 => {}
+        4 => {
+            let n1 = mesh.half_edges[he as usize].next;
+            let opposite = mesh.half_edges[n1 as usize].next;
+            let twin = mesh.half_edges[opposite as usize].twin;
+            if twin == INVALID || mesh.half_edges[twin as usize].face == INVALID {
+                return opposite; // boundary — stop here
+            }
+            return twin;
+        }
+        _ => return INVALID,
+    }
+
     // In a triangle: he -> next -> next gives the opposite edge
     let n1 = mesh.half_edges[he as usize].next;
     if n1 == INVALID {
@@ -131,9 +150,10 @@ fn next_loop_edge(mesh: &HalfEdgeMesh, he: HalfEdgeId) -> HalfEdgeId {
 
 /// Insert an edge loop by splitting all edges perpendicular to a given edge.
 ///
-/// For each face crossed by the loop, a new vertex is inserted at the midpoint
-/// of the crossed edge, and the face is split into two triangles through the
-/// new vertex.
+/// A new vertex is inserted at the midpoint of every crossed edge. Quads
+/// crossed through two opposite edges are split into two quads, triangles
+/// crossed through one edge into two triangles. Other faces keep the
+/// midpoints in their outline so the mesh stays connected.
 ///
 /// Returns the modified mesh.
 pub fn insert_edge_loop(mesh: &HalfEdgeMesh, start_he: HalfEdgeId) -> HalfEdgeMesh {
@@ -146,7 +166,7 @@ pub fn insert_edge_loop(mesh: &HalfEdgeMesh, start_he: HalfEdgeId) -> HalfEdgeMe
     let mut edit = mesh.to_edit_mesh();
 
     // Collect edges to split (as vertex pairs) and create midpoint vertices
-    let mut edge_midpoints: Vec<(u32, u32, u32)> = Vec::new(); // (from, to, new_vertex_id)
+    let mut midpoints: HashMap<Edge, u32> = HashMap::new();
     let mut processed_edges: HashSet<(u32, u32)> = HashSet::new();
 
     for &he_id in &loop_edges {
@@ -170,57 +190,56 @@ pub fn insert_edge_loop(mesh: &HalfEdgeMesh, start_he: HalfEdgeId) -> HalfEdgeMe
         edit.positions.push(mid_pos);
         edit.normals.push(mid_normal);
         edit.uvs.push(mid_uv);
+        edit.attributes.push_lerp(from, to, 0.5);
 
-        edge_midpoints.push((key.0, key.1, new_id));
+        midpoints.insert(Edge::new(key.0, key.1), new_id);
     }
 
-    // Split all triangles that contain a split edge
-    let mut new_triangles = Vec::new();
-    for tri in &edit.triangles {
-        let mut splits: Vec<(usize, usize, u32)> = Vec::new(); // (idx_a, idx_b, midpoint_id)
-
-        for &(from, to, mid) in &edge_midpoints {
-            // Check if this triangle contains this edge
-            let positions_in_tri: Vec<usize> = tri
-                .iter()
-                .enumerate()
-                .filter(|(_, v)| **v == from || **v == to)
-                .map(|(i, _)| i)
-                .collect();
-
-            if positions_in_tri.len() == 2 {
-                splits.push((positions_in_tri[0], positions_in_tri[1], mid));
+    // Split every polygon that contains a split edge
+    let mut new_faces = Vec::new();
+    let mut new_materials = Vec::new();
+    for (face, slot) in edit.polygons().into_iter().zip(edit.polygon_materials()) {
+        let n = face.len();
+        // (index of edge start in the face, midpoint_id)
+        let splits: Vec<(usize, u32)> = (0..n)
+            .filter_map(|i| {
+                midpoints
+                    .get(&Edge::new(face[i], face[(i + 1) % n]))
+                    .map(|&mid| (i, mid))
+            })
+            .collect();
+
+        match (n, splits.as_slice()) {
+            (_, []) => new_faces.push(face),
+            (3, &[(i, mid)]) => {
+                // One edge split: triangle becomes 2 triangles
+                let (va, vb, vc) = (face[i], face[(i + 1) % 3], face[(i + 2) % 3]);
+                new_faces.push(vec![va, mid, vc]);
+                new_faces.push(vec![mid, vb, vc]);
             }
-        }
-
-        if splits.is_empty() {
-            new_triangles.push(*tri);
-        } else if splits.len() == 1 {
-            // One edge split: triangle becomes 2 triangles
-            let (ia, ib, mid) = splits[0];
-            let ic = 3 - ia - ib; // the other index
-            let va = tri[ia];
-            let vb = tri[ib];
-            let vc = tri[ic];
-
-            // Split: (va, mid, vc) and (mid, vb, vc)
-            // Preserve winding by checking original order
-            if (ia + 1) % 3 == ib {
-                // a->b is forward in winding
-                new_triangles.push([va, mid, vc]);
-                new_triangles.push([mid, vb, vc]);
-            } else {
-                // b->a is forward
-                new_triangles.push([vb, mid, vc]);
-                new_triangles.push([mid, va, vc]);
+            (4, &[(i, m0), (j, m1)]) if j == i + 2 => {
+                // Opposite edges split: quad becomes 2 quads
+                let (va, vb, vc, vd) = (face[i], face[i + 1], face[i + 2], face[(i + 3) % 4]);
+                new_faces.push(vec![va, m0, m1, vd]);
+                new_faces.push(vec![m0, vb, vc, m1]);
+            }
+            _ => {
+                // Irregular — keep the face, with midpoints in its outline
+                let mut outline = Vec::with_capacity(n + splits.len());
+                for (i, &v) in face.iter().enumerate() {
+                    outline.push(v);
+                    if let Some(&(_, mid)) = splits.iter().find(|(si, _)| *si == i) {
+                        outline.push(mid);
+                    }
+                }
+                new_faces.push(outline);
             }
-        } else {
-            // Multiple splits on one triangle — rare, just keep original
-            new_triangles.push(*tri);
         }
+        // Split pieces keep the material of the face they came from
+        new_materials.resize(new_faces.len(), slot);
     }
 
-    edit.triangles = new_triangles;
+    edit.set_faces(new_faces, new_materials);
     edit.recompute_normals();
 
     let mut result = HalfEdgeMesh::from_edit_mesh(&edit);
@@ -3,6 +3,14 @@
 //! `EditMesh` is an indexed face list — a thin wrapper around Bevy mesh data.
 //! It supports face queries, adjacency building, and boundary edge detection
 //! needed for extrusion and cutting.
+//!
+//! Faces can be polygons (quads, n-gons). `triangles` always holds the
+//! triangulation, so triangle-level code keeps working; `faces` records the
+//! polygon each run of triangles came from. Each polygon can also carry a
+//! material slot, which splits the rendered mesh into one submesh per slot.
+//!
+//! Optional per-vertex attributes (vertex colors, a second UV channel) live
+//! in `VertexAttributes` and follow the vertices through editing operations.
 
 use bevy::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
 use bevy::prelude::*;
@@ -11,6 +19,9 @@ use std::collections::{HashMap, HashSet};
 /// Index of a triangle face in the mesh.
 pub type FaceIndex = usize;
 
+/// Coplanarity tolerance (cosine) when merging triangle pairs into quads.
+const QUAD_COPLANAR_COS: f32 = 0.9999;
+
 /// Canonical edge representation (lower vertex index first).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Edge(pub u32, pub u32);
@@ -22,6 +33,68 @@ impl Edge {
     }
 }
 
+/// Optional per-vertex attributes. Each list is either empty (attribute
+/// absent) or holds one entry per vertex.
+#[derive(Debug, Clone, Default)]
+pub struct VertexAttributes {
+    /// Linear RGBA vertex colors, used for tinting and blend masks.
+    pub colors: Vec<Vec4>,
+    /// Second UV channel (`ATTRIBUTE_UV_1`), used for lightmaps.
+    pub uvs2: Vec<Vec2>,
+}
+
+impl VertexAttributes {
+    /// Whether no optional attribute is present.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty() && self.uvs2.is_empty()
+    }
+
+    /// Append a vertex blended between vertices `a` and `b` (`t` = 0 gives `a`).
+    pub fn push_lerp(&mut self, a: u32, b: u32, t: f32) {
+        if !self.colors.is_empty() {
+            let color = self.colors[a as usize].lerp(self.colors[b as usize], t);
+            self.colors.push(color);
+        }
+        if !self.uvs2.is_empty() {
+            let uv = self.uvs2[a as usize].lerp(self.uvs2[b as usize], t);
+            self.uvs2.push(uv);
+        }
+    }
+
+    /// Append a copy of vertex `v`.
+    pub fn push_copy(&mut self, v: u32) {
+        self.push_lerp(v, v, 0.0);
+    }
+
+    /// Attributes for a new vertex list where vertex `i` copies `origins[i]`.
+    pub fn remap(&self, origins: &[u32]) -> Self {
+        Self {
+            colors: remap_values(&self.colors, origins),
+            uvs2: remap_values(&self.uvs2, origins),
+        }
+    }
+
+    /// Drop any attribute that doesn't have exactly `vertex_count` entries.
+    pub fn validated(mut self, vertex_count: usize) -> Self {
+        if self.colors.len() != vertex_count {
+            self.colors.clear();
+        }
+        if self.uvs2.len() != vertex_count {
+            self.uvs2.clear();
+        }
+        self
+    }
+}
+
+/// Pick `values[origins[i]]` for each `i`; an absent (empty) list stays empty.
+fn remap_values<T: Copy>(values: &[T], origins: &[u32]) -> Vec<T> {
+    if values.is_empty() {
+        Vec::new()
+    } else {
+        origins.iter().map(|&v| values[v as usize]).collect()
+    }
+}
+
 /// Indexed triangle mesh suitable for face-level editing.
 #[derive(Debug, Clone)]
 pub struct EditMesh {
@@ -29,6 +102,17 @@ pub struct EditMesh {
     pub normals: Vec<Vec3>,
     pub uvs: Vec<Vec2>,
     pub triangles: Vec<[u32; 3]>,
+    /// Polygon faces as vertex loops, in the same winding as `triangles`.
+    ///
+    /// When non-empty, `triangles` holds a triangulation of these faces,
+    /// face by face in order (`len - 2` triangles each). Empty means every
+    /// triangle is its own face.
+    pub faces: Vec<Vec<u32>>,
+    /// Material slot of each polygon, parallel to `polygons()`. Empty (or
+    /// stale after a topology change) means every face uses slot 0.
+    pub face_materials: Vec<u32>,
+    /// Optional per-vertex attributes (vertex colors, second UV channel).
+    pub attributes: VertexAttributes,
 }
 
 impl EditMesh {
@@ -59,6 +143,22 @@ impl EditMesh {
             _ => vec![Vec2::ZERO; positions.len()],
         };
 
+        let attributes = VertexAttributes {
+            colors: match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+                Some(VertexAttributeValues::Float32x4(v)) => {
+                    v.iter().map(|c| Vec4::from(*c)).collect()
+                }
+                _ => Vec::new(),
+            },
+            uvs2: match mesh.attribute(Mesh::ATTRIBUTE_UV_1) {
+                Some(VertexAttributeValues::Float32x2(v)) => {
+                    v.iter().map(|u| Vec2::from(*u)).collect()
+                }
+                _ => Vec::new(),
+            },
+        }
+        .validated(positions.len());
+
         let triangles = match mesh.indices() {
             Some(Indices::U32(indices)) => indices
                 .chunks(3)
@@ -78,12 +178,318 @@ impl EditMesh {
             }
         };
 
-        Some(EditMesh {
+        let mut edit_mesh = EditMesh {
             positions,
             normals,
             uvs,
             triangles,
-        })
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes,
+        };
+        edit_mesh.detect_quads();
+        Some(edit_mesh)
+    }
+
+    /// Build an `EditMesh` from polygon faces, triangulating each one.
+    ///
+    /// `face_materials` gives each face's material slot; pass an empty list
+    /// to put every face in slot 0.
+    pub fn from_polygons(
+        positions: Vec<Vec3>,
+        normals: Vec<Vec3>,
+        uvs: Vec<Vec2>,
+        faces: Vec<Vec<u32>>,
+        face_materials: Vec<u32>,
+    ) -> Self {
+        let mut mesh = EditMesh {
+            positions,
+            normals,
+            uvs,
+            triangles: Vec::new(),
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
+        };
+        mesh.set_faces(faces, face_materials);
+        mesh
+    }
+
+    /// Replace the polygon faces (and their material slots) and rebuild
+    /// `triangles` from them. Faces with fewer than 3 vertices are dropped.
+    pub fn set_faces(&mut self, faces: Vec<Vec<u32>>, face_materials: Vec<u32>) {
+        let keep_materials = face_materials.len() == faces.len();
+        let (faces, materials): (Vec<Vec<u32>>, Vec<u32>) = faces
+            .into_iter()
+            .zip(face_materials.into_iter().chain(std::iter::repeat(0)))
+            .filter(|(face, _)| face.len() >= 3)
+            .unzip();
+        self.triangles = faces
+            .iter()
+            .flat_map(|face| triangulate_polygon(face, &self.positions))
+            .collect();
+        self.faces = faces;
+        self.face_materials = if keep_materials && materials.iter().any(|&m| m != 0) {
+            materials
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Whether `faces` describes the current triangles.
+    pub fn has_polygons(&self) -> bool {
+        !self.faces.is_empty()
+            && self
+                .faces
+                .iter()
+                .map(|f| f.len().saturating_sub(2))
+                .sum::<usize>()
+                == self.triangles.len()
+    }
+
+    /// Polygon faces as vertex loops. Falls back to one face per triangle.
+    pub fn polygons(&self) -> Vec<Vec<u32>> {
+        if self.has_polygons() {
+            self.faces.clone()
+        } else {
+            self.triangles.iter().map(|t| t.to_vec()).collect()
+        }
+    }
+
+    /// Whether `face_materials` assigns a slot to every polygon.
+    pub fn has_face_materials(&self) -> bool {
+        !self.face_materials.is_empty() && self.face_materials.len() == self.polygon_count()
+    }
+
+    /// Material slot of each polygon, parallel to `polygons()`.
+    pub fn polygon_materials(&self) -> Vec<u32> {
+        if self.has_face_materials() {
+            self.face_materials.clone()
+        } else {
+            vec![0; self.polygon_count()]
+        }
+    }
+
+    /// Material slot of each triangle (the slot of the polygon it belongs to).
+    pub fn triangle_materials(&self) -> Vec<u32> {
+        let materials = self.polygon_materials();
+        self.triangle_polygons()
+            .into_iter()
+            .map(|pi| materials[pi])
+            .collect()
+    }
+
+    /// Put every polygon touched by the selection into material `slot`.
+    pub fn assign_material(&mut self, selected: &HashSet<FaceIndex>, slot: u32) {
+        let mut materials = self.polygon_materials();
+        for pi in self.selected_polygons(selected) {
+            materials[pi] = slot;
+        }
+        self.face_materials = if materials.iter().any(|&m| m != 0) {
+            materials
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Split into one compact mesh per material slot, in slot order.
+    pub fn split_by_material(&self) -> Vec<(u32, EditMesh)> {
+        let materials = self.polygon_materials();
+        let mut slots: Vec<u32> = materials.clone();
+        slots.sort_unstable();
+        slots.dedup();
+
+        let polygons = self.polygons();
+        slots
+            .into_iter()
+            .map(|slot| {
+                let mut positions = Vec::new();
+                let mut normals = Vec::new();
+                let mut uvs = Vec::new();
+                let mut faces = Vec::new();
+                let mut origins = Vec::new();
+                let mut vertex_map: HashMap<u32, u32> = HashMap::new();
+
+                for (face, _) in polygons.iter().zip(&materials).filter(|(_, m)| **m == slot) {
+                    let new_face: Vec<u32> = face
+                        .iter()
+                        .map(|&v| {
+                            *vertex_map.entry(v).or_insert_with(|| {
+                                positions.push(self.positions[v as usize]);
+                                normals.push(self.normals[v as usize]);
+                                uvs.push(self.uvs[v as usize]);
+                                origins.push(v);
+                                positions.len() as u32 - 1
+                            })
+                        })
+                        .collect();
+                    faces.push(new_face);
+                }
+
+                let mut part = EditMesh::from_polygons(positions, normals, uvs, faces, Vec::new());
+                part.attributes = self.attributes.remap(&origins);
+                (slot, part)
+            })
+            .collect()
+    }
+
+    /// Number of polygon faces.
+    pub fn polygon_count(&self) -> usize {
+        if self.has_polygons() {
+            self.faces.len()
+        } else {
+            self.triangles.len()
+        }
+    }
+
+    /// For each triangle, the index of the polygon it belongs to.
+    pub fn triangle_polygons(&self) -> Vec<usize> {
+        if !self.has_polygons() {
+            return (0..self.triangles.len()).collect();
+        }
+        self.faces
+            .iter()
+            .enumerate()
+            .flat_map(|(pi, face)| std::iter::repeat(pi).take(face.len() - 2))
+            .collect()
+    }
+
+    /// Triangle index range of each polygon.
+    pub fn polygon_triangle_ranges(&self) -> Vec<std::ops::Range<usize>> {
+        if !self.has_polygons() {
+            return (0..self.triangles.len()).map(|t| t..t + 1).collect();
+        }
+        let mut start = 0;
+        self.faces
+            .iter()
+            .map(|face| {
+                let range = start..start + face.len() - 2;
+                start = range.end;
+                range
+            })
+            .collect()
+    }
+
+    /// Polygons that own any of the given triangles.
+    pub fn selected_polygons(&self, selected: &HashSet<FaceIndex>) -> HashSet<usize> {
+        let tri_polys = self.triangle_polygons();
+        selected
+            .iter()
+            .filter_map(|&fi| tri_polys.get(fi).copied())
+            .collect()
+    }
+
+    /// Grow a triangle selection so every touched polygon is fully selected.
+    pub fn expand_to_polygons(&self, selected: &HashSet<FaceIndex>) -> HashSet<FaceIndex> {
+        if !self.has_polygons() {
+            return selected.clone();
+        }
+        let ranges = self.polygon_triangle_ranges();
+        self.selected_polygons(selected)
+            .into_iter()
+            .flat_map(|pi| ranges[pi].clone())
+            .collect()
+    }
+
+    /// Edges inside a polygon (triangulation diagonals), not real mesh edges.
+    pub fn internal_edges(&self) -> HashSet<Edge> {
+        let mut internal = HashSet::new();
+        if !self.has_polygons() {
+            return internal;
+        }
+        for face in &self.faces {
+            let outline: HashSet<Edge> = (0..face.len())
+                .map(|i| Edge::new(face[i], face[(i + 1) % face.len()]))
+                .collect();
+            for tri in triangulate_polygon(face, &self.positions) {
+                for edge in [
+                    Edge::new(tri[0], tri[1]),
+                    Edge::new(tri[1], tri[2]),
+                    Edge::new(tri[2], tri[0]),
+                ] {
+                    if !outline.contains(&edge) {
+                        internal.insert(edge);
+                    }
+                }
+            }
+        }
+        internal
+    }
+
+    /// Merge pairs of coplanar triangles that share a diagonal into quads.
+    ///
+    /// Recovers quad structure from triangulated input (Bevy primitives,
+    /// imported meshes). Does nothing if the mesh already has polygons.
+    pub fn detect_quads(&mut self) {
+        if self.has_polygons() || self.triangles.len() < 2 {
+            return;
+        }
+
+        let adj = self.build_adjacency();
+        let materials = self.triangle_materials();
+        let mut partner: Vec<Option<usize>> = vec![None; self.triangles.len()];
+
+        // Pair each triangle across its longest edge (the split diagonal)
+        for fi in 0..self.triangles.len() {
+            if partner[fi].is_some() {
+                continue;
+            }
+            let Some(diagonal) = self.longest_edge(fi) else {
+                continue;
+            };
+            let Some(other) = adj[&diagonal].iter().copied().find(|&o| o != fi) else {
+                continue;
+            };
+            if adj[&diagonal].len() != 2
+                || partner[other].is_some()
+                || materials[fi] != materials[other]
+                || self.longest_edge(other) != Some(diagonal)
+                || self.face_normal(fi).dot(self.face_normal(other)) < QUAD_COPLANAR_COS
+            {
+                continue;
+            }
+            let Some(quad) = quad_from_pair(self.triangles[fi], self.triangles[other], diagonal)
+            else {
+                continue;
+            };
+            if !is_convex_polygon(&quad, &self.positions) {
+                continue;
+            }
+            partner[fi] = Some(other);
+            partner[other] = Some(fi);
+        }
+
+        if partner.iter().all(Option::is_none) {
+            return;
+        }
+
+        let mut faces = Vec::with_capacity(self.triangles.len());
+        let mut face_materials = Vec::with_capacity(self.triangles.len());
+        for fi in 0..self.triangles.len() {
+            match partner[fi] {
+                Some(other) if other < fi => continue,
+                Some(other) => {
+                    let diagonal = self.longest_edge(fi).unwrap();
+                    faces.push(
+                        quad_from_pair(self.triangles[fi], self.triangles[other], diagonal)
+                            .unwrap(),
+                    );
+                }
+                None => faces.push(self.triangles[fi].to_vec()),
+            }
+            face_materials.push(materials[fi]);
+        }
+        self.set_faces(faces, face_materials);
+    }
+
+    fn longest_edge(&self, face: FaceIndex) -> Option<Edge> {
+        let edges = self.face_edges(face);
+        let length = |e: &Edge| {
+            self.positions[e.0 as usize].distance_squared(self.positions[e.1 as usize])
+        };
+        edges
+            .into_iter()
+            .max_by(|a, b| length(a).total_cmp(&length(b)))
     }
 
     /// Convert back to a Bevy `Mesh` with normals and tangents.
@@ -103,6 +509,8 @@ impl EditMesh {
             self.uvs.iter().map(|u| [u.x, u.y]).collect::<Vec<_>>(),
         );
 
+        self.insert_vertex_attributes(&mut mesh);
+
         let indices: Vec<u32> = self.triangles.iter().flat_map(|t| t.iter().copied()).collect();
         mesh.insert_indices(Indices::U32(indices));
 
@@ -125,6 +533,7 @@ impl EditMesh {
                     Mesh::ATTRIBUTE_UV_0,
                     self.uvs.iter().map(|u| [u.x, u.y]).collect::<Vec<_>>(),
                 );
+                self.insert_vertex_attributes(&mut fallback);
                 let indices: Vec<u32> = self.triangles.iter().flat_map(|t| t.iter().copied()).collect();
                 fallback.insert_indices(Indices::U32(indices));
                 fallback
@@ -132,6 +541,36 @@ impl EditMesh {
         }
     }
 
+    /// Add the optional vertex attributes that match the vertex count.
+    fn insert_vertex_attributes(&self, mesh: &mut Mesh) {
+        if self.has_vertex_colors() {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_COLOR,
+                self.attributes.colors.iter().map(|c| c.to_array()).collect::<Vec<_>>(),
+            );
+        }
+        if !self.attributes.uvs2.is_empty() && self.attributes.uvs2.len() == self.positions.len() {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_UV_1,
+                self.attributes.uvs2.iter().map(|u| [u.x, u.y]).collect::<Vec<_>>(),
+            );
+        }
+    }
+
+    /// Whether every vertex has a color.
+    pub fn has_vertex_colors(&self) -> bool {
+        !self.attributes.colors.is_empty() && self.attributes.colors.len() == self.positions.len()
+    }
+
+    /// Vertex colors, or white for every vertex when the mesh is unpainted.
+    pub fn vertex_colors(&self) -> Vec<Vec4> {
+        if self.has_vertex_colors() {
+            self.attributes.colors.clone()
+        } else {
+            vec![Vec4::ONE; self.positions.len()]
+        }
+    }
+
     /// Compute the face normal for a triangle.
     pub fn face_normal(&self, face: FaceIndex) -> Vec3 {
         let [a, b, c] = self.triangles[face];
@@ -256,3 +695,126 @@ impl EditMesh {
         self.triangles.len()
     }
 }
+
+/// Newell normal of a polygon loop (robust for non-planar and concave faces).
+pub fn polygon_normal(face: &[u32], positions: &[Vec3]) -> Vec3 {
+    let mut normal = Vec3::ZERO;
+    for i in 0..face.len() {
+        let a = positions[face[i] as usize];
+        let b = positions[face[(i + 1) % face.len()] as usize];
+        normal += a.cross(b);
+    }
+    normal.normalize_or_zero()
+}
+
+/// Triangulate a polygon loop by ear clipping on its projected plane.
+///
+/// Convex polygons come out as a fan from the first vertex. Degenerate
+/// polygons fall back to a fan.
+pub fn triangulate_polygon(face: &[u32], positions: &[Vec3]) -> Vec<[u32; 3]> {
+    if face.len() < 3 {
+        return Vec::new();
+    }
+    if face.len() == 3 {
+        return vec![[face[0], face[1], face[2]]];
+    }
+
+    let normal = match polygon_normal(face, positions) {
+        n if n == Vec3::ZERO => Vec3::Y,
+        n => n,
+    };
+    let u_axis = normal.any_orthogonal_vector().normalize();
+    let v_axis = normal.cross(u_axis);
+    let project = |vi: u32| -> Vec2 {
+        let p = positions[vi as usize];
+        Vec2::new(p.dot(u_axis), p.dot(v_axis))
+    };
+
+    let mut remaining: Vec<u32> = face.to_vec();
+    let mut triangles = Vec::with_capacity(face.len() - 2);
+
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        // Start at vertex 1 so convex faces fan out from vertex 0
+        let ear = (0..n).map(|step| (step + 1) % n).find(|&i| {
+            let prev = (i + n - 1) % n;
+            let next = (i + 1) % n;
+            let a = project(remaining[prev]);
+            let b = project(remaining[i]);
+            let c = project(remaining[next]);
+            if (b - a).perp_dot(c - a) <= 0.0 {
+                return false; // Reflex vertex
+            }
+            !(0..n).any(|k| {
+                k != prev
+                    && k != i
+                    && k != next
+                    && point_in_triangle_2d(project(remaining[k]), a, b, c)
+            })
+        });
+
+        let Some(i) = ear else {
+            // Degenerate loop — fan the rest
+            for k in 1..remaining.len() - 1 {
+                triangles.push([remaining[0], remaining[k], remaining[k + 1]]);
+            }
+            return triangles;
+        };
+        let prev = (i + n - 1) % n;
+        let next = (i + 1) % n;
+        triangles.push([remaining[prev], remaining[i], remaining[next]]);
+        remaining.remove(i);
+    }
+
+    triangles.push([remaining[0], remaining[1], remaining[2]]);
+    triangles
+}
+
+/// Check if point P is inside triangle ABC (2D, using barycentric coordinates).
+fn point_in_triangle_2d(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let v0 = c - a;
+    let v1 = b - a;
+    let v2 = p - a;
+
+    let dot00 = v0.dot(v0);
+    let dot01 = v0.dot(v1);
+    let dot02 = v0.dot(v2);
+    let dot11 = v1.dot(v1);
+    let dot12 = v1.dot(v2);
+
+    let inv_denom = 1.0 / (dot00 * dot11 - dot01 * dot01);
+    let u = (dot11 * dot02 - dot01 * dot12) * inv_denom;
+    let v = (dot00 * dot12 - dot01 * dot02) * inv_denom;
+
+    u >= 0.0 && v >= 0.0 && u + v < 1.0
+}
+
+/// Join two triangles sharing `diagonal` into a quad loop, keeping winding.
+fn quad_from_pair(a: [u32; 3], b: [u32; 3], diagonal: Edge) -> Option<Vec<u32>> {
+    // Rotate `a` so the diagonal is its last edge: [apex, d0, d1]
+    let apex_a = a.iter().position(|&v| v != diagonal.0 && v != diagonal.1)?;
+    let d0 = a[(apex_a + 1) % 3];
+    let d1 = a[(apex_a + 2) % 3];
+    let apex_b = *b.iter().find(|&&v| v != diagonal.0 && v != diagonal.1)?;
+    // `b` runs d1 -> d0 for consistent winding
+    let pos_d1 = b.iter().position(|&v| v == d1)?;
+    if b[(pos_d1 + 1) % 3] != d0 {
+        return None;
+    }
+    Some(vec![a[apex_a], d0, apex_b, d1])
+}
+
+/// Whether a polygon loop is strictly convex on its plane.
+fn is_convex_polygon(face: &[u32], positions: &[Vec3]) -> bool {
+    let normal = polygon_normal(face, positions);
+    if normal == Vec3::ZERO {
+        return false;
+    }
+    let n = face.len();
+    (0..n).all(|i| {
+        let a = positions[face[(i + n - 1) % n] as usize];
+        let b = positions[face[i] as usize];
+        let c = positions[face[(i + 1) % n] as usize];
+        (b - a).cross(c - b).dot(normal) > 1e-8
+    })
+}
@@ -12,7 +12,7 @@ use super::edit_mesh::{EditMesh, FaceIndex};
 ///
 /// - Boundary vertices are duplicated: the original stays for side walls, the copy moves.
 /// - Interior vertices (not on any boundary edge) are simply offset.
-/// - Side wall quads are created along each boundary edge.
+/// - Side wall quads are created along each boundary edge (as quad faces).
 /// - If `angle` is nonzero, the extrusion direction tilts per-face.
 ///
 /// Returns a new `EditMesh` with the extrusion applied.
@@ -25,10 +25,12 @@ pub fn extrude_faces(
     if selected.is_empty() || distance.abs() < 1e-6 {
         return mesh.clone();
     }
+    let selected = &mesh.expand_to_polygons(selected);
 
     let mut new_positions = mesh.positions.clone();
     let mut new_normals = mesh.normals.clone();
     let mut new_uvs = mesh.uvs.clone();
+    let mut new_attributes = mesh.attributes.clone();
     let mut new_triangles = mesh.triangles.clone();
 
     // Compute area-weighted average normal of selected faces
@@ -56,6 +58,7 @@ pub fn extrude_faces(
         new_positions.push(mesh.positions[v as usize] + offset);
         new_normals.push(mesh.normals[v as usize]);
         new_uvs.push(mesh.uvs[v as usize]);
+        new_attributes.push_copy(v);
         dup_map.insert(v, new_idx);
     }
 
@@ -75,6 +78,16 @@ pub fn extrude_faces(
             }
         }
     }
+    let mut new_faces = mesh.polygons();
+    let mut new_materials = mesh.polygon_materials();
+    let triangle_materials = mesh.triangle_materials();
+    for pi in mesh.selected_polygons(selected) {
+        for idx in new_faces[pi].iter_mut() {
+            if let Some(&dup) = dup_map.get(idx) {
+                *idx = dup;
+            }
+        }
+    }
 
     // Create side wall quads along boundary edges
     let boundary_edges = mesh.boundary_edges(selected);
@@ -105,10 +118,15 @@ pub fn extrude_faces(
             let eb_dup = dup_map[&eb];
             new_triangles.push([ea, eb, eb_dup]);
             new_triangles.push([ea, eb_dup, ea_dup]);
+            new_faces.push(vec![ea, eb, eb_dup, ea_dup]);
+            // Side walls take the material of the face they were pulled from
+            new_materials.push(triangle_materials[fi]);
         } else {
             // Fallback winding
             new_triangles.push([a, b, b_dup]);
             new_triangles.push([a, b_dup, a_dup]);
+            new_faces.push(vec![a, b, b_dup, a_dup]);
+            new_materials.push(0);
         }
     }
 
@@ -117,7 +135,13 @@ pub fn extrude_faces(
         normals: new_normals,
         uvs: new_uvs,
         triangles: new_triangles,
+        faces: new_faces,
+        face_materials: Vec::new(),
+        attributes: new_attributes,
     };
+    if new_materials.iter().any(|&m| m != 0) {
+        result.face_materials = new_materials;
+    }
     result.recompute_normals();
     result
 }
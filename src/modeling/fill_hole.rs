@@ -1,29 +1,27 @@
 //! Hole filling for meshes with open boundaries.
 //!
 //! Detects boundary edge loops (edges with only one adjacent face)
-//! and fills them with triangles using ear-clipping triangulation.
+//! and fills each with a single polygon face, triangulated by ear clipping.
 
-use bevy::prelude::*;
 use std::collections::HashMap;
 
 use super::edit_mesh::EditMesh;
 
-/// Fill all holes in the mesh by triangulating boundary loops.
+/// Fill all holes in the mesh with polygon faces.
 ///
 /// A "hole" is a loop of boundary edges (edges with only one adjacent face).
-/// Each hole is filled by ear-clipping triangulation projected onto
-/// the loop's average plane.
+/// Each hole becomes one n-gon face, triangulated by ear clipping projected
+/// onto the loop's average plane.
 pub fn fill_holes(mesh: &EditMesh) -> EditMesh {
     let mut result = mesh.clone();
     let loops = find_boundary_loops(mesh);
 
-    for boundary_loop in &loops {
-        if boundary_loop.len() < 3 {
-            continue;
-        }
-        let new_tris = triangulate_loop(boundary_loop, &result.positions);
-        result.triangles.extend(new_tris);
-    }
+    let mut faces = result.polygons();
+    faces.extend(loops.into_iter().filter(|boundary_loop| boundary_loop.len() >= 3));
+    // Hole caps go in the base material slot
+    let mut materials = result.polygon_materials();
+    materials.resize(faces.len(), 0);
+    result.set_faces(faces, materials);
 
     result.recompute_normals();
     result
@@ -94,132 +92,3 @@ fn find_boundary_loops(mesh: &EditMesh) -> Vec<Vec<u32>> {
 
     loops
 }
-
-/// Triangulate a vertex loop using ear-clipping.
-///
-/// Projects the loop onto its average plane for 2D ear detection,
-/// then creates triangles in 3D.
-fn triangulate_loop(vertex_loop: &[u32], positions: &[Vec3]) -> Vec<[u32; 3]> {
-    if vertex_loop.len() < 3 {
-        return Vec::new();
-    }
-    if vertex_loop.len() == 3 {
-        return vec![[vertex_loop[0], vertex_loop[1], vertex_loop[2]]];
-    }
-
-    // Compute loop normal (area-weighted)
-    let center: Vec3 = vertex_loop
-        .iter()
-        .map(|&vi| positions[vi as usize])
-        .sum::<Vec3>()
-        / vertex_loop.len() as f32;
-
-    let mut normal = Vec3::ZERO;
-    for i in 0..vertex_loop.len() {
-        let j = (i + 1) % vertex_loop.len();
-        let a = positions[vertex_loop[i] as usize] - center;
-        let b = positions[vertex_loop[j] as usize] - center;
-        normal += a.cross(b);
-    }
-    normal = normal.normalize_or_zero();
-    if normal == Vec3::ZERO {
-        normal = Vec3::Y; // Fallback
-    }
-
-    // Project to 2D for ear detection
-    let (u_axis, v_axis) = make_orthonormal_basis(normal);
-    let project = |vi: u32| -> Vec2 {
-        let p = positions[vi as usize] - center;
-        Vec2::new(p.dot(u_axis), p.dot(v_axis))
-    };
-
-    // Ear-clip
-    let mut remaining: Vec<u32> = vertex_loop.to_vec();
-    let mut triangles = Vec::new();
-
-    let mut safety = remaining.len() * remaining.len();
-    while remaining.len() > 3 && safety > 0 {
-        safety -= 1;
-        let n = remaining.len();
-        let mut found_ear = false;
-
-        for i in 0..n {
-            let prev = (i + n - 1) % n;
-            let next = (i + 1) % n;
-
-            let a = project(remaining[prev]);
-            let b = project(remaining[i]);
-            let c = project(remaining[next]);
-
-            // Check if this is a convex vertex (ear tip candidate)
-            if cross_2d(b - a, c - a) <= 0.0 {
-                continue; // Reflex vertex
-            }
-
-            // Check no other vertex is inside this triangle
-            let has_interior_point = (0..n).any(|k| {
-                k != prev
-                    && k != i
-                    && k != next
-                    && point_in_triangle_2d(project(remaining[k]), a, b, c)
-            });
-
-            if has_interior_point {
-                continue;
-            }
-
-            // Valid ear — clip it
-            triangles.push([remaining[prev], remaining[i], remaining[next]]);
-            remaining.remove(i);
-            found_ear = true;
-            break;
-        }
-
-        if !found_ear {
-            break; // Degenerate polygon
-        }
-    }
-
-    // Last triangle
-    if remaining.len() == 3 {
-        triangles.push([remaining[0], remaining[1], remaining[2]]);
-    }
-
-    triangles
-}
-
-/// 2D cross product (z-component).
-fn cross_2d(a: Vec2, b: Vec2) -> f32 {
-    a.x * b.y - a.y * b.x
-}
-
-/// Check if point P is inside triangle ABC (2D, using barycentric coordinates).
-fn point_in_triangle_2d(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
-    let v0 = c - a;
-    let v1 = b - a;
-    let v2 = p - a;
-
-    let dot00 = v0.dot(v0);
-    let dot01 = v0.dot(v1);
-    let dot02 = v0.dot(v2);
-    let dot11 = v1.dot(v1);
-    let dot12 = v1.dot(v2);
-
-    let inv_denom = 1.0 / (dot00 * dot11 - dot01 * dot01);
-    let u = (dot11 * dot02 - dot01 * dot12) * inv_denom;
-    let v = (dot00 * dot12 - dot01 * dot02) * inv_denom;
-
-    u >= 0.0 && v >= 0.0 && u + v < 1.0
-}
-
-/// Build an orthonormal basis from a normal vector.
-fn make_orthonormal_basis(normal: Vec3) -> (Vec3, Vec3) {
-    let up = if normal.y.abs() < 0.99 {
-        Vec3::Y
-    } else {
-        Vec3::X
-    };
-    let u = normal.cross(up).normalize();
-    let v = normal.cross(u).normalize();
-    (u, v)
-}
@@ -1,17 +1,19 @@
 //! Gizmo rendering for the mesh modeling tool.
 //!
 //! Draws grid overlays, selected face highlights, extrude previews,
-//! freeform polygon outlines, and cut boundary highlights.
+//! freeform polygon outlines, cut boundary highlights, and the brush cursor.
 
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
-use crate::editor::EditorState;
+use crate::editor::{EditorCamera, EditorState};
 use crate::gizmos::XRayGizmoConfig;
 use crate::selection::Selected;
 
 use super::edit_mesh::EditMesh;
 use super::half_edge::HalfEdgeMesh;
-use super::{ElementSelection, MeshModelState, ModelOperation, SelectionMode};
+use super::selection::{pick_face, world_to_local_ray};
+use super::{ElementSelection, MeshModelState, ModelOperation, ModelTool, SelectionMode};
 
 // Bevy-native colors matching the theme palette
 const HIGHLIGHT_ORANGE: Color = Color::srgb(0.808, 0.569, 0.341);
@@ -23,6 +25,7 @@ const VERTEX_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
 const EDGE_COLOR: Color = Color::srgb(0.4, 0.8, 1.0);
 const SEAM_COLOR: Color = Color::srgb(0.9, 0.2, 0.2);
 const HARD_EDGE_COLOR: Color = Color::srgb(0.2, 0.6, 1.0);
+const BRUSH_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
 const VERTEX_SIZE: f32 = 0.02;
 
 /// Draw gizmos for the mesh modeling tool: selected faces, grid overlay, operation previews.
@@ -246,31 +249,24 @@ fn draw_inset_preview(
 
     let inset_frac = state.inset_distance.clamp(0.0, 0.99);
 
-    for &fi in &state.selected_faces {
-        if fi >= mesh.triangles.len() {
-            continue;
+    let polygons = mesh.polygons();
+    for pi in mesh.selected_polygons(&state.selected_faces) {
+        let points: Vec<Vec3> = polygons[pi]
+            .iter()
+            .map(|&v| mesh.positions[v as usize])
+            .collect();
+        let center = points.iter().sum::<Vec3>() / points.len() as f32;
+        let inner: Vec<Vec3> = points
+            .iter()
+            .map(|p| transform.transform_point(p.lerp(center, inset_frac)))
+            .collect();
+
+        for i in 0..inner.len() {
+            gizmos.line(inner[i], inner[(i + 1) % inner.len()], PREVIEW_CYAN);
+
+            // Draw connecting lines from original to inset vertices
+            gizmos.line(transform.transform_point(points[i]), inner[i], PREVIEW_CYAN);
         }
-        let [a, b, c] = mesh.triangles[fi];
-        let p0 = mesh.positions[a as usize];
-        let p1 = mesh.positions[b as usize];
-        let p2 = mesh.positions[c as usize];
-        let center = (p0 + p1 + p2) / 3.0;
-
-        let q0 = transform.transform_point(p0.lerp(center, inset_frac));
-        let q1 = transform.transform_point(p1.lerp(center, inset_frac));
-        let q2 = transform.transform_point(p2.lerp(center, inset_frac));
-
-        gizmos.line(q0, q1, PREVIEW_CYAN);
-        gizmos.line(q1, q2, PREVIEW_CYAN);
-        gizmos.line(q2, q0, PREVIEW_CYAN);
-
-        // Draw connecting lines from original to inset vertices
-        let wp0 = transform.transform_point(p0);
-        let wp1 = transform.transform_point(p1);
-        let wp2 = transform.transform_point(p2);
-        gizmos.line(wp0, q0, PREVIEW_CYAN);
-        gizmos.line(wp1, q1, PREVIEW_CYAN);
-        gizmos.line(wp2, q2, PREVIEW_CYAN);
     }
 }
 
@@ -352,3 +348,80 @@ fn draw_push_pull_preview(
         gizmos.line(p0, p1, PREVIEW_CYAN);
     }
 }
+
+/// Draw the brush radius on the surface under the cursor while a brush tool is active.
+pub fn draw_brush_cursor(
+    mut gizmos: Gizmos<XRayGizmoConfig>,
+    model_state: Res<MeshModelState>,
+    editor_state: Res<EditorState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<EditorCamera>>,
+    selected_query: Query<&GlobalTransform, With<Selected>>,
+) {
+    if !editor_state.gizmos_visible || model_state.model_tool == ModelTool::Edit {
+        return;
+    }
+    let Some(ref edit_mesh) = model_state.edit_mesh else {
+        return;
+    };
+    let Some(target) = model_state.target_entity else {
+        return;
+    };
+    let Ok(global_transform) = selected_query.get(target) else {
+        return;
+    };
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position) else {
+        return;
+    };
+
+    let (local_origin, local_dir) =
+        world_to_local_ray(global_transform, ray.origin, *ray.direction);
+    let Some(hit) = pick_face(edit_mesh, local_origin, local_dir, model_state.xray_selection)
+    else {
+        return;
+    };
+
+    // The radius is in mesh space; scale it for display
+    let position = global_transform.transform_point(hit.point);
+    let normal = global_transform
+        .affine()
+        .transform_vector3(edit_mesh.face_normal(hit.face))
+        .normalize_or_zero();
+    let scale = global_transform.compute_transform().scale.max_element();
+    let radius = model_state.brush_radius * scale;
+    let rotation = Quat::from_rotation_arc(Vec3::Z, normal);
+    gizmos.circle(Isometry3d::new(position, rotation), radius, BRUSH_COLOR);
+    // Inner circle shows the strength
+    gizmos.circle(
+        Isometry3d::new(position, rotation),
+        radius * model_state.brush_strength,
+        BRUSH_COLOR.with_alpha(0.4),
+    );
+
+    // Mirrored dab of a symmetric sculpt stroke
+    if model_state.model_tool == ModelTool::Sculpt && model_state.sculpt_symmetry {
+        let axis = model_state.mirror_axis;
+        let mirrored_position = global_transform.transform_point(axis.reflect(hit.point));
+        let mirrored_normal = global_transform
+            .affine()
+            .transform_vector3(axis.reflect(edit_mesh.face_normal(hit.face)))
+            .normalize_or_zero();
+        gizmos.circle(
+            Isometry3d::new(
+                mirrored_position,
+                Quat::from_rotation_arc(Vec3::Z, mirrored_normal),
+            ),
+            radius,
+            BRUSH_COLOR.with_alpha(0.4),
+        );
+    }
+}
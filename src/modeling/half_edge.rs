@@ -9,7 +9,7 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 
-use super::edit_mesh::EditMesh;
+use super::edit_mesh::{EditMesh, VertexAttributes};
 
 /// Index into the half-edge array.
 pub type HalfEdgeId = u32;
@@ -51,6 +51,8 @@ pub struct HVertex {
 pub struct HFace {
     /// One half-edge on the boundary of this face.
     pub half_edge: HalfEdgeId,
+    /// Material slot (see `EditMesh::face_materials`).
+    pub material: u32,
 }
 
 /// Half-edge mesh with index-based arena storage.
@@ -62,16 +64,21 @@ pub struct HalfEdgeMesh {
     pub half_edges: Vec<HalfEdge>,
     pub vertices: Vec<HVertex>,
     pub faces: Vec<HFace>,
+    /// Optional per-vertex attributes, indexed like `vertices`.
+    pub attributes: VertexAttributes,
 }
 
 impl HalfEdgeMesh {
     /// Build a `HalfEdgeMesh` from an `EditMesh`.
     ///
-    /// The EditMesh must consist of triangles. Each triangle produces 3 half-edges.
-    /// Twin half-edges are linked where triangles share edges; boundary edges get
-    /// boundary twins (face = INVALID) so traversal never hits dead ends.
+    /// Each polygon face (or triangle, for meshes without polygons) produces
+    /// one half-edge per side. Twin half-edges are linked where faces share
+    /// edges; boundary edges get boundary twins (face = INVALID) so traversal
+    /// never hits dead ends.
     pub fn from_edit_mesh(mesh: &EditMesh) -> Self {
-        let num_faces = mesh.triangles.len();
+        let polygons = mesh.polygons();
+        let materials = mesh.polygon_materials();
+        let num_faces = polygons.len();
 
         // Create vertices
         let mut vertices: Vec<HVertex> = mesh
@@ -88,26 +95,26 @@ impl HalfEdgeMesh {
             .collect();
 
         // Create faces and half-edges
-        // Each triangle gets 3 half-edges
-        let mut half_edges: Vec<HalfEdge> = Vec::with_capacity(num_faces * 3);
+        // Each face gets one half-edge per side
+        let mut half_edges: Vec<HalfEdge> = Vec::with_capacity(num_faces * 4);
         let mut faces: Vec<HFace> = Vec::with_capacity(num_faces);
 
         // Map from directed edge (from, to) -> half-edge index for twin linking
         let mut edge_map: HashMap<(u32, u32), HalfEdgeId> = HashMap::new();
 
-        for (fi, tri) in mesh.triangles.iter().enumerate() {
+        for (fi, poly) in polygons.iter().enumerate() {
             let face_id = fi as FaceId;
             let base = half_edges.len() as HalfEdgeId;
+            let n = poly.len() as u32;
 
-            // Create 3 half-edges for this triangle
-            for i in 0..3u32 {
-                let from = tri[i as usize];
+            for i in 0..n {
+                let from = poly[i as usize];
                 let he_id = base + i;
 
                 half_edges.push(HalfEdge {
                     twin: INVALID,
-                    next: base + (i + 1) % 3,
-                    prev: base + (i + 2) % 3,
+                    next: base + (i + 1) % n,
+                    prev: base + (i + n - 1) % n,
                     vertex: from,
                     face: face_id,
                 });
@@ -118,11 +125,14 @@ impl HalfEdgeMesh {
                 }
 
                 // Register in edge map for twin linking
-                let to = tri[((i + 1) % 3) as usize];
+                let to = poly[((i + 1) % n) as usize];
                 edge_map.insert((from, to), he_id);
             }
 
-            faces.push(HFace { half_edge: base });
+            faces.push(HFace {
+                half_edge: base,
+                material: materials[fi],
+            });
         }
 
         // Link twins: for each half-edge (from, to), find the half-edge (to, from)
@@ -165,6 +175,7 @@ impl HalfEdgeMesh {
             half_edges,
             vertices,
             faces,
+            attributes: mesh.attributes.clone(),
         }
     }
 
@@ -193,32 +204,22 @@ impl HalfEdgeMesh {
 
     /// Convert back to an `EditMesh`.
     ///
-    /// Each face becomes a triangle (faces must remain triangular).
+    /// Each face becomes a polygon, in face order, triangulated for rendering.
     /// Vertex data is copied back with any modifications from editing operations.
     pub fn to_edit_mesh(&self) -> EditMesh {
         let positions: Vec<Vec3> = self.vertices.iter().map(|v| v.position).collect();
         let normals: Vec<Vec3> = self.vertices.iter().map(|v| v.normal).collect();
         let uvs: Vec<Vec2> = self.vertices.iter().map(|v| v.uv).collect();
 
-        let mut triangles = Vec::with_capacity(self.faces.len());
-        for face in &self.faces {
-            let he0 = face.half_edge;
-            let he1 = self.half_edges[he0 as usize].next;
-            let he2 = self.half_edges[he1 as usize].next;
+        let faces: Vec<Vec<u32>> = (0..self.faces.len())
+            .map(|fi| self.face_vertices(fi as FaceId))
+            .collect();
 
-            triangles.push([
-                self.half_edges[he0 as usize].vertex,
-                self.half_edges[he1 as usize].vertex,
-                self.half_edges[he2 as usize].vertex,
-            ]);
-        }
+        let materials = self.faces.iter().map(|f| f.material).collect();
 
-        EditMesh {
-            positions,
-            normals,
-            uvs,
-            triangles,
-        }
+        let mut mesh = EditMesh::from_polygons(positions, normals, uvs, faces, materials);
+        mesh.attributes = self.attributes.clone().validated(self.vertices.len());
+        mesh
     }
 
     // -------------------------------------------------------------------
@@ -333,10 +334,14 @@ impl HalfEdgeMesh {
         if verts.len() < 3 {
             return Vec3::ZERO;
         }
-        let p0 = self.vertices[verts[0] as usize].position;
-        let p1 = self.vertices[verts[1] as usize].position;
-        let p2 = self.vertices[verts[2] as usize].position;
-        (p1 - p0).cross(p2 - p0).normalize_or_zero()
+        // Newell's method, so non-planar quads get an averaged normal
+        let mut normal = Vec3::ZERO;
+        for i in 0..verts.len() {
+            let a = self.vertices[verts[i] as usize].position;
+            let b = self.vertices[verts[(i + 1) % verts.len()] as usize].position;
+            normal += a.cross(b);
+        }
+        normal.normalize_or_zero()
     }
 
     /// Compute face centroid.
@@ -352,16 +357,20 @@ impl HalfEdgeMesh {
         sum / verts.len() as f32
     }
 
-    /// Compute face area (for triangles).
+    /// Compute face area (vector area, exact for planar polygons).
     pub fn face_area(&self, face: FaceId) -> f32 {
         let verts = self.face_vertices(face);
         if verts.len() < 3 {
             return 0.0;
         }
         let p0 = self.vertices[verts[0] as usize].position;
-        let p1 = self.vertices[verts[1] as usize].position;
-        let p2 = self.vertices[verts[2] as usize].position;
-        (p1 - p0).cross(p2 - p0).length() * 0.5
+        let mut area = Vec3::ZERO;
+        for i in 1..verts.len() - 1 {
+            let p1 = self.vertices[verts[i] as usize].position;
+            let p2 = self.vertices[verts[i + 1] as usize].position;
+            area += (p1 - p0).cross(p2 - p0);
+        }
+        area.length() * 0.5
     }
 
     /// Check if a half-edge is on the boundary (its face is INVALID).
@@ -380,10 +389,7 @@ impl HalfEdgeMesh {
             if verts.len() < 3 {
                 continue;
             }
-            let p0 = self.vertices[verts[0] as usize].position;
-            let p1 = self.vertices[verts[1] as usize].position;
-            let p2 = self.vertices[verts[2] as usize].position;
-            let normal = (p1 - p0).cross(p2 - p0);
+            let normal = self.face_normal(fi as FaceId) * self.face_area(fi as FaceId);
             for &v in &verts {
                 self.vertices[v as usize].normal += normal;
             }
@@ -430,7 +436,40 @@ impl HalfEdgeMesh {
             }
         }
 
-        self.faces.push(HFace { half_edge: base });
+        self.faces.push(HFace {
+            half_edge: base,
+            material: 0,
+        });
+        face_id
+    }
+
+    /// Add a polygon face over the given vertex loop. Returns the new face ID.
+    ///
+    /// Like `add_face`, twins are left unlinked until `rebuild_twins()`.
+    pub fn add_polygon(&mut self, verts: &[VertexId]) -> FaceId {
+        let face_id = self.faces.len() as FaceId;
+        let base = self.half_edges.len() as HalfEdgeId;
+        let n = verts.len() as u32;
+
+        for i in 0..n {
+            self.half_edges.push(HalfEdge {
+                twin: INVALID,
+                next: base + (i + 1) % n,
+                prev: base + (i + n - 1) % n,
+                vertex: verts[i as usize],
+                face: face_id,
+            });
+
+            let he_id = base + i;
+            if self.vertices[verts[i as usize] as usize].half_edge == INVALID {
+                self.vertices[verts[i as usize] as usize].half_edge = he_id;
+            }
+        }
+
+        self.faces.push(HFace {
+            half_edge: base,
+            material: 0,
+        });
         face_id
     }
 
@@ -536,7 +575,7 @@ impl HalfEdgeMesh {
             if current == start_he || current == INVALID {
                 break;
             }
-            if result.len() > 64 {
+            if result.len() > self.half_edges.len() {
                 break; // safety
             }
         }
@@ -619,7 +658,7 @@ impl HalfEdgeMesh {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::modeling::edit_mesh::EditMesh;
+    use crate::modeling::edit_mesh::{EditMesh, VertexAttributes};
 
     fn make_single_triangle() -> EditMesh {
         EditMesh {
@@ -631,6 +670,9 @@ mod tests {
             normals: vec![Vec3::Z; 3],
             uvs: vec![Vec2::ZERO; 3],
             triangles: vec![[0, 1, 2]],
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
         }
     }
 
@@ -649,6 +691,9 @@ mod tests {
             normals: vec![Vec3::Z; 4],
             uvs: vec![Vec2::ZERO; 4],
             triangles: vec![[0, 1, 2], [1, 3, 2]],
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
         }
     }
 
@@ -691,6 +736,9 @@ mod tests {
             normals,
             uvs,
             triangles,
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
         }
     }
 
@@ -725,6 +773,30 @@ mod tests {
         assert_eq!(back.triangles.len(), 12);
     }
 
+    #[test]
+    fn round_trip_keeps_face_materials() {
+        let mut mesh = make_cube();
+        // Top face (both triangles) into slot 2
+        mesh.assign_material(&[8, 9].into_iter().collect(), 2);
+        let back = HalfEdgeMesh::from_edit_mesh(&mesh).to_edit_mesh();
+
+        assert_eq!(back.triangle_materials(), mesh.triangle_materials());
+        let slots: Vec<u32> = back.split_by_material().iter().map(|(slot, _)| *slot).collect();
+        assert_eq!(slots, vec![0, 2]);
+    }
+
+    #[test]
+    fn round_trip_keeps_vertex_colors() {
+        let mut mesh = make_cube();
+        mesh.attributes.colors = (0..mesh.positions.len())
+            .map(|i| Vec4::new(i as f32 / 8.0, 0.0, 0.0, 1.0))
+            .collect();
+        let back = HalfEdgeMesh::from_edit_mesh(&mesh).to_edit_mesh();
+
+        assert_eq!(back.attributes.colors, mesh.attributes.colors);
+        assert!(back.attributes.uvs2.is_empty());
+    }
+
     #[test]
     fn twin_linkage() {
         let mesh = make_two_triangles();
@@ -788,4 +860,23 @@ mod tests {
             assert!(!faces.is_empty(), "vertex {} has no adjacent faces", vi);
         }
     }
+
+    #[test]
+    fn quad_faces_round_trip() {
+        let mut mesh = make_cube();
+        mesh.detect_quads();
+        assert_eq!(mesh.polygon_count(), 6);
+
+        let he = HalfEdgeMesh::from_edit_mesh(&mesh);
+        assert_eq!(he.faces.len(), 6);
+        for fi in 0..he.faces.len() {
+            assert_eq!(he.face_vertices(fi as FaceId).len(), 4);
+        }
+        // Quad diagonals are not edges: a cube has 12
+        assert_eq!(he.unique_edges().len(), 12);
+
+        let back = he.to_edit_mesh();
+        assert_eq!(back.faces.len(), 6);
+        assert_eq!(back.triangles.len(), 12);
+    }
 }
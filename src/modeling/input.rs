@@ -5,7 +5,7 @@ use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy_egui::EguiContexts;
 
-use crate::commands::TakeSnapshotCommand;
+use crate::commands::RecordEntitiesCommand;
 use crate::editor::{EditorCamera, EditorMode, EditorState};
 use crate::scene::SceneEntity;
 use crate::selection::Selected;
@@ -23,10 +23,13 @@ use super::fill_hole::fill_holes;
 use super::half_edge::HalfEdgeMesh;
 use super::inset::inset_faces;
 use super::marker::EditMeshMarker;
+use super::material_slots::{split_for_display, sync_material_slot_children};
 use super::mirror::mirror_mesh;
+use super::paint::{paint_vertices, PaintBrush};
 use super::plane_cut::plane_cut;
 use super::push_pull::push_pull_faces;
 use super::remesh::remesh;
+use super::sculpt::{sculpt_dab, GrabStroke, SculptBrush, SculptDab};
 use super::selection::{
     expand_to_face_groups, freeform_select, pick_edge, pick_face, pick_vertex,
     surface_group_select, uv_grid_select, world_grid_select, world_to_local_ray,
@@ -41,7 +44,7 @@ use super::uv_project::project_uvs_faces;
 use super::uv_seam::toggle_seam_he;
 use super::uv_unwrap::unwrap_uvs;
 use super::weld::weld_vertices;
-use super::{ElementSelection, GridType, MeshModelState, ModelOperation, SelectionMode};
+use super::{ElementSelection, GridType, MeshModelState, ModelOperation, ModelTool, SelectionMode};
 
 /// Handle keyboard input in Model mode.
 pub fn handle_model_input(
@@ -63,6 +66,13 @@ pub fn handle_model_input(
 
     // Escape: cancel operation or exit mode
     if keyboard.just_pressed(KeyCode::Escape) {
+        if model_state.model_tool != ModelTool::Edit {
+            model_state.model_tool = ModelTool::Edit;
+            model_state.brush_stroke_active = false;
+            model_state.grab_stroke = None;
+            info!("Tool: Edit");
+            return;
+        }
         if model_state.drawing_freeform {
             model_state.drawing_freeform = false;
             model_state.freeform_points.clear();
@@ -81,6 +91,35 @@ pub fn handle_model_input(
 
     let ctrl = keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
 
+    // C toggles the vertex paint brush, K the sculpt brushes
+    let tool_key = if ctrl {
+        None
+    } else if keyboard.just_pressed(KeyCode::KeyC) {
+        Some(ModelTool::Paint)
+    } else if keyboard.just_pressed(KeyCode::KeyK) {
+        Some(ModelTool::Sculpt)
+    } else {
+        None
+    };
+    if let Some(tool) = tool_key {
+        model_state.model_tool = if model_state.model_tool == tool {
+            ModelTool::Edit
+        } else {
+            tool
+        };
+        model_state.pending_operation = ModelOperation::Select;
+        model_state.brush_stroke_active = false;
+        model_state.grab_stroke = None;
+        info!("Tool: {}", model_state.model_tool.display_name());
+        return;
+    }
+
+    // Brush tools only respond to brush hotkeys
+    if model_state.model_tool != ModelTool::Edit {
+        handle_brush_keys(&keyboard, &mut model_state);
+        return;
+    }
+
     // Selection mode switching (A/S/D) — only without Ctrl
     if !ctrl && keyboard.just_pressed(KeyCode::KeyA) {
         model_state.selection_mode = SelectionMode::Vertex;
@@ -326,6 +365,53 @@ pub fn handle_model_input(
     }
 }
 
+/// Brush radius (`[` / `]`) and strength (Shift+`[` / Shift+`]`) hotkeys,
+/// plus brush type (1-6) and symmetry (M) for the Sculpt tool.
+fn handle_brush_keys(keyboard: &ButtonInput<KeyCode>, model_state: &mut MeshModelState) {
+    if model_state.model_tool == ModelTool::Sculpt {
+        const BRUSH_KEYS: [KeyCode; 6] = [
+            KeyCode::Digit1,
+            KeyCode::Digit2,
+            KeyCode::Digit3,
+            KeyCode::Digit4,
+            KeyCode::Digit5,
+            KeyCode::Digit6,
+        ];
+        if let Some(i) = BRUSH_KEYS.iter().position(|&key| keyboard.just_pressed(key)) {
+            model_state.sculpt_brush = SculptBrush::ALL[i];
+            info!("Sculpt brush: {}", model_state.sculpt_brush.display_name());
+        }
+        if keyboard.just_pressed(KeyCode::KeyM) {
+            model_state.sculpt_symmetry = !model_state.sculpt_symmetry;
+            info!(
+                "Sculpt symmetry: {}",
+                if model_state.sculpt_symmetry {
+                    model_state.mirror_axis.display_name()
+                } else {
+                    "OFF"
+                }
+            );
+        }
+    }
+
+    let smaller = keyboard.just_pressed(KeyCode::BracketLeft);
+    let larger = keyboard.just_pressed(KeyCode::BracketRight);
+    if !smaller && !larger {
+        return;
+    }
+
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+    if shift {
+        let step = if larger { 0.1 } else { -0.1 };
+        model_state.brush_strength = (model_state.brush_strength + step).clamp(0.05, 1.0);
+        info!("Brush strength: {:.2}", model_state.brush_strength);
+    } else {
+        let factor = if larger { 1.25 } else { 0.8 };
+        model_state.brush_radius = (model_state.brush_radius * factor).clamp(0.01, 10.0);
+        info!("Brush radius: {:.2}", model_state.brush_radius);
+    }
+}
+
 /// Handle mouse click for element selection (vertex/edge/face) and freeform point placement.
 pub fn handle_model_click(
     mouse_button: Res<ButtonInput<MouseButton>>,
@@ -342,8 +428,10 @@ pub fn handle_model_click(
         return;
     }
 
-    // Don't change selection when an operation is active — clicks are for the operation
-    if model_state.pending_operation != ModelOperation::Select {
+    // Don't change selection when an operation or brush is active — clicks are for it
+    if model_state.pending_operation != ModelOperation::Select
+        || model_state.model_tool != ModelTool::Edit
+    {
         return;
     }
 
@@ -514,7 +602,8 @@ pub fn handle_model_click(
                                 camera,
                                 camera_transform,
                             );
-                            let selected = expand_to_face_groups(&edit_mesh, &raw);
+                            let selected = edit_mesh
+                                .expand_to_polygons(&expand_to_face_groups(&edit_mesh, &raw));
                             if shift {
                                 model_state.selected_faces.extend(selected);
                             } else {
@@ -541,7 +630,7 @@ pub fn handle_model_click(
             // Grid-based face selection
             let new_selection = match model_state.grid_type {
                 GridType::WorldSpace => {
-                    // WorldSpace grid: select triangles in the clicked grid cell.
+                    // WorldSpace grid: select polygons in the clicked grid cell.
                     // No face-group expansion — the grid IS the selection unit,
                     // letting you pick sub-regions of large faces for operations.
                     let world_point = entity_transform.transform_point(hit.point);
@@ -559,11 +648,13 @@ pub fn handle_model_click(
                     expand_to_face_groups(&edit_mesh, &raw)
                 }
                 GridType::UVSpace => {
-                    // UV grid: select triangles in the UV cell, no face-group expansion
+                    // UV grid: select polygons in the UV cell, no face-group expansion
                     uv_grid_select(&edit_mesh, hit.face, model_state.uv_grid_size)
                 }
                 GridType::Freeform => unreachable!(),
             };
+            // Always select whole polygons, never half a quad
+            let new_selection = edit_mesh.expand_to_polygons(&new_selection);
 
             if shift {
                 model_state.selected_faces.extend(new_selection);
@@ -611,7 +702,11 @@ pub fn handle_model_confirm(
     // Face-based operations require face selection; edge/vertex ops check their own selection
     let face_ops = matches!(
         model_state.pending_operation,
-        ModelOperation::Extrude | ModelOperation::Cut | ModelOperation::Inset | ModelOperation::PushPull
+        ModelOperation::Extrude
+            | ModelOperation::Cut
+            | ModelOperation::Inset
+            | ModelOperation::PushPull
+            | ModelOperation::AssignMaterial
     );
     if face_ops && model_state.selected_faces.is_empty() {
         return;
@@ -623,8 +718,10 @@ pub fn handle_model_confirm(
                 return;
             }
 
-            // Take snapshot for undo
-            commands.queue(TakeSnapshotCommand {
+            // Record the edited entity for undo
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Extrude faces".to_string(),
             });
 
@@ -648,11 +745,6 @@ pub fn handle_model_confirm(
             info!("Extrusion applied");
         }
         ModelOperation::Cut => {
-            // Take snapshot for undo
-            commands.queue(TakeSnapshotCommand {
-                description: "Cut mesh".to_string(),
-            });
-
             let (remaining, cut_out) = cut_faces(edit_mesh, &model_state.selected_faces);
 
             if cut_out.triangles.is_empty() || remaining.triangles.is_empty() {
@@ -660,6 +752,14 @@ pub fn handle_model_confirm(
                 return;
             }
 
+            // Record the edited entity and the one about to be spawned (undo despawns it)
+            let cut_entity = commands.spawn_empty().id();
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target, cut_entity],
+                recursive: false,
+                description: "Cut mesh".to_string(),
+            });
+
             // Update original entity with remaining mesh
             apply_mesh_to_entity(&remaining, target, &mut commands, &mut meshes);
 
@@ -676,7 +776,7 @@ pub fn handle_model_confirm(
             let cut_collider = cut_marker.to_collider();
             let cut_handle = meshes.add(cut_bevy);
 
-            commands.spawn((
+            commands.entity(cut_entity).insert((
                 SceneEntity,
                 Name::new(format!("{} (cut)", entity_name)),
                 EditMeshMarker::from_edit_mesh(&cut_out),
@@ -697,7 +797,9 @@ pub fn handle_model_confirm(
                 return;
             }
 
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Inset faces".to_string(),
             });
 
@@ -738,7 +840,9 @@ pub fn handle_model_confirm(
                 return;
             };
 
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Bevel edges".to_string(),
             });
 
@@ -759,7 +863,9 @@ pub fn handle_model_confirm(
                 return;
             }
 
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Push/pull faces".to_string(),
             });
 
@@ -797,7 +903,9 @@ pub fn handle_model_confirm(
                 return;
             };
 
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Bridge edge loops".to_string(),
             });
 
@@ -826,7 +934,9 @@ pub fn handle_model_confirm(
                 }
             };
 
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Weld vertices".to_string(),
             });
 
@@ -860,7 +970,9 @@ pub fn handle_model_confirm(
                 return;
             };
 
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Insert edge loop".to_string(),
             });
 
@@ -877,7 +989,9 @@ pub fn handle_model_confirm(
             info!("Edge loop inserted");
         }
         ModelOperation::Mirror => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Mirror mesh".to_string(),
             });
 
@@ -891,7 +1005,9 @@ pub fn handle_model_confirm(
             info!("Mirror applied ({:?})", model_state.mirror_axis);
         }
         ModelOperation::Smooth => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Smooth mesh".to_string(),
             });
 
@@ -909,7 +1025,9 @@ pub fn handle_model_confirm(
             info!("Smooth applied");
         }
         ModelOperation::Subdivide => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Subdivide mesh".to_string(),
             });
 
@@ -923,7 +1041,9 @@ pub fn handle_model_confirm(
             info!("Subdivide applied");
         }
         ModelOperation::FillHoles => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Fill holes".to_string(),
             });
 
@@ -937,10 +1057,6 @@ pub fn handle_model_confirm(
             info!("Holes filled");
         }
         ModelOperation::PlaneCut => {
-            commands.queue(TakeSnapshotCommand {
-                description: "Plane cut".to_string(),
-            });
-
             // Cut along the chosen axis through the mesh center
             let center = edit_mesh
                 .positions
@@ -959,6 +1075,13 @@ pub fn handle_model_confirm(
             if front.triangles.is_empty() || back.triangles.is_empty() {
                 info!("Plane cut produced empty geometry, skipping");
             } else {
+                let back_entity = commands.spawn_empty().id();
+                commands.queue(RecordEntitiesCommand {
+                    entities: vec![target, back_entity],
+                    recursive: false,
+                    description: "Plane cut".to_string(),
+                });
+
                 // Update original entity with front half
                 apply_mesh_to_entity(&front, target, &mut commands, &mut meshes);
 
@@ -968,7 +1091,7 @@ pub fn handle_model_confirm(
                 let back_collider = back_marker.to_collider();
                 let back_handle = meshes.add(back_bevy);
 
-                commands.spawn((
+                commands.entity(back_entity).insert((
                     SceneEntity,
                     Name::new("Mesh (cut)"),
                     back_marker,
@@ -985,7 +1108,9 @@ pub fn handle_model_confirm(
             model_state.pending_operation = ModelOperation::Select;
         }
         ModelOperation::Simplify => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Simplify mesh".to_string(),
             });
 
@@ -999,7 +1124,9 @@ pub fn handle_model_confirm(
             info!("Simplify applied (ratio: {})", model_state.simplify_ratio);
         }
         ModelOperation::Remesh => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Remesh".to_string(),
             });
 
@@ -1017,7 +1144,9 @@ pub fn handle_model_confirm(
             info!("Boolean operations require a second mesh entity (not yet implemented)");
         }
         ModelOperation::UvProject => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "UV Project".to_string(),
             });
 
@@ -1043,7 +1172,9 @@ pub fn handle_model_confirm(
             info!("UV projection applied ({:?})", model_state.uv_projection);
         }
         ModelOperation::UvUnwrap => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "UV Unwrap".to_string(),
             });
 
@@ -1057,7 +1188,9 @@ pub fn handle_model_confirm(
             info!("UV unwrap applied");
         }
         ModelOperation::AutoSmooth => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Auto smooth normals".to_string(),
             });
 
@@ -1075,7 +1208,9 @@ pub fn handle_model_confirm(
             info!("Auto smooth applied ({}°)", model_state.auto_smooth_angle);
         }
         ModelOperation::FlatNormals => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Flat normals".to_string(),
             });
 
@@ -1089,7 +1224,9 @@ pub fn handle_model_confirm(
             info!("Flat normals applied");
         }
         ModelOperation::CatmullClark => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Catmull-Clark subdivide".to_string(),
             });
 
@@ -1103,7 +1240,9 @@ pub fn handle_model_confirm(
             info!("Catmull-Clark subdivide applied");
         }
         ModelOperation::SnapToGrid => {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Snap to grid".to_string(),
             });
 
@@ -1122,6 +1261,24 @@ pub fn handle_model_confirm(
             model_state.pending_operation = ModelOperation::Select;
             info!("Snap to grid applied");
         }
+        ModelOperation::AssignMaterial => {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
+                description: "Assign material".to_string(),
+            });
+
+            let mut new_mesh = edit_mesh.clone();
+            new_mesh.assign_material(&model_state.selected_faces, model_state.material_slot);
+            apply_mesh_to_entity(&new_mesh, target, &mut commands, &mut meshes);
+
+            // Topology is unchanged, so the face selection stays valid
+            let he_mesh = HalfEdgeMesh::from_edit_mesh(&new_mesh);
+            model_state.half_edge_mesh = Some(he_mesh);
+            model_state.edit_mesh = Some(new_mesh);
+            model_state.pending_operation = ModelOperation::Select;
+            info!("Assigned material slot {}", model_state.material_slot);
+        }
         ModelOperation::Select => {}
     }
 }
@@ -1156,7 +1313,9 @@ pub fn handle_model_delete(
                 return;
             };
 
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Delete faces".to_string(),
             });
 
@@ -1178,7 +1337,9 @@ pub fn handle_model_delete(
                 return;
             };
 
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Dissolve edges".to_string(),
             });
 
@@ -1200,7 +1361,9 @@ pub fn handle_model_delete(
                 return;
             };
 
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordEntitiesCommand {
+                entities: vec![target],
+                recursive: false,
                 description: "Dissolve vertices".to_string(),
             });
 
@@ -1320,18 +1483,268 @@ pub fn handle_extrude_drag(
 }
 
 /// Apply an EditMesh to an entity: update marker, Mesh3d, and Collider.
+/// Paint vertex colors under the cursor while the left mouse button is held
+/// with the Paint tool. A stroke starts when the press hits the mesh and
+/// takes a single undo snapshot.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_model_paint(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<EditorCamera>>,
+    selected_query: Query<&GlobalTransform, With<Selected>>,
+    mut model_state: ResMut<MeshModelState>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    editor_state: Res<EditorState>,
+    mut contexts: EguiContexts,
+) {
+    if model_state.model_tool != ModelTool::Paint {
+        return;
+    }
+    if !mouse_button.pressed(MouseButton::Left) || mouse_button.pressed(MouseButton::Right) {
+        if model_state.brush_stroke_active {
+            model_state.brush_stroke_active = false;
+        }
+        return;
+    }
+
+    // New strokes only start from a fresh press outside the UI
+    let starting = !model_state.brush_stroke_active;
+    if starting
+        && (!mouse_button.just_pressed(MouseButton::Left)
+            || !should_process_input(&editor_state, &mut contexts))
+    {
+        return;
+    }
+
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position) else {
+        return;
+    };
+    let Some(target) = model_state.target_entity else {
+        return;
+    };
+    let Ok(entity_transform) = selected_query.get(target) else {
+        return;
+    };
+    let Some(edit_mesh) = model_state.edit_mesh.clone() else {
+        return;
+    };
+
+    let (local_origin, local_dir) =
+        world_to_local_ray(entity_transform, ray.origin, *ray.direction);
+    let Some(hit) = pick_face(&edit_mesh, local_origin, local_dir, model_state.xray_selection)
+    else {
+        return;
+    };
+
+    if starting {
+        commands.queue(RecordEntitiesCommand {
+            entities: vec![target],
+            recursive: false,
+            description: "Paint vertex colors".to_string(),
+        });
+        model_state.brush_stroke_active = true;
+    }
+
+    let brush = PaintBrush {
+        color: model_state.paint_color,
+        radius: model_state.brush_radius,
+        strength: model_state.brush_strength,
+        falloff: model_state.soft_falloff,
+        channel: model_state.paint_channel,
+    };
+    let new_mesh = paint_vertices(&edit_mesh, hit.face, hit.point, &brush);
+
+    apply_mesh_to_entity(&new_mesh, target, &mut commands, &mut meshes);
+
+    // Painting keeps topology, so the half-edge mesh only needs the new colors
+    if let Some(he_mesh) = model_state.half_edge_mesh.as_mut() {
+        he_mesh.attributes = new_mesh.attributes.clone();
+    }
+    model_state.edit_mesh = Some(new_mesh);
+}
+
+/// Sculpt the mesh under the cursor while the left mouse button is held with
+/// the Sculpt tool; Ctrl inverts the brush. A stroke starts when the press
+/// hits the mesh and records the target for a single undo step.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_model_sculpt(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<EditorCamera>>,
+    selected_query: Query<&GlobalTransform, With<Selected>>,
+    mut model_state: ResMut<MeshModelState>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    editor_state: Res<EditorState>,
+    mut contexts: EguiContexts,
+) {
+    let released =
+        !mouse_button.pressed(MouseButton::Left) || mouse_button.pressed(MouseButton::Right);
+
+    // Also runs when a tool switch cut the stroke short
+    if model_state.sculpt_collider_stale
+        && (released || model_state.model_tool != ModelTool::Sculpt)
+    {
+        model_state.sculpt_collider_stale = false;
+        if let (Some(target), Some(edit_mesh)) =
+            (model_state.target_entity, model_state.edit_mesh.as_ref())
+        {
+            let collider = EditMeshMarker::from_edit_mesh(edit_mesh).to_collider();
+            if let Ok(mut entity_commands) = commands.get_entity(target) {
+                entity_commands.insert(collider);
+            }
+        }
+    }
+
+    if model_state.model_tool != ModelTool::Sculpt {
+        return;
+    }
+    if released {
+        if model_state.brush_stroke_active {
+            model_state.brush_stroke_active = false;
+            model_state.grab_stroke = None;
+            // Dabs only move vertices; refresh the half-edge mesh once per stroke
+            let he_mesh = model_state.edit_mesh.as_ref().map(HalfEdgeMesh::from_edit_mesh);
+            model_state.half_edge_mesh = he_mesh;
+        }
+        return;
+    }
+
+    // New strokes only start from a fresh press outside the UI
+    let starting = !model_state.brush_stroke_active;
+    if starting
+        && (!mouse_button.just_pressed(MouseButton::Left)
+            || !should_process_input(&editor_state, &mut contexts))
+    {
+        return;
+    }
+
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position) else {
+        return;
+    };
+    let Some(target) = model_state.target_entity else {
+        return;
+    };
+    let Ok(entity_transform) = selected_query.get(target) else {
+        return;
+    };
+
+    let (local_origin, local_dir) =
+        world_to_local_ray(entity_transform, ray.origin, *ray.direction);
+
+    // Grab: drag the captured vertices along the plane facing the camera
+    if let Some(grab) = &model_state.grab_stroke {
+        let Some(point) = grab.drag_point(local_origin, local_dir) else {
+            return;
+        };
+        let new_mesh = grab.apply(point);
+        update_entity_mesh(&new_mesh, target, &mut commands, &mut meshes, false);
+        model_state.edit_mesh = Some(new_mesh);
+        model_state.sculpt_collider_stale = true;
+        return;
+    }
+
+    let Some(edit_mesh) = model_state.edit_mesh.clone() else {
+        return;
+    };
+    let Some(hit) = pick_face(&edit_mesh, local_origin, local_dir, model_state.xray_selection)
+    else {
+        return;
+    };
+
+    if starting {
+        commands.queue(RecordEntitiesCommand {
+            entities: vec![target],
+            recursive: false,
+            description: format!("Sculpt ({})", model_state.sculpt_brush.display_name()),
+        });
+        model_state.brush_stroke_active = true;
+    }
+
+    let symmetry = model_state
+        .sculpt_symmetry
+        .then_some(model_state.mirror_axis);
+
+    if model_state.sculpt_brush == SculptBrush::Grab {
+        model_state.grab_stroke = Some(GrabStroke::begin(
+            &edit_mesh,
+            hit.point,
+            model_state.brush_radius,
+            model_state.soft_falloff,
+            local_dir,
+            symmetry,
+        ));
+        return;
+    }
+
+    let ctrl = keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
+    let dab = SculptDab {
+        brush: model_state.sculpt_brush,
+        center: hit.point,
+        radius: model_state.brush_radius,
+        strength: model_state.brush_strength,
+        falloff: model_state.soft_falloff,
+        invert: ctrl,
+    };
+    let new_mesh = sculpt_dab(&edit_mesh, &dab, symmetry);
+
+    update_entity_mesh(&new_mesh, target, &mut commands, &mut meshes, false);
+    model_state.edit_mesh = Some(new_mesh);
+    model_state.sculpt_collider_stale = true;
+}
+
 fn apply_mesh_to_entity(
     new_mesh: &EditMesh,
     entity: Entity,
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
+) {
+    update_entity_mesh(new_mesh, entity, commands, meshes, true);
+}
+
+/// Replace the entity's edit mesh and display mesh. Sculpt dabs pass
+/// `rebuild_collider: false`, since a trimesh collider per dab is too slow.
+fn update_entity_mesh(
+    new_mesh: &EditMesh,
+    entity: Entity,
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    rebuild_collider: bool,
 ) {
     let marker = EditMeshMarker::from_edit_mesh(new_mesh);
-    let bevy_mesh = new_mesh.to_bevy_mesh();
-    let collider = marker.to_collider();
+    let (base, slot_meshes) = split_for_display(new_mesh);
+    let bevy_mesh = base.to_bevy_mesh();
+    let collider = rebuild_collider.then(|| marker.to_collider());
     let mesh_handle = meshes.add(bevy_mesh);
 
     if let Ok(mut entity_commands) = commands.get_entity(entity) {
-        entity_commands.insert((marker, Mesh3d(mesh_handle), collider));
+        entity_commands.insert((marker, Mesh3d(mesh_handle)));
+        if let Some(collider) = collider {
+            entity_commands.insert(collider);
+        }
     }
+    // Faces in other material slots render as child meshes
+    commands.queue(move |world: &mut World| {
+        sync_material_slot_children(world, entity, slot_meshes);
+    });
 }
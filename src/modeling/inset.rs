@@ -11,10 +11,11 @@ use super::edit_mesh::{EditMesh, FaceIndex};
 
 /// Inset each selected face individually by the given fraction (0.0 = no change, 1.0 = collapse to centroid).
 ///
-/// For each selected triangle:
-/// 1. Create 3 new "inner" vertices lerped toward the face centroid
-/// 2. Replace the original triangle with the inner triangle
-/// 3. Create 3 connecting quads (6 triangles) bridging inner to outer edges
+/// Works on whole polygons (a selected triangle selects its polygon). For
+/// each selected polygon with N corners:
+/// 1. Create N new "inner" vertices lerped toward the face centroid
+/// 2. Replace the original polygon with the inner polygon
+/// 3. Create N connecting quads bridging inner to outer edges
 ///
 /// Returns a new `EditMesh` with the inset applied.
 pub fn inset_faces(
@@ -28,79 +29,66 @@ pub fn inset_faces(
 
     let frac = inset_fraction.clamp(0.001, 0.999);
 
+    let polygons = mesh.polygons();
+    let selected_polygons = mesh.selected_polygons(selected);
+
     let mut new_positions = mesh.positions.clone();
     let mut new_normals = mesh.normals.clone();
     let mut new_uvs = mesh.uvs.clone();
-    let mut new_triangles = Vec::with_capacity(mesh.triangles.len() + selected.len() * 6);
-
-    // Copy non-selected triangles unchanged
-    for (fi, tri) in mesh.triangles.iter().enumerate() {
-        if !selected.contains(&fi) {
-            new_triangles.push(*tri);
+    let mut new_attributes = mesh.attributes.clone();
+    let materials = mesh.polygon_materials();
+    let mut new_faces = Vec::with_capacity(polygons.len() + selected_polygons.len() * 4);
+    let mut new_materials = Vec::with_capacity(new_faces.capacity());
+
+    // Copy non-selected polygons unchanged
+    for (pi, face) in polygons.iter().enumerate() {
+        if !selected_polygons.contains(&pi) {
+            new_faces.push(face.clone());
+            new_materials.push(materials[pi]);
         }
     }
 
-    // Process each selected face
-    for &fi in selected {
-        if fi >= mesh.triangles.len() {
+    // Process each selected polygon
+    for (pi, face) in polygons.iter().enumerate() {
+        if !selected_polygons.contains(&pi) {
             continue;
         }
 
-        let [a, b, c] = mesh.triangles[fi];
-        let pa = mesh.positions[a as usize];
-        let pb = mesh.positions[b as usize];
-        let pc = mesh.positions[c as usize];
-        let center = (pa + pb + pc) / 3.0;
-
-        let na = mesh.normals[a as usize];
-        let nb = mesh.normals[b as usize];
-        let nc = mesh.normals[c as usize];
-        let ncenter = ((na + nb + nc) / 3.0).normalize_or_zero();
-
-        let ua = mesh.uvs[a as usize];
-        let ub = mesh.uvs[b as usize];
-        let uc = mesh.uvs[c as usize];
-        let uv_center = (ua + ub + uc) / 3.0;
+        let count = face.len() as f32;
+        let center = face.iter().map(|&v| mesh.positions[v as usize]).sum::<Vec3>() / count;
+        let ncenter = (face.iter().map(|&v| mesh.normals[v as usize]).sum::<Vec3>() / count)
+            .normalize_or_zero();
+        let uv_center = face.iter().map(|&v| mesh.uvs[v as usize]).sum::<Vec2>() / count;
 
         // Create inner vertices (lerped toward centroid)
-        let ia = new_positions.len() as u32;
-        new_positions.push(pa.lerp(center, frac));
-        new_normals.push(na.lerp(ncenter, frac).normalize_or_zero());
-        new_uvs.push(ua.lerp(uv_center, frac));
-
-        let ib = new_positions.len() as u32;
-        new_positions.push(pb.lerp(center, frac));
-        new_normals.push(nb.lerp(ncenter, frac).normalize_or_zero());
-        new_uvs.push(ub.lerp(uv_center, frac));
-
-        let ic = new_positions.len() as u32;
-        new_positions.push(pc.lerp(center, frac));
-        new_normals.push(nc.lerp(ncenter, frac).normalize_or_zero());
-        new_uvs.push(uc.lerp(uv_center, frac));
-
-        // Inner triangle (same winding as original)
-        new_triangles.push([ia, ib, ic]);
-
-        // Bridge quads connecting outer edge to inner edge
-        // Edge a→b: outer (a, b), inner (ia, ib)
-        new_triangles.push([a, b, ib]);
-        new_triangles.push([a, ib, ia]);
-
-        // Edge b→c: outer (b, c), inner (ib, ic)
-        new_triangles.push([b, c, ic]);
-        new_triangles.push([b, ic, ib]);
-
-        // Edge c→a: outer (c, a), inner (ic, ia)
-        new_triangles.push([c, a, ia]);
-        new_triangles.push([c, ia, ic]);
+        let inner: Vec<u32> = face
+            .iter()
+            .map(|&v| {
+                let idx = new_positions.len() as u32;
+                new_positions.push(mesh.positions[v as usize].lerp(center, frac));
+                new_normals.push(mesh.normals[v as usize].lerp(ncenter, frac).normalize_or_zero());
+                new_uvs.push(mesh.uvs[v as usize].lerp(uv_center, frac));
+                new_attributes.push_copy(v);
+                idx
+            })
+            .collect();
+
+        // Inner polygon (same winding as original)
+        new_faces.push(inner.clone());
+        new_materials.push(materials[pi]);
+
+        // Bridge quads connecting each outer edge to its inner edge
+        let n = face.len();
+        for i in 0..n {
+            let j = (i + 1) % n;
+            new_faces.push(vec![face[i], face[j], inner[j], inner[i]]);
+            new_materials.push(materials[pi]);
+        }
     }
 
-    let mut result = EditMesh {
-        positions: new_positions,
-        normals: new_normals,
-        uvs: new_uvs,
-        triangles: new_triangles,
-    };
+    let mut result =
+        EditMesh::from_polygons(new_positions, new_normals, new_uvs, new_faces, new_materials);
+    result.attributes = new_attributes;
     result.recompute_normals();
     result
 }
@@ -8,19 +8,36 @@ use avian3d::prelude::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use super::edit_mesh::EditMesh;
+use super::edit_mesh::{EditMesh, VertexAttributes};
 
 /// Serializable component storing the full vertex data of an edited mesh.
 ///
 /// When present, this governs the entity's mesh — `regenerate_runtime_components`
 /// rebuilds `Mesh3d` and `Collider::trimesh()` from this data.
-#[derive(Component, Serialize, Deserialize, Clone, Reflect)]
-#[reflect(Component)]
+#[derive(Component, Serialize, Deserialize, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct EditMeshMarker {
     pub positions: Vec<[f32; 3]>,
     pub normals: Vec<[f32; 3]>,
     pub uvs: Vec<[f32; 2]>,
+    /// Triangle list (the triangulation of `faces`, when present).
     pub indices: Vec<u32>,
+    /// Polygon faces as vertex loops. Empty for plain triangle meshes.
+    #[serde(default)]
+    #[reflect(default)]
+    pub faces: Vec<Vec<u32>>,
+    /// Material slot per polygon face. Empty when every face uses slot 0.
+    #[serde(default)]
+    #[reflect(default)]
+    pub face_materials: Vec<u32>,
+    /// Per-vertex RGBA colors. Empty when the mesh has no vertex colors.
+    #[serde(default)]
+    #[reflect(default)]
+    pub colors: Vec<[f32; 4]>,
+    /// Second UV channel (lightmaps). Empty when absent.
+    #[serde(default)]
+    #[reflect(default)]
+    pub uvs2: Vec<[f32; 2]>,
 }
 
 impl EditMeshMarker {
@@ -31,12 +48,24 @@ impl EditMeshMarker {
             normals: mesh.normals.iter().map(|n| [n.x, n.y, n.z]).collect(),
             uvs: mesh.uvs.iter().map(|u| [u.x, u.y]).collect(),
             indices: mesh.triangles.iter().flat_map(|t| t.iter().copied()).collect(),
+            faces: if mesh.has_polygons() {
+                mesh.faces.clone()
+            } else {
+                Vec::new()
+            },
+            face_materials: if mesh.has_face_materials() {
+                mesh.face_materials.clone()
+            } else {
+                Vec::new()
+            },
+            colors: mesh.attributes.colors.iter().map(|c| c.to_array()).collect(),
+            uvs2: mesh.attributes.uvs2.iter().map(|u| [u.x, u.y]).collect(),
         }
     }
 
     /// Convert back to an `EditMesh`.
     pub fn to_edit_mesh(&self) -> EditMesh {
-        EditMesh {
+        let mut mesh = EditMesh {
             positions: self.positions.iter().map(|p| Vec3::from(*p)).collect(),
             normals: self.normals.iter().map(|n| Vec3::from(*n)).collect(),
             uvs: self.uvs.iter().map(|u| Vec2::from(*u)).collect(),
@@ -45,7 +74,22 @@ impl EditMeshMarker {
                 .chunks(3)
                 .map(|c| [c[0], c[1], c[2]])
                 .collect(),
+            faces: self.faces.clone(),
+            face_materials: self.face_materials.clone(),
+            attributes: VertexAttributes {
+                colors: self.colors.iter().map(|c| Vec4::from(*c)).collect(),
+                uvs2: self.uvs2.iter().map(|u| Vec2::from(*u)).collect(),
+            }
+            .validated(self.positions.len()),
+        };
+        // Stale polygon data (e.g. hand-edited indices) falls back to triangles
+        if !mesh.has_polygons() {
+            mesh.faces.clear();
         }
+        if !mesh.has_face_materials() {
+            mesh.face_materials.clear();
+        }
+        mesh
     }
 
     /// Build a Bevy `Mesh` from this marker's data.
@@ -68,21 +112,75 @@ impl EditMeshMarker {
 /// Regenerate `Mesh3d` and `Collider` for entities with `EditMeshMarker` that
 /// are missing their runtime mesh (e.g. after scene load).
 pub fn regenerate_edit_meshes(world: &mut World) {
-    let mut to_update: Vec<(Entity, EditMeshMarker)> = Vec::new();
-    {
-        let mut query =
-            world.query_filtered::<(Entity, &EditMeshMarker), Without<Mesh3d>>();
-        for (entity, marker) in query.iter(world) {
-            to_update.push((entity, marker.clone()));
-        }
+    let to_update: Vec<Entity> = {
+        let mut query = world.query_filtered::<Entity, (With<EditMeshMarker>, Without<Mesh3d>)>();
+        query.iter(world).collect()
+    };
+
+    // Goes through the modifier stack, if any, so the displayed mesh matches
+    for entity in to_update {
+        super::modifier::rebuild_evaluated_mesh(world, entity);
     }
+}
 
-    for (entity, marker) in to_update {
-        let mesh = marker.to_bevy_mesh();
-        let collider = marker.to_collider();
-        let mesh_handle = world.resource_mut::<Assets<Mesh>>().add(mesh);
-        if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
-            entity_mut.insert((Mesh3d(mesh_handle), collider));
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::entity::EntityHashMap;
+    use bevy::scene::serde::SceneDeserializer;
+    use serde::de::DeserializeSeed;
+
+    /// An edited mesh as saved before polygon faces were stored
+    const PRE_POLYGON_SCENE: &str = r#"(
+  resources: {},
+  entities: {
+    4294967296: (
+      components: {
+        "bevy_modal_editor::modeling::marker::EditMeshMarker": (
+          positions: [(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)],
+          normals: [(0.0, 0.0, 1.0), (0.0, 0.0, 1.0), (0.0, 0.0, 1.0)],
+          uvs: [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)],
+          indices: [0, 1, 2],
+        ),
+      },
+    ),
+  },
+)"#;
+
+    /// Load a scene through the same reflection path as scene files and undo
+    fn load_marker(scene: &str) -> EditMeshMarker {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<EditMeshMarker>();
+        world.insert_resource(registry.clone());
+
+        let scene = {
+            let registry = registry.read();
+            let mut deserializer = ron::de::Deserializer::from_str(scene).unwrap();
+            SceneDeserializer {
+                type_registry: &registry,
+            }
+            .deserialize(&mut deserializer)
+            .unwrap()
+        };
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        let entity = *entity_map.values().next().unwrap();
+        world.get::<EditMeshMarker>(entity).unwrap().clone()
+    }
+
+    #[test]
+    fn loads_marker_saved_before_polygons() {
+        let marker = load_marker(PRE_POLYGON_SCENE);
+        assert_eq!(marker.positions.len(), 3);
+        assert_eq!(marker.indices, vec![0, 1, 2]);
+        assert!(marker.faces.is_empty());
+        assert!(marker.face_materials.is_empty());
+        assert!(marker.colors.is_empty());
+        assert!(marker.uvs2.is_empty());
+
+        let mesh = marker.to_edit_mesh();
+        assert_eq!(mesh.triangles, vec![[0, 1, 2]]);
     }
 }
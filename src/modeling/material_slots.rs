@@ -0,0 +1,119 @@
+//! Per-face material slots for edited meshes.
+//!
+//! Each polygon of an `EditMesh` carries a slot index (`face_materials`).
+//! Slot 0 renders with the entity's own `MaterialRef`; higher slots take
+//! their material from the entity's `MaterialSlots`. At runtime the mesh is
+//! split per slot: the entity keeps the slot-0 faces and every other slot
+//! becomes a `MaterialSlotMesh` child with its own material.
+
+use bevy::prelude::*;
+use bevy_editor_game::{MaterialLibrary, MaterialRef};
+use serde::{Deserialize, Serialize};
+
+use super::edit_mesh::EditMesh;
+use crate::materials::{apply_material_def_standalone, resolve_material_ref};
+
+/// Extra materials of an edited mesh. `slots[i]` is material slot `i + 1`;
+/// slot 0 is the entity's own `MaterialRef`.
+#[derive(Component, Serialize, Deserialize, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct MaterialSlots {
+    pub slots: Vec<MaterialRef>,
+}
+
+impl MaterialSlots {
+    /// Material for a slot above 0, if one is configured.
+    pub fn get(&self, slot: u32) -> Option<&MaterialRef> {
+        slot.checked_sub(1).and_then(|i| self.slots.get(i as usize))
+    }
+}
+
+/// Runtime child holding the faces of one material slot. Never serialized:
+/// `build_editor_scene` leaves it out of its parent's `Children`.
+#[derive(Component)]
+pub struct MaterialSlotMesh {
+    pub slot: u32,
+}
+
+/// Split a mesh for display: the slot-0 part for the entity itself, plus one
+/// mesh per higher slot. Meshes without slot assignments are returned as-is.
+pub fn split_for_display(mesh: &EditMesh) -> (EditMesh, Vec<(u32, EditMesh)>) {
+    if !mesh.has_face_materials() {
+        return (mesh.clone(), Vec::new());
+    }
+
+    let mut base = None;
+    let mut others = Vec::new();
+    for (slot, part) in mesh.split_by_material() {
+        if slot == 0 {
+            base = Some(part);
+        } else {
+            others.push((slot, part));
+        }
+    }
+    // Every face may have moved out of slot 0
+    let base = base.unwrap_or_else(|| {
+        EditMesh::from_polygons(Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new())
+    });
+    (base, others)
+}
+
+/// Replace the entity's `MaterialSlotMesh` children with one child per slot
+/// mesh, each using the material configured for its slot.
+pub fn sync_material_slot_children(
+    world: &mut World,
+    entity: Entity,
+    slot_meshes: Vec<(u32, EditMesh)>,
+) {
+    // Despawn the previous slot children
+    let old_children: Vec<Entity> = world
+        .get::<Children>(entity)
+        .map(|children| {
+            children
+                .iter()
+                .filter(|&child| world.get::<MaterialSlotMesh>(child).is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+    for child in old_children {
+        world.despawn(child);
+    }
+
+    if slot_meshes.is_empty() || world.get_entity(entity).is_err() {
+        return;
+    }
+
+    let slots = world.get::<MaterialSlots>(entity).cloned().unwrap_or_default();
+    let empty_library = MaterialLibrary::default();
+
+    for (slot, mesh) in slot_meshes {
+        // Only the slot's own definition is cloned out of the library
+        let def = slots.get(slot).and_then(|material_ref| {
+            let library = world
+                .get_resource::<MaterialLibrary>()
+                .unwrap_or(&empty_library);
+            resolve_material_ref(material_ref, library).cloned()
+        });
+        let mesh_handle = world.resource_mut::<Assets<Mesh>>().add(mesh.to_bevy_mesh());
+        let child = world
+            .spawn((
+                MaterialSlotMesh { slot },
+                Mesh3d(mesh_handle),
+                Transform::default(),
+                Visibility::default(),
+                ChildOf(entity),
+            ))
+            .id();
+
+        match def {
+            Some(def) => apply_material_def_standalone(world, child, &def),
+            None => {
+                // Unassigned slot: plain default material
+                let material = world
+                    .resource_mut::<Assets<StandardMaterial>>()
+                    .add(StandardMaterial::default());
+                world.entity_mut(child).insert(MeshMaterial3d(material));
+            }
+        }
+    }
+}
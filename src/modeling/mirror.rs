@@ -3,10 +3,13 @@
 //! Duplicates all geometry and reflects positions across the chosen axis,
 //! flipping triangle winding to preserve correct face orientation.
 
+use bevy::prelude::{Reflect, Vec3};
+use serde::{Deserialize, Serialize};
+
 use super::edit_mesh::EditMesh;
 
 /// Axis to mirror across.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect)]
 pub enum MirrorAxis {
     X,
     Y,
@@ -21,6 +24,15 @@ impl MirrorAxis {
             MirrorAxis::Z => "Z",
         }
     }
+
+    /// Reflect a point or direction across this axis' plane through the origin.
+    pub fn reflect(&self, v: Vec3) -> Vec3 {
+        match self {
+            MirrorAxis::X => Vec3::new(-v.x, v.y, v.z),
+            MirrorAxis::Y => Vec3::new(v.x, -v.y, v.z),
+            MirrorAxis::Z => Vec3::new(v.x, v.y, -v.z),
+        }
+    }
 }
 
 /// Mirror the entire mesh across an axis-aligned plane through the origin.
@@ -69,10 +81,34 @@ pub fn mirror_mesh(mesh: &EditMesh, axis: MirrorAxis) -> EditMesh {
         ]);
     }
 
+    // Mirrored polygons follow the same reversed winding
+    let faces = if mesh.has_polygons() {
+        let mut faces = mesh.faces.clone();
+        faces.extend(
+            mesh.faces
+                .iter()
+                .map(|face| face.iter().rev().map(|&v| v + vert_count).collect()),
+        );
+        faces
+    } else {
+        Vec::new()
+    };
+
     EditMesh {
         positions,
         normals,
         uvs,
         triangles,
+        faces,
+        // The mirrored half repeats the original's material slots
+        face_materials: if mesh.has_face_materials() {
+            mesh.face_materials.repeat(2)
+        } else {
+            Vec::new()
+        },
+        // Mirrored vertices copy their originals' attributes
+        attributes: mesh
+            .attributes
+            .remap(&(0..vert_count).chain(0..vert_count).collect::<Vec<_>>()),
     }
 }
@@ -17,14 +17,18 @@ pub mod half_edge;
 pub mod inset;
 mod input;
 pub mod marker;
+pub mod material_slots;
 pub mod mirror;
+pub mod modifier;
 pub mod plane_cut;
 pub mod push_pull;
 pub mod remesh;
+pub mod sculpt;
 pub mod selection;
 pub mod simplify;
 pub mod catmull_clark;
 pub mod normals;
+pub mod paint;
 pub mod select_ops;
 pub mod snap;
 pub mod smooth;
@@ -38,6 +42,8 @@ use bevy::prelude::*;
 use std::collections::HashSet;
 
 use edit_mesh::Edge;
+use paint::PaintChannel;
+use sculpt::{GrabStroke, SculptBrush};
 use snap::SnapMode;
 use soft_select::FalloffCurve;
 use uv_project::{ProjectionAxis, UvProjection};
@@ -47,9 +53,12 @@ use crate::editor::EditorMode;
 use crate::selection::Selected;
 
 use edit_mesh::{EditMesh, FaceIndex};
-use gizmos::draw_model_gizmos;
+use gizmos::{draw_brush_cursor, draw_model_gizmos};
 use half_edge::HalfEdgeMesh;
-use input::{handle_extrude_drag, handle_model_click, handle_model_confirm, handle_model_delete, handle_model_input};
+use input::{
+    handle_extrude_drag, handle_model_click, handle_model_confirm, handle_model_delete,
+    handle_model_input, handle_model_paint, handle_model_sculpt,
+};
 use marker::EditMeshMarker;
 
 /// Grid projection type for face selection.
@@ -130,6 +139,8 @@ pub enum ModelOperation {
     CatmullClark,
     /// Snap selected vertices to grid.
     SnapToGrid,
+    /// Put selected faces into the active material slot.
+    AssignMaterial,
 }
 
 impl ModelOperation {
@@ -158,6 +169,7 @@ impl ModelOperation {
             ModelOperation::FlatNormals => "Flat Normals",
             ModelOperation::CatmullClark => "Catmull-Clark",
             ModelOperation::SnapToGrid => "Snap to Grid",
+            ModelOperation::AssignMaterial => "Assign Material",
         }
     }
 
@@ -213,6 +225,36 @@ impl SelectionMode {
     }
 }
 
+/// Active Model mode tool: element editing or a brush.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModelTool {
+    /// Select elements and run operations (default).
+    #[default]
+    Edit,
+    /// Paint vertex colors with a brush.
+    Paint,
+    /// Sculpt the surface with brushes.
+    Sculpt,
+}
+
+impl ModelTool {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ModelTool::Edit => "Edit",
+            ModelTool::Paint => "Paint",
+            ModelTool::Sculpt => "Sculpt",
+        }
+    }
+
+    pub fn key_hint(&self) -> &'static str {
+        match self {
+            ModelTool::Edit => "Esc",
+            ModelTool::Paint => "C",
+            ModelTool::Sculpt => "K",
+        }
+    }
+}
+
 /// Selected mesh elements — vertices, edges, or faces.
 #[derive(Debug, Clone, Default)]
 pub enum ElementSelection {
@@ -371,6 +413,29 @@ pub struct MeshModelState {
     pub soft_radius: f32,
     /// Soft selection falloff curve.
     pub soft_falloff: FalloffCurve,
+    /// Material slot that Assign Material puts selected faces into.
+    pub material_slot: u32,
+    // -- Brush tools --
+    /// Active tool (element editing or a brush).
+    pub model_tool: ModelTool,
+    /// Brush radius in mesh-local units.
+    pub brush_radius: f32,
+    /// Brush strength per dab (0–1).
+    pub brush_strength: f32,
+    /// Whether a brush stroke is in progress (left mouse held).
+    pub brush_stroke_active: bool,
+    /// Vertex paint color (RGB tint, alpha used by the Mask channel).
+    pub paint_color: Vec4,
+    /// Which part of the vertex color the paint brush writes.
+    pub paint_channel: PaintChannel,
+    /// Active sculpt brush.
+    pub sculpt_brush: SculptBrush,
+    /// Mirror sculpt strokes across `mirror_axis`.
+    pub sculpt_symmetry: bool,
+    /// In-progress Grab stroke.
+    pub grab_stroke: Option<GrabStroke>,
+    /// Sculpt dabs skip the collider; it is rebuilt once the stroke ends.
+    pub sculpt_collider_stale: bool,
 }
 
 impl Default for MeshModelState {
@@ -421,6 +486,17 @@ impl Default for MeshModelState {
             soft_selection: false,
             soft_radius: 1.0,
             soft_falloff: FalloffCurve::Smooth,
+            material_slot: 0,
+            model_tool: ModelTool::Edit,
+            brush_radius: 0.5,
+            brush_strength: 0.5,
+            brush_stroke_active: false,
+            paint_color: Vec4::new(1.0, 0.0, 0.0, 1.0),
+            paint_channel: PaintChannel::Color,
+            sculpt_brush: SculptBrush::Draw,
+            sculpt_symmetry: false,
+            grab_stroke: None,
+            sculpt_collider_stale: false,
         }
     }
 }
@@ -467,6 +543,17 @@ impl MeshModelState {
         self.soft_selection = false;
         self.soft_radius = 1.0;
         self.soft_falloff = FalloffCurve::Smooth;
+        self.material_slot = 0;
+        self.model_tool = ModelTool::Edit;
+        self.brush_radius = 0.5;
+        self.brush_strength = 0.5;
+        self.brush_stroke_active = false;
+        self.paint_color = Vec4::new(1.0, 0.0, 0.0, 1.0);
+        self.paint_channel = PaintChannel::Color;
+        self.sculpt_brush = SculptBrush::Draw;
+        self.sculpt_symmetry = false;
+        self.grab_stroke = None;
+        self.sculpt_collider_stale = false;
     }
 }
 
@@ -486,34 +573,40 @@ impl Plugin for MeshModelPlugin {
                     handle_model_confirm,
                     handle_model_delete,
                     handle_extrude_drag,
+                    handle_model_paint,
+                    handle_model_sculpt,
                     sync_target_entity,
                 )
                     .run_if(in_state(EditorMode::Blockout)),
             )
             .add_systems(
                 Update,
-                draw_model_gizmos.run_if(in_state(EditorMode::Blockout)),
-            );
+                (draw_model_gizmos, draw_brush_cursor).run_if(in_state(EditorMode::Blockout)),
+            )
+            .add_systems(Update, modifier::evaluate_modifier_stacks);
     }
 }
 
 /// When entering Model mode, lock onto the currently selected entity.
 fn on_enter_model_mode(
     mut model_state: ResMut<MeshModelState>,
-    selected: Query<(Entity, &Mesh3d), With<Selected>>,
+    selected: Query<(Entity, &Mesh3d, Option<&EditMeshMarker>), With<Selected>>,
     meshes: Res<Assets<Mesh>>,
 ) {
     // Use first selected entity with a mesh as target
-    if let Some((entity, mesh_handle)) = selected.iter().next() {
+    if let Some((entity, mesh_handle, marker)) = selected.iter().next() {
         model_state.target_entity = Some(entity);
 
-        // Try to load the mesh into EditMesh, then build HalfEdgeMesh
-        if let Some(mesh) = meshes.get(&mesh_handle.0) {
-            if let Some(edit_mesh) = EditMesh::from_bevy_mesh(mesh) {
-                let he_mesh = HalfEdgeMesh::from_edit_mesh(&edit_mesh);
-                model_state.half_edge_mesh = Some(he_mesh);
-                model_state.edit_mesh = Some(edit_mesh);
-            }
+        // Edit the base mesh (not the modifier result) when there is one,
+        // then build HalfEdgeMesh
+        let edit_mesh = match marker {
+            Some(marker) => Some(marker.to_edit_mesh()),
+            None => meshes.get(&mesh_handle.0).and_then(EditMesh::from_bevy_mesh),
+        };
+        if let Some(edit_mesh) = edit_mesh {
+            let he_mesh = HalfEdgeMesh::from_edit_mesh(&edit_mesh);
+            model_state.half_edge_mesh = Some(he_mesh);
+            model_state.edit_mesh = Some(edit_mesh);
         }
 
         info!("Entered Model mode — target entity {:?}", entity);
@@ -531,16 +624,22 @@ fn on_exit_model_mode(mut model_state: ResMut<MeshModelState>) {
 /// Keep target_entity in sync with selection changes while in Model mode.
 fn sync_target_entity(
     mut model_state: ResMut<MeshModelState>,
-    selected: Query<(Entity, &Mesh3d), With<Selected>>,
+    selected: Query<(Entity, &Mesh3d, Option<&EditMeshMarker>), With<Selected>>,
     meshes: Res<Assets<Mesh>>,
 ) {
-    // Helper: load edit mesh + half-edge mesh from a Bevy mesh handle
+    // Helper: load edit mesh + half-edge mesh, preferring the base mesh
+    // stored in the marker over the (possibly modified) Bevy mesh
     fn load_mesh(
         model_state: &mut MeshModelState,
         mesh_handle: &Mesh3d,
+        marker: Option<&EditMeshMarker>,
         meshes: &Assets<Mesh>,
     ) {
-        if let Some(mesh) = meshes.get(&mesh_handle.0) {
+        if let Some(marker) = marker {
+            let edit_mesh = marker.to_edit_mesh();
+            model_state.half_edge_mesh = Some(HalfEdgeMesh::from_edit_mesh(&edit_mesh));
+            model_state.edit_mesh = Some(edit_mesh);
+        } else if let Some(mesh) = meshes.get(&mesh_handle.0) {
             if let Some(edit_mesh) = EditMesh::from_bevy_mesh(mesh) {
                 let he_mesh = HalfEdgeMesh::from_edit_mesh(&edit_mesh);
                 model_state.half_edge_mesh = Some(he_mesh);
@@ -556,11 +655,11 @@ fn sync_target_entity(
     if let Some(target) = model_state.target_entity {
         if selected.get(target).is_err() {
             // Target lost — try to pick up a new one
-            if let Some((entity, mesh_handle)) = selected.iter().next() {
+            if let Some((entity, mesh_handle, marker)) = selected.iter().next() {
                 model_state.target_entity = Some(entity);
                 model_state.selected_faces.clear();
                 model_state.element_selection.clear();
-                load_mesh(&mut model_state, mesh_handle, &meshes);
+                load_mesh(&mut model_state, mesh_handle, marker, &meshes);
             } else {
                 model_state.target_entity = None;
                 model_state.edit_mesh = None;
@@ -571,9 +670,9 @@ fn sync_target_entity(
         }
     } else {
         // No target — try to acquire from selection
-        if let Some((entity, mesh_handle)) = selected.iter().next() {
+        if let Some((entity, mesh_handle, marker)) = selected.iter().next() {
             model_state.target_entity = Some(entity);
-            load_mesh(&mut model_state, mesh_handle, &meshes);
+            load_mesh(&mut model_state, mesh_handle, marker, &meshes);
         }
     }
 }
@@ -0,0 +1,476 @@
+//! Non-destructive modifier stack for edited meshes.
+//!
+//! When an entity has a `MeshModifierStack`, its `EditMeshMarker` holds the
+//! *base* mesh and the displayed `Mesh3d` / `Collider` are the base with each
+//! enabled modifier applied in order. The result is re-evaluated whenever the
+//! stack, the base or a `Boolean` operand changes. Applying a modifier bakes it into the base.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::boolean::{boolean_op, BooleanOp};
+use super::catmull_clark::catmull_clark_subdivide;
+use super::edit_mesh::EditMesh;
+use super::fill_hole::fill_holes;
+use super::marker::EditMeshMarker;
+use super::material_slots::{split_for_display, sync_material_slot_children};
+use super::mirror::{mirror_mesh, MirrorAxis};
+use super::normals::{auto_smooth_normals, flat_normals};
+use super::remesh::remesh;
+use super::simplify::simplify_mesh;
+use super::smooth::{smooth_mesh, subdivide_mesh};
+use super::weld::weld_vertices;
+
+/// Subdivision modifiers are capped to keep re-evaluation interactive.
+pub const MAX_SUBDIVISION_LEVELS: u32 = 3;
+
+/// A whole-mesh operation with its parameters.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Reflect)]
+pub enum MeshModifier {
+    Mirror { axis: MirrorAxis },
+    Smooth { iterations: u32, factor: f32 },
+    Subdivide { levels: u32 },
+    CatmullClark { levels: u32 },
+    FillHoles,
+    Weld { threshold: f32 },
+    Simplify { ratio: f32 },
+    Remesh { edge_length: f32 },
+    AutoSmooth { angle: f32 },
+    FlatNormals,
+    /// CSG with another mesh entity, looked up by `Name`.
+    Boolean { op: BooleanOp, target: String },
+}
+
+impl MeshModifier {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Mirror { .. } => "Mirror",
+            Self::Smooth { .. } => "Smooth",
+            Self::Subdivide { .. } => "Subdivide",
+            Self::CatmullClark { .. } => "Catmull-Clark",
+            Self::FillHoles => "Fill Holes",
+            Self::Weld { .. } => "Weld",
+            Self::Simplify { .. } => "Simplify",
+            Self::Remesh { .. } => "Remesh",
+            Self::AutoSmooth { .. } => "Auto Smooth",
+            Self::FlatNormals => "Flat Normals",
+            Self::Boolean { .. } => "Boolean",
+        }
+    }
+
+    pub fn variant_index(&self) -> usize {
+        match self {
+            Self::Mirror { .. } => 0,
+            Self::Smooth { .. } => 1,
+            Self::Subdivide { .. } => 2,
+            Self::CatmullClark { .. } => 3,
+            Self::FillHoles => 4,
+            Self::Weld { .. } => 5,
+            Self::Simplify { .. } => 6,
+            Self::Remesh { .. } => 7,
+            Self::AutoSmooth { .. } => 8,
+            Self::FlatNormals => 9,
+            Self::Boolean { .. } => 10,
+        }
+    }
+
+    pub const VARIANT_LABELS: &[&str] = &[
+        "Mirror",
+        "Smooth",
+        "Subdivide",
+        "Catmull-Clark",
+        "Fill Holes",
+        "Weld",
+        "Simplify",
+        "Remesh",
+        "Auto Smooth",
+        "Flat Normals",
+        "Boolean",
+    ];
+
+    pub fn from_variant_index(idx: usize) -> Self {
+        match idx {
+            1 => Self::Smooth {
+                iterations: 3,
+                factor: 0.5,
+            },
+            2 => Self::Subdivide { levels: 1 },
+            3 => Self::CatmullClark { levels: 1 },
+            4 => Self::FillHoles,
+            5 => Self::Weld { threshold: 0.01 },
+            6 => Self::Simplify { ratio: 0.5 },
+            7 => Self::Remesh { edge_length: 0.25 },
+            8 => Self::AutoSmooth { angle: 30.0 },
+            9 => Self::FlatNormals,
+            10 => Self::Boolean {
+                op: BooleanOp::Union,
+                target: String::new(),
+            },
+            _ => Self::Mirror {
+                axis: MirrorAxis::X,
+            },
+        }
+    }
+
+    /// Name of the entity a `Boolean` modifier reads its second mesh from.
+    pub fn operand_name(&self) -> Option<&str> {
+        match self {
+            Self::Boolean { target, .. } if !target.is_empty() => Some(target),
+            _ => None,
+        }
+    }
+
+    /// Run the modifier. `operand` is the `Boolean` target mesh in the
+    /// modified entity's local space; without it a boolean is a no-op.
+    pub fn apply(&self, mesh: &EditMesh, operand: Option<&EditMesh>) -> EditMesh {
+        match self {
+            Self::Mirror { axis } => mirror_mesh(mesh, *axis),
+            Self::Smooth { iterations, factor } => smooth_mesh(mesh, *iterations, *factor),
+            Self::Subdivide { levels } => {
+                let mut result = mesh.clone();
+                for _ in 0..(*levels).min(MAX_SUBDIVISION_LEVELS) {
+                    result = subdivide_mesh(&result);
+                }
+                result
+            }
+            Self::CatmullClark { levels } => {
+                let mut result = mesh.clone();
+                for _ in 0..(*levels).min(MAX_SUBDIVISION_LEVELS) {
+                    result = catmull_clark_subdivide(&result);
+                }
+                result
+            }
+            Self::FillHoles => fill_holes(mesh),
+            Self::Weld { threshold } => {
+                let all: HashSet<u32> = (0..mesh.positions.len() as u32).collect();
+                weld_vertices(mesh, &all, *threshold)
+            }
+            Self::Simplify { ratio } => simplify_mesh(mesh, *ratio),
+            Self::Remesh { edge_length } => remesh(mesh, *edge_length),
+            Self::AutoSmooth { angle } => auto_smooth_normals(mesh, *angle),
+            Self::FlatNormals => flat_normals(mesh),
+            Self::Boolean { op, .. } => match operand {
+                Some(other) => boolean_op(mesh, other, *op),
+                None => mesh.clone(),
+            },
+        }
+    }
+}
+
+/// One slot in a modifier stack.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Reflect)]
+pub struct ModifierEntry {
+    pub modifier: MeshModifier,
+    pub enabled: bool,
+}
+
+impl ModifierEntry {
+    pub fn new(modifier: MeshModifier) -> Self {
+        Self {
+            modifier,
+            enabled: true,
+        }
+    }
+}
+
+/// Ordered, serializable modifiers evaluated on top of an `EditMeshMarker`.
+#[derive(Component, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct MeshModifierStack {
+    pub modifiers: Vec<ModifierEntry>,
+}
+
+impl MeshModifierStack {
+    /// Apply every enabled modifier to `base`, in order.
+    pub fn evaluate(&self, base: &EditMesh, operands: &HashMap<String, EditMesh>) -> EditMesh {
+        evaluate_entries(&self.modifiers, base, operands)
+    }
+
+    /// Bake the modifiers up to and including `index` into a new base mesh,
+    /// removing them from the stack. Disabled modifiers in that range are
+    /// dropped without being applied.
+    pub fn apply_through(
+        &mut self,
+        index: usize,
+        base: &EditMesh,
+        operands: &HashMap<String, EditMesh>,
+    ) -> EditMesh {
+        let end = (index + 1).min(self.modifiers.len());
+        let baked: Vec<ModifierEntry> = self.modifiers.drain(..end).collect();
+        evaluate_entries(&baked, base, operands)
+    }
+}
+
+fn evaluate_entries(
+    entries: &[ModifierEntry],
+    base: &EditMesh,
+    operands: &HashMap<String, EditMesh>,
+) -> EditMesh {
+    let mut mesh = base.clone();
+    for entry in entries.iter().filter(|e| e.enabled) {
+        let operand = entry
+            .modifier
+            .operand_name()
+            .and_then(|name| operands.get(name));
+        mesh = entry.modifier.apply(&mesh, operand);
+    }
+    mesh
+}
+
+/// Meshes referenced by `Boolean` modifiers on `entity`, moved into its
+/// local space.
+pub fn collect_boolean_operands(
+    world: &mut World,
+    entity: Entity,
+    stack: &MeshModifierStack,
+) -> HashMap<String, EditMesh> {
+    let mut operands = HashMap::new();
+    let names: HashSet<&str> = stack
+        .modifiers
+        .iter()
+        .filter_map(|e| e.modifier.operand_name())
+        .collect();
+    if names.is_empty() {
+        return operands;
+    }
+
+    // Scene entities are roots; local transforms are valid even before
+    // propagation (e.g. right after a scene load)
+    let owner_inverse = world
+        .get::<Transform>(entity)
+        .map(|t| t.compute_affine().inverse())
+        .unwrap_or_default();
+
+    let mut query = world.query::<(
+        Entity,
+        &Name,
+        &Transform,
+        Option<&EditMeshMarker>,
+        Option<&Mesh3d>,
+    )>();
+    for (other, name, transform, marker, mesh3d) in query.iter(world) {
+        if other == entity
+            || !names.contains(name.as_str())
+            || operands.contains_key(name.as_str())
+        {
+            continue;
+        }
+        let mesh = match (marker, mesh3d) {
+            (Some(marker), _) => Some(marker.to_edit_mesh()),
+            (None, Some(mesh3d)) => world
+                .resource::<Assets<Mesh>>()
+                .get(&mesh3d.0)
+                .and_then(EditMesh::from_bevy_mesh),
+            _ => None,
+        };
+        let Some(mut mesh) = mesh else {
+            continue;
+        };
+
+        let to_local = owner_inverse * transform.compute_affine();
+        for position in &mut mesh.positions {
+            *position = to_local.transform_point3(*position);
+        }
+        for normal in &mut mesh.normals {
+            *normal = to_local.transform_vector3(*normal).normalize_or_zero();
+        }
+        operands.insert(name.as_str().to_string(), mesh);
+    }
+    operands
+}
+
+/// The mesh `entity` displays: its `EditMeshMarker` with any modifiers applied.
+pub fn evaluated_edit_mesh(world: &mut World, entity: Entity) -> Option<EditMesh> {
+    let base = world.get::<EditMeshMarker>(entity)?.to_edit_mesh();
+    let Some(stack) = world.get::<MeshModifierStack>(entity).cloned() else {
+        return Some(base);
+    };
+    let operands = collect_boolean_operands(world, entity, &stack);
+    Some(stack.evaluate(&base, &operands))
+}
+
+/// Rebuild `Mesh3d` and `Collider` for `entity` from its evaluated mesh.
+///
+/// The entity displays the slot-0 faces; other material slots are rebuilt
+/// as child meshes. The collider always covers the whole mesh.
+pub fn rebuild_evaluated_mesh(world: &mut World, entity: Entity) {
+    let Some(mesh) = evaluated_edit_mesh(world, entity) else {
+        return;
+    };
+    let collider = EditMeshMarker::from_edit_mesh(&mesh).to_collider();
+    let (base, slot_meshes) = split_for_display(&mesh);
+    let mesh_handle = world.resource_mut::<Assets<Mesh>>().add(base.to_bevy_mesh());
+    if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+        entity_mut.insert((Mesh3d(mesh_handle), collider));
+    }
+    sync_material_slot_children(world, entity, slot_meshes);
+}
+
+/// Re-evaluate stacks whose modifiers or base mesh changed, or whose
+/// `Boolean` operands moved or were edited.
+pub fn evaluate_modifier_stacks(world: &mut World) {
+    let mut changed: Vec<Entity> = {
+        let mut query = world.query_filtered::<Entity, (
+            With<MeshModifierStack>,
+            With<EditMeshMarker>,
+            Or<(Changed<MeshModifierStack>, Changed<EditMeshMarker>)>,
+        )>();
+        query.iter(world).collect()
+    };
+
+    // Operands are found by name, so a renamed entity can start or stop
+    // being one
+    let changed_operands: HashSet<String> = {
+        let mut query = world.query_filtered::<&Name, Or<(
+            Changed<GlobalTransform>,
+            Changed<EditMeshMarker>,
+            Changed<Name>,
+        )>>();
+        query.iter(world).map(|name| name.as_str().to_string()).collect()
+    };
+
+    // Operands are evaluated in the owner's local space, so moving the owner
+    // changes the result too
+    let mut query = world.query_filtered::<
+        (Entity, &MeshModifierStack, Ref<GlobalTransform>),
+        With<EditMeshMarker>,
+    >();
+    let dependents: Vec<Entity> = query
+        .iter(world)
+        .filter(|(entity, stack, transform)| {
+            let mut operands = stack
+                .modifiers
+                .iter()
+                .filter_map(|e| e.modifier.operand_name())
+                .peekable();
+            !changed.contains(entity)
+                && operands.peek().is_some()
+                && (transform.is_changed()
+                    || operands.any(|name| changed_operands.contains(name)))
+        })
+        .map(|(entity, ..)| entity)
+        .collect();
+    changed.extend(dependents);
+
+    for entity in changed {
+        rebuild_evaluated_mesh(world, entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modeling::edit_mesh::VertexAttributes;
+
+    fn triangle() -> EditMesh {
+        EditMesh {
+            positions: vec![
+                Vec3::new(0.5, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(0.5, 1.0, 0.0),
+            ],
+            normals: vec![Vec3::Z; 3],
+            uvs: vec![Vec2::ZERO; 3],
+            triangles: vec![[0, 1, 2]],
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
+        }
+    }
+
+    #[test]
+    fn test_disabled_modifiers_are_skipped() {
+        let mut stack = MeshModifierStack {
+            modifiers: vec![ModifierEntry::new(MeshModifier::Mirror {
+                axis: MirrorAxis::X,
+            })],
+        };
+        let operands = HashMap::new();
+        assert_eq!(stack.evaluate(&triangle(), &operands).triangles.len(), 2);
+
+        stack.modifiers[0].enabled = false;
+        assert_eq!(stack.evaluate(&triangle(), &operands).triangles.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_through_bakes_and_removes() {
+        let mut stack = MeshModifierStack {
+            modifiers: vec![
+                ModifierEntry::new(MeshModifier::Mirror {
+                    axis: MirrorAxis::X,
+                }),
+                ModifierEntry::new(MeshModifier::Subdivide { levels: 1 }),
+            ],
+        };
+        let operands = HashMap::new();
+        let base = stack.apply_through(0, &triangle(), &operands);
+        assert_eq!(base.triangles.len(), 2);
+        assert_eq!(stack.modifiers.len(), 1);
+        assert_eq!(stack.evaluate(&base, &operands).triangles.len(), 8);
+    }
+
+    #[test]
+    fn test_stack_roundtrip_ron() {
+        let stack = MeshModifierStack {
+            modifiers: vec![
+                ModifierEntry::new(MeshModifier::Smooth {
+                    iterations: 2,
+                    factor: 0.25,
+                }),
+                ModifierEntry::new(MeshModifier::Boolean {
+                    op: BooleanOp::Subtract,
+                    target: "Cutter".into(),
+                }),
+            ],
+        };
+        let ron_str = ron::to_string(&stack).unwrap();
+        let back: MeshModifierStack = ron::from_str(&ron_str).unwrap();
+        assert_eq!(back, stack);
+    }
+
+    #[test]
+    fn test_moving_operand_reevaluates_owner() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Mesh>>();
+        let cube = EditMesh::from_bevy_mesh(&Cuboid::default().mesh().build()).unwrap();
+        let owner = world
+            .spawn((
+                EditMeshMarker::from_edit_mesh(&cube),
+                MeshModifierStack {
+                    modifiers: vec![ModifierEntry::new(MeshModifier::Boolean {
+                        op: BooleanOp::Subtract,
+                        target: "Cutter".into(),
+                    })],
+                },
+                Transform::default(),
+                GlobalTransform::default(),
+            ))
+            .id();
+        let cutter = world
+            .spawn((
+                Name::new("Cutter"),
+                EditMeshMarker::from_edit_mesh(&cube),
+                Transform::from_xyz(0.5, 0.5, 0.5),
+                GlobalTransform::from_xyz(0.5, 0.5, 0.5),
+            ))
+            .id();
+        let system = world.register_system(evaluate_modifier_stacks);
+        let mesh_id = |world: &World| world.get::<Mesh3d>(owner).map(|m| m.id());
+
+        world.run_system(system).unwrap();
+        let first = mesh_id(&world);
+        assert!(first.is_some());
+
+        // Nothing changed
+        world.run_system(system).unwrap();
+        assert_eq!(mesh_id(&world), first);
+
+        let moved = Transform::from_xyz(0.25, 0.5, 0.5);
+        *world.get_mut::<Transform>(cutter).unwrap() = moved;
+        *world.get_mut::<GlobalTransform>(cutter).unwrap() = GlobalTransform::from(moved);
+        world.run_system(system).unwrap();
+        assert_ne!(mesh_id(&world), first);
+    }
+}
@@ -3,7 +3,7 @@
 use bevy::prelude::*;
 use std::collections::{HashMap, HashSet};
 
-use super::edit_mesh::{Edge, EditMesh, FaceIndex};
+use super::edit_mesh::{polygon_normal, Edge, EditMesh, FaceIndex};
 
 /// Recompute normals with auto-smooth: faces with a shared edge whose normals
 /// differ by more than `angle_threshold_degrees` get hard (split) normals,
@@ -22,10 +22,13 @@ pub fn auto_smooth_normals_with_hard_edges(
     let threshold_cos = angle_threshold_degrees.to_radians().cos();
     let adj = mesh.build_adjacency();
 
+    // Polygon diagonals never split normals, so each polygon stays in one group
+    let internal = mesh.internal_edges();
+
     // Step 1: Determine which edges are "hard" (need split normals)
-    let mut all_hard_edges = hard_edges.clone();
+    let mut all_hard_edges: HashSet<Edge> = hard_edges.difference(&internal).copied().collect();
     for (edge, faces) in &adj {
-        if faces.len() == 2 {
+        if faces.len() == 2 && !internal.contains(edge) {
             let n0 = mesh.face_normal(faces[0]);
             let n1 = mesh.face_normal(faces[1]);
             if n0.dot(n1) < threshold_cos {
@@ -72,32 +75,52 @@ pub fn auto_smooth_normals_with_hard_edges(
     let mut result_positions = Vec::new();
     let mut result_normals = Vec::new();
     let mut result_uvs = Vec::new();
+    let mut origins = Vec::new();
     let mut result_triangles = Vec::new();
 
     // Map: (original_vertex, smooth_group_index) -> new_vertex_index
     let mut vertex_map: HashMap<(u32, usize), u32> = HashMap::new();
 
+    let mut face_group = vec![0usize; mesh.triangles.len()];
     for (group_idx, group) in smooth_groups.iter().enumerate() {
         for &fi in group {
-            let old_tri = mesh.triangles[fi];
-            let mut new_tri = [0u32; 3];
-
-            for (i, &old_vi) in old_tri.iter().enumerate() {
-                let key = (old_vi, group_idx);
-                let new_vi = *vertex_map.entry(key).or_insert_with(|| {
-                    let idx = result_positions.len() as u32;
-                    result_positions.push(mesh.positions[old_vi as usize]);
-                    result_normals.push(Vec3::ZERO); // Will compute below
-                    result_uvs.push(mesh.uvs[old_vi as usize]);
-                    idx
-                });
-                new_tri[i] = new_vi;
-            }
+            face_group[fi] = group_idx;
+        }
+    }
 
-            result_triangles.push(new_tri);
+    // Keep the original triangle order so polygon faces stay valid
+    for (fi, old_tri) in mesh.triangles.iter().enumerate() {
+        let mut new_tri = [0u32; 3];
+
+        for (i, &old_vi) in old_tri.iter().enumerate() {
+            let key = (old_vi, face_group[fi]);
+            let new_vi = *vertex_map.entry(key).or_insert_with(|| {
+                let idx = result_positions.len() as u32;
+                result_positions.push(mesh.positions[old_vi as usize]);
+                result_normals.push(Vec3::ZERO); // Will compute below
+                result_uvs.push(mesh.uvs[old_vi as usize]);
+                origins.push(old_vi);
+                idx
+            });
+            new_tri[i] = new_vi;
         }
+
+        result_triangles.push(new_tri);
     }
 
+    let result_faces: Vec<Vec<u32>> = if mesh.has_polygons() {
+        mesh.faces
+            .iter()
+            .zip(mesh.polygon_triangle_ranges())
+            .map(|(face, range)| {
+                let group_idx = face_group[range.start];
+                face.iter().map(|&vi| vertex_map[&(vi, group_idx)]).collect()
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     // Step 4: Compute smooth normals per new vertex
     for tri in &result_triangles {
         let v0 = result_positions[tri[0] as usize];
@@ -118,6 +141,9 @@ pub fn auto_smooth_normals_with_hard_edges(
         normals: result_normals,
         uvs: result_uvs,
         triangles: result_triangles,
+        faces: result_faces,
+        face_materials: mesh.face_materials.clone(),
+        attributes: mesh.attributes.remap(&origins),
     }
 }
 
@@ -126,27 +152,27 @@ pub fn flat_normals(mesh: &EditMesh) -> EditMesh {
     let mut positions = Vec::new();
     let mut normals = Vec::new();
     let mut uvs = Vec::new();
-    let mut triangles = Vec::new();
+    let mut origins = Vec::new();
+    let mut faces = Vec::new();
 
-    for (fi, tri) in mesh.triangles.iter().enumerate() {
+    for face in mesh.polygons() {
         let base = positions.len() as u32;
-        let n = mesh.face_normal(fi);
+        let n = polygon_normal(&face, &mesh.positions);
 
-        for &vi in tri {
+        for &vi in &face {
             positions.push(mesh.positions[vi as usize]);
             normals.push(n);
             uvs.push(mesh.uvs[vi as usize]);
+            origins.push(vi);
         }
 
-        triangles.push([base, base + 1, base + 2]);
+        faces.push((base..base + face.len() as u32).collect());
     }
 
-    EditMesh {
-        positions,
-        normals,
-        uvs,
-        triangles,
-    }
+    let mut result =
+        EditMesh::from_polygons(positions, normals, uvs, faces, mesh.polygon_materials());
+    result.attributes = mesh.attributes.remap(&origins);
+    result
 }
 
 /// Toggle an edge as hard/soft in the given set. Returns true if added (now hard).
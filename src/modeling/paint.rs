@@ -0,0 +1,90 @@
+//! Vertex color painting for the mesh modeling tool.
+//!
+//! A brush dab blends the vertex colors around the point under the cursor
+//! toward the paint color. Weights use the same distance falloff as soft
+//! selection, so coincident vertices on split seams paint together.
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use super::edit_mesh::{EditMesh, FaceIndex};
+use super::soft_select::{compute_soft_weights, FalloffCurve};
+
+/// Which part of the vertex color a brush dab writes.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PaintChannel {
+    /// RGB tint; alpha is left unchanged.
+    #[default]
+    Color,
+    /// Alpha only, used as a blend mask by materials.
+    Mask,
+}
+
+impl PaintChannel {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PaintChannel::Color => "Color",
+            PaintChannel::Mask => "Mask",
+        }
+    }
+}
+
+/// Brush settings for a single dab.
+#[derive(Clone, Copy, Debug)]
+pub struct PaintBrush {
+    /// Target color (RGB used by `Color`, alpha by `Mask`).
+    pub color: Vec4,
+    /// Brush radius in mesh-local units.
+    pub radius: f32,
+    /// Blend amount per dab at the brush center (0–1).
+    pub strength: f32,
+    pub falloff: FalloffCurve,
+    pub channel: PaintChannel,
+}
+
+/// Apply one brush dab centered on `point`, which lies on triangle `face`.
+///
+/// Unpainted meshes start out white. Returns the mesh unchanged if `face`
+/// is out of range.
+pub fn paint_vertices(
+    mesh: &EditMesh,
+    face: FaceIndex,
+    point: Vec3,
+    brush: &PaintBrush,
+) -> EditMesh {
+    let Some(tri) = mesh.triangles.get(face) else {
+        return mesh.clone();
+    };
+
+    // Seed the falloff from the triangle corner nearest the hit point
+    let Some(&nearest) = tri.iter().min_by(|&&a, &&b| {
+        let da = mesh.positions[a as usize].distance_squared(point);
+        let db = mesh.positions[b as usize].distance_squared(point);
+        da.total_cmp(&db)
+    }) else {
+        return mesh.clone();
+    };
+    let seeds: HashSet<u32> = HashSet::from([nearest]);
+    let weights = compute_soft_weights(mesh, &seeds, brush.radius, brush.falloff);
+
+    let mut result = mesh.clone();
+    let mut colors = mesh.vertex_colors();
+    let strength = brush.strength.clamp(0.0, 1.0);
+    for (color, weight) in colors.iter_mut().zip(weights) {
+        let t = weight * strength;
+        if t <= 0.0 {
+            continue;
+        }
+        match brush.channel {
+            PaintChannel::Color => {
+                let rgb = color.truncate().lerp(brush.color.truncate(), t);
+                *color = rgb.extend(color.w);
+            }
+            PaintChannel::Mask => {
+                color.w += (brush.color.w - color.w) * t;
+            }
+        }
+    }
+    result.attributes.colors = colors;
+    result
+}
@@ -6,7 +6,7 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 
-use super::edit_mesh::EditMesh;
+use super::edit_mesh::{EditMesh, VertexAttributes};
 
 /// Slice a mesh with a plane defined by a point and normal.
 ///
@@ -203,6 +203,9 @@ impl MeshBuilder {
             normals: self.normals,
             uvs: self.uvs,
             triangles: self.triangles,
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
         };
         mesh.recompute_normals();
         mesh
@@ -24,10 +24,12 @@ pub fn push_pull_faces(
     if selected.is_empty() || distance.abs() < 1e-6 {
         return mesh.clone();
     }
+    let selected = &mesh.expand_to_polygons(selected);
 
     let mut new_positions = mesh.positions.clone();
     let mut new_normals = mesh.normals.clone();
     let mut new_uvs = mesh.uvs.clone();
+    let mut new_attributes = mesh.attributes.clone();
     let mut new_triangles = mesh.triangles.clone();
 
     // Compute per-vertex offset: average of adjacent selected face normals
@@ -66,6 +68,7 @@ pub fn push_pull_faces(
         new_positions.push(mesh.positions[v as usize] + vert_offset[v as usize]);
         new_normals.push(mesh.normals[v as usize]);
         new_uvs.push(mesh.uvs[v as usize]);
+        new_attributes.push_copy(v);
         dup_map.insert(v, new_idx);
     }
 
@@ -89,11 +92,27 @@ pub fn push_pull_faces(
         }
     }
 
+    // Polygons move with their triangles
+    let mut new_faces = Vec::new();
+    if mesh.has_polygons() {
+        new_faces = mesh.faces.clone();
+        for pi in mesh.selected_polygons(selected) {
+            for idx in new_faces[pi].iter_mut() {
+                if let Some(&dup) = dup_map.get(idx) {
+                    *idx = dup;
+                }
+            }
+        }
+    }
+
     let mut result = EditMesh {
         positions: new_positions,
         normals: new_normals,
         uvs: new_uvs,
         triangles: new_triangles,
+        faces: new_faces,
+        face_materials: mesh.face_materials.clone(),
+        attributes: new_attributes,
     };
     result.recompute_normals();
     result
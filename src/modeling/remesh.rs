@@ -6,7 +6,7 @@
 use bevy::prelude::*;
 use std::collections::{HashMap, HashSet};
 
-use super::edit_mesh::EditMesh;
+use super::edit_mesh::{EditMesh, VertexAttributes};
 
 /// Remesh to target a uniform edge length.
 ///
@@ -339,6 +339,9 @@ impl RemeshState {
             normals,
             uvs,
             triangles,
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
         };
         mesh.recompute_normals();
         mesh
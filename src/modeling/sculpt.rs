@@ -0,0 +1,473 @@
+//! Sculpt brushes for the mesh modeling tool.
+//!
+//! A brush dab moves the vertices within the brush radius of the point under
+//! the cursor, weighted by the soft selection falloff curves. Vertices that
+//! share a position (split normals, UV seams) move together so sculpting
+//! never tears the surface open.
+
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use super::edit_mesh::EditMesh;
+use super::mirror::MirrorAxis;
+use super::soft_select::FalloffCurve;
+
+/// Displacement of a full-strength Draw/Inflate dab, as a fraction of the radius.
+const DAB_DEPTH: f32 = 0.1;
+/// Fraction of the distance to the brush center a full-strength Pinch covers.
+const PINCH_RATE: f32 = 0.5;
+/// Vertices closer than this are treated as one position.
+const WELD_EPSILON: f32 = 1e-5;
+
+/// Sculpt brush type.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SculptBrush {
+    /// Push the surface out along the brush's average normal.
+    #[default]
+    Draw,
+    /// Push each vertex out along its own normal.
+    Inflate,
+    /// Pull vertices onto the brush's average plane.
+    Flatten,
+    /// Move vertices toward the average of their neighbors.
+    Smooth,
+    /// Pull vertices toward the brush center along the surface.
+    Pinch,
+    /// Drag the vertices under the brush with the cursor.
+    Grab,
+}
+
+impl SculptBrush {
+    pub const ALL: [SculptBrush; 6] = [
+        SculptBrush::Draw,
+        SculptBrush::Inflate,
+        SculptBrush::Flatten,
+        SculptBrush::Smooth,
+        SculptBrush::Pinch,
+        SculptBrush::Grab,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SculptBrush::Draw => "Draw",
+            SculptBrush::Inflate => "Inflate",
+            SculptBrush::Flatten => "Flatten",
+            SculptBrush::Smooth => "Smooth",
+            SculptBrush::Pinch => "Pinch",
+            SculptBrush::Grab => "Grab",
+        }
+    }
+
+    pub fn key_hint(&self) -> &'static str {
+        match self {
+            SculptBrush::Draw => "1",
+            SculptBrush::Inflate => "2",
+            SculptBrush::Flatten => "3",
+            SculptBrush::Smooth => "4",
+            SculptBrush::Pinch => "5",
+            SculptBrush::Grab => "6",
+        }
+    }
+}
+
+/// Settings for a single brush dab.
+#[derive(Clone, Copy, Debug)]
+pub struct SculptDab {
+    pub brush: SculptBrush,
+    /// Dab center on the surface, in mesh-local space.
+    pub center: Vec3,
+    /// Brush radius in mesh-local units.
+    pub radius: f32,
+    /// Effect per dab at the brush center (0–1).
+    pub strength: f32,
+    pub falloff: FalloffCurve,
+    /// Reverse Draw, Inflate and Pinch (carve, deflate, spread).
+    pub invert: bool,
+}
+
+/// Apply one dab, plus its reflection across `symmetry` when set.
+///
+/// Grab strokes are driven by `GrabStroke` instead; a Grab dab is a no-op.
+pub fn sculpt_dab(mesh: &EditMesh, dab: &SculptDab, symmetry: Option<MirrorAxis>) -> EditMesh {
+    let mut result = mesh.clone();
+    let groups = PositionGroups::new(mesh);
+
+    apply_dab(&mut result, &groups, dab);
+    if let Some(axis) = symmetry {
+        let center = axis.reflect(dab.center);
+        // A dab centered on the mirror plane already covers both sides
+        if center.distance_squared(dab.center) > WELD_EPSILON * WELD_EPSILON {
+            apply_dab(&mut result, &groups, &SculptDab { center, ..*dab });
+        }
+    }
+
+    result.recompute_normals();
+    result
+}
+
+/// Move the vertex groups within the dab radius according to the brush.
+fn apply_dab(mesh: &mut EditMesh, groups: &PositionGroups, dab: &SculptDab) {
+    if dab.radius <= 0.0 || dab.brush == SculptBrush::Grab {
+        return;
+    }
+
+    let weights: Vec<(usize, f32)> = (0..groups.members.len())
+        .filter_map(|g| {
+            let d = groups.position(mesh, g).distance(dab.center);
+            let w = if d < dab.radius {
+                dab.falloff.weight(d / dab.radius)
+            } else {
+                0.0
+            };
+            (w > 0.0).then_some((g, w))
+        })
+        .collect();
+    if weights.is_empty() {
+        return;
+    }
+
+    let normals = groups.normals(mesh);
+    let neighbors = (dab.brush == SculptBrush::Smooth).then(|| groups.neighbors(mesh));
+
+    // Brush plane: weighted average of the affected surface
+    let total: f32 = weights.iter().map(|(_, w)| w).sum();
+    let plane_point = weights
+        .iter()
+        .map(|&(g, w)| groups.position(mesh, g) * w)
+        .sum::<Vec3>()
+        / total;
+    let plane_normal = weights
+        .iter()
+        .map(|&(g, w)| normals[g] * w)
+        .sum::<Vec3>()
+        .normalize_or_zero();
+
+    let strength = dab.strength.clamp(0.0, 1.0);
+    let sign = if dab.invert { -1.0 } else { 1.0 };
+    let depth = dab.radius * DAB_DEPTH * strength * sign;
+
+    // Compute every new position from the old ones before writing any back
+    let moved: Vec<(usize, Vec3)> = weights
+        .iter()
+        .map(|&(g, w)| {
+            let p = groups.position(mesh, g);
+            let offset = match dab.brush {
+                SculptBrush::Draw => plane_normal * depth * w,
+                SculptBrush::Inflate => normals[g] * depth * w,
+                SculptBrush::Flatten => {
+                    -plane_normal * (p - plane_point).dot(plane_normal) * strength * w
+                }
+                SculptBrush::Smooth => neighbors
+                    .as_ref()
+                    .map(|n| &n[g])
+                    .filter(|n| !n.is_empty())
+                    .map_or(Vec3::ZERO, |n| {
+                        let avg = n.iter().map(|&o| groups.position(mesh, o)).sum::<Vec3>()
+                            / n.len() as f32;
+                        (avg - p) * strength * w
+                    }),
+                SculptBrush::Pinch => {
+                    let to_center = dab.center - p;
+                    let tangent = to_center - plane_normal * to_center.dot(plane_normal);
+                    tangent * PINCH_RATE * strength * sign * w
+                }
+                SculptBrush::Grab => Vec3::ZERO,
+            };
+            (g, p + offset)
+        })
+        .collect();
+
+    for (g, p) in moved {
+        groups.set_position(mesh, g, p);
+    }
+}
+
+/// A Grab stroke: the vertices under the brush at the start follow the cursor.
+#[derive(Clone, Debug)]
+pub struct GrabStroke {
+    /// Mesh at stroke start; each update re-applies the whole drag to it.
+    base: EditMesh,
+    /// Weight of each vertex for the primary dab.
+    weights: Vec<f32>,
+    /// Mirror axis and vertex weights of the reflected dab (symmetry on).
+    mirrored: Option<(MirrorAxis, Vec<f32>)>,
+    /// Where the drag started, in mesh-local space.
+    anchor: Vec3,
+    /// Normal of the drag plane through `anchor`, facing the camera.
+    plane_normal: Vec3,
+}
+
+impl GrabStroke {
+    /// Start a grab at `center`, dragging in the plane facing `view_dir`.
+    pub fn begin(
+        mesh: &EditMesh,
+        center: Vec3,
+        radius: f32,
+        falloff: FalloffCurve,
+        view_dir: Vec3,
+        symmetry: Option<MirrorAxis>,
+    ) -> Self {
+        let mirrored = symmetry.and_then(|axis| {
+            let mirrored_center = axis.reflect(center);
+            (mirrored_center.distance_squared(center) > WELD_EPSILON * WELD_EPSILON)
+                .then(|| (axis, vertex_weights(mesh, mirrored_center, radius, falloff)))
+        });
+        Self {
+            base: mesh.clone(),
+            weights: vertex_weights(mesh, center, radius, falloff),
+            mirrored,
+            anchor: center,
+            plane_normal: -view_dir.normalize_or_zero(),
+        }
+    }
+
+    /// Intersect a mesh-local ray with the drag plane.
+    pub fn drag_point(&self, origin: Vec3, dir: Vec3) -> Option<Vec3> {
+        let denom = dir.dot(self.plane_normal);
+        if denom.abs() < 1e-6 {
+            return None;
+        }
+        let t = (self.anchor - origin).dot(self.plane_normal) / denom;
+        (t >= 0.0).then(|| origin + dir * t)
+    }
+
+    /// The stroke-start mesh with the grabbed vertices dragged to `point`.
+    pub fn apply(&self, point: Vec3) -> EditMesh {
+        let delta = point - self.anchor;
+        let mut result = self.base.clone();
+        for (p, &w) in result.positions.iter_mut().zip(&self.weights) {
+            *p += delta * w;
+        }
+        if let Some((axis, weights)) = &self.mirrored {
+            let delta = axis.reflect(delta);
+            for (p, &w) in result.positions.iter_mut().zip(weights) {
+                *p += delta * w;
+            }
+        }
+        result.recompute_normals();
+        result
+    }
+}
+
+/// Falloff weight of every vertex for a brush centered at `center`.
+fn vertex_weights(mesh: &EditMesh, center: Vec3, radius: f32, falloff: FalloffCurve) -> Vec<f32> {
+    mesh.positions
+        .iter()
+        .map(|p| {
+            let d = p.distance(center);
+            if radius > 0.0 && d < radius {
+                falloff.weight(d / radius)
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Vertices grouped by position, so coincident vertices move as one.
+struct PositionGroups {
+    /// Group of each vertex.
+    group_of: Vec<usize>,
+    /// Vertices of each group.
+    members: Vec<Vec<u32>>,
+}
+
+impl PositionGroups {
+    fn new(mesh: &EditMesh) -> Self {
+        let mut index: HashMap<[i64; 3], usize> = HashMap::new();
+        let mut group_of = Vec::with_capacity(mesh.positions.len());
+        let mut members: Vec<Vec<u32>> = Vec::new();
+        for (vi, p) in mesh.positions.iter().enumerate() {
+            let key = (*p / WELD_EPSILON).round().as_i64vec3().to_array();
+            let g = *index.entry(key).or_insert_with(|| {
+                members.push(Vec::new());
+                members.len() - 1
+            });
+            members[g].push(vi as u32);
+            group_of.push(g);
+        }
+        Self { group_of, members }
+    }
+
+    fn position(&self, mesh: &EditMesh, group: usize) -> Vec3 {
+        mesh.positions[self.members[group][0] as usize]
+    }
+
+    fn set_position(&self, mesh: &mut EditMesh, group: usize, position: Vec3) {
+        for &v in &self.members[group] {
+            mesh.positions[v as usize] = position;
+        }
+    }
+
+    /// Area-weighted surface normal of each group.
+    fn normals(&self, mesh: &EditMesh) -> Vec<Vec3> {
+        let mut normals = vec![Vec3::ZERO; self.members.len()];
+        for tri in &mesh.triangles {
+            let [a, b, c] = tri.map(|v| mesh.positions[v as usize]);
+            let normal = (b - a).cross(c - a);
+            for &v in tri {
+                normals[self.group_of[v as usize]] += normal;
+            }
+        }
+        for n in &mut normals {
+            *n = n.normalize_or_zero();
+        }
+        normals
+    }
+
+    /// Groups connected to each group by a triangle edge.
+    fn neighbors(&self, mesh: &EditMesh) -> Vec<HashSet<usize>> {
+        let mut neighbors = vec![HashSet::new(); self.members.len()];
+        for tri in &mesh.triangles {
+            let [a, b, c] = tri.map(|v| self.group_of[v as usize]);
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                if from != to {
+                    neighbors[from].insert(to);
+                    neighbors[to].insert(from);
+                }
+            }
+        }
+        neighbors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modeling::edit_mesh::VertexAttributes;
+
+    /// Flat 3x3 vertex grid in the XY plane with unit spacing, centered on the
+    /// origin and facing +Z. Vertex 4 is the center.
+    fn grid() -> EditMesh {
+        let mut positions = Vec::new();
+        for y in -1..=1 {
+            for x in -1..=1 {
+                positions.push(Vec3::new(x as f32, y as f32, 0.0));
+            }
+        }
+        let mut triangles = Vec::new();
+        for y in 0..2u32 {
+            for x in 0..2u32 {
+                let a = y * 3 + x;
+                triangles.push([a, a + 1, a + 4]);
+                triangles.push([a, a + 4, a + 3]);
+            }
+        }
+        EditMesh {
+            positions,
+            normals: vec![Vec3::Z; 9],
+            uvs: vec![Vec2::ZERO; 9],
+            triangles,
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
+        }
+    }
+
+    fn dab(brush: SculptBrush, center: Vec3, radius: f32) -> SculptDab {
+        SculptDab {
+            brush,
+            center,
+            radius,
+            strength: 1.0,
+            falloff: FalloffCurve::Linear,
+            invert: false,
+        }
+    }
+
+    #[test]
+    fn test_vertex_weights_follow_falloff() {
+        let weights = vertex_weights(&grid(), Vec3::ZERO, 2.0, FalloffCurve::Linear);
+        assert!((weights[4] - 1.0).abs() < 1e-6);
+        assert!((weights[1] - 0.5).abs() < 1e-6);
+        assert!((weights[0] - (1.0 - 2f32.sqrt() / 2.0)).abs() < 1e-6);
+
+        // Nothing at or beyond the radius, and nothing for a zero radius
+        let weights = vertex_weights(&grid(), Vec3::ZERO, 1.0, FalloffCurve::Smooth);
+        assert_eq!(weights[1], 0.0);
+        assert_eq!(weights[0], 0.0);
+        assert!(vertex_weights(&grid(), Vec3::ZERO, 0.0, FalloffCurve::Linear)
+            .iter()
+            .all(|&w| w == 0.0));
+    }
+
+    #[test]
+    fn test_draw_displaces_along_normal_by_falloff() {
+        let result = sculpt_dab(&grid(), &dab(SculptBrush::Draw, Vec3::ZERO, 2.0), None);
+        let depth = 2.0 * DAB_DEPTH;
+        assert!(result.positions[4].abs_diff_eq(Vec3::Z * depth, 1e-5));
+        assert!(result.positions[1].abs_diff_eq(Vec3::new(0.0, -1.0, depth * 0.5), 1e-5));
+
+        let carved = sculpt_dab(
+            &grid(),
+            &SculptDab {
+                invert: true,
+                strength: 0.5,
+                ..dab(SculptBrush::Draw, Vec3::ZERO, 2.0)
+            },
+            None,
+        );
+        assert!(carved.positions[4].abs_diff_eq(-Vec3::Z * depth * 0.5, 1e-5));
+    }
+
+    #[test]
+    fn test_dab_leaves_vertices_outside_radius() {
+        let result = sculpt_dab(&grid(), &dab(SculptBrush::Inflate, Vec3::ZERO, 0.5), None);
+        assert!(result.positions[4].z > 0.0);
+        for v in [0, 1, 2, 3, 5, 6, 7, 8] {
+            assert_eq!(result.positions[v], grid().positions[v]);
+        }
+    }
+
+    #[test]
+    fn test_coincident_vertices_move_together() {
+        let mut mesh = grid();
+        // A seam duplicate of the center vertex
+        mesh.positions.push(Vec3::ZERO);
+        mesh.normals.push(Vec3::Z);
+        mesh.uvs.push(Vec2::ONE);
+
+        let result = sculpt_dab(&mesh, &dab(SculptBrush::Draw, Vec3::ZERO, 2.0), None);
+        assert!(result.positions[4].z > 0.0);
+        assert_eq!(result.positions[9], result.positions[4]);
+    }
+
+    #[test]
+    fn test_symmetry_mirrors_dab() {
+        let result = sculpt_dab(
+            &grid(),
+            &dab(SculptBrush::Draw, Vec3::X, 0.5),
+            Some(MirrorAxis::X),
+        );
+        let depth = 0.5 * DAB_DEPTH;
+        assert!((result.positions[5].z - depth).abs() < 1e-5);
+        assert!((result.positions[3].z - depth).abs() < 1e-5);
+        assert_eq!(result.positions[4], Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_smooth_pulls_toward_neighbor_average() {
+        let mut mesh = grid();
+        mesh.positions[4] = Vec3::Z;
+        let result = sculpt_dab(&mesh, &dab(SculptBrush::Smooth, Vec3::Z, 0.5), None);
+        assert!(result.positions[4].abs_diff_eq(Vec3::ZERO, 1e-5));
+    }
+
+    #[test]
+    fn test_grab_drags_weighted_vertices() {
+        let mesh = grid();
+        let grab = GrabStroke::begin(&mesh, Vec3::ZERO, 2.0, FalloffCurve::Linear, -Vec3::Z, None);
+
+        let point = grab
+            .drag_point(Vec3::new(0.0, 0.0, 5.0), -Vec3::Z)
+            .expect("ray hits the drag plane");
+        assert!(point.abs_diff_eq(Vec3::ZERO, 1e-5));
+
+        let result = grab.apply(Vec3::Z);
+        assert!(result.positions[4].abs_diff_eq(Vec3::Z, 1e-5));
+        assert!(result.positions[1].abs_diff_eq(Vec3::new(0.0, -1.0, 0.5), 1e-5));
+        // Each update starts over from the stroke-start mesh
+        let back = grab.apply(Vec3::ZERO);
+        assert_eq!(back.positions, mesh.positions);
+    }
+}
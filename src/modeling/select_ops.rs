@@ -23,7 +23,7 @@ pub fn grow_face_selection(mesh: &EditMesh, selected: &HashSet<FaceIndex>) -> Ha
         }
     }
 
-    grown
+    mesh.expand_to_polygons(&grown)
 }
 
 /// Shrink face selection by removing faces on the boundary of the selection.
@@ -45,6 +45,8 @@ pub fn shrink_face_selection(mesh: &EditMesh, selected: &HashSet<FaceIndex>) ->
         }
     }
 
+    // Drop whole polygons, never half a quad
+    let boundary_faces = mesh.expand_to_polygons(&boundary_faces);
     selected.difference(&boundary_faces).copied().collect()
 }
 
@@ -104,7 +106,7 @@ pub fn select_by_normal(
         }
     }
 
-    result
+    mesh.expand_to_polygons(&result)
 }
 
 /// Grow vertex selection by one ring of adjacent vertices.
@@ -7,7 +7,7 @@ use bevy::prelude::*;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::cmp::Ordering;
 
-use super::edit_mesh::EditMesh;
+use super::edit_mesh::{EditMesh, VertexAttributes};
 
 /// Simplify a mesh by collapsing edges until the triangle count is reduced
 /// to `target_ratio` of the original (0.0–1.0).
@@ -298,6 +298,9 @@ impl SimplifyState {
             normals: new_normals,
             uvs: new_uvs,
             triangles: new_triangles,
+            faces: Vec::new(),
+            face_materials: Vec::new(),
+            attributes: VertexAttributes::default(),
         };
         mesh.recompute_normals();
         mesh
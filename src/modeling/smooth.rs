@@ -6,7 +6,7 @@
 use bevy::prelude::*;
 use std::collections::{HashMap, HashSet};
 
-use super::edit_mesh::EditMesh;
+use super::edit_mesh::{EditMesh, VertexAttributes};
 
 /// Laplacian smoothing: iteratively move each vertex toward the average
 /// of its neighbors.
@@ -65,6 +65,7 @@ pub fn subdivide_mesh(mesh: &EditMesh) -> EditMesh {
     let mut positions = mesh.positions.clone();
     let mut normals = mesh.normals.clone();
     let mut uvs = mesh.uvs.clone();
+    let mut attributes = mesh.attributes.clone();
     let mut triangles = Vec::new();
 
     // Cache: edge → midpoint vertex index (to avoid duplicates)
@@ -74,6 +75,7 @@ pub fn subdivide_mesh(mesh: &EditMesh) -> EditMesh {
                             positions: &mut Vec<Vec3>,
                             normals: &mut Vec<Vec3>,
                             uvs: &mut Vec<Vec2>,
+                            attributes: &mut VertexAttributes,
                             cache: &mut HashMap<(u32, u32), u32>|
      -> u32 {
         let key = if a <= b { (a, b) } else { (b, a) };
@@ -87,15 +89,40 @@ pub fn subdivide_mesh(mesh: &EditMesh) -> EditMesh {
                 .normalize_or_zero(),
         );
         uvs.push((uvs[a as usize] + uvs[b as usize]) * 0.5);
+        attributes.push_lerp(a, b, 0.5);
         cache.insert(key, idx);
         idx
     };
 
     for tri in &mesh.triangles {
         let [a, b, c] = *tri;
-        let ab = get_midpoint(a, b, &mut positions, &mut normals, &mut uvs, &mut edge_midpoints);
-        let bc = get_midpoint(b, c, &mut positions, &mut normals, &mut uvs, &mut edge_midpoints);
-        let ca = get_midpoint(c, a, &mut positions, &mut normals, &mut uvs, &mut edge_midpoints);
+        let ab = get_midpoint(
+            a,
+            b,
+            &mut positions,
+            &mut normals,
+            &mut uvs,
+            &mut attributes,
+            &mut edge_midpoints,
+        );
+        let bc = get_midpoint(
+            b,
+            c,
+            &mut positions,
+            &mut normals,
+            &mut uvs,
+            &mut attributes,
+            &mut edge_midpoints,
+        );
+        let ca = get_midpoint(
+            c,
+            a,
+            &mut positions,
+            &mut normals,
+            &mut uvs,
+            &mut attributes,
+            &mut edge_midpoints,
+        );
 
         triangles.push([a, ab, ca]);
         triangles.push([ab, b, bc]);
@@ -103,11 +130,24 @@ pub fn subdivide_mesh(mesh: &EditMesh) -> EditMesh {
         triangles.push([ab, bc, ca]);
     }
 
+    // Each triangle's 4 children keep its material slot
+    let face_materials = if mesh.has_face_materials() {
+        mesh.triangle_materials()
+            .into_iter()
+            .flat_map(|slot| [slot; 4])
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     let mut result = EditMesh {
         positions,
         normals,
         uvs,
         triangles,
+        faces: Vec::new(),
+        face_materials,
+        attributes,
     };
     result.recompute_normals();
     result
@@ -111,9 +111,39 @@ pub fn weld_vertices(
         }
     }
 
+    // Polygon meshes: remap face loops, drop repeated corners, re-triangulate
+    if mesh.has_polygons() {
+        let faces = mesh
+            .faces
+            .iter()
+            .map(|face| {
+                let mut remapped: Vec<u32> = face
+                    .iter()
+                    .map(|v| remap.get(v).copied().unwrap_or(*v))
+                    .collect();
+                remapped.dedup();
+                if remapped.len() > 1 && remapped.first() == remapped.last() {
+                    remapped.pop();
+                }
+                remapped
+            })
+            .collect();
+        let mut result = EditMesh::from_polygons(
+            new_positions,
+            new_normals,
+            new_uvs,
+            faces,
+            mesh.polygon_materials(),
+        );
+        result.attributes = mesh.attributes.clone();
+        result.recompute_normals();
+        return result;
+    }
+
     // Remap triangles and remove degenerates
     let mut new_triangles = Vec::with_capacity(mesh.triangles.len());
-    for tri in &mesh.triangles {
+    let mut new_materials = Vec::with_capacity(mesh.triangles.len());
+    for (tri, slot) in mesh.triangles.iter().zip(mesh.triangle_materials()) {
         let mut new_tri = *tri;
         for v in new_tri.iter_mut() {
             if let Some(&target) = remap.get(v) {
@@ -123,6 +153,7 @@ pub fn weld_vertices(
         // Skip degenerate triangles
         if new_tri[0] != new_tri[1] && new_tri[1] != new_tri[2] && new_tri[2] != new_tri[0] {
             new_triangles.push(new_tri);
+            new_materials.push(slot);
         }
     }
 
@@ -131,7 +162,13 @@ pub fn weld_vertices(
         normals: new_normals,
         uvs: new_uvs,
         triangles: new_triangles,
+        faces: Vec::new(),
+        face_materials: Vec::new(),
+        attributes: mesh.attributes.clone(),
     };
+    if new_materials.iter().any(|&m| m != 0) {
+        result.face_materials = new_materials;
+    }
     result.recompute_normals();
     result
 }
@@ -4,8 +4,10 @@ use std::collections::HashMap;
 use std::fs;
 
 use super::assets::{bundle_assets_into_prefab, collect_asset_paths, remap_material_library};
+use super::overrides::{attach_override_values, detach_override_values};
+use super::prefab::{PrefabInstance, PrefabRoot};
 use super::registry::PrefabRegistry;
-use super::spawn::{CreatePrefabEvent, SpawnPrefabEvent};
+use super::spawn::{CreatePrefabEvent, CreatePrefabVariantEvent, SpawnPrefabEvent};
 use crate::scene::{build_editor_scene, SceneEntity};
 use crate::selection::Selected;
 
@@ -70,8 +72,17 @@ impl Command for CreatePrefabCommand {
         let mut prefab_materials = collect_referenced_materials(world, &valid_entities, &material_library);
         remap_material_library(&mut prefab_materials, &remap);
 
+        // Prefab instances among the selection are kept as references
+        let nested_prefabs: Vec<Entity> = valid_entities
+            .iter()
+            .filter(|&&e| world.entity(e).contains::<PrefabRoot>())
+            .copied()
+            .collect();
+        attach_override_values(world, &nested_prefabs);
+
         // Build scene from the selected entities
         let scene = build_editor_scene(world, valid_entities.into_iter());
+        detach_override_values(world, &nested_prefabs);
 
         // Serialize
         let type_registry = world.resource::<AppTypeRegistry>().clone();
@@ -91,6 +102,7 @@ impl Command for CreatePrefabCommand {
                     &self.name,
                     &prefab_materials,
                     &HashMap::new(),
+                    None,
                 ) {
                     error!("Failed to write prefab metadata: {}", e);
                     return;
@@ -128,6 +140,105 @@ impl Command for CreatePrefabCommand {
     }
 }
 
+/// Command to create a variant prefab from a prefab instance: the instance's
+/// overrides become the variant, which references the base prefab.
+struct CreatePrefabVariantCommand {
+    name: String,
+    instance: Entity,
+}
+
+impl Command for CreatePrefabVariantCommand {
+    fn apply(self, world: &mut World) {
+        let Some(base) = world
+            .get::<PrefabInstance>(self.instance)
+            .filter(|_| world.entity(self.instance).contains::<PrefabRoot>())
+            .map(|instance| instance.prefab_name.clone())
+        else {
+            warn!("Select the root of a prefab instance to create a variant");
+            return;
+        };
+        if self.name == base {
+            warn!("A prefab variant needs a different name than its base '{}'", base);
+            return;
+        }
+
+        let prefab_dir = world.resource::<PrefabRegistry>().root_directory.join(&self.name);
+        if let Err(e) = fs::create_dir_all(&prefab_dir) {
+            error!("Failed to create prefab directory: {}", e);
+            return;
+        }
+
+        attach_override_values(world, &[self.instance]);
+        let mut scene = build_editor_scene(world, std::iter::once(self.instance));
+        detach_override_values(world, &[self.instance]);
+
+        // The variant is placed by its own instances; keep the base at its origin.
+        // Its members come from the base prefab, so the root is saved without hierarchy.
+        for dynamic_entity in &mut scene.entities {
+            dynamic_entity
+                .components
+                .retain(|c| !c.represents::<ChildOf>() && !c.represents::<Children>());
+            for component in &mut dynamic_entity.components {
+                if component.represents::<Transform>() {
+                    *component = Box::new(Transform::IDENTITY);
+                }
+            }
+        }
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        match scene.serialize(&type_registry) {
+            Ok(serialized) => {
+                let scene_path = prefab_dir.join(format!("{}.scn.ron", self.name));
+                if let Err(e) = fs::write(&scene_path, &serialized) {
+                    error!("Failed to write prefab scene: {}", e);
+                    return;
+                }
+
+                if let Err(e) = PrefabRegistry::save_metadata(
+                    &prefab_dir,
+                    &self.name,
+                    &MaterialLibrary::default(),
+                    &HashMap::new(),
+                    Some(&base),
+                ) {
+                    error!("Failed to write prefab metadata: {}", e);
+                    return;
+                }
+
+                info!("Created prefab '{}' as a variant of '{}'", self.name, base);
+            }
+            Err(e) => {
+                error!("Failed to serialize prefab scene: {:?}", e);
+                return;
+            }
+        }
+
+        drop(type_registry);
+
+        if let Some(mut registry) = world.get_resource_mut::<PrefabRegistry>() {
+            registry.refresh();
+        }
+
+        // Replace the instance with an instance of the variant
+        let transform = world
+            .get::<GlobalTransform>(self.instance)
+            .map(|t| t.compute_transform())
+            .unwrap_or_default();
+        if let Ok(mut e) = world.get_entity_mut(self.instance) {
+            e.remove::<Selected>();
+        }
+        world.despawn(self.instance);
+
+        world.write_message(SpawnPrefabEvent {
+            prefab_name: self.name.clone(),
+            position: transform.translation,
+            rotation: transform.rotation,
+        });
+    }
+}
+
 /// Collect materials referenced by the given entities into a subset library.
 fn collect_referenced_materials(
     world: &World,
@@ -180,3 +291,15 @@ pub fn handle_create_prefab(
         });
     }
 }
+
+pub fn handle_create_prefab_variant(
+    mut events: MessageReader<CreatePrefabVariantEvent>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        commands.queue(CreatePrefabVariantCommand {
+            name: event.name.clone(),
+            instance: event.instance,
+        });
+    }
+}
@@ -1,6 +1,7 @@
 mod assets;
 mod create;
 mod editing;
+mod overrides;
 mod prefab;
 mod registry;
 mod spawn;
@@ -9,16 +10,22 @@ pub use assets::{
     bundle_assets_into_prefab, collect_asset_paths, remap_material_library,
     resolve_prefab_asset_path,
 };
-pub use create::handle_create_prefab;
+pub use create::{handle_create_prefab, handle_create_prefab_variant};
 pub use editing::{PrefabEditingContext, PrefabOpenConfirmDialog};
-pub use prefab::{PrefabInstance, PrefabRoot};
-pub use registry::{PrefabEntry, PrefabRegistry};
+pub(crate) use overrides::{attach_override_values, detach_override_values};
+pub use overrides::{ApplyPrefabOverrideEvent, PrefabSourceCache, RevertPrefabOverrideEvent};
+pub use prefab::{
+    PrefabInstance, PrefabOverride, PrefabOverrideValue, PrefabOverrideValues, PrefabOverrides,
+    PrefabRoot,
+};
+pub use registry::{PrefabEntry, PrefabRegistry, ResolvedPrefab};
 pub use spawn::{
-    handle_spawn_prefab, ClosePrefabEvent, CreatePrefabEvent, OpenPrefabEvent, SpawnPrefabEvent,
+    handle_spawn_prefab, ClosePrefabEvent, CreatePrefabEvent, CreatePrefabVariantEvent,
+    OpenPrefabEvent, SpawnPrefabEvent,
 };
 
 use bevy::prelude::*;
-use bevy_editor_game::SpawnPrefabRequest;
+use bevy_editor_game::{GameState, SpawnPrefabRequest};
 use bevy_egui::EguiPrimaryContextPass;
 
 pub struct PrefabsPlugin;
@@ -27,23 +34,39 @@ impl Plugin for PrefabsPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<PrefabInstance>()
             .register_type::<PrefabRoot>()
+            .register_type::<PrefabOverrides>()
+            .register_type::<PrefabOverrideValues>()
             .init_resource::<PrefabRegistry>()
             .init_resource::<PrefabOpenConfirmDialog>()
+            .init_resource::<PrefabSourceCache>()
             .add_message::<SpawnPrefabEvent>()
             .add_message::<CreatePrefabEvent>()
+            .add_message::<CreatePrefabVariantEvent>()
             .add_message::<OpenPrefabEvent>()
             .add_message::<ClosePrefabEvent>()
             .add_message::<SpawnPrefabRequest>()
+            .add_message::<RevertPrefabOverrideEvent>()
+            .add_message::<ApplyPrefabOverrideEvent>()
             .add_systems(PreStartup, registry::scan_prefab_directory)
             .add_systems(
                 Update,
                 (
                     handle_spawn_prefab,
                     handle_create_prefab,
+                    handle_create_prefab_variant,
+                    spawn::expand_prefab_references,
                     editing::handle_open_prefab,
                     editing::handle_close_prefab,
                     editing::check_open_after_save,
                     forward_game_prefab_requests,
+                    overrides::handle_revert_prefab_override,
+                    overrides::handle_apply_prefab_override,
+                ),
+            )
+            .add_systems(
+                Update,
+                overrides::sync_prefab_instances.run_if(
+                    in_state(GameState::Editing).and(any_with_component::<PrefabInstance>),
                 ),
             )
             .add_systems(
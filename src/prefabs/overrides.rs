@@ -0,0 +1,1353 @@
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+use bevy::reflect::serde::{ReflectDeserializer, ReflectSerializer};
+use bevy::reflect::{ReflectPath, ReflectRef, TypePath, TypeRegistry};
+use bevy::scene::serde::SceneDeserializer;
+use bevy::scene::DynamicEntity;
+use bevy_editor_game::SceneComponentRegistry;
+use serde::de::DeserializeSeed;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use super::prefab::{
+    PrefabInstance, PrefabOverride, PrefabOverrideValue, PrefabOverrideValues, PrefabOverrides,
+    PrefabRoot,
+};
+use super::registry::PrefabRegistry;
+use crate::commands::RecordEntitiesCommand;
+use crate::scene::{
+    build_editor_scene, clear_derived_components, despawn_scene_entities,
+    regenerate_runtime_components, resolve_entity_references, serialize_scene_stable, PersistentId,
+};
+
+/// How often prefab scene files are checked for changes on disk, in seconds
+const SOURCE_POLL_INTERVAL: f64 = 1.0;
+
+/// Event to revert a prefab instance entity's override back to the prefab value.
+/// `target: None` reverts all of the entity's overrides.
+#[derive(Message)]
+pub struct RevertPrefabOverrideEvent {
+    pub entity: Entity,
+    pub target: Option<PrefabOverride>,
+}
+
+/// Event to write a prefab instance entity's override back into the prefab file.
+/// `target: None` applies all of the entity's overrides.
+#[derive(Message)]
+pub struct ApplyPrefabOverrideEvent {
+    pub entity: Entity,
+    pub target: Option<PrefabOverride>,
+}
+
+/// A prefab scene entity, keyed by [`source_key`] in [`PrefabSource`]
+struct SourceEntity {
+    parent: Option<u32>,
+    /// Root of a nested prefab reference; its contents come from the other prefab
+    nested: bool,
+    /// Tracked components only (see [`is_tracked`])
+    components: Vec<Box<dyn PartialReflect>>,
+}
+
+/// Parsed contents of a prefab scene file (resolved against its base for a variant)
+struct PrefabSource {
+    hash: u64,
+    /// Modification times of the files in [`PrefabRegistry::scene_paths`]
+    modified: Vec<Option<SystemTime>>,
+    entities: BTreeMap<u32, SourceEntity>,
+}
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+impl PrefabSource {
+    fn load(world: &World, prefab_name: &str) -> Result<Self, String> {
+        let registry = world.resource::<PrefabRegistry>();
+        let modified = modified_times(&registry.scene_paths(prefab_name));
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let (scene, hash) = registry.load_scene(prefab_name, &[], &type_registry.read())?;
+
+        let keys: EntityHashMap<u32> = scene
+            .entities
+            .iter()
+            .map(|entity| (entity.entity, source_key(entity)))
+            .collect();
+
+        let mut entities = BTreeMap::new();
+        for entity in scene.entities {
+            let parent = entity
+                .components
+                .iter()
+                .find(|c| c.represents::<ChildOf>())
+                .and_then(|c| ChildOf::from_reflect(c.as_ref()))
+                .and_then(|child_of| keys.get(&child_of.parent()).copied());
+            let nested = nested_reference(&entity).is_some();
+            let components = entity
+                .components
+                .into_iter()
+                .filter(|c| is_tracked(component_type_path(c.as_ref())))
+                .collect();
+            entities.insert(
+                keys[&entity.entity],
+                SourceEntity {
+                    parent,
+                    nested,
+                    components,
+                },
+            );
+        }
+
+        Ok(Self {
+            hash,
+            modified,
+            entities,
+        })
+    }
+}
+
+/// Cache of parsed prefab scenes used to track and re-sync prefab instances
+#[derive(Resource, Default)]
+pub struct PrefabSourceCache {
+    sources: HashMap<String, PrefabSource>,
+    last_poll: f64,
+}
+
+impl PrefabSourceCache {
+    /// Drop a cached prefab so it is re-read on the next sync
+    pub fn invalidate(&mut self, prefab_name: &str) {
+        self.sources.remove(prefab_name);
+    }
+
+    /// Load the prefab if it isn't cached, or reload it if `poll` is set and one of
+    /// its files (its own, or a variant's bases) changed
+    fn refresh(&mut self, world: &World, prefab_name: &str, poll: bool) {
+        let scene_paths = world.resource::<PrefabRegistry>().scene_paths(prefab_name);
+        if scene_paths.is_empty() {
+            return;
+        }
+
+        if let Some(source) = self.sources.get(prefab_name) {
+            if !poll || modified_times(&scene_paths) == source.modified {
+                return;
+            }
+        }
+
+        match PrefabSource::load(world, prefab_name) {
+            Ok(source) => {
+                if self
+                    .sources
+                    .get(prefab_name)
+                    .is_some_and(|old| old.hash != source.hash)
+                {
+                    info!("Prefab '{}' changed on disk, re-syncing instances", prefab_name);
+                }
+                self.sources.insert(prefab_name.to_string(), source);
+            }
+            Err(e) => warn!("Failed to load prefab '{}': {}", prefab_name, e),
+        }
+    }
+}
+
+/// Hash of a prefab scene file's contents, recorded on synced instance entities.
+///
+/// FNV-1a over the bytes: the hash is saved with scenes, so it must not change
+/// between runs or Rust versions the way `DefaultHasher` may.
+pub(crate) fn content_hash(content: &str) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    content.bytes().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Hash of a variant prefab: its own file combined with its resolved base
+pub(crate) fn variant_hash(content: &str, base_hash: u64) -> u64 {
+    content_hash(&format!("{:016x}{}", base_hash, content))
+}
+
+/// Key of an entity in a prefab scene: its `PersistentId`, or its scene entity
+/// index for prefabs saved without persistent IDs.
+pub(crate) fn source_key(entity: &DynamicEntity) -> u32 {
+    entity
+        .components
+        .iter()
+        .find(|c| c.represents::<PersistentId>())
+        .and_then(|c| PersistentId::from_reflect(c.as_ref()))
+        .map(|id| id.0)
+        .unwrap_or_else(|| entity.entity.index_u32())
+}
+
+/// Name of the prefab referenced by a nested prefab root in a prefab scene
+pub(crate) fn nested_reference(entity: &DynamicEntity) -> Option<String> {
+    if !entity.components.iter().any(|c| c.represents::<PrefabRoot>()) {
+        return None;
+    }
+    entity
+        .components
+        .iter()
+        .find(|c| c.represents::<PrefabInstance>())
+        .and_then(|c| PrefabInstance::from_reflect(c.as_ref()))
+        .map(|instance| instance.prefab_name)
+}
+
+pub(crate) fn deserialize_prefab_scene(
+    type_registry: &TypeRegistry,
+    content: &str,
+) -> Result<DynamicScene, String> {
+    let scene_deserializer = SceneDeserializer { type_registry };
+
+    let mut ron_deserializer = ron::de::Deserializer::from_str(content)
+        .map_err(|e| format!("Failed to parse prefab scene: {}", e))?;
+    scene_deserializer
+        .deserialize(&mut ron_deserializer)
+        .map_err(|e| format!("Failed to deserialize prefab scene: {}", e))
+}
+
+fn component_type_path(component: &dyn PartialReflect) -> &str {
+    component
+        .get_represented_type_info()
+        .map(|info| info.type_path())
+        .unwrap_or_else(|| component.reflect_type_path())
+}
+
+/// Whether a component is prefab data, as opposed to hierarchy or prefab bookkeeping
+fn is_tracked(type_path: &str) -> bool {
+    ![
+        ChildOf::type_path(),
+        Children::type_path(),
+        PersistentId::type_path(),
+        PrefabInstance::type_path(),
+        PrefabRoot::type_path(),
+        PrefabOverrides::type_path(),
+        PrefabOverrideValues::type_path(),
+    ]
+    .contains(&type_path)
+}
+
+/// Editor components of a single entity, as they would be saved
+fn instance_components(world: &World, entity: Entity) -> Vec<Box<dyn PartialReflect>> {
+    build_editor_scene(world, std::iter::once(entity))
+        .entities
+        .into_iter()
+        .next()
+        .map(|e| e.components)
+        .unwrap_or_default()
+}
+
+fn find_component<'a>(
+    components: &'a [Box<dyn PartialReflect>],
+    type_path: &str,
+) -> Option<&'a dyn PartialReflect> {
+    components
+        .iter()
+        .find(|c| component_type_path(c.as_ref()) == type_path)
+        .map(|c| c.as_ref())
+}
+
+/// Compare an instance entity's components against its prefab source entity
+fn compute_overrides(
+    instance: &[Box<dyn PartialReflect>],
+    source: &SourceEntity,
+) -> Vec<PrefabOverride> {
+    let mut overrides = Vec::new();
+
+    for component in instance {
+        let type_path = component_type_path(component.as_ref());
+        if !is_tracked(type_path) {
+            continue;
+        }
+        match find_component(&source.components, type_path) {
+            Some(source_component) => diff_values(
+                type_path,
+                String::new(),
+                component.as_ref(),
+                source_component,
+                &mut overrides,
+            ),
+            // Added on the instance
+            None => overrides.push(PrefabOverride {
+                component: type_path.to_string(),
+                field: String::new(),
+            }),
+        }
+    }
+
+    // Removed from the instance
+    for component in &source.components {
+        let type_path = component_type_path(component.as_ref());
+        if find_component(instance, type_path).is_none() {
+            overrides.push(PrefabOverride {
+                component: type_path.to_string(),
+                field: String::new(),
+            });
+        }
+    }
+
+    overrides.sort_by(|a, b| (&a.component, &a.field).cmp(&(&b.component, &b.field)));
+    overrides
+}
+
+/// Recursively compare two values, recording the paths of differing leaf fields.
+/// Structs and tuples are compared field by field; anything else as a whole.
+fn diff_values(
+    component: &str,
+    path: String,
+    instance: &dyn PartialReflect,
+    source: &dyn PartialReflect,
+    out: &mut Vec<PrefabOverride>,
+) {
+    match (instance.reflect_ref(), source.reflect_ref()) {
+        (ReflectRef::Struct(a), ReflectRef::Struct(b)) if a.field_len() == b.field_len() => {
+            for i in 0..a.field_len() {
+                let (Some(name), Some(field)) = (a.name_at(i), a.field_at(i)) else {
+                    continue;
+                };
+                let field_path = format!("{}.{}", path, name);
+                match b.field(name) {
+                    Some(source_field) => diff_values(component, field_path, field, source_field, out),
+                    None => out.push(PrefabOverride {
+                        component: component.to_string(),
+                        field: field_path,
+                    }),
+                }
+            }
+            return;
+        }
+        (ReflectRef::TupleStruct(a), ReflectRef::TupleStruct(b)) if a.field_len() == b.field_len() => {
+            for i in 0..a.field_len() {
+                if let (Some(field), Some(source_field)) = (a.field(i), b.field(i)) {
+                    diff_values(component, format!("{}.{}", path, i), field, source_field, out);
+                }
+            }
+            return;
+        }
+        (ReflectRef::Tuple(a), ReflectRef::Tuple(b)) if a.field_len() == b.field_len() => {
+            for i in 0..a.field_len() {
+                if let (Some(field), Some(source_field)) = (a.field(i), b.field(i)) {
+                    diff_values(component, format!("{}.{}", path, i), field, source_field, out);
+                }
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    if instance.reflect_partial_eq(source) != Some(true) {
+        out.push(PrefabOverride {
+            component: component.to_string(),
+            field: path,
+        });
+    }
+}
+
+fn reflect_component(registry: &TypeRegistry, type_path: &str) -> Option<ReflectComponent> {
+    registry
+        .get_with_type_path(type_path)
+        .and_then(|r| r.data::<ReflectComponent>())
+        .cloned()
+}
+
+/// Copy the field at `path` from one value into another. Returns whether it was copied.
+fn copy_field(path: &str, from: &dyn PartialReflect, to: &mut dyn PartialReflect) -> bool {
+    let Ok(value) = path.reflect_element(from) else {
+        return false;
+    };
+    let Ok(target) = path.reflect_element_mut(to) else {
+        return false;
+    };
+    match target.try_apply(value) {
+        Ok(()) => true,
+        Err(e) => {
+            warn!("Failed to copy prefab field '{}': {}", path, e);
+            false
+        }
+    }
+}
+
+fn serialize_value(registry: &TypeRegistry, value: &dyn PartialReflect) -> Option<String> {
+    let serializer = ReflectSerializer::new(value, registry);
+    ron::to_string(&serializer)
+        .map_err(|e| warn!("Failed to serialize prefab override value: {}", e))
+        .ok()
+}
+
+fn deserialize_value(registry: &TypeRegistry, value: &str) -> Option<Box<dyn PartialReflect>> {
+    let mut ron_deserializer = ron::de::Deserializer::from_str(value).ok()?;
+    ReflectDeserializer::new(registry)
+        .deserialize(&mut ron_deserializer)
+        .map_err(|e| warn!("Failed to deserialize prefab override value: {}", e))
+        .ok()
+}
+
+/// Members of the instance rooted at `root`, with their source keys
+fn instance_members(world: &mut World, root: Entity) -> Vec<(Entity, u32)> {
+    let Some(root_instance) = world.get::<PrefabInstance>(root).cloned() else {
+        return Vec::new();
+    };
+    let mut query = world.query::<(Entity, &PrefabInstance)>();
+    query
+        .iter(world)
+        .filter(|(_, instance)| {
+            instance.prefab_name == root_instance.prefab_name
+                && instance.instance_id == root_instance.instance_id
+        })
+        .filter_map(|(entity, instance)| instance.source_id.map(|key| (entity, key)))
+        .collect()
+}
+
+/// Record the overridden values of each prefab instance rooted at `roots` on the root
+/// itself, so the instance can be saved by reference inside another prefab.
+pub(crate) fn attach_override_values(world: &mut World, roots: &[Entity]) {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    // Gather every root's values before attaching any: attached roots hide their
+    // members from `build_editor_scene`
+    let mut attached = Vec::new();
+    for &root in roots {
+        let mut members = instance_members(world, root);
+        members.sort_by_key(|&(_, key)| key);
+
+        let mut values = Vec::new();
+        for (entity, key) in members {
+            let Some(tracked) = world.get::<PrefabOverrides>(entity) else {
+                continue;
+            };
+            let targets = tracked.overrides.clone();
+            let current = instance_components(world, entity);
+            for target in targets {
+                let value = find_component(&current, &target.component).and_then(|component| {
+                    if target.field.is_empty() {
+                        Some(component)
+                    } else {
+                        target.field.as_str().reflect_element(component).ok()
+                    }
+                });
+                // A missing field can't be expressed; only whole components may be absent
+                if value.is_none() && !target.field.is_empty() {
+                    continue;
+                }
+                values.push(PrefabOverrideValue {
+                    source_id: key,
+                    value: value.and_then(|v| serialize_value(&type_registry, v)),
+                    target,
+                });
+            }
+        }
+
+        attached.push((root, PrefabOverrideValues { values }));
+    }
+
+    for (root, values) in attached {
+        world.entity_mut(root).insert(values);
+    }
+}
+
+/// Remove the values recorded by [`attach_override_values`]
+pub(crate) fn detach_override_values(world: &mut World, roots: &[Entity]) {
+    for &root in roots {
+        if let Ok(mut entity_mut) = world.get_entity_mut(root) {
+            entity_mut.remove::<PrefabOverrideValues>();
+        }
+    }
+}
+
+/// Apply recorded override values to the freshly spawned instance rooted at `root`
+pub(crate) fn apply_override_values(world: &mut World, root: Entity, values: &PrefabOverrideValues) {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let members: HashMap<u32, Entity> = instance_members(world, root)
+        .into_iter()
+        .map(|(entity, key)| (key, entity))
+        .collect();
+
+    for value in &values.values {
+        let Some(&entity) = members.get(&value.source_id) else {
+            warn!(
+                "Override on {} refers to an entity that is no longer in the prefab",
+                value.target.label()
+            );
+            continue;
+        };
+        let Some(reflect_component) = reflect_component(&type_registry, &value.target.component)
+        else {
+            continue;
+        };
+        let data = value
+            .value
+            .as_deref()
+            .and_then(|v| deserialize_value(&type_registry, v));
+
+        if value.target.field.is_empty() {
+            let mut entity_mut = world.entity_mut(entity);
+            match data {
+                Some(data) => reflect_component.insert(&mut entity_mut, data.as_ref(), &type_registry),
+                None => reflect_component.remove(&mut entity_mut),
+            }
+        } else if let Some(data) = data {
+            if let Some(mut component) = reflect_component.reflect_mut(world.entity_mut(entity)) {
+                if let Ok(field) = value
+                    .target
+                    .field
+                    .as_str()
+                    .reflect_element_mut(component.as_partial_reflect_mut())
+                {
+                    if let Err(e) = field.try_apply(data.as_ref()) {
+                        warn!("Failed to apply override {}: {}", value.target.label(), e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Apply recorded override values to the entities of a prefab scene, matched by
+/// [`source_key`]. Used to resolve a variant against its base.
+pub(crate) fn apply_values_to_scene(
+    scene: &mut DynamicScene,
+    values: &PrefabOverrideValues,
+    type_registry: &TypeRegistry,
+) {
+    for value in &values.values {
+        let Some(entity) = scene
+            .entities
+            .iter_mut()
+            .find(|entity| source_key(entity) == value.source_id)
+        else {
+            warn!(
+                "Override on {} refers to an entity that is no longer in the prefab",
+                value.target.label()
+            );
+            continue;
+        };
+        let data = value
+            .value
+            .as_deref()
+            .and_then(|v| deserialize_value(type_registry, v));
+        let index = entity
+            .components
+            .iter()
+            .position(|c| component_type_path(c.as_ref()) == value.target.component);
+
+        if value.target.field.is_empty() {
+            match (data, index) {
+                (Some(data), Some(index)) => entity.components[index] = data,
+                (Some(data), None) => entity.components.push(data),
+                (None, Some(index)) => {
+                    entity.components.remove(index);
+                }
+                (None, None) => {}
+            }
+        } else if let (Some(data), Some(index)) = (data, index) {
+            if let Ok(field) = value
+                .target
+                .field
+                .as_str()
+                .reflect_element_mut(entity.components[index].as_mut())
+            {
+                if let Err(e) = field.try_apply(data.as_ref()) {
+                    warn!("Failed to apply override {}: {}", value.target.label(), e);
+                }
+            }
+        }
+    }
+}
+
+/// Entities of one prefab instance currently in the world
+#[derive(Default)]
+struct InstanceMembers {
+    root: Option<Entity>,
+    /// Entities with a prefab source, and their source key
+    members: Vec<(Entity, u32)>,
+}
+
+/// Bring an instance up to date with its prefab source, keeping overridden values.
+/// Returns the entities that now exist for the instance's source entities.
+fn resync_instance(
+    world: &mut World,
+    registry: &TypeRegistry,
+    source: &PrefabSource,
+    prefab_name: &str,
+    instance_id: &str,
+    members: &InstanceMembers,
+) -> Vec<(Entity, u32)> {
+    let mut synced: Vec<(Entity, u32)> = Vec::new();
+    let mut removed: Vec<Entity> = Vec::new();
+
+    for &(entity, key) in &members.members {
+        let Some(source_entity) = source.entities.get(&key) else {
+            // Deleted from the prefab
+            removed.push(entity);
+            continue;
+        };
+
+        let current = instance_components(world, entity);
+        let overrides = world
+            .get::<PrefabOverrides>(entity)
+            .map(|o| o.overrides.clone())
+            .unwrap_or_default();
+        let kept: HashSet<&str> = overrides
+            .iter()
+            .filter(|o| o.field.is_empty())
+            .map(|o| o.component.as_str())
+            .collect();
+
+        for component in &source_entity.components {
+            let type_path = component_type_path(component.as_ref());
+            if kept.contains(type_path) {
+                continue;
+            }
+            let Some(reflect_component) = reflect_component(registry, type_path) else {
+                continue;
+            };
+
+            let mut value = component.to_dynamic();
+            if let Some(instance_value) = find_component(&current, type_path) {
+                for o in overrides.iter().filter(|o| o.component == type_path) {
+                    copy_field(&o.field, instance_value, value.as_mut());
+                }
+            }
+            reflect_component.insert(&mut world.entity_mut(entity), value.as_ref(), registry);
+        }
+
+        // Components the prefab no longer has, unless the instance added them itself
+        for component in &current {
+            let type_path = component_type_path(component.as_ref());
+            if !is_tracked(type_path)
+                || kept.contains(type_path)
+                || find_component(&source_entity.components, type_path).is_some()
+            {
+                continue;
+            }
+            if let Some(reflect_component) = reflect_component(registry, type_path) {
+                reflect_component.remove(&mut world.entity_mut(entity));
+            }
+        }
+
+        world.entity_mut(entity).insert(PrefabOverrides {
+            source_hash: source.hash,
+            overrides,
+        });
+        clear_derived_components(world, entity);
+        synced.push((entity, key));
+    }
+
+    despawn_scene_entities(world, &removed);
+
+    // Entities added to the prefab
+    let existing: HashSet<u32> = members.members.iter().map(|(_, key)| *key).collect();
+    let mut spawned: Vec<(Entity, u32)> = Vec::new();
+    for (&key, source_entity) in &source.entities {
+        if existing.contains(&key) || source_entity.nested {
+            continue;
+        }
+        let entity = world
+            .spawn((
+                PrefabInstance {
+                    prefab_name: prefab_name.to_string(),
+                    instance_id: instance_id.to_string(),
+                    source_id: Some(key),
+                },
+                PrefabOverrides {
+                    source_hash: source.hash,
+                    overrides: Vec::new(),
+                },
+            ))
+            .id();
+        for component in &source_entity.components {
+            let type_path = component_type_path(component.as_ref());
+            if let Some(reflect_component) = reflect_component(registry, type_path) {
+                reflect_component.insert(&mut world.entity_mut(entity), component.as_ref(), registry);
+            }
+        }
+        spawned.push((entity, key));
+    }
+
+    synced.extend_from_slice(&spawned);
+
+    // Parent new entities under their prefab parent, or the instance root
+    let by_key: HashMap<u32, Entity> = synced.iter().map(|&(e, key)| (key, e)).collect();
+    for &(entity, key) in &spawned {
+        let parent = source.entities[&key]
+            .parent
+            .and_then(|parent_key| by_key.get(&parent_key).copied())
+            .or(members.root);
+        if let Some(parent) = parent {
+            world.entity_mut(entity).insert(ChildOf(parent));
+        }
+    }
+
+    synced
+}
+
+/// Whether any of an entity's components changed since the system last ran
+fn changed_since_last_run(world: &World, entity: Entity) -> bool {
+    let (last_run, this_run) = (world.last_change_tick(), world.read_change_tick());
+    let entity_ref = world.entity(entity);
+    entity_ref.archetype().components().iter().any(|&id| {
+        entity_ref
+            .get_change_ticks_by_id(id)
+            .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+    })
+}
+
+/// Keep prefab instances in sync with their prefab files and track their overrides.
+///
+/// Instances synced against an older version of their prefab are re-synced first
+/// (keeping overridden values), then the overrides of any changed instance entity
+/// are recomputed against the prefab.
+pub fn sync_prefab_instances(world: &mut World) {
+    let mut instances: BTreeMap<(String, String), InstanceMembers> = BTreeMap::new();
+    {
+        let mut query = world.query::<(Entity, &PrefabInstance, Has<PrefabRoot>)>();
+        for (entity, instance, is_root) in query.iter(world) {
+            let members = instances
+                .entry((instance.prefab_name.clone(), instance.instance_id.clone()))
+                .or_default();
+            if is_root {
+                members.root = Some(entity);
+            } else if let Some(key) = instance.source_id {
+                members.members.push((entity, key));
+            }
+        }
+    }
+    if instances.is_empty() {
+        return;
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    let resynced = world.resource_scope(|world, mut cache: Mut<PrefabSourceCache>| {
+        let now = world.resource::<Time<Real>>().elapsed_secs_f64();
+        let poll = now - cache.last_poll >= SOURCE_POLL_INTERVAL;
+        if poll {
+            cache.last_poll = now;
+        }
+        let prefab_names: HashSet<&String> = instances.keys().map(|(name, _)| name).collect();
+        for prefab_name in prefab_names {
+            cache.refresh(world, prefab_name, poll);
+        }
+
+        let mut resynced = false;
+        for ((prefab_name, instance_id), members) in &mut instances {
+            let Some(source) = cache.sources.get(prefab_name) else {
+                continue;
+            };
+
+            // Entities without a recorded hash have never been diffed; treat them as current
+            let outdated = members.members.iter().any(|&(entity, _)| {
+                world
+                    .get::<PrefabOverrides>(entity)
+                    .is_some_and(|o| o.source_hash != source.hash)
+            });
+            if outdated {
+                members.members =
+                    resync_instance(world, &type_registry, source, prefab_name, instance_id, members);
+                resynced = true;
+            }
+        }
+
+        for ((prefab_name, _), members) in &instances {
+            let Some(source) = cache.sources.get(prefab_name) else {
+                continue;
+            };
+            for &(entity, key) in &members.members {
+                if world.get_entity(entity).is_err() || !changed_since_last_run(world, entity) {
+                    continue;
+                }
+                let Some(source_entity) = source.entities.get(&key) else {
+                    continue;
+                };
+
+                let tracked = PrefabOverrides {
+                    source_hash: source.hash,
+                    overrides: compute_overrides(&instance_components(world, entity), source_entity),
+                };
+                if world.get::<PrefabOverrides>(entity) != Some(&tracked) {
+                    world.entity_mut(entity).insert(tracked);
+                }
+            }
+        }
+
+        resynced
+    });
+
+    drop(type_registry);
+
+    if resynced {
+        regenerate_runtime_components(world);
+        resolve_entity_references(world);
+    }
+}
+
+/// Command to revert overrides on a prefab instance entity to the prefab's values
+struct RevertPrefabOverrideCommand {
+    entity: Entity,
+    target: Option<PrefabOverride>,
+}
+
+impl Command for RevertPrefabOverrideCommand {
+    fn apply(self, world: &mut World) {
+        let Some(instance) = world.get::<PrefabInstance>(self.entity).cloned() else {
+            return;
+        };
+        let Some(key) = instance.source_id else {
+            return;
+        };
+        let Some(tracked) = world.get::<PrefabOverrides>(self.entity).cloned() else {
+            return;
+        };
+        let targets = match self.target {
+            Some(target) => vec![target],
+            None => tracked.overrides.clone(),
+        };
+        if targets.is_empty() {
+            return;
+        }
+
+        RecordEntitiesCommand {
+            entities: vec![self.entity],
+            recursive: false,
+            description: "Revert prefab override".to_string(),
+        }
+        .apply(world);
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        world.resource_scope(|world, cache: Mut<PrefabSourceCache>| {
+            let Some(source_entity) = cache
+                .sources
+                .get(&instance.prefab_name)
+                .filter(|source| source.hash == tracked.source_hash)
+                .and_then(|source| source.entities.get(&key))
+            else {
+                warn!("Prefab '{}' is not loaded, can't revert", instance.prefab_name);
+                return;
+            };
+
+            for target in &targets {
+                let Some(reflect_component) = reflect_component(&type_registry, &target.component)
+                else {
+                    continue;
+                };
+                let source_value = find_component(&source_entity.components, &target.component);
+
+                if target.field.is_empty() {
+                    let mut entity_mut = world.entity_mut(self.entity);
+                    match source_value {
+                        Some(value) => reflect_component.insert(&mut entity_mut, value, &type_registry),
+                        None => reflect_component.remove(&mut entity_mut),
+                    }
+                } else if let Some(value) = source_value {
+                    if let Some(mut component) = reflect_component.reflect_mut(world.entity_mut(self.entity)) {
+                        copy_field(&target.field, value, component.as_partial_reflect_mut());
+                    }
+                }
+            }
+        });
+
+        drop(type_registry);
+
+        clear_derived_components(world, self.entity);
+        regenerate_runtime_components(world);
+        resolve_entity_references(world);
+
+        info!("Reverted {} prefab override(s) on {:?}", targets.len(), self.entity);
+    }
+}
+
+/// Command to write overrides on a prefab instance entity back into the prefab file.
+/// The other instances pick the change up on the next sync.
+struct ApplyPrefabOverrideCommand {
+    entity: Entity,
+    target: Option<PrefabOverride>,
+}
+
+impl Command for ApplyPrefabOverrideCommand {
+    fn apply(self, world: &mut World) {
+        let Some(instance) = world.get::<PrefabInstance>(self.entity).cloned() else {
+            return;
+        };
+        let Some(key) = instance.source_id else {
+            return;
+        };
+        let targets = match self.target {
+            Some(target) => vec![target],
+            None => world
+                .get::<PrefabOverrides>(self.entity)
+                .map(|o| o.overrides.clone())
+                .unwrap_or_default(),
+        };
+        if targets.is_empty() {
+            return;
+        }
+
+        let Some(scene_path) = world
+            .resource::<PrefabRegistry>()
+            .get(&instance.prefab_name)
+            .map(|entry| entry.scene_path.clone())
+        else {
+            warn!("Prefab not found: {}", instance.prefab_name);
+            return;
+        };
+
+        let content = match fs::read_to_string(&scene_path) {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Failed to read prefab scene '{}': {}", instance.prefab_name, e);
+                return;
+            }
+        };
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let mut scene = match deserialize_prefab_scene(&type_registry.read(), &content) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+
+        let base = world
+            .resource::<PrefabRegistry>()
+            .get(&instance.prefab_name)
+            .and_then(|entry| entry.base.clone());
+        let current = instance_components(world, self.entity);
+        let applied = match &base {
+            // A variant stores its changes as values on its base reference
+            Some(base) => {
+                apply_to_variant(&mut scene, base, key, &current, &targets, &type_registry.read())
+            }
+            None => apply_to_source(&mut scene, key, &current, &targets),
+        };
+        let Some(skipped) = applied else {
+            warn!(
+                "Entity {} no longer exists in prefab '{}'",
+                key, instance.prefab_name
+            );
+            return;
+        };
+
+        if !skipped.is_empty() {
+            warn!(
+                "Could not apply {} override(s) to prefab '{}': {}",
+                skipped.len(),
+                instance.prefab_name,
+                skipped.join(", ")
+            );
+        }
+        if skipped.len() == targets.len() {
+            return;
+        }
+
+        let serialized = match serialize_prefab_scene(world, scene) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+        if let Err(e) = fs::write(&scene_path, serialized) {
+            error!("Failed to write prefab scene: {}", e);
+            return;
+        }
+
+        world
+            .resource_mut::<PrefabSourceCache>()
+            .invalidate(&instance.prefab_name);
+
+        info!(
+            "Applied {} override(s) to prefab '{}'",
+            targets.len() - skipped.len(),
+            instance.prefab_name
+        );
+    }
+}
+
+/// Write instance values into the prefab scene entity with source key `key`.
+/// Returns the labels of overrides that couldn't be applied, or `None` when the
+/// entity is no longer in the prefab.
+fn apply_to_source(
+    scene: &mut DynamicScene,
+    key: u32,
+    current: &[Box<dyn PartialReflect>],
+    targets: &[PrefabOverride],
+) -> Option<Vec<String>> {
+    let source_entity = scene.entities.iter_mut().find(|e| source_key(e) == key)?;
+
+    let mut skipped = Vec::new();
+    for target in targets {
+        let instance_value = find_component(current, &target.component);
+        let source_index = source_entity
+            .components
+            .iter()
+            .position(|c| component_type_path(c.as_ref()) == target.component);
+
+        let applied = match (target.field.is_empty(), instance_value, source_index) {
+            (true, Some(value), Some(index)) => {
+                source_entity.components[index] = value.to_dynamic();
+                true
+            }
+            (true, Some(value), None) => {
+                source_entity.components.push(value.to_dynamic());
+                true
+            }
+            (true, None, Some(index)) => {
+                source_entity.components.remove(index);
+                true
+            }
+            // Removed from the instance and already absent from the prefab
+            (true, None, None) => true,
+            (false, Some(value), Some(index)) => copy_field(
+                &target.field,
+                value,
+                source_entity.components[index].as_mut(),
+            ),
+            // The field's component is missing from the instance or the prefab
+            (false, _, _) => false,
+        };
+        if !applied {
+            skipped.push(target.label());
+        }
+    }
+    Some(skipped)
+}
+
+/// Record instance values as override values on a variant's base reference, so
+/// they become part of the variant rather than its base. Returns the labels of
+/// overrides that couldn't be applied, or `None` when the variant file has no
+/// reference to `base`.
+fn apply_to_variant(
+    scene: &mut DynamicScene,
+    base: &str,
+    key: u32,
+    current: &[Box<dyn PartialReflect>],
+    targets: &[PrefabOverride],
+    type_registry: &TypeRegistry,
+) -> Option<Vec<String>> {
+    let root = scene
+        .entities
+        .iter_mut()
+        .find(|entity| nested_reference(entity).as_deref() == Some(base))?;
+    let mut values = root
+        .components
+        .iter()
+        .find(|c| c.represents::<PrefabOverrideValues>())
+        .and_then(|c| PrefabOverrideValues::from_reflect(c.as_ref()))
+        .unwrap_or_default();
+
+    let mut skipped = Vec::new();
+    for target in targets {
+        let component = find_component(current, &target.component);
+        let value = if target.field.is_empty() {
+            component
+        } else {
+            component.and_then(|c| target.field.as_str().reflect_element(c).ok())
+        };
+        // A missing field can't be expressed; only whole components may be absent
+        if value.is_none() && !target.field.is_empty() {
+            skipped.push(target.label());
+            continue;
+        }
+        let value = value.and_then(|v| serialize_value(type_registry, v));
+
+        let same_component =
+            |v: &PrefabOverrideValue| v.source_id == key && v.target.component == target.component;
+        if target.field.is_empty() {
+            // The whole component replaces any field values recorded for it
+            values.values.retain(|v| !same_component(v));
+        } else if let Some(whole) = values
+            .values
+            .iter_mut()
+            .find(|v| same_component(v) && v.target.field.is_empty())
+        {
+            // A recorded whole component already covers the field; record it again
+            whole.value = component.and_then(|c| serialize_value(type_registry, c));
+            continue;
+        }
+
+        match values
+            .values
+            .iter_mut()
+            .find(|v| v.source_id == key && v.target == *target)
+        {
+            Some(existing) => existing.value = value,
+            None => values.values.push(PrefabOverrideValue {
+                source_id: key,
+                target: target.clone(),
+                value,
+            }),
+        }
+    }
+
+    values.values.sort_by(|a, b| {
+        (a.source_id, &a.target.component, &a.target.field).cmp(&(
+            b.source_id,
+            &b.target.component,
+            &b.target.field,
+        ))
+    });
+    match root
+        .components
+        .iter()
+        .position(|c| c.represents::<PrefabOverrideValues>())
+    {
+        Some(index) => root.components[index] = Box::new(values),
+        None => root.components.push(Box::new(values)),
+    }
+    Some(skipped)
+}
+
+/// Serialize an edited prefab scene like a saved scene, with stable IDs and ordering.
+/// Entities keep their source keys, so existing instances still match them.
+fn serialize_prefab_scene(world: &mut World, mut scene: DynamicScene) -> Result<String, String> {
+    for dynamic_entity in &mut scene.entities {
+        if !dynamic_entity
+            .components
+            .iter()
+            .any(|c| c.represents::<PersistentId>())
+        {
+            let key = source_key(dynamic_entity);
+            dynamic_entity.components.push(Box::new(PersistentId(key)));
+        }
+    }
+
+    let mut prefab_world = World::new();
+    prefab_world.insert_resource(world.resource::<AppTypeRegistry>().clone());
+    // Game-registered components are only kept while their registry is present
+    if let Some(registry) = world.remove_resource::<SceneComponentRegistry>() {
+        prefab_world.insert_resource(registry);
+    }
+
+    let result = scene
+        .write_to_world(&mut prefab_world, &mut EntityHashMap::default())
+        .map_err(|e| format!("Failed to rebuild prefab scene: {:?}", e))
+        .and_then(|_| {
+            serialize_scene_stable(&mut prefab_world)
+                .map_err(|e| format!("Failed to serialize prefab scene: {}", e))
+        });
+
+    if let Some(registry) = prefab_world.remove_resource::<SceneComponentRegistry>() {
+        world.insert_resource(registry);
+    }
+    result
+}
+
+pub fn handle_revert_prefab_override(
+    mut events: MessageReader<RevertPrefabOverrideEvent>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        commands.queue(RevertPrefabOverrideCommand {
+            entity: event.entity,
+            target: event.target.clone(),
+        });
+    }
+}
+
+pub fn handle_apply_prefab_override(
+    mut events: MessageReader<ApplyPrefabOverrideEvent>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        commands.queue(ApplyPrefabOverrideCommand {
+            entity: event.entity,
+            target: event.target.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::headless_app;
+    use crate::scene::SceneEntity;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy_editor_game::MaterialLibrary;
+    use std::path::Path;
+
+    const KEY: u32 = 7;
+
+    fn transform_override(field: &str) -> PrefabOverride {
+        PrefabOverride {
+            component: Transform::type_path().to_string(),
+            field: field.to_string(),
+        }
+    }
+
+    /// Save a single-entity "crate" prefab with the given transform under `root`
+    fn write_crate_prefab(world: &mut World, root: &Path, transform: Transform) {
+        let entity = world
+            .spawn((SceneEntity, PersistentId(KEY), Name::new("Crate"), transform))
+            .id();
+        let dir = root.join("crate");
+        fs::create_dir_all(&dir).unwrap();
+        let scene = build_editor_scene(world, std::iter::once(entity));
+        let serialized = scene
+            .serialize(&world.resource::<AppTypeRegistry>().read())
+            .unwrap();
+        fs::write(dir.join("crate.scn.ron"), serialized).unwrap();
+        PrefabRegistry::save_metadata(&dir, "crate", &MaterialLibrary::default(), &HashMap::new(), None)
+            .unwrap();
+        world.despawn(entity);
+    }
+
+    /// App with the "crate" prefab on disk and one instance of it.
+    /// Returns the prefab root directory and the instance's crate entity.
+    fn crate_instance(test: &str) -> (App, PathBuf, Entity) {
+        let mut app = headless_app();
+        let root = std::env::temp_dir().join(format!("overrides_{}_{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let world = app.world_mut();
+        write_crate_prefab(world, &root, Transform::default());
+
+        let mut registry = PrefabRegistry::default();
+        registry.root_directory = root.clone();
+        registry.refresh();
+        world.insert_resource(registry);
+        world.init_resource::<PrefabSourceCache>();
+
+        let instance = |source_id| PrefabInstance {
+            prefab_name: "crate".to_string(),
+            instance_id: "crate_1".to_string(),
+            source_id,
+        };
+        let instance_root = world
+            .spawn((SceneEntity, PrefabRoot, instance(None), Transform::default()))
+            .id();
+        let member = world
+            .spawn((
+                SceneEntity,
+                instance(Some(KEY)),
+                Name::new("Crate"),
+                Transform::default(),
+                ChildOf(instance_root),
+            ))
+            .id();
+        sync(world);
+        (app, root, member)
+    }
+
+    fn sync(world: &mut World) {
+        world.run_system_once(sync_prefab_instances).unwrap();
+    }
+
+    fn overrides(world: &World, entity: Entity) -> Vec<PrefabOverride> {
+        world.get::<PrefabOverrides>(entity).unwrap().overrides.clone()
+    }
+
+    #[test]
+    fn content_hash_is_stable_fnv1a() {
+        assert_eq!(content_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(content_hash("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn instance_changes_are_diffed_against_prefab() {
+        let (mut app, root, member) = crate_instance("diff");
+        let world = app.world_mut();
+        assert!(overrides(world, member).is_empty());
+
+        world.get_mut::<Transform>(member).unwrap().translation.x = 2.0;
+        world.entity_mut(member).remove::<Name>();
+        sync(world);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(
+            overrides(world, member),
+            vec![
+                PrefabOverride {
+                    component: Name::type_path().to_string(),
+                    field: String::new(),
+                },
+                transform_override(".translation.x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn revert_restores_prefab_values() {
+        let (mut app, root, member) = crate_instance("revert");
+        let world = app.world_mut();
+        world.get_mut::<Transform>(member).unwrap().translation = Vec3::new(2.0, 3.0, 0.0);
+        sync(world);
+
+        RevertPrefabOverrideCommand {
+            entity: member,
+            target: Some(transform_override(".translation.x")),
+        }
+        .apply(world);
+        assert_eq!(world.get::<Transform>(member).unwrap().translation, Vec3::new(0.0, 3.0, 0.0));
+
+        RevertPrefabOverrideCommand {
+            entity: member,
+            target: None,
+        }
+        .apply(world);
+        sync(world);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(world.get::<Transform>(member).unwrap().translation, Vec3::ZERO);
+        assert!(overrides(world, member).is_empty());
+    }
+
+    #[test]
+    fn resync_picks_up_prefab_changes_and_keeps_overrides() {
+        let (mut app, root, member) = crate_instance("resync");
+        let world = app.world_mut();
+        world.get_mut::<Transform>(member).unwrap().translation.x = 2.0;
+        sync(world);
+
+        write_crate_prefab(world, &root, Transform::from_xyz(0.0, 3.0, 0.0));
+        world.resource_mut::<PrefabSourceCache>().invalidate("crate");
+        sync(world);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(world.get::<Transform>(member).unwrap().translation, Vec3::new(2.0, 3.0, 0.0));
+        assert_eq!(overrides(world, member), vec![transform_override(".translation.x")]);
+    }
+
+    #[test]
+    fn apply_writes_overrides_into_prefab() {
+        let (mut app, root, member) = crate_instance("apply");
+        let world = app.world_mut();
+        world.get_mut::<Transform>(member).unwrap().translation.x = 2.0;
+        sync(world);
+
+        ApplyPrefabOverrideCommand {
+            entity: member,
+            target: None,
+        }
+        .apply(world);
+        // The instance now matches the updated prefab
+        sync(world);
+        assert!(overrides(world, member).is_empty());
+
+        let resolved = {
+            let type_registry = world.resource::<AppTypeRegistry>().read();
+            world
+                .resource::<PrefabRegistry>()
+                .resolve("crate", &[], &type_registry)
+                .unwrap()
+        };
+        let _ = fs::remove_dir_all(&root);
+
+        let transform = resolved.scene.entities[0]
+            .components
+            .iter()
+            .find(|c| c.represents::<Transform>())
+            .and_then(|c| Transform::from_reflect(c.as_ref()))
+            .unwrap();
+        assert_eq!(transform.translation, Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(
+            world.get::<PrefabOverrides>(member).unwrap().source_hash,
+            resolved.source_hash
+        );
+    }
+}
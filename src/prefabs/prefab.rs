@@ -10,9 +10,71 @@ pub struct PrefabInstance {
     pub prefab_name: String,
     /// Unique per-instance identifier, e.g. "fireball_1"
     pub instance_id: String,
+    /// Key of the matching entity in the prefab scene (its `PersistentId`, or its
+    /// scene entity index for prefabs saved without one). `None` on the instance root.
+    #[serde(default)]
+    #[reflect(default)]
+    pub source_id: Option<u32>,
 }
 
 /// Marker on the root entity of a prefab instance (the group container).
 #[derive(Component, Reflect, Serialize, Deserialize, Clone, Debug, Default)]
 #[reflect(Component, Serialize, Deserialize)]
 pub struct PrefabRoot;
+
+/// A single value on a prefab instance entity that differs from the prefab.
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PrefabOverride {
+    /// Type path of the overridden component
+    pub component: String,
+    /// Reflect path of the overridden field (e.g. ".translation.x").
+    /// Empty when the component itself was added to or removed from the instance.
+    pub field: String,
+}
+
+impl PrefabOverride {
+    /// Short label for display, e.g. "Transform.translation.x"
+    pub fn label(&self) -> String {
+        let component = self
+            .component
+            .rsplit("::")
+            .next()
+            .unwrap_or(&self.component);
+        if self.field.is_empty() {
+            component.to_string()
+        } else {
+            format!("{}{}", component, self.field)
+        }
+    }
+}
+
+/// Per-entity record of how a prefab instance entity differs from its prefab source.
+///
+/// Kept up to date while editing and saved with the scene, so overridden values
+/// survive when the prefab changes on disk and its instances are re-synced.
+#[derive(Component, Reflect, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[reflect(Component, Serialize, Deserialize)]
+pub struct PrefabOverrides {
+    /// Hash of the prefab scene file this entity was last synced with
+    pub source_hash: u64,
+    pub overrides: Vec<PrefabOverride>,
+}
+
+/// A value overridden on one entity of a nested prefab instance.
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PrefabOverrideValue {
+    /// Key of the entity in the nested prefab (see [`PrefabInstance::source_id`])
+    pub source_id: u32,
+    pub target: PrefabOverride,
+    /// The overridden value as reflection RON. `None` when the component was removed.
+    pub value: Option<String>,
+}
+
+/// Root of a prefab instance stored by reference inside another prefab (a nested
+/// prefab or a variant's base). Only the root and its overridden values are saved;
+/// the referenced prefab's entities are spawned from its own file.
+#[derive(Component, Reflect, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[reflect(Component, Serialize, Deserialize)]
+pub struct PrefabOverrideValues {
+    pub values: Vec<PrefabOverrideValue>,
+}
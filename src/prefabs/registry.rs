@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy::reflect::TypeRegistry;
 use bevy_editor_game::MaterialLibrary;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -7,6 +8,11 @@ use std::path::PathBuf;
 
 use bevy_vfx::VfxSystem;
 
+use super::overrides::{
+    apply_values_to_scene, content_hash, deserialize_prefab_scene, nested_reference, variant_hash,
+};
+use super::prefab::PrefabOverrideValues;
+
 /// Cached metadata about a discovered prefab directory.
 #[derive(Clone, Debug)]
 pub struct PrefabEntry {
@@ -20,6 +26,8 @@ pub struct PrefabEntry {
     pub material_library: MaterialLibrary,
     /// Particle library loaded from prefab.meta
     pub particle_presets: HashMap<String, VfxSystem>,
+    /// Base prefab when this prefab is a variant
+    pub base: Option<String>,
 }
 
 /// Sidecar metadata stored alongside prefab.scene (same format as scene metadata).
@@ -29,6 +37,18 @@ struct PrefabMetadata {
     material_library: MaterialLibrary,
     #[serde(default)]
     particle_presets: HashMap<String, VfxSystem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    base: Option<String>,
+}
+
+/// A prefab scene with its nested prefab references resolved, ready to spawn.
+pub struct ResolvedPrefab {
+    pub name: String,
+    /// Hash of the prefab scene file (see `PrefabOverrides::source_hash`)
+    pub source_hash: u64,
+    pub scene: DynamicScene,
+    /// Nested prefab references in `scene`, keyed by their scene entity
+    pub nested: Vec<(Entity, ResolvedPrefab)>,
 }
 
 /// Resource containing all discovered prefab directories from assets/prefabs/.
@@ -67,6 +87,20 @@ impl PrefabRegistry {
         self.entries.keys().map(|s| s.as_str()).collect()
     }
 
+    /// Scene files a prefab is built from: its own, then its bases' for a variant
+    pub fn scene_paths(&self, name: &str) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        let mut current = self.get(name);
+        while let Some(entry) = current {
+            if paths.contains(&entry.scene_path) {
+                break;
+            }
+            paths.push(entry.scene_path.clone());
+            current = entry.base.as_deref().and_then(|base| self.get(base));
+        }
+        paths
+    }
+
     /// Scan the prefab root directory for subdirectories containing prefab.scene
     pub fn refresh(&mut self) {
         self.entries.clear();
@@ -123,6 +157,7 @@ impl PrefabRegistry {
                     scene_path,
                     material_library: metadata.material_library,
                     particle_presets: metadata.particle_presets,
+                    base: metadata.base,
                 },
             );
         }
@@ -130,16 +165,94 @@ impl PrefabRegistry {
         info!("Discovered {} prefabs", self.entries.len());
     }
 
+    /// Load a prefab scene and, recursively, the prefabs it references.
+    /// `parents` are the prefabs the result will be nested in, used to reject
+    /// reference cycles.
+    pub fn resolve(
+        &self,
+        name: &str,
+        parents: &[String],
+        type_registry: &TypeRegistry,
+    ) -> Result<ResolvedPrefab, String> {
+        let (scene, source_hash) = self.load_scene(name, parents, type_registry)?;
+
+        let mut stack = parents.to_vec();
+        stack.push(name.to_string());
+
+        let mut nested = Vec::new();
+        for entity in &scene.entities {
+            if let Some(reference) = nested_reference(entity) {
+                nested.push((entity.entity, self.resolve(&reference, &stack, type_registry)?));
+            }
+        }
+
+        Ok(ResolvedPrefab {
+            name: name.to_string(),
+            source_hash,
+            scene,
+            nested,
+        })
+    }
+
+    /// Load a prefab's own entities and their source hash, without resolving
+    /// nested references. A variant is loaded as its base with the variant's
+    /// override values applied, so its entities keep the base's source keys.
+    pub fn load_scene(
+        &self,
+        name: &str,
+        parents: &[String],
+        type_registry: &TypeRegistry,
+    ) -> Result<(DynamicScene, u64), String> {
+        if parents.iter().any(|parent| parent == name) {
+            let mut chain: Vec<&str> = parents.iter().map(|p| p.as_str()).collect();
+            chain.push(name);
+            return Err(format!("Prefab reference cycle: {}", chain.join(" -> ")));
+        }
+
+        let entry = self
+            .get(name)
+            .ok_or_else(|| format!("Prefab not found: {}", name))?;
+        let content = fs::read_to_string(&entry.scene_path)
+            .map_err(|e| format!("Failed to read prefab scene '{}': {}", name, e))?;
+        let scene = deserialize_prefab_scene(type_registry, &content)?;
+
+        let Some(base) = &entry.base else {
+            return Ok((scene, content_hash(&content)));
+        };
+
+        let values = scene
+            .entities
+            .iter()
+            .find(|entity| nested_reference(entity).as_deref() == Some(base.as_str()))
+            .and_then(|entity| {
+                entity
+                    .components
+                    .iter()
+                    .find(|c| c.represents::<PrefabOverrideValues>())
+            })
+            .and_then(|c| PrefabOverrideValues::from_reflect(c.as_ref()))
+            .unwrap_or_default();
+
+        let mut stack = parents.to_vec();
+        stack.push(name.to_string());
+        let (mut base_scene, base_hash) = self.load_scene(base, &stack, type_registry)?;
+        apply_values_to_scene(&mut base_scene, &values, type_registry);
+
+        Ok((base_scene, variant_hash(&content, base_hash)))
+    }
+
     /// Save metadata sidecar for a prefab ({name}.scn.ron.meta)
     pub fn save_metadata(
         prefab_dir: &std::path::Path,
         prefab_name: &str,
         material_library: &MaterialLibrary,
         particle_presets: &HashMap<String, VfxSystem>,
+        base: Option<&str>,
     ) -> Result<(), String> {
         let metadata = PrefabMetadata {
             material_library: material_library.clone(),
             particle_presets: particle_presets.clone(),
+            base: base.map(str::to_string),
         };
 
         let content = ron::ser::to_string_pretty(&metadata, ron::ser::PrettyConfig::default())
@@ -156,3 +269,135 @@ impl PrefabRegistry {
 pub fn scan_prefab_directory(mut registry: ResMut<PrefabRegistry>) {
     registry.refresh();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::overrides::source_key;
+    use crate::headless::headless_app;
+    use crate::prefabs::{PrefabInstance, PrefabOverride, PrefabOverrideValue, PrefabRoot};
+    use crate::scene::{build_editor_scene, PersistentId, SceneEntity};
+    use bevy::reflect::serde::ReflectSerializer;
+    use bevy::reflect::TypePath;
+    use std::path::Path;
+
+    fn prefab_root(test: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("prefab_{}_{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    /// Save `entity` as the prefab `name` under `root`
+    fn write_prefab(world: &World, root: &Path, name: &str, entity: Entity, base: Option<&str>) {
+        let dir = root.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        let scene = build_editor_scene(world, std::iter::once(entity));
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let serialized = scene.serialize(&type_registry).unwrap();
+        fs::write(dir.join(format!("{}.scn.ron", name)), serialized).unwrap();
+        PrefabRegistry::save_metadata(&dir, name, &MaterialLibrary::default(), &HashMap::new(), base)
+            .unwrap();
+    }
+
+    /// Spawn the reference root a variant of `base` is saved as
+    fn variant_root(world: &mut World, base: &str, values: Vec<PrefabOverrideValue>) -> Entity {
+        world
+            .spawn((
+                SceneEntity,
+                PrefabRoot,
+                PrefabInstance {
+                    prefab_name: base.to_string(),
+                    instance_id: format!("{}_1", base),
+                    source_id: None,
+                },
+                PrefabOverrideValues { values },
+                Transform::IDENTITY,
+            ))
+            .id()
+    }
+
+    fn registry_at(root: &Path) -> PrefabRegistry {
+        let mut registry = PrefabRegistry {
+            root_directory: root.to_path_buf(),
+            ..default()
+        };
+        registry.refresh();
+        registry
+    }
+
+    #[test]
+    fn variant_resolves_to_base_with_values_applied() {
+        let mut app = headless_app();
+        let root = prefab_root("variant");
+        let world = app.world_mut();
+
+        let crate_entity = world
+            .spawn((
+                SceneEntity,
+                PersistentId(7),
+                Name::new("Crate"),
+                Transform::default(),
+            ))
+            .id();
+        write_prefab(world, &root, "crate", crate_entity, None);
+
+        let value = {
+            let type_registry = world.resource::<AppTypeRegistry>().read();
+            ron::to_string(&ReflectSerializer::new(&2.0f32, &type_registry)).unwrap()
+        };
+        let variant = variant_root(
+            world,
+            "crate",
+            vec![PrefabOverrideValue {
+                source_id: 7,
+                target: PrefabOverride {
+                    component: Transform::type_path().to_string(),
+                    field: ".translation.x".to_string(),
+                },
+                value: Some(value),
+            }],
+        );
+        write_prefab(world, &root, "crate_moved", variant, Some("crate"));
+
+        let registry = registry_at(&root);
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let base = registry.resolve("crate", &[], &type_registry).unwrap();
+        let resolved = registry.resolve("crate_moved", &[], &type_registry).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(resolved.name, "crate_moved");
+        assert!(resolved.nested.is_empty());
+        assert_ne!(resolved.source_hash, base.source_hash);
+        assert_eq!(resolved.scene.entities.len(), 1);
+
+        let entity = &resolved.scene.entities[0];
+        assert_eq!(source_key(entity), 7);
+        let transform = entity
+            .components
+            .iter()
+            .find(|c| c.represents::<Transform>())
+            .and_then(|c| Transform::from_reflect(c.as_ref()))
+            .unwrap();
+        assert_eq!(transform.translation, Vec3::new(2.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn variant_base_cycle_is_rejected() {
+        let mut app = headless_app();
+        let root = prefab_root("variant_cycle");
+        let world = app.world_mut();
+
+        let a = variant_root(world, "b", Vec::new());
+        write_prefab(world, &root, "a", a, Some("b"));
+        let b = variant_root(world, "a", Vec::new());
+        write_prefab(world, &root, "b", b, Some("a"));
+
+        let registry = registry_at(&root);
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let result = registry.resolve("a", &[], &type_registry);
+        let _ = fs::remove_dir_all(&root);
+
+        let error = result.err().expect("a base cycle should not resolve");
+        assert!(error.contains("cycle"), "unexpected error: {}", error);
+    }
+}
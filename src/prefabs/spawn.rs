@@ -1,11 +1,13 @@
 use bevy::prelude::*;
-use bevy::scene::serde::SceneDeserializer;
-use serde::de::DeserializeSeed;
-use std::fs;
+use std::collections::HashSet;
 
-use super::prefab::{PrefabInstance, PrefabRoot};
-use super::registry::PrefabRegistry;
-use crate::scene::{regenerate_runtime_components, GroupMarker, SceneEntity};
+use super::editing::PrefabEditingContext;
+use super::overrides::{apply_override_values, nested_reference, source_key};
+use super::prefab::{PrefabInstance, PrefabOverrideValues, PrefabOverrides, PrefabRoot};
+use super::registry::{PrefabRegistry, ResolvedPrefab};
+use crate::scene::{
+    despawn_scene_entities, regenerate_runtime_components, GroupMarker, PersistentId, SceneEntity,
+};
 
 /// Event to spawn a prefab instance into the scene
 #[derive(Message)]
@@ -22,6 +24,14 @@ pub struct CreatePrefabEvent {
     pub entities: Vec<Entity>,
 }
 
+/// Event to create a variant prefab from a prefab instance and its overrides
+#[derive(Message)]
+pub struct CreatePrefabVariantEvent {
+    pub name: String,
+    /// Root entity of the prefab instance
+    pub instance: Entity,
+}
+
 /// Event to open a prefab for editing in a separate context
 #[derive(Message)]
 pub struct OpenPrefabEvent {
@@ -35,78 +45,30 @@ pub struct ClosePrefabEvent;
 /// Command that loads a prefab scene into the world additively
 struct SpawnPrefabCommand {
     prefab_name: String,
-    scene_content: String,
     position: Vec3,
     rotation: Quat,
-    instance_id: String,
 }
 
 impl Command for SpawnPrefabCommand {
     fn apply(self, world: &mut World) {
-        // Deserialize the prefab scene
+        // Resolve the prefab and the prefabs it references
         let type_registry = world.resource::<AppTypeRegistry>().clone();
-        let type_registry = type_registry.read();
-        let scene_deserializer = SceneDeserializer {
-            type_registry: &type_registry,
-        };
-
-        let mut ron_deserializer = match ron::de::Deserializer::from_str(&self.scene_content) {
-            Ok(d) => d,
-            Err(e) => {
-                error!("Failed to parse prefab '{}': {}", self.prefab_name, e);
-                return;
-            }
-        };
-
-        let scene: DynamicScene = match scene_deserializer.deserialize(&mut ron_deserializer) {
-            Ok(s) => s,
+        let resolved = match world.resource::<PrefabRegistry>().resolve(
+            &self.prefab_name,
+            &[],
+            &type_registry.read(),
+        ) {
+            Ok(resolved) => resolved,
             Err(e) => {
-                error!(
-                    "Failed to deserialize prefab '{}': {:?}",
-                    self.prefab_name, e
-                );
+                error!("Failed to spawn prefab '{}': {}", self.prefab_name, e);
                 return;
             }
         };
 
-        drop(type_registry);
-
-        // Write scene entities into the world (additive — doesn't clear existing entities)
-        let mut entity_map = bevy::ecs::entity::EntityHashMap::default();
-        if let Err(e) = scene.write_to_world(world, &mut entity_map) {
-            error!(
-                "Failed to instantiate prefab '{}': {:?}",
-                self.prefab_name, e
-            );
-            return;
-        }
-
-        // Collect the new entities that were created
-        let new_entities: Vec<Entity> = entity_map.values().copied().collect();
-
-        // Tag all new entities with PrefabInstance
-        for &entity in &new_entities {
-            world.entity_mut(entity).insert(PrefabInstance {
-                prefab_name: self.prefab_name.clone(),
-                instance_id: self.instance_id.clone(),
-            });
-        }
-
-        // Find root entities (those without a parent among the new entities)
-        let root_entities: Vec<Entity> = new_entities
-            .iter()
-            .filter(|&&entity| {
-                let parent = world.entity(entity).get::<ChildOf>();
-                match parent {
-                    Some(child_of) => !new_entities.contains(&child_of.parent()),
-                    None => true,
-                }
-            })
-            .copied()
-            .collect();
+        let instance_id = unused_instance_id(world, &self.prefab_name);
 
         // Create a group container for the prefab instance
-        let display_name = format!("[Prefab] {}", self.instance_id);
+        let display_name = format!("[Prefab] {}", instance_id);
         let group_entity = world
             .spawn((
                 SceneEntity,
@@ -114,7 +76,8 @@ impl Command for SpawnPrefabCommand {
                 PrefabRoot,
                 PrefabInstance {
                     prefab_name: self.prefab_name.clone(),
-                    instance_id: self.instance_id.clone(),
+                    instance_id: instance_id.clone(),
+                    source_id: None,
                 },
                 Name::new(display_name),
                 Transform::from_translation(self.position)
@@ -122,11 +85,14 @@ impl Command for SpawnPrefabCommand {
             ))
             .id();
 
-        // Reparent root entities under the group and zero out their world offset
-        // (since the group provides the position)
-        for &root in &root_entities {
-            world.entity_mut(root).insert(ChildOf(group_entity));
-        }
+        let count = match write_instance(world, resolved, &instance_id, group_entity) {
+            Ok(count) => count,
+            Err(e) => {
+                error!("Failed to instantiate prefab '{}': {}", self.prefab_name, e);
+                despawn_scene_entities(world, &[group_entity]);
+                return;
+            }
+        };
 
         // Regenerate runtime components (meshes, materials, colliders, lights)
         regenerate_runtime_components(world);
@@ -134,44 +100,213 @@ impl Command for SpawnPrefabCommand {
 
         info!(
             "Spawned prefab '{}' as instance '{}' ({} entities)",
-            self.prefab_name,
-            self.instance_id,
-            new_entities.len()
+            self.prefab_name, instance_id, count
         );
     }
 }
 
-pub fn handle_spawn_prefab(
-    mut events: MessageReader<SpawnPrefabEvent>,
-    mut commands: Commands,
-    mut registry: ResMut<PrefabRegistry>,
-) {
-    for event in events.read() {
-        let Some(entry) = registry.get(&event.prefab_name) else {
-            warn!("Prefab not found: {}", event.prefab_name);
+/// Next instance ID for a prefab that no entity in the world is using yet
+/// (instances loaded from a scene keep the IDs they were saved with)
+fn unused_instance_id(world: &mut World, prefab_name: &str) -> String {
+    let used: HashSet<String> = {
+        let mut query = world.query::<&PrefabInstance>();
+        query
+            .iter(world)
+            .filter(|instance| instance.prefab_name == prefab_name)
+            .map(|instance| instance.instance_id.clone())
+            .collect()
+    };
+    let mut registry = world.resource_mut::<PrefabRegistry>();
+    loop {
+        let instance_id = registry.next_instance_id(prefab_name);
+        if !used.contains(&instance_id) {
+            return instance_id;
+        }
+    }
+}
+
+/// Write a resolved prefab's entities into the world under `root`, tagging them as
+/// members of `instance_id`. Nested prefab references are spawned as instances of
+/// their own with the overridden values they were saved with. A variant's entities
+/// come from its base, but are members of the variant.
+/// Returns the number of entities written.
+fn write_instance(
+    world: &mut World,
+    resolved: ResolvedPrefab,
+    instance_id: &str,
+    root: Entity,
+) -> Result<usize, String> {
+    let ResolvedPrefab {
+        name,
+        source_hash,
+        scene,
+        nested,
+    } = resolved;
+
+    // Remember which prefab entity each instance entity comes from
+    let source_keys: Vec<(Entity, u32)> = scene
+        .entities
+        .iter()
+        .filter(|entity| nested_reference(entity).is_none())
+        .map(|entity| (entity.entity, source_key(entity)))
+        .collect();
+
+    // Write scene entities into the world (additive — doesn't clear existing entities)
+    let mut entity_map = bevy::ecs::entity::EntityHashMap::default();
+    scene
+        .write_to_world(world, &mut entity_map)
+        .map_err(|e| format!("{:?}", e))?;
+
+    // Collect the new entities that were created
+    let new_entities: Vec<Entity> = entity_map.values().copied().collect();
+
+    // Tag all new entities with PrefabInstance. Persistent IDs belong to the prefab;
+    // the scene assigns the instance entities its own.
+    for &(scene_entity, key) in &source_keys {
+        let Some(&entity) = entity_map.get(&scene_entity) else {
             continue;
         };
+        world
+            .entity_mut(entity)
+            .remove::<PersistentId>()
+            .insert((
+                PrefabInstance {
+                    prefab_name: name.clone(),
+                    instance_id: instance_id.to_string(),
+                    source_id: Some(key),
+                },
+                PrefabOverrides {
+                    source_hash,
+                    overrides: Vec::new(),
+                },
+            ));
+    }
 
-        // Read the prefab scene file
-        let content = match fs::read_to_string(&entry.scene_path) {
-            Ok(c) => c,
+    // Find root entities (those without a parent among the new entities)
+    let root_entities: Vec<Entity> = new_entities
+        .iter()
+        .filter(|&&entity| {
+            let parent = world.entity(entity).get::<ChildOf>();
+            match parent {
+                Some(child_of) => !new_entities.contains(&child_of.parent()),
+                None => true,
+            }
+        })
+        .copied()
+        .collect();
+
+    // Reparent root entities under the group (the group provides the position)
+    for &entity in &root_entities {
+        world.entity_mut(entity).insert(ChildOf(root));
+    }
+
+    // Spawn nested prefab references in place, then restore their overrides
+    let mut count = new_entities.len();
+    for (scene_entity, nested_prefab) in nested {
+        let Some(&nested_root) = entity_map.get(&scene_entity) else {
+            continue;
+        };
+        count += spawn_nested_instance(world, nested_root, nested_prefab)?;
+    }
+
+    Ok(count)
+}
+
+/// Turn a bare prefab reference root into a full instance of the referenced prefab
+fn spawn_nested_instance(
+    world: &mut World,
+    nested_root: Entity,
+    resolved: ResolvedPrefab,
+) -> Result<usize, String> {
+    let values = world
+        .entity_mut(nested_root)
+        .take::<PrefabOverrideValues>()
+        .unwrap_or_default();
+    let nested_id = unused_instance_id(world, &resolved.name);
+    world.entity_mut(nested_root).remove::<PersistentId>().insert((
+        PrefabInstance {
+            prefab_name: resolved.name.clone(),
+            instance_id: nested_id.clone(),
+            source_id: None,
+        },
+        Name::new(format!("[Prefab] {}", nested_id)),
+    ));
+
+    let count = write_instance(world, resolved, &nested_id, nested_root)?;
+    apply_override_values(world, nested_root, &values);
+    Ok(count)
+}
+
+/// Command that expands a bare prefab reference loaded from a scene file, e.g. a
+/// nested prefab inside a prefab opened for editing
+struct ExpandPrefabReferenceCommand {
+    entity: Entity,
+}
+
+impl Command for ExpandPrefabReferenceCommand {
+    fn apply(self, world: &mut World) {
+        let Some(prefab_name) = world
+            .get::<PrefabInstance>(self.entity)
+            .map(|instance| instance.prefab_name.clone())
+        else {
+            return;
+        };
+
+        // The prefab being edited can't contain itself
+        let parents: Vec<String> = world
+            .get_resource::<PrefabEditingContext>()
+            .map(|context| vec![context.prefab_name.clone()])
+            .unwrap_or_default();
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let resolved = match world.resource::<PrefabRegistry>().resolve(
+            &prefab_name,
+            &parents,
+            &type_registry.read(),
+        ) {
+            Ok(resolved) => resolved,
             Err(e) => {
-                error!(
-                    "Failed to read prefab scene '{}': {}",
-                    event.prefab_name, e
-                );
-                continue;
+                error!("Failed to expand prefab reference '{}': {}", prefab_name, e);
+                return;
             }
         };
 
-        let instance_id = registry.next_instance_id(&event.prefab_name);
+        match spawn_nested_instance(world, self.entity, resolved) {
+            Ok(count) => {
+                regenerate_runtime_components(world);
+                crate::scene::resolve_entity_references(world);
+                info!("Expanded prefab reference '{}' ({} entities)", prefab_name, count);
+            }
+            Err(e) => error!("Failed to expand prefab reference '{}': {}", prefab_name, e),
+        }
+    }
+}
+
+/// Expand prefab references that were loaded from a scene file
+pub fn expand_prefab_references(
+    references: Query<Entity, (With<PrefabRoot>, Added<PrefabOverrideValues>)>,
+    mut commands: Commands,
+) {
+    for entity in &references {
+        commands.queue(ExpandPrefabReferenceCommand { entity });
+    }
+}
+
+pub fn handle_spawn_prefab(
+    mut events: MessageReader<SpawnPrefabEvent>,
+    mut commands: Commands,
+    registry: Res<PrefabRegistry>,
+) {
+    for event in events.read() {
+        if registry.get(&event.prefab_name).is_none() {
+            warn!("Prefab not found: {}", event.prefab_name);
+            continue;
+        }
 
         commands.queue(SpawnPrefabCommand {
             prefab_name: event.prefab_name.clone(),
-            scene_content: content,
             position: event.position,
             rotation: event.rotation,
-            instance_id,
         });
 
         info!("Queued prefab spawn: {}", event.prefab_name);
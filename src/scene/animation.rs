@@ -0,0 +1,296 @@
+use bevy::animation::RepeatAnimation;
+use bevy::gltf::Gltf;
+use bevy::prelude::*;
+use bevy_editor_game::{
+    AnimationLibrary, AnimationRef, AnimationSource, GamePausedEvent, GameResumedEvent,
+    GameStartedEvent, GameState,
+};
+
+use super::GltfSource;
+
+/// Runtime binding of an `AnimationRef` to its clip and `AnimationPlayer`.
+/// Not serialized; rebuilt whenever the reference changes.
+#[derive(Component)]
+pub struct AnimationBinding {
+    /// Source the binding was resolved from
+    source: AnimationSource,
+    /// GLTF file loaded to look up `AnimationSource::Gltf` clips by name
+    gltf: Option<Handle<Gltf>>,
+    /// `GltfSource` path `gltf` was loaded from
+    gltf_path: Option<String>,
+    clip: Option<Handle<AnimationClip>>,
+    graph: Option<(Handle<AnimationGraph>, AnimationNodeIndex)>,
+    /// Entity with the `AnimationPlayer` driven by this binding
+    player: Option<Entity>,
+    /// Start playing as soon as the player is ready (set on game start)
+    pending_autoplay: bool,
+    /// Preview position while editing, in seconds
+    pub preview_time: f32,
+    /// Whether the preview is advancing on its own while editing
+    pub previewing: bool,
+}
+
+impl AnimationBinding {
+    fn new(source: AnimationSource) -> Self {
+        Self {
+            source,
+            gltf: None,
+            gltf_path: None,
+            clip: None,
+            graph: None,
+            player: None,
+            pending_autoplay: false,
+            preview_time: 0.0,
+            previewing: false,
+        }
+    }
+
+    /// Whether the clip is resolved and an animation player was found
+    pub fn is_bound(&self) -> bool {
+        self.graph.is_some() && self.player.is_some()
+    }
+}
+
+pub struct AnimationRefPlugin;
+
+impl Plugin for AnimationRefPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AnimationRef>()
+            .register_type::<AnimationSource>()
+            .add_systems(
+                Update,
+                (
+                    bind_animation_refs,
+                    preview_animations.run_if(in_state(GameState::Editing)),
+                    handle_game_started,
+                    autoplay_animations,
+                    handle_game_paused,
+                    handle_game_resumed,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Names of the clips an `AnimationRef` on `entity` can pick from for the given source kind
+pub fn available_animation_clips(world: &World, entity: Entity, gltf: bool) -> Vec<String> {
+    let mut names: Vec<String> = if gltf {
+        world
+            .get::<AnimationBinding>(entity)
+            .and_then(|binding| binding.gltf.as_ref())
+            .and_then(|handle| world.resource::<Assets<Gltf>>().get(handle))
+            .map(|gltf| gltf.named_animations.keys().map(|k| k.to_string()).collect())
+            .unwrap_or_default()
+    } else {
+        world
+            .get_resource::<AnimationLibrary>()
+            .map(|library| library.clips.keys().cloned().collect())
+            .unwrap_or_default()
+    };
+    names.sort();
+    names
+}
+
+/// Length of the clip bound to `entity`, once it has loaded
+pub fn animation_duration(world: &World, entity: Entity) -> Option<f32> {
+    let handle = world.get::<AnimationBinding>(entity)?.clip.as_ref()?;
+    world
+        .resource::<Assets<AnimationClip>>()
+        .get(handle)
+        .map(|clip| clip.duration())
+}
+
+/// Resolve clips and find the animation player for each `AnimationRef`
+fn bind_animation_refs(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    library: Res<AnimationLibrary>,
+    gltfs: Res<Assets<Gltf>>,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+    mut refs: Query<(
+        Entity,
+        &AnimationRef,
+        Option<&GltfSource>,
+        Option<&mut AnimationBinding>,
+    )>,
+    children: Query<&Children>,
+    players: Query<(), With<AnimationPlayer>>,
+) {
+    for (entity, animation, gltf_source, binding) in &mut refs {
+        let Some(mut binding) = binding else {
+            commands
+                .entity(entity)
+                .insert(AnimationBinding::new(animation.source.clone()));
+            continue;
+        };
+
+        // Re-resolve when the reference or the model file it looks clips up in changes
+        let gltf_changed = gltf_source.is_some_and(|gltf_source| {
+            binding
+                .gltf_path
+                .as_ref()
+                .is_some_and(|path| *path != gltf_source.path)
+        });
+        if binding.source != animation.source || gltf_changed {
+            *binding = AnimationBinding::new(animation.source.clone());
+        }
+
+        // The model was reloaded (e.g. its GltfSource path changed)
+        if binding.player.is_some_and(|player| !players.contains(player)) {
+            binding.player = None;
+        }
+
+        if binding.graph.is_none() {
+            let clip = match &animation.source {
+                AnimationSource::Library(name) => library.clips.get(name).cloned(),
+                AnimationSource::Gltf(name) => {
+                    let Some(gltf_source) = gltf_source else {
+                        continue;
+                    };
+                    if binding.gltf.is_none() {
+                        binding.gltf_path = Some(gltf_source.path.clone());
+                    }
+                    let handle = binding
+                        .gltf
+                        .get_or_insert_with(|| asset_server.load(gltf_source.path.clone()))
+                        .clone();
+                    gltfs
+                        .get(&handle)
+                        .and_then(|gltf| gltf.named_animations.get(name.as_str()).cloned())
+                }
+            };
+            let Some(clip) = clip else {
+                continue;
+            };
+            let (graph, node) = AnimationGraph::from_clip(clip.clone());
+            binding.clip = Some(clip);
+            binding.graph = Some((graphs.add(graph), node));
+        }
+
+        if binding.player.is_none() {
+            // GLTF scenes spawn asynchronously; keep looking until the player shows up
+            let player = std::iter::once(entity)
+                .chain(children.iter_descendants(entity))
+                .find(|&e| players.contains(e));
+            let graph = binding.graph.as_ref().map(|(graph, _)| graph.clone());
+            if let (Some(player), Some(graph)) = (player, graph) {
+                commands.entity(player).insert(AnimationGraphHandle(graph));
+                binding.player = Some(player);
+            }
+        }
+    }
+}
+
+/// Hold each bound animation at its preview time while editing
+fn preview_animations(
+    time: Res<Time>,
+    clips: Res<Assets<AnimationClip>>,
+    mut refs: Query<(&AnimationRef, &mut AnimationBinding)>,
+    mut players: Query<&mut AnimationPlayer>,
+) {
+    for (animation, mut binding) in &mut refs {
+        let (Some(player), Some((_, node))) = (binding.player, binding.graph.clone()) else {
+            continue;
+        };
+        let Ok(mut player) = players.get_mut(player) else {
+            continue;
+        };
+
+        if binding.previewing {
+            binding.preview_time += time.delta_secs() * animation.speed;
+        }
+
+        // Wrap looping clips, stop others at either end
+        let duration = binding
+            .clip
+            .as_ref()
+            .and_then(|handle| clips.get(handle))
+            .map(|clip| clip.duration())
+            .unwrap_or(0.0);
+        if duration > 0.0 && !(0.0..=duration).contains(&binding.preview_time) {
+            if animation.looping {
+                binding.preview_time = binding.preview_time.rem_euclid(duration);
+            } else {
+                binding.preview_time = binding.preview_time.clamp(0.0, duration);
+                binding.previewing = false;
+            }
+        }
+
+        player.play(node).pause().seek_to(binding.preview_time);
+    }
+}
+
+/// Queue autoplay for every animation when the game starts
+fn handle_game_started(
+    mut events: MessageReader<GameStartedEvent>,
+    mut refs: Query<(&AnimationRef, &mut AnimationBinding)>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+    for (animation, mut binding) in &mut refs {
+        binding.previewing = false;
+        binding.pending_autoplay = animation.autoplay;
+    }
+}
+
+/// Start queued animations once their player is ready
+fn autoplay_animations(
+    mut refs: Query<(&AnimationRef, &mut AnimationBinding)>,
+    mut players: Query<&mut AnimationPlayer>,
+) {
+    for (animation, mut binding) in &mut refs {
+        if !binding.pending_autoplay {
+            continue;
+        }
+        let (Some(player), Some((_, node))) = (binding.player, binding.graph.clone()) else {
+            continue;
+        };
+        let Ok(mut player) = players.get_mut(player) else {
+            continue;
+        };
+
+        player.stop_all();
+        let active = player.play(node);
+        active
+            .set_speed(animation.speed)
+            .set_repeat(if animation.looping {
+                RepeatAnimation::Forever
+            } else {
+                RepeatAnimation::Never
+            })
+            .seek_to(0.0)
+            .resume();
+        binding.pending_autoplay = false;
+    }
+}
+
+fn handle_game_paused(
+    mut events: MessageReader<GamePausedEvent>,
+    refs: Query<&AnimationBinding>,
+    mut players: Query<&mut AnimationPlayer>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+    for binding in &refs {
+        if let Some(mut player) = binding.player.and_then(|p| players.get_mut(p).ok()) {
+            player.pause_all();
+        }
+    }
+}
+
+fn handle_game_resumed(
+    mut events: MessageReader<GameResumedEvent>,
+    refs: Query<&AnimationBinding>,
+    mut players: Query<&mut AnimationPlayer>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+    for binding in &refs {
+        if let Some(mut player) = binding.player.and_then(|p| players.get_mut(p).ok()) {
+            player.resume_all();
+        }
+    }
+}
@@ -1,3 +1,4 @@
+mod animation;
 pub mod blockout;
 mod collider_constructor;
 pub mod generators;
@@ -6,7 +7,12 @@ mod primitives;
 mod scene_source;
 mod serialization;
 mod splat_source;
+mod spline_deform;
+mod trigger_volume;
+mod validation;
+mod workspace;
 
+pub use animation::*;
 pub use blockout::*;
 pub use collider_constructor::*;
 pub use gltf_source::*;
@@ -14,6 +20,10 @@ pub use primitives::*;
 pub use scene_source::*;
 pub use serialization::*;
 pub use splat_source::*;
+pub use spline_deform::*;
+pub use trigger_volume::*;
+pub use validation::*;
+pub use workspace::*;
 
 use avian3d::prelude::*;
 use bevy::ecs::entity::EntityHashMap;
@@ -22,7 +32,7 @@ use bevy::pbr::decal::{ForwardDecal, ForwardDecalMaterial, ForwardDecalMaterialE
 use bevy::prelude::*;
 use bevy::scene::serde::SceneDeserializer;
 use bevy_editor_game::{
-    AssetRef, BaseMaterialProps, CustomEntityRegistry, MaterialDefinition,
+    AnimationRef, AssetRef, BaseMaterialProps, CustomEntityRegistry, MaterialDefinition,
     MaterialExtensionData, MaterialLibrary, MaterialRef, MeshLibrary, MeshRef,
     SceneComponentRegistry, ValidationRegistry,
 };
@@ -30,7 +40,7 @@ use bevy_editor_game::{
 use crate::materials::{load_base_textures, MaterialTypeRegistry, resolve_material_ref};
 use bevy_outliner::prelude::{HasSilhouetteMesh, SilhouetteMesh};
 use bevy_procedural::{ProceduralEntity, ProceduralPlacer, ProceduralTemplate};
-use bevy_spline_3d::prelude::{ResolvedSplineFollower, SplineFollower};
+use bevy_spline_3d::prelude::{ResolvedSplineFollower, SplineFollower, SplineMeshDeform};
 use bevy_spline_3d::prelude::{Spline, SplineType};
 use serde::de::DeserializeSeed;
 
@@ -53,6 +63,7 @@ pub fn build_editor_scene(world: &World, entities: impl Iterator<Item = Entity>)
         .deny_all()
         // Core
         .allow_component::<SceneEntity>()
+        .allow_component::<PersistentId>()
         .allow_component::<Name>()
         .allow_component::<Transform>()
         .allow_component::<RigidBody>()
@@ -63,6 +74,8 @@ pub fn build_editor_scene(world: &World, entities: impl Iterator<Item = Entity>)
         .allow_component::<MaterialRef>()
         // Library meshes
         .allow_component::<MeshRef>()
+        // Animation
+        .allow_component::<AnimationRef>()
         // Backwards compat: still extract old types if present on legacy entities
         .allow_component::<PrimitiveMaterial>()
         .allow_component::<MaterialType>()
@@ -76,16 +89,24 @@ pub fn build_editor_scene(world: &World, entities: impl Iterator<Item = Entity>)
         .allow_component::<SplineMarker>()
         .allow_component::<Spline>()
         .allow_component::<SplineFollower>()
+        .allow_component::<SplineMeshDeform>()
+        .allow_component::<DeformSource>()
         // Fog
         .allow_component::<FogVolumeMarker>()
         // Decals
         .allow_component::<DecalMarker>()
+        // Audio
+        .allow_component::<crate::audio::SoundEmitterMarker>()
+        // Trigger volumes
+        .allow_component::<TriggerVolumeMarker>()
         // VFX
         .allow_component::<bevy_vfx::VfxSystem>()
         // Effects
         .allow_component::<crate::effects::EffectMarker>()
         // Edited meshes
         .allow_component::<crate::modeling::marker::EditMeshMarker>()
+        .allow_component::<crate::modeling::modifier::MeshModifierStack>()
+        .allow_component::<crate::modeling::material_slots::MaterialSlots>()
         // Blockout shapes
         .allow_component::<StairsMarker>()
         .allow_component::<RampMarker>()
@@ -94,6 +115,8 @@ pub fn build_editor_scene(world: &World, entities: impl Iterator<Item = Entity>)
         // Prefabs
         .allow_component::<crate::prefabs::PrefabInstance>()
         .allow_component::<crate::prefabs::PrefabRoot>()
+        .allow_component::<crate::prefabs::PrefabOverrides>()
+        .allow_component::<crate::prefabs::PrefabOverrideValues>()
         // External sources
         .allow_component::<GltfSource>()
         .allow_component::<SceneSource>()
@@ -113,7 +136,46 @@ pub fn build_editor_scene(world: &World, entities: impl Iterator<Item = Entity>)
         builder = registry.apply(builder);
     }
 
-    builder.extract_entities(entities).build()
+    // Prefab instances stored by reference (see `PrefabOverrideValues`) keep only their root
+    let collapsed: Vec<Entity> = world
+        .try_query_filtered::<Entity, (
+            With<crate::prefabs::PrefabRoot>,
+            With<crate::prefabs::PrefabOverrideValues>,
+        )>()
+        .map(|mut query| query.iter(world).collect())
+        .unwrap_or_default();
+    let mut scene = if collapsed.is_empty() {
+        builder.extract_entities(entities).build()
+    } else {
+        let entities = entities.filter(|&entity| {
+            let mut current = entity;
+            while let Some(child_of) = world.get::<ChildOf>(current) {
+                current = child_of.parent();
+                if collapsed.contains(&current) {
+                    return false;
+                }
+            }
+            true
+        });
+        builder.extract_entities(entities).build()
+    };
+    for dynamic_entity in &mut scene.entities {
+        if collapsed.contains(&dynamic_entity.entity) {
+            dynamic_entity
+                .components
+                .retain(|component| !component.represents::<Children>());
+        }
+        // Material slot meshes are rebuilt from the edit mesh, never saved
+        dynamic_entity.components.retain_mut(|component| {
+            !component.represents::<Children>()
+                || retain_reflected_children(component.as_mut(), &mut |child| {
+                    world
+                        .get::<crate::modeling::material_slots::MaterialSlotMesh>(child)
+                        .is_none()
+                })
+        });
+    }
+    scene
 }
 
 /// Regenerate runtime components (meshes, materials, colliders, lights, fog volumes)
@@ -273,6 +335,24 @@ pub fn regenerate_runtime_components(world: &mut World) {
         }
     }
 
+    // Handle sound emitters (voices are managed by the audio plugin)
+    let mut emitters_to_update: Vec<Entity> = Vec::new();
+    {
+        let mut query = world.query_filtered::<Entity, (
+            With<crate::audio::SoundEmitterMarker>,
+            Without<Collider>,
+        )>();
+        for entity in query.iter(world) {
+            emitters_to_update.push(entity);
+        }
+    }
+
+    for entity in emitters_to_update {
+        if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.insert((Visibility::default(), Collider::sphere(LIGHT_COLLIDER_RADIUS)));
+        }
+    }
+
     // Handle decals — collect entities missing either decal runtime component
     let mut decals_to_update: Vec<(Entity, DecalMarker)> = Vec::new();
     {
@@ -539,7 +619,8 @@ fn apply_material_def_from_fns(
 
 /// Migrate legacy PrimitiveMaterial + MaterialType to MaterialRef.
 /// Entities that have old components but no MaterialRef get one created.
-fn migrate_legacy_materials(world: &mut World) {
+/// Returns the number of migrated entities.
+pub fn migrate_legacy_materials(world: &mut World) -> usize {
     let mut to_migrate: Vec<(Entity, Color, MaterialType)> = Vec::new();
     {
         let mut query = world.query_filtered::<(
@@ -564,6 +645,7 @@ fn migrate_legacy_materials(world: &mut World) {
     let grid_default_data =
         ron::to_string(&crate::materials::grid::GridMaterialProps::default()).unwrap_or_default();
 
+    let count = to_migrate.len();
     for (entity, color, mat_type) in to_migrate {
         let mat_ref = match mat_type {
             MaterialType::Standard => MaterialRef::Inline(MaterialDefinition::standard(color)),
@@ -580,6 +662,7 @@ fn migrate_legacy_materials(world: &mut World) {
             e.insert(mat_ref);
         }
     }
+    count
 }
 
 /// Regenerate materials for blockout shapes that have MaterialRef but no rendered material.
@@ -666,7 +749,9 @@ fn regenerate_mesh_refs(
 /// This is the shared implementation used by both undo/redo and play/reset.
 /// It handles: silhouette cleanup, despawning SceneEntity entities,
 /// RON deserialization, writing to world, and regenerating runtime components.
-pub fn restore_scene_from_data(world: &mut World, data: &str) {
+///
+/// Returns the map from entity IDs stored in `data` to the newly spawned entities.
+pub fn restore_scene_from_data(world: &mut World, data: &str) -> EntityHashMap<Entity> {
     // Clean up silhouette entities BEFORE despawning scene entities.
     // The outliner creates separate SilhouetteMesh entities (on render layer 31) that are
     // NOT children of the source entity. If we despawn the source first, the outliner's
@@ -715,12 +800,12 @@ pub fn restore_scene_from_data(world: &mut World, data: &str) {
 
     let Ok(mut ron_deserializer) = ron::de::Deserializer::from_str(data) else {
         warn!("Failed to parse scene data");
-        return;
+        return EntityHashMap::default();
     };
 
     let Ok(scene) = scene_deserializer.deserialize(&mut ron_deserializer) else {
         warn!("Failed to deserialize scene data");
-        return;
+        return EntityHashMap::default();
     };
 
     drop(type_registry);
@@ -729,7 +814,7 @@ pub fn restore_scene_from_data(world: &mut World, data: &str) {
     let mut entity_map = EntityHashMap::default();
     if let Err(e) = scene.write_to_world(world, &mut entity_map) {
         warn!("Failed to restore scene: {:?}", e);
-        return;
+        return entity_map;
     }
 
     info!("Wrote {} entities to world from scene data", entity_map.len());
@@ -741,6 +826,64 @@ pub fn restore_scene_from_data(world: &mut World, data: &str) {
     resolve_entity_references(world);
 
     info!("Scene restoration complete");
+
+    entity_map
+}
+
+/// Despawn scene entities (and their descendants) along with their outline silhouettes.
+///
+/// Silhouettes must go first for the same reason as in `restore_scene_from_data`.
+pub fn despawn_scene_entities(world: &mut World, entities: &[Entity]) {
+    let mut silhouettes: Vec<Entity> = Vec::new();
+    let mut stack: Vec<Entity> = entities.to_vec();
+    while let Some(entity) = stack.pop() {
+        if let Some(has_silhouette) = world.get::<HasSilhouetteMesh>(entity) {
+            silhouettes.push(has_silhouette.silhouette);
+        }
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend_from_slice(children);
+        }
+    }
+    for entity in silhouettes {
+        if let Ok(entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.despawn();
+        }
+    }
+
+    for &entity in entities {
+        if let Ok(entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.despawn();
+        }
+    }
+}
+
+/// Remove runtime components derived from scene markers so that
+/// `regenerate_runtime_components` rebuilds them from the current marker values.
+///
+/// Blockout meshes and decals are left alone; they follow their markers via change detection.
+pub fn clear_derived_components(world: &mut World, entity: Entity) {
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+
+    if entity_mut.contains::<PrimitiveMarker>()
+        || entity_mut.contains::<MeshRef>()
+        || entity_mut.contains::<crate::modeling::marker::EditMeshMarker>()
+    {
+        entity_mut.remove::<Mesh3d>();
+    }
+    if entity_mut.contains::<MaterialRef>() {
+        entity_mut.remove::<MeshMaterial3d<StandardMaterial>>();
+    }
+    if entity_mut.contains::<SceneLightMarker>() {
+        entity_mut.remove::<PointLight>();
+    }
+    if entity_mut.contains::<DirectionalLightMarker>() {
+        entity_mut.remove::<DirectionalLight>();
+    }
+    if entity_mut.contains::<FogVolumeMarker>() {
+        entity_mut.remove::<FogVolume>();
+    }
 }
 
 /// Event to spawn the demo scene
@@ -751,21 +894,46 @@ pub struct ScenePlugin;
 
 impl Plugin for ScenePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<SceneComponentRegistry>()
-            .init_resource::<CustomEntityRegistry>()
-            .init_resource::<ValidationRegistry>()
+        app.add_plugins(HeadlessEditorPlugin)
             .add_plugins(PrimitivesPlugin)
             .add_plugins(SerializationPlugin)
+            .add_plugins(WorkspacePlugin)
             .add_plugins(GltfSourcePlugin)
+            .add_plugins(AnimationRefPlugin)
             .add_plugins(SceneSourcePlugin)
             .add_plugins(ColliderConstructorPlugin)
             .add_plugins(SplatSourcePlugin)
             .add_plugins(BlockoutPlugin)
+            .add_plugins(TriggerVolumePlugin)
+            .add_plugins(SplineDeformSourcePlugin)
             .add_plugins(generators::SceneGeneratorPlugin)
             .add_message::<SpawnDemoSceneEvent>()
-            .add_systems(Update, (handle_spawn_demo_scene, sync_decal_markers))
+            .add_systems(Update, (handle_spawn_demo_scene, sync_decal_markers));
+    }
+}
+
+/// Scene type registration and game registries, without any rendering or UI.
+///
+/// `ScenePlugin` adds this for the editor. Headless tools add it on top of
+/// `MinimalPlugins` so scene files can be read, validated and written without
+/// a window. Every component `build_editor_scene` extracts is registered here,
+/// along with the built-in validation rules.
+pub struct HeadlessEditorPlugin;
+
+impl Plugin for HeadlessEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SceneComponentRegistry>()
+            .init_resource::<CustomEntityRegistry>()
+            .init_resource::<ValidationRegistry>()
+            // Engine and physics types
+            .register_type::<Name>()
+            .register_type::<Transform>()
+            .register_type::<ChildOf>()
+            .register_type::<Children>()
+            .register_type::<RigidBody>()
             // Register types for scene serialization
             .register_type::<SceneEntity>()
+            .register_type::<PersistentId>()
             .register_type::<SceneProceduralObject>()
             .register_type::<PrimitiveMarker>()
             .register_type::<PrimitiveShape>()
@@ -776,17 +944,38 @@ impl Plugin for ScenePlugin {
             .register_type::<RecursiveColliderConstructor>()
             .register_type::<ColliderType>()
             .register_type::<SceneSource>()
+            .register_type::<GltfSource>()
+            .register_type::<SplatSource>()
+            .register_type::<AnimationRef>()
             // Spline types
             .register_type::<SplineMarker>()
             .register_type::<Spline>()
             .register_type::<SplineType>()
+            .register_type::<SplineFollower>()
+            .register_type::<SplineMeshDeform>()
+            .register_type::<DeformSource>()
             // Edited mesh types
             .register_type::<crate::modeling::marker::EditMeshMarker>()
+            .register_type::<crate::modeling::modifier::MeshModifierStack>()
+            .register_type::<crate::modeling::material_slots::MaterialSlots>()
+            .register_type::<crate::modeling::modifier::ModifierEntry>()
+            .register_type::<crate::modeling::modifier::MeshModifier>()
+            .register_type::<crate::modeling::mirror::MirrorAxis>()
+            .register_type::<crate::modeling::boolean::BooleanOp>()
+            // Blockout shapes
+            .register_type::<StairsMarker>()
+            .register_type::<RampMarker>()
+            .register_type::<ArchMarker>()
+            .register_type::<LShapeMarker>()
             // Fog volume types
             .register_type::<FogVolumeMarker>()
             // Decal types
             .register_type::<DecalMarker>()
             .register_type::<DecalType>()
+            // Audio, trigger volume and VFX types
+            .register_type::<crate::audio::SoundEmitterMarker>()
+            .register_type::<TriggerVolumeMarker>()
+            .register_type::<bevy_vfx::VfxSystem>()
             // Effect types
             .register_type::<crate::effects::EffectMarker>()
             .register_type::<crate::effects::EffectStep>()
@@ -794,6 +983,15 @@ impl Plugin for ScenePlugin {
             .register_type::<crate::effects::EffectAction>()
             .register_type::<crate::effects::RigidBodyKind>()
             .register_type::<crate::effects::SpawnLocation>()
+            // Prefab types
+            .register_type::<crate::prefabs::PrefabInstance>()
+            .register_type::<crate::prefabs::PrefabRoot>()
+            .register_type::<crate::prefabs::PrefabOverrides>()
+            .register_type::<crate::prefabs::PrefabOverrideValues>()
+            // Procedural placement types
+            .register_type::<ProceduralPlacer>()
+            .register_type::<ProceduralEntity>()
+            .register_type::<ProceduralTemplate>()
             // Material types (new system)
             .register_type::<MaterialRef>()
             .register_type::<MaterialDefinition>()
@@ -809,6 +1007,8 @@ impl Plugin for ScenePlugin {
             // Asset reference types
             .register_type::<AssetRef>()
             .register_type::<bevy_editor_game::AssetType>();
+
+        validation::register_builtin_rules(app);
     }
 }
 
@@ -1,7 +1,7 @@
 use avian3d::prelude::*;
 use bevy::light::{ClusteredDecal, FogVolume};
 use bevy::prelude::*;
-use bevy_spline_3d::prelude::{Spline, SplineType};
+use bevy_spline_3d::prelude::{Spline, SplineMeshDeform, SplineType};
 use serde::{Deserialize, Serialize};
 
 use bevy::pbr::ExtendedMaterial;
@@ -9,10 +9,11 @@ use bevy_editor_game::{BaseMaterialProps, CustomEntityRegistry, MaterialDefiniti
 use bevy_grid_shader::GridMaterial;
 
 use super::blockout::{spawn_arch, spawn_lshape, spawn_ramp, spawn_stairs, GridMat};
-use super::SceneEntity;
-use crate::commands::TakeSnapshotCommand;
+use super::{DeformSource, SceneEntity, TriggerVolumeMarker};
+use crate::commands::{RecordEntitiesCommand, RecordReparentCommand};
 use crate::constants::{light_colors, physics, primitive_colors};
 use crate::materials::grid::GridMaterialProps;
+use crate::audio::SoundEmitterMarker;
 use crate::effects::{EffectLibrary, EffectMarker};
 use bevy_vfx::{VfxLibrary, VfxSystem};
 use crate::selection::Selected;
@@ -228,6 +229,8 @@ pub enum SpawnEntityKind {
     DirectionalLight,
     /// A spline curve
     Spline(SplineType),
+    /// A spline bending a mesh along its length
+    DeformSpline,
     /// A volumetric fog volume
     FogVolume,
     /// Parametric stairs
@@ -248,6 +251,10 @@ pub enum SpawnEntityKind {
     EffectPreset(String),
     /// A clustered decal (projected texture)
     Decal,
+    /// A spatial sound emitter
+    SoundEmitter,
+    /// A sensor volume reporting enters and exits
+    TriggerVolume,
     /// A mesh from the mesh library (GLTF asset libraries)
     LibraryMesh(String),
     /// A custom entity type registered by the game
@@ -270,6 +277,7 @@ impl SpawnEntityKind {
                 SplineType::BSpline => "B-Spline",
             }
             .to_string(),
+            SpawnEntityKind::DeformSpline => "Mesh Deform Spline".to_string(),
             SpawnEntityKind::FogVolume => "Fog Volume".to_string(),
             SpawnEntityKind::Stairs => "Stairs".to_string(),
             SpawnEntityKind::Ramp => "Ramp".to_string(),
@@ -280,6 +288,8 @@ impl SpawnEntityKind {
             SpawnEntityKind::Effect => "Effect".to_string(),
             SpawnEntityKind::EffectPreset(name) => format!("Effect: {}", name),
             SpawnEntityKind::Decal => "Decal".to_string(),
+            SpawnEntityKind::SoundEmitter => "Sound Emitter".to_string(),
+            SpawnEntityKind::TriggerVolume => "Trigger Volume".to_string(),
             SpawnEntityKind::LibraryMesh(name) => {
                 // Use the part after "::" as display name, or the full name
                 name.rsplit("::").next().unwrap_or(name).to_string()
@@ -396,6 +406,7 @@ fn handle_spawn_entity(
             SpawnEntityKind::PointLight => spawn_point_light(&mut commands, event.position, event.rotation, &name),
             SpawnEntityKind::DirectionalLight => spawn_directional_light(&mut commands, event.position, event.rotation, &name),
             SpawnEntityKind::Spline(spline_type) => spawn_spline(&mut commands, *spline_type, event.position, event.rotation, &name),
+            SpawnEntityKind::DeformSpline => spawn_deform_spline(&mut commands, event.position, event.rotation, &name),
             SpawnEntityKind::FogVolume => spawn_fog_volume(&mut commands, event.position, event.rotation, &name),
             SpawnEntityKind::Stairs => spawn_stairs(&mut commands, &mut meshes, &mut grid_materials, event.position, event.rotation, &name),
             SpawnEntityKind::Ramp => spawn_ramp(&mut commands, &mut meshes, &mut grid_materials, event.position, event.rotation, &name),
@@ -420,6 +431,8 @@ fn handle_spawn_entity(
                 spawn_effect(&mut commands, event.position, event.rotation, &name, marker)
             }
             SpawnEntityKind::Decal => spawn_decal(&mut commands, event.position, event.rotation, &name),
+            SpawnEntityKind::SoundEmitter => spawn_sound_emitter(&mut commands, event.position, event.rotation, &name),
+            SpawnEntityKind::TriggerVolume => spawn_trigger_volume(&mut commands, event.position, event.rotation, &name),
             SpawnEntityKind::LibraryMesh(mesh_name) => {
                 spawn_library_mesh(
                     &mut commands,
@@ -587,6 +600,18 @@ pub fn spawn_spline(commands: &mut Commands, spline_type: SplineType, position:
         .id()
 }
 
+/// Spawn a Catmull-Rom spline that bends a cube along its length
+pub fn spawn_deform_spline(commands: &mut Commands, position: Vec3, rotation: Quat, name: &str) -> Entity {
+    let shape = PrimitiveShape::Cube;
+    let entity = spawn_spline(commands, SplineType::CatmullRom, position, rotation, name);
+    commands.entity(entity).insert((
+        SplineMeshDeform::default(),
+        DeformSource::Primitive(shape),
+        MaterialRef::Inline(MaterialDefinition::standard(shape.default_color())),
+    ));
+    entity
+}
+
 /// Spawn a fog volume entity
 pub fn spawn_fog_volume(commands: &mut Commands, position: Vec3, rotation: Quat, name: &str) -> Entity {
     let marker = FogVolumeMarker::default();
@@ -674,6 +699,36 @@ pub fn spawn_decal(commands: &mut Commands, position: Vec3, rotation: Quat, name
         .id()
 }
 
+/// Spawn a sound emitter entity with default settings
+pub fn spawn_sound_emitter(commands: &mut Commands, position: Vec3, rotation: Quat, name: &str) -> Entity {
+    commands
+        .spawn((
+            SceneEntity,
+            Name::new(name.to_string()),
+            SoundEmitterMarker::default(),
+            Transform::from_translation(position).with_rotation(rotation),
+            Visibility::default(),
+            Collider::sphere(physics::LIGHT_COLLIDER_RADIUS),
+        ))
+        .id()
+}
+
+/// Spawn a trigger volume entity with a default box shape
+pub fn spawn_trigger_volume(commands: &mut Commands, position: Vec3, rotation: Quat, name: &str) -> Entity {
+    let marker = TriggerVolumeMarker::default();
+    commands
+        .spawn((
+            SceneEntity,
+            Name::new(name.to_string()),
+            marker.shape.collider(),
+            Sensor,
+            marker,
+            Transform::from_translation(position).with_rotation(rotation),
+            Visibility::default(),
+        ))
+        .id()
+}
+
 /// Spawn a library mesh entity by name.
 fn spawn_library_mesh(
     commands: &mut Commands,
@@ -713,7 +768,8 @@ fn handle_parent_to_group(
     for event in events.read() {
         // Verify the parent is a valid group
         if groups.get(event.parent).is_ok() {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordReparentCommand {
+                entities: vec![event.child],
                 description: "Parent to group".to_string(),
             });
             commands.entity(event.child).set_parent_in_place(event.parent);
@@ -726,7 +782,8 @@ fn handle_parent_to_group(
 
 fn handle_unparent(mut events: MessageReader<UnparentEvent>, mut commands: Commands) {
     for event in events.read() {
-        commands.queue(TakeSnapshotCommand {
+        commands.queue(RecordReparentCommand {
+            entities: vec![event.entity],
             description: "Unparent entity".to_string(),
         });
         commands.entity(event.entity).remove_parent_in_place();
@@ -741,7 +798,8 @@ fn handle_unparent_selected(
 ) {
     for _ in events.read() {
         if !selected.is_empty() {
-            commands.queue(TakeSnapshotCommand {
+            commands.queue(RecordReparentCommand {
+                entities: selected.iter().collect(),
                 description: "Unparent selected entities".to_string(),
             });
             for entity in selected.iter() {
@@ -767,21 +825,26 @@ fn handle_group_selected(
             return;
         }
 
-        commands.queue(TakeSnapshotCommand {
+        // Record the selection with the group about to be spawned; undo despawns
+        // the group and restores the selection's parents and transforms
+        let group_entity = commands.spawn_empty().id();
+        let mut recorded = selected_entities.clone();
+        recorded.push(group_entity);
+        commands.queue(RecordEntitiesCommand {
+            entities: recorded,
+            recursive: true,
             description: "Group selected entities".to_string(),
         });
 
         // Create the group with identity transform
         let name = generate_unique_name("Group", &existing_entities);
-        let group_entity = commands
-            .spawn((
-                SceneEntity,
-                GroupMarker,
-                Name::new(name.clone()),
-                Transform::default(),
-                Visibility::default(),
-            ))
-            .id();
+        commands.entity(group_entity).insert((
+            SceneEntity,
+            GroupMarker,
+            Name::new(name.clone()),
+            Transform::default(),
+            Visibility::default(),
+        ));
 
         // Parent all selected entities to the group
         for entity in selected_entities {
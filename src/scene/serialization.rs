@@ -1,23 +1,26 @@
 use avian3d::prelude::RigidBody;
+use bevy::ecs::entity::EntityHashMap;
 use bevy::prelude::*;
+use bevy::reflect::ReflectMut;
 use bevy::scene::serde::SceneDeserializer;
 use bevy::window::PrimaryWindow;
 use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
 use bevy_spline_3d::prelude::Spline;
 use serde::de::DeserializeSeed;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
-use bevy_editor_game::{CameraRenderSettings, MaterialLibrary, MaterialRef};
+use bevy_editor_game::{CameraRenderSettings, MaterialDefinition, MaterialLibrary, MaterialRef};
 
 use super::{
     build_editor_scene, regenerate_runtime_components, PrimitiveMarker, SceneEntity,
-    SceneLightMarker,
+    SceneLightMarker, SceneWorkspace,
 };
 use crate::editor::{CameraMark, CameraMarks};
 use crate::ui::draw_error_dialog as draw_themed_error_dialog;
+use crate::ui::Settings;
 
 /// Event to save the scene
 #[derive(Message)]
@@ -82,16 +85,54 @@ impl SceneFile {
     }
 }
 
+/// Stable identifier of a scene entity. Stable saves use it as the entity key in the
+/// scene file, so files don't churn when ECS entity IDs change between sessions.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[reflect(Component)]
+pub struct PersistentId(pub u32);
+
 /// Sidecar data for editor-specific metadata (camera marks, material library, etc.)
 #[derive(Serialize, Deserialize, Default)]
 struct EditorMetadata {
+    #[serde(serialize_with = "serialize_sorted_map")]
     camera_marks: HashMap<String, CameraMark>,
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted_material_library")]
     material_library: MaterialLibrary,
     #[serde(default)]
     camera_render_settings: Option<CameraRenderSettings>,
 }
 
+/// Write a map in key order so the sidecar file is stable across saves
+fn serialize_sorted_map<S: Serializer, V: Serialize>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+/// Mirrors `MaterialLibrary`'s serialized shape with materials in name order
+fn serialize_sorted_material_library<S: Serializer>(
+    library: &MaterialLibrary,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct SortedMaterialLibrary<'a> {
+        materials: BTreeMap<&'a String, &'a MaterialDefinition>,
+    }
+
+    SortedMaterialLibrary {
+        materials: library.materials.iter().collect(),
+    }
+    .serialize(serializer)
+}
+
+/// Read the material library from a scene's `.meta` sidecar, if it has one
+pub fn load_scene_material_library(scene_path: &str) -> Option<MaterialLibrary> {
+    let metadata_content = fs::read_to_string(format!("{}.meta", scene_path)).ok()?;
+    let metadata = ron::from_str::<EditorMetadata>(&metadata_content).ok()?;
+    Some(metadata.material_library)
+}
+
 /// Serializable transform data (used by prefabs)
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct SerializedTransform {
@@ -162,6 +203,7 @@ impl Plugin for SerializationPlugin {
             .add_systems(
                 Update,
                 (
+                    assign_missing_persistent_ids,
                     handle_save_scene,
                     handle_force_save_scene,
                     handle_load_scene,
@@ -225,23 +267,44 @@ impl Command for SaveSceneCommand {
     fn apply(self, world: &mut World) {
         info!("SaveSceneCommand running for path: {}", self.path);
 
-        // Collect scene entity IDs
-        let scene_entity_ids: Vec<Entity> = {
-            let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
-            query.iter(world).collect()
-        };
+        // Prefabs inside a prefab are saved by reference, with their overridden values
+        let nested_prefabs: Vec<Entity> =
+            if world.contains_resource::<crate::prefabs::PrefabEditingContext>() {
+                let mut query =
+                    world.query_filtered::<Entity, With<crate::prefabs::PrefabRoot>>();
+                query.iter(world).collect()
+            } else {
+                Vec::new()
+            };
+        crate::prefabs::attach_override_values(world, &nested_prefabs);
+
+        let stable_ids = world
+            .get_resource::<Settings>()
+            .is_none_or(|settings| settings.stable_scene_ids);
 
-        info!("Found {} scene entities to save", scene_entity_ids.len());
+        info!("Serializing scene...");
+        let result = if stable_ids {
+            serialize_scene_stable(world)
+        } else {
+            // Collect scene entity IDs
+            let scene_entity_ids: Vec<Entity> = {
+                let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
+                query.iter(world).collect()
+            };
 
-        // Build the scene using shared allow-list
-        let scene = build_editor_scene(world, scene_entity_ids.into_iter());
+            info!("Found {} scene entities to save", scene_entity_ids.len());
 
-        // Serialize the scene
-        let type_registry = world.resource::<AppTypeRegistry>().clone();
-        let type_registry = type_registry.read();
+            // Build the scene using shared allow-list
+            let scene = build_editor_scene(world, scene_entity_ids.into_iter());
 
-        info!("Serializing scene...");
-        match scene.serialize(&type_registry) {
+            let type_registry = world.resource::<AppTypeRegistry>().clone();
+            let type_registry = type_registry.read();
+            scene.serialize(&type_registry)
+        };
+
+        crate::prefabs::detach_override_values(world, &nested_prefabs);
+
+        match result {
             Ok(serialized) => {
                 // Write scene file
                 if let Err(e) = fs::write(&self.path, &serialized) {
@@ -297,6 +360,200 @@ impl Command for SaveSceneCommand {
     }
 }
 
+/// Give newly spawned scene entities a `PersistentId` so that undo snapshots and
+/// tab stashes carry it; duplicates (e.g. from inserted scenes) are fixed up on save
+fn assign_missing_persistent_ids(
+    mut commands: Commands,
+    missing: Query<Entity, (With<SceneEntity>, Without<PersistentId>)>,
+    existing: Query<&PersistentId>,
+) {
+    if missing.is_empty() {
+        return;
+    }
+
+    let mut next = existing.iter().map(|id| id.0).max().unwrap_or(0) + 1;
+    let mut entities: Vec<Entity> = missing.iter().collect();
+    entities.sort();
+    for entity in entities {
+        commands.entity(entity).insert(PersistentId(next));
+        next += 1;
+    }
+}
+
+/// Make every scene entity's `PersistentId` present and unique.
+/// Existing IDs are kept; missing or duplicate ones get fresh IDs above the current maximum.
+pub fn assign_persistent_ids(world: &mut World) {
+    let mut entities: Vec<(Option<u32>, Entity)> = {
+        let mut query = world.query_filtered::<(Entity, Option<&PersistentId>), With<SceneEntity>>();
+        query.iter(world).map(|(e, id)| (id.map(|id| id.0), e)).collect()
+    };
+    // Entities with IDs first, so the lowest entity keeps a duplicated ID
+    entities.sort_by_key(|&(id, entity)| (id.is_none(), id, entity));
+
+    let mut next = entities.iter().filter_map(|(id, _)| *id).max().unwrap_or(0) + 1;
+    let mut used = HashSet::new();
+    for (id, entity) in entities {
+        if let Some(id) = id {
+            if used.insert(id) {
+                continue;
+            }
+        }
+        world.entity_mut(entity).insert(PersistentId(next));
+        used.insert(next);
+        next += 1;
+    }
+}
+
+/// Build a scene keyed by `PersistentId` with entities in ID order.
+/// Entity references in components (e.g. `ChildOf`/`Children`) are rewritten to the same keys;
+/// hierarchy links to entities outside the scene are dropped.
+pub fn build_stable_scene(world: &World, entities: &[Entity]) -> DynamicScene {
+    let mut scene = build_editor_scene(world, entities.iter().copied());
+
+    let keys: EntityHashMap<Entity> = scene
+        .entities
+        .iter()
+        .filter_map(|dynamic_entity| {
+            let id = world.get::<PersistentId>(dynamic_entity.entity)?;
+            Some((dynamic_entity.entity, Entity::from_raw_u32(id.0)?))
+        })
+        .collect();
+
+    for dynamic_entity in &mut scene.entities {
+        if let Some(&key) = keys.get(&dynamic_entity.entity) {
+            dynamic_entity.entity = key;
+        }
+        strip_unsaved_relationships(&mut dynamic_entity.components, &keys);
+        for component in &mut dynamic_entity.components {
+            visit_reflected_entities(component.as_mut(), &mut |entity| {
+                if let Some(&key) = keys.get(entity) {
+                    *entity = key;
+                }
+            });
+        }
+    }
+
+    // Components are already written in type path order by the scene serializer
+    scene.entities.sort_by_key(|dynamic_entity| dynamic_entity.entity.to_bits());
+    scene
+}
+
+/// Remove `ChildOf`/`Children` links to entities that aren't saved, such as GLTF nodes
+/// and material slot meshes spawned at runtime. They would otherwise be written as raw
+/// runtime entity bits and duplicated on load.
+fn strip_unsaved_relationships(
+    components: &mut Vec<Box<dyn PartialReflect>>,
+    keys: &EntityHashMap<Entity>,
+) {
+    components.retain_mut(|component| {
+        if component.represents::<ChildOf>() {
+            return ChildOf::from_reflect(component.as_ref())
+                .is_some_and(|child_of| keys.contains_key(&child_of.parent()));
+        }
+        !component.represents::<Children>()
+            || retain_reflected_children(component.as_mut(), &mut |child| {
+                keys.contains_key(&child)
+            })
+    });
+}
+
+/// Keep the entities of a reflected `Children` component for which `keep` returns
+/// true. Returns whether any children remain.
+pub fn retain_reflected_children(
+    children: &mut dyn PartialReflect,
+    keep: &mut dyn FnMut(Entity) -> bool,
+) -> bool {
+    let ReflectMut::TupleStruct(children) = children.reflect_mut() else {
+        return true;
+    };
+    let Some(ReflectMut::List(list)) = children.field_mut(0).map(|f| f.reflect_mut()) else {
+        return true;
+    };
+    for i in (0..list.len()).rev() {
+        let kept = list
+            .get(i)
+            .and_then(|child| child.try_downcast_ref::<Entity>())
+            .is_some_and(|&child| keep(child));
+        if !kept {
+            list.remove(i);
+        }
+    }
+    !list.is_empty()
+}
+
+/// Serialize all scene entities with stable IDs and a deterministic order
+pub fn serialize_scene_stable(world: &mut World) -> Result<String, ron::Error> {
+    assign_persistent_ids(world);
+
+    let scene_entity_ids: Vec<Entity> = {
+        let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
+        query.iter(world).collect()
+    };
+
+    info!("Found {} scene entities to save", scene_entity_ids.len());
+
+    let scene = build_stable_scene(world, &scene_entity_ids);
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    scene.serialize(&type_registry)
+}
+
+/// Call `f` with every `Entity` found in a reflected value
+pub fn visit_reflected_entities(value: &mut dyn PartialReflect, f: &mut dyn FnMut(&mut Entity)) {
+    if let Some(entity) = value.try_downcast_mut::<Entity>() {
+        f(entity);
+        return;
+    }
+
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for i in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(i) {
+                    visit_reflected_entities(field, f);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for i in 0..value.field_len() {
+                if let Some(field) = value.field_mut(i) {
+                    visit_reflected_entities(field, f);
+                }
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for i in 0..value.field_len() {
+                if let Some(field) = value.field_mut(i) {
+                    visit_reflected_entities(field, f);
+                }
+            }
+        }
+        ReflectMut::List(value) => {
+            for i in 0..value.len() {
+                if let Some(item) = value.get_mut(i) {
+                    visit_reflected_entities(item, f);
+                }
+            }
+        }
+        ReflectMut::Array(value) => {
+            for i in 0..value.len() {
+                if let Some(item) = value.get_mut(i) {
+                    visit_reflected_entities(item, f);
+                }
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for i in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(i) {
+                    visit_reflected_entities(field, f);
+                }
+            }
+        }
+        // Maps and sets of entities don't occur in saved components
+        _ => {}
+    }
+}
+
 fn handle_load_scene(
     mut events: MessageReader<LoadSceneEvent>,
     mut commands: Commands,
@@ -496,7 +753,14 @@ fn detect_scene_changes(
     changed_materials: Query<(), (With<SceneEntity>, Changed<MaterialRef>)>,
     added_entities: Query<(), Added<SceneEntity>>,
     mut removed_entities: RemovedComponents<SceneEntity>,
+    workspace: Res<SceneWorkspace>,
 ) {
+    // Switching tabs respawns the whole scene and restores its own modified flag
+    if workspace.is_changed() {
+        removed_entities.clear();
+        return;
+    }
+
     // Skip if already modified
     if scene_file.modified {
         return;
@@ -591,3 +855,165 @@ fn draw_overwrite_dialog(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_world() -> World {
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<SceneEntity>();
+            registry.register::<PersistentId>();
+            registry.register::<Name>();
+            registry.register::<Transform>();
+            registry.register::<ChildOf>();
+            registry.register::<Children>();
+        }
+        let mut world = World::new();
+        world.insert_resource(registry);
+        world
+    }
+
+    fn load(world: &mut World, content: &str) {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let scene = {
+            let registry = registry.read();
+            let mut deserializer = ron::de::Deserializer::from_str(content).unwrap();
+            SceneDeserializer {
+                type_registry: &registry,
+            }
+            .deserialize(&mut deserializer)
+            .unwrap()
+        };
+        scene
+            .write_to_world(world, &mut EntityHashMap::default())
+            .unwrap();
+    }
+
+    fn find(world: &mut World, name: &str) -> Entity {
+        let mut query = world.query::<(Entity, &Name)>();
+        query
+            .iter(world)
+            .find(|(_, n)| n.as_str() == name)
+            .map(|(e, _)| e)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_stable_save_round_trip_is_byte_identical() {
+        let mut world = make_world();
+        let parent = world
+            .spawn((SceneEntity, Name::new("Parent"), Transform::from_xyz(1.0, 2.0, 3.0)))
+            .id();
+        world.spawn((
+            SceneEntity,
+            Name::new("Child A"),
+            Transform::from_xyz(0.5, 0.0, -0.25),
+            ChildOf(parent),
+        ));
+        world.spawn((
+            SceneEntity,
+            Name::new("Child B"),
+            Transform::from_scale(Vec3::splat(2.0)),
+            ChildOf(parent),
+        ));
+        world.spawn((
+            SceneEntity,
+            Name::new("Loose"),
+            Transform::from_rotation(Quat::from_rotation_y(0.3)),
+        ));
+
+        let saved = serialize_scene_stable(&mut world).unwrap();
+
+        // Shift entity indices so the loaded entities differ from the originals
+        let mut loaded = make_world();
+        for _ in 0..7 {
+            loaded.spawn_empty();
+        }
+        load(&mut loaded, &saved);
+        let resaved = serialize_scene_stable(&mut loaded).unwrap();
+
+        assert_eq!(saved, resaved);
+    }
+
+    #[test]
+    fn test_stable_save_skips_runtime_children() {
+        let mut world = make_world();
+        let parent = world
+            .spawn((SceneEntity, Name::new("Model"), Transform::default()))
+            .id();
+        world.spawn((SceneEntity, Name::new("Child"), ChildOf(parent)));
+        // Runtime children, e.g. GLTF nodes, are rebuilt on load rather than saved
+        world.spawn((Name::new("Node"), Transform::default(), ChildOf(parent)));
+        let runtime_parent = world.spawn(Transform::default()).id();
+        world.spawn((SceneEntity, Name::new("Orphan"), ChildOf(runtime_parent)));
+
+        let saved = serialize_scene_stable(&mut world).unwrap();
+
+        let mut loaded = make_world();
+        for _ in 0..5 {
+            loaded.spawn_empty();
+        }
+        load(&mut loaded, &saved);
+        let loaded_parent = find(&mut loaded, "Model");
+        let orphan = find(&mut loaded, "Orphan");
+        assert_eq!(loaded.get::<Children>(loaded_parent).map(|c| c.len()), Some(1));
+        assert!(loaded.get::<ChildOf>(orphan).is_none());
+
+        loaded.spawn((Name::new("Node"), Transform::default(), ChildOf(loaded_parent)));
+        let resaved = serialize_scene_stable(&mut loaded).unwrap();
+
+        assert_eq!(saved, resaved);
+    }
+
+    #[test]
+    fn test_material_slot_children_are_not_saved() {
+        let mut world = make_world();
+        let model = world.spawn((SceneEntity, Name::new("Model"))).id();
+        world.spawn((
+            crate::modeling::material_slots::MaterialSlotMesh { slot: 1 },
+            ChildOf(model),
+        ));
+
+        let scene = build_editor_scene(&world, std::iter::once(model));
+
+        assert!(!scene.entities[0]
+            .components
+            .iter()
+            .any(|c| c.represents::<Children>()));
+    }
+
+    #[test]
+    fn test_assign_persistent_ids_fixes_duplicates() {
+        let mut world = make_world();
+        let a = world.spawn((SceneEntity, PersistentId(4))).id();
+        let b = world.spawn((SceneEntity, PersistentId(4))).id();
+        let c = world.spawn(SceneEntity).id();
+
+        assign_persistent_ids(&mut world);
+
+        assert_eq!(world.get::<PersistentId>(a), Some(&PersistentId(4)));
+        assert_eq!(world.get::<PersistentId>(b), Some(&PersistentId(5)));
+        assert_eq!(world.get::<PersistentId>(c), Some(&PersistentId(6)));
+    }
+
+    #[test]
+    fn test_stable_scene_keys_children_by_persistent_id() {
+        let mut world = make_world();
+        let parent = world.spawn((SceneEntity, PersistentId(10))).id();
+        world.spawn((SceneEntity, PersistentId(3), ChildOf(parent)));
+
+        let entities: Vec<Entity> = vec![parent];
+        let scene = build_stable_scene(&world, &entities);
+        let children = scene.entities[0]
+            .components
+            .iter()
+            .find_map(|c| c.try_downcast_ref::<Children>())
+            .unwrap();
+
+        assert_eq!(scene.entities[0].entity, Entity::from_raw_u32(10).unwrap());
+        assert_eq!(children[0], Entity::from_raw_u32(3).unwrap());
+    }
+}
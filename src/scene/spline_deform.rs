@@ -0,0 +1,114 @@
+//! Scene references for meshes bent along splines.
+//!
+//! `SplineMeshDeform` only holds a runtime mesh handle. `DeformSource` is the
+//! serialized description of that mesh, a primitive shape or a library mesh,
+//! and is resolved to the handle on spawn and after every scene load. The
+//! spline entity's `MaterialRef` is applied to the generated mesh.
+
+use bevy::prelude::*;
+use bevy_editor_game::{MaterialDefinition, MaterialLibrary, MaterialRef, MeshLibrary, MeshRef};
+use bevy_spline_3d::prelude::{GeneratedDeformMesh, SplineMeshDeform};
+use serde::{Deserialize, Serialize};
+
+use super::PrimitiveShape;
+use crate::materials::{apply_material_def_standalone, resolve_material_ref};
+
+/// Mesh bent along the spline by the `SplineMeshDeform` on the same entity.
+#[derive(Component, Serialize, Deserialize, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub enum DeformSource {
+    /// A built-in primitive mesh
+    Primitive(PrimitiveShape),
+    /// A mesh from the mesh library
+    Mesh(MeshRef),
+}
+
+impl Default for DeformSource {
+    fn default() -> Self {
+        DeformSource::Primitive(PrimitiveShape::Cube)
+    }
+}
+
+impl DeformSource {
+    pub fn label(&self) -> String {
+        match self {
+            DeformSource::Primitive(shape) => shape.display_name().to_string(),
+            DeformSource::Mesh(MeshRef::Library(name)) => name.clone(),
+        }
+    }
+}
+
+pub struct SplineDeformSourcePlugin;
+
+impl Plugin for SplineDeformSourcePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<DeformSource>()
+            .add_systems(Update, (resolve_deform_sources, apply_deform_materials));
+    }
+}
+
+/// Point each `SplineMeshDeform` at the mesh its `DeformSource` describes
+fn resolve_deform_sources(
+    mut deforms: Query<(Ref<DeformSource>, &mut SplineMeshDeform)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mesh_library: Option<Res<MeshLibrary>>,
+) {
+    for (source, mut deform) in &mut deforms {
+        // Scene loads and undo rebuild the deform with an empty handle
+        let unresolved = deform.mesh == Handle::default();
+        if !source.is_changed() && !unresolved {
+            continue;
+        }
+
+        let mesh = match &*source {
+            DeformSource::Primitive(shape) => Some(meshes.add(shape.create_mesh())),
+            DeformSource::Mesh(MeshRef::Library(name)) => mesh_library
+                .as_ref()
+                .and_then(|library| library.meshes.get(name))
+                .cloned(),
+        };
+        match mesh {
+            Some(mesh) => deform.mesh = mesh,
+            // Library meshes may still be loading; retry while unresolved
+            None if !unresolved => deform.mesh = Handle::default(),
+            None => {}
+        }
+    }
+}
+
+/// Apply the spline's `MaterialRef` to its generated mesh when either changes
+fn apply_deform_materials(world: &mut World) {
+    let mut targets: Vec<(Entity, MaterialRef)> = Vec::new();
+    {
+        let mut generated = world.query::<(Entity, Ref<GeneratedDeformMesh>)>();
+        let mut materials = world.query_filtered::<Option<Ref<MaterialRef>>, With<DeformSource>>();
+        for (entity, marker) in generated.iter(world) {
+            let Ok(material) = materials.get(world, marker.deform) else {
+                continue;
+            };
+            match material {
+                Some(material) if marker.is_added() || material.is_changed() => {
+                    targets.push((entity, material.clone()));
+                }
+                None if marker.is_added() => targets.push((
+                    entity,
+                    MaterialRef::Inline(MaterialDefinition::standard(Color::WHITE)),
+                )),
+                _ => {}
+            }
+        }
+    }
+    if targets.is_empty() {
+        return;
+    }
+
+    let library = world
+        .get_resource::<MaterialLibrary>()
+        .cloned()
+        .unwrap_or_default();
+    for (entity, material) in targets {
+        if let Some(def) = resolve_material_ref(&material, &library).cloned() {
+            apply_material_def_standalone(world, entity, &def);
+        }
+    }
+}
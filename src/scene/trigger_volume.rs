@@ -0,0 +1,249 @@
+//! Trigger volumes: sensor shapes that report entities entering and leaving.
+//!
+//! `TriggerVolumeMarker` is the serialized description; the sensor collider
+//! is rebuilt from it whenever it changes. While the game is playing, overlaps
+//! that pass the volume's filter are reported as `TriggerEnter` / `TriggerExit`
+//! messages. Volumes are named sensors, so effect `OnEnterVolume` /
+//! `OnExitVolume` steps can reference them by name; those steps are driven
+//! by the same messages and respect the filter.
+
+use std::collections::HashSet;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_editor_game::{GameState, TriggerEnter, TriggerExit};
+use serde::{Deserialize, Serialize};
+
+/// Collision shape of a trigger volume, in local space.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum TriggerShape {
+    Box { half_extents: Vec3 },
+    Sphere { radius: f32 },
+    /// Y-aligned capsule; `half_height` excludes the caps.
+    Capsule { radius: f32, half_height: f32 },
+}
+
+impl Default for TriggerShape {
+    fn default() -> Self {
+        Self::Box {
+            half_extents: Vec3::ONE,
+        }
+    }
+}
+
+impl TriggerShape {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Box { .. } => "Box",
+            Self::Sphere { .. } => "Sphere",
+            Self::Capsule { .. } => "Capsule",
+        }
+    }
+
+    pub fn variant_index(&self) -> usize {
+        match self {
+            Self::Box { .. } => 0,
+            Self::Sphere { .. } => 1,
+            Self::Capsule { .. } => 2,
+        }
+    }
+
+    pub const VARIANT_LABELS: &[&str] = &["Box", "Sphere", "Capsule"];
+
+    pub fn from_variant_index(idx: usize) -> Self {
+        match idx {
+            1 => Self::Sphere { radius: 1.0 },
+            2 => Self::Capsule {
+                radius: 0.5,
+                half_height: 0.5,
+            },
+            _ => Self::default(),
+        }
+    }
+
+    pub fn collider(&self) -> Collider {
+        match *self {
+            Self::Box { half_extents } => {
+                let size = half_extents.max(Vec3::splat(0.01)) * 2.0;
+                Collider::cuboid(size.x, size.y, size.z)
+            }
+            Self::Sphere { radius } => Collider::sphere(radius.max(0.01)),
+            Self::Capsule {
+                radius,
+                half_height,
+            } => Collider::capsule(radius.max(0.01), half_height.max(0.0) * 2.0),
+        }
+    }
+}
+
+/// Which overlapping entities a trigger volume reports.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Reflect)]
+pub enum TriggerFilter {
+    /// Every overlapping entity
+    #[default]
+    Any,
+    /// Entities whose `Name` matches
+    Tag(String),
+    /// Entities with a component of this registered type (short or full path)
+    Component(String),
+}
+
+impl TriggerFilter {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Any => "Any",
+            Self::Tag(_) => "Tag",
+            Self::Component(_) => "Component",
+        }
+    }
+
+    pub fn variant_index(&self) -> usize {
+        match self {
+            Self::Any => 0,
+            Self::Tag(_) => 1,
+            Self::Component(_) => 2,
+        }
+    }
+
+    pub const VARIANT_LABELS: &[&str] = &["Any", "Tag", "Component"];
+
+    pub fn from_variant_index(idx: usize) -> Self {
+        match idx {
+            1 => Self::Tag(String::new()),
+            2 => Self::Component(String::new()),
+            _ => Self::Any,
+        }
+    }
+}
+
+/// Marker component for trigger volume entities (stores serializable settings)
+#[derive(Component, Serialize, Deserialize, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct TriggerVolumeMarker {
+    pub shape: TriggerShape,
+    pub filter: TriggerFilter,
+}
+
+/// Entities currently inside a trigger volume that passed its filter.
+/// Runtime only; cleared when play stops.
+#[derive(Component, Default)]
+pub struct TriggerOccupants(pub HashSet<Entity>);
+
+pub struct TriggerVolumePlugin;
+
+impl Plugin for TriggerVolumePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TriggerVolumeMarker>()
+            .register_type::<TriggerShape>()
+            .register_type::<TriggerFilter>()
+            .add_message::<TriggerEnter>()
+            .add_message::<TriggerExit>()
+            .add_systems(
+                Update,
+                (
+                    sync_trigger_volume_colliders,
+                    emit_trigger_messages.run_if(in_state(GameState::Playing)),
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(GameState::Editing), clear_trigger_occupants);
+    }
+}
+
+/// Rebuild the sensor collider whenever a trigger volume's settings change
+fn sync_trigger_volume_colliders(
+    mut commands: Commands,
+    volumes: Query<(Entity, &TriggerVolumeMarker), Changed<TriggerVolumeMarker>>,
+) {
+    for (entity, marker) in &volumes {
+        commands.entity(entity).insert((
+            marker.shape.collider(),
+            Sensor,
+            CollidingEntities::default(),
+        ));
+    }
+}
+
+/// Diff each volume's filtered overlaps against last frame and report changes
+fn emit_trigger_messages(world: &mut World) {
+    let volumes: Vec<(Entity, TriggerFilter, HashSet<Entity>, HashSet<Entity>)> = {
+        let mut query = world.query::<(
+            Entity,
+            &TriggerVolumeMarker,
+            &CollidingEntities,
+            Option<&TriggerOccupants>,
+        )>();
+        query
+            .iter(world)
+            .map(|(entity, marker, colliding, occupants)| {
+                (
+                    entity,
+                    marker.filter.clone(),
+                    colliding.iter().copied().collect(),
+                    occupants.map(|o| o.0.clone()).unwrap_or_default(),
+                )
+            })
+            .collect()
+    };
+
+    for (volume, filter, colliding, previous) in volumes {
+        let component_id = match &filter {
+            TriggerFilter::Component(type_name) => {
+                let Some(id) = resolve_component_id(world, type_name) else {
+                    continue;
+                };
+                Some(id)
+            }
+            _ => None,
+        };
+
+        let current: HashSet<Entity> = colliding
+            .into_iter()
+            .filter(|&entity| {
+                let Ok(entity_ref) = world.get_entity(entity) else {
+                    return false;
+                };
+                match &filter {
+                    TriggerFilter::Any => true,
+                    TriggerFilter::Tag(tag) => entity_ref
+                        .get::<Name>()
+                        .is_some_and(|name| name.as_str() == tag),
+                    TriggerFilter::Component(_) => {
+                        component_id.is_some_and(|id| entity_ref.contains_id(id))
+                    }
+                }
+            })
+            .collect();
+
+        if current == previous {
+            continue;
+        }
+        for &entity in current.difference(&previous) {
+            world.write_message(TriggerEnter { volume, entity });
+        }
+        for &entity in previous.difference(&current) {
+            world.write_message(TriggerExit { volume, entity });
+        }
+        if let Ok(mut entity_mut) = world.get_entity_mut(volume) {
+            entity_mut.insert(TriggerOccupants(current));
+        }
+    }
+}
+
+/// Look up a registered component type by short (`Player`) or full path
+fn resolve_component_id(world: &World, type_name: &str) -> Option<bevy::ecs::component::ComponentId> {
+    let type_id = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        registry
+            .get_with_short_type_path(type_name)
+            .or_else(|| registry.get_with_type_path(type_name))?
+            .type_id()
+    };
+    world.components().get_id(type_id)
+}
+
+fn clear_trigger_occupants(mut commands: Commands, volumes: Query<Entity, With<TriggerOccupants>>) {
+    for entity in &volumes {
+        commands.entity(entity).remove::<TriggerOccupants>();
+    }
+}
@@ -0,0 +1,75 @@
+//! Built-in validation rules for scene content.
+//!
+//! Registered by `HeadlessEditorPlugin`, so they run both in the editor's
+//! validation panel and in the headless `validate` command.
+
+use bevy::prelude::*;
+use bevy_editor_game::{
+    MaterialLibrary, MaterialRef, MeshLibrary, MeshRef, RegisterValidationExt, ValidationMessage,
+    ValidationRule, ValidationSeverity,
+};
+
+use super::SceneEntity;
+
+/// Register the editor's built-in validation rules on `app`
+pub(crate) fn register_builtin_rules(app: &mut App) {
+    app.register_validation(ValidationRule {
+        name: "Library Materials",
+        validate: validate_library_materials,
+    })
+    .register_validation(ValidationRule {
+        name: "Library Meshes",
+        validate: validate_library_meshes,
+    });
+}
+
+/// Flag `MaterialRef::Library` names that aren't in the `MaterialLibrary`
+pub fn validate_library_materials(world: &mut World) -> Vec<ValidationMessage> {
+    let known: Vec<String> = world
+        .get_resource::<MaterialLibrary>()
+        .map(|library| library.materials.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let mut query = world.query_filtered::<(Entity, &MaterialRef), With<SceneEntity>>();
+    query
+        .iter(world)
+        .filter_map(|(entity, material)| match material {
+            MaterialRef::Library(name) if !known.contains(name) => Some(ValidationMessage {
+                severity: ValidationSeverity::Error,
+                message: format!("Material '{}' is missing from the material library", name),
+                entity: Some(entity),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Flag `MeshRef`s without a name, and names missing from the `MeshLibrary`
+/// once it has loaded. Headless runs have no mesh library, so only empty
+/// names are caught there.
+pub fn validate_library_meshes(world: &mut World) -> Vec<ValidationMessage> {
+    let known: Option<Vec<String>> = world
+        .get_resource::<MeshLibrary>()
+        .filter(|library| !library.meshes.is_empty())
+        .map(|library| library.meshes.keys().cloned().collect());
+
+    let mut query = world.query_filtered::<(Entity, &MeshRef), With<SceneEntity>>();
+    query
+        .iter(world)
+        .filter_map(|(entity, mesh_ref)| {
+            let MeshRef::Library(name) = mesh_ref;
+            let message = if name.is_empty() {
+                "Library mesh reference has no name".to_string()
+            } else if known.as_ref().is_some_and(|known| !known.contains(name)) {
+                format!("Mesh '{}' is missing from the mesh library", name)
+            } else {
+                return None;
+            };
+            Some(ValidationMessage {
+                severity: ValidationSeverity::Error,
+                message,
+                entity: Some(entity),
+            })
+        })
+        .collect()
+}
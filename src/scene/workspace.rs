@@ -0,0 +1,515 @@
+use bevy::prelude::*;
+use bevy_editor_game::{GameState, MaterialLibrary};
+use bevy_egui::EguiContexts;
+use std::path::Path;
+
+use super::{
+    build_editor_scene, despawn_scene_entities, restore_scene_from_data, LoadSceneEvent,
+    SceneEntity, SceneFile,
+};
+use crate::commands::SnapshotHistory;
+use crate::editor::{CameraMarks, EditorCamera, EditorMode, EditorState, FlyCamera};
+use crate::prefabs::PrefabEditingContext;
+use crate::selection::Selected;
+use crate::utils::should_process_input;
+
+/// Event to open a scene in a new tab (`None` opens an empty untitled scene)
+#[derive(Message)]
+pub struct OpenSceneTabEvent {
+    pub path: Option<String>,
+}
+
+/// Event to make another tab the active scene
+#[derive(Message)]
+pub struct SwitchSceneTabEvent {
+    pub index: usize,
+}
+
+/// Event to close a tab. Modified tabs ask for confirmation first.
+#[derive(Message)]
+pub struct CloseSceneTabEvent {
+    pub index: usize,
+}
+
+/// Resource for the "unsaved changes" confirmation dialog when closing a tab
+#[derive(Resource, Default)]
+pub struct CloseTabConfirmDialog {
+    pub open: bool,
+    pub index: usize,
+}
+
+/// Editor state of a tab that is not currently loaded into the world
+struct StashedScene {
+    /// Serialized RON of the tab's scene entities
+    snapshot: String,
+    /// Selected entities, as IDs stored in the snapshot
+    selection: Vec<Entity>,
+    camera_marks: CameraMarks,
+    history: SnapshotHistory,
+    material_library: MaterialLibrary,
+    camera: Option<(Transform, FlyCamera)>,
+}
+
+/// A scene open in the workspace
+pub struct SceneTab {
+    /// Path to the scene file (None if untitled)
+    pub path: Option<String>,
+    /// Whether the scene has unsaved modifications
+    pub modified: bool,
+    /// Present while the tab is inactive
+    stash: Option<StashedScene>,
+}
+
+impl SceneTab {
+    fn untitled() -> Self {
+        Self {
+            path: None,
+            modified: false,
+            stash: None,
+        }
+    }
+
+    /// Get the display name for the tab
+    pub fn display_name(&self) -> &str {
+        self.path
+            .as_ref()
+            .and_then(|p| Path::new(p).file_name())
+            .and_then(|n| n.to_str())
+            .unwrap_or("Untitled")
+    }
+}
+
+/// Resource tracking all open scenes. The active tab lives in the world and mirrors
+/// `SceneFile`; the others are stashed along with their selection, marks and history.
+#[derive(Resource)]
+pub struct SceneWorkspace {
+    tabs: Vec<SceneTab>,
+    active: usize,
+}
+
+impl Default for SceneWorkspace {
+    fn default() -> Self {
+        Self {
+            tabs: vec![SceneTab::untitled()],
+            active: 0,
+        }
+    }
+}
+
+impl SceneWorkspace {
+    pub fn tabs(&self) -> &[SceneTab] {
+        &self.tabs
+    }
+
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Find the tab that has `path` open
+    pub fn find(&self, path: &str) -> Option<usize> {
+        self.tabs
+            .iter()
+            .position(|tab| tab.path.as_deref() == Some(path))
+    }
+
+    /// Index of the next (or previous) tab, wrapping around
+    pub fn neighbour(&self, forward: bool) -> usize {
+        let count = self.tabs.len();
+        if forward {
+            (self.active + 1) % count
+        } else {
+            (self.active + count - 1) % count
+        }
+    }
+
+    /// Forget unsaved changes in an inactive tab (the active tab's flag lives in `SceneFile`)
+    pub fn discard_changes(&mut self, index: usize) {
+        if let Some(tab) = self.tabs.get_mut(index) {
+            tab.modified = false;
+        }
+    }
+}
+
+pub struct WorkspacePlugin;
+
+impl Plugin for WorkspacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SceneWorkspace>()
+            .init_resource::<CloseTabConfirmDialog>()
+            .add_message::<OpenSceneTabEvent>()
+            .add_message::<SwitchSceneTabEvent>()
+            .add_message::<CloseSceneTabEvent>()
+            .add_systems(
+                Update,
+                (
+                    sync_active_tab,
+                    handle_scene_tab_input,
+                    handle_open_scene_tab,
+                    handle_switch_scene_tab,
+                    handle_close_scene_tab,
+                ),
+            );
+    }
+}
+
+/// Mirror the active scene's file path and modified flag into its tab
+fn sync_active_tab(scene_file: Res<SceneFile>, mut workspace: ResMut<SceneWorkspace>) {
+    if !scene_file.is_changed() {
+        return;
+    }
+
+    // Bypass change detection: a changed workspace means the world was swapped
+    let workspace = workspace.bypass_change_detection();
+    let active = workspace.active;
+    let tab = &mut workspace.tabs[active];
+    tab.path = scene_file.path.clone();
+    tab.modified = scene_file.modified;
+}
+
+/// Handle Ctrl+Tab / Ctrl+Shift+Tab to cycle through open scenes
+fn handle_scene_tab_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    editor_state: Res<EditorState>,
+    editor_mode: Res<State<EditorMode>>,
+    workspace: Res<SceneWorkspace>,
+    mut switch_events: MessageWriter<SwitchSceneTabEvent>,
+    mut contexts: EguiContexts,
+) {
+    if !should_process_input(&editor_state, &mut contexts) {
+        return;
+    }
+
+    // Other modes use Tab for their own cycling
+    if *editor_mode.get() != EditorMode::View && *editor_mode.get() != EditorMode::Edit {
+        return;
+    }
+
+    let ctrl = keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
+    if !ctrl || !keyboard.just_pressed(KeyCode::Tab) || workspace.tabs.len() < 2 {
+        return;
+    }
+
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+    switch_events.write(SwitchSceneTabEvent {
+        index: workspace.neighbour(!shift),
+    });
+}
+
+/// Tabs can only be changed while editing, and not while a prefab is open
+fn can_change_tabs(
+    game_state: &State<GameState>,
+    prefab_context: &Option<Res<PrefabEditingContext>>,
+) -> bool {
+    if *game_state.get() != GameState::Editing {
+        warn!("Stop the simulation before changing scene tabs");
+        return false;
+    }
+    if prefab_context.is_some() {
+        warn!("Close the open prefab before changing scene tabs");
+        return false;
+    }
+    true
+}
+
+fn handle_open_scene_tab(
+    mut events: MessageReader<OpenSceneTabEvent>,
+    mut commands: Commands,
+    game_state: Res<State<GameState>>,
+    prefab_context: Option<Res<PrefabEditingContext>>,
+) {
+    for event in events.read() {
+        if !can_change_tabs(&game_state, &prefab_context) {
+            continue;
+        }
+        commands.queue(OpenSceneTabCommand {
+            path: event.path.clone(),
+        });
+    }
+}
+
+fn handle_switch_scene_tab(
+    mut events: MessageReader<SwitchSceneTabEvent>,
+    mut commands: Commands,
+    game_state: Res<State<GameState>>,
+    prefab_context: Option<Res<PrefabEditingContext>>,
+) {
+    for event in events.read() {
+        if !can_change_tabs(&game_state, &prefab_context) {
+            continue;
+        }
+        commands.queue(SwitchSceneTabCommand { index: event.index });
+    }
+}
+
+fn handle_close_scene_tab(
+    mut events: MessageReader<CloseSceneTabEvent>,
+    mut commands: Commands,
+    workspace: Res<SceneWorkspace>,
+    scene_file: Res<SceneFile>,
+    game_state: Res<State<GameState>>,
+    prefab_context: Option<Res<PrefabEditingContext>>,
+    mut confirm_dialog: ResMut<CloseTabConfirmDialog>,
+) {
+    for event in events.read() {
+        if !can_change_tabs(&game_state, &prefab_context) {
+            continue;
+        }
+        let Some(tab) = workspace.tabs.get(event.index) else {
+            continue;
+        };
+
+        let modified = if event.index == workspace.active {
+            scene_file.modified
+        } else {
+            tab.modified
+        };
+
+        // If the scene has unsaved changes, show confirmation dialog
+        if modified {
+            confirm_dialog.open = true;
+            confirm_dialog.index = event.index;
+            continue;
+        }
+
+        commands.queue(CloseSceneTabCommand { index: event.index });
+    }
+}
+
+/// Capture the live scene and its editor state so it can be swapped out
+fn stash_active_scene(world: &mut World) -> Option<StashedScene> {
+    let scene_entity_ids: Vec<Entity> = {
+        let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
+        query.iter(world).collect()
+    };
+
+    let scene = build_editor_scene(world, scene_entity_ids.into_iter());
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    let snapshot = match scene.serialize(&type_registry) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to stash scene tab: {:?}", e);
+            return None;
+        }
+    };
+
+    drop(type_registry);
+
+    let selection: Vec<Entity> = {
+        let mut query = world.query_filtered::<Entity, (With<Selected>, With<SceneEntity>)>();
+        query.iter(world).collect()
+    };
+
+    let camera = {
+        let mut query = world.query_filtered::<(&Transform, &FlyCamera), With<EditorCamera>>();
+        query
+            .iter(world)
+            .next()
+            .map(|(transform, fly_cam)| (*transform, fly_cam.clone()))
+    };
+
+    Some(StashedScene {
+        snapshot,
+        selection,
+        camera_marks: world.resource::<CameraMarks>().clone(),
+        history: std::mem::take(&mut *world.resource_mut::<SnapshotHistory>()),
+        material_library: world
+            .get_resource::<MaterialLibrary>()
+            .cloned()
+            .unwrap_or_default(),
+        camera,
+    })
+}
+
+/// Clear selection before despawning to avoid stale outline commands
+fn clear_selection(world: &mut World) {
+    let selected_entities: Vec<Entity> = {
+        let mut query = world.query_filtered::<Entity, With<Selected>>();
+        query.iter(world).collect()
+    };
+    for entity in selected_entities {
+        if let Ok(mut e) = world.get_entity_mut(entity) {
+            e.remove::<Selected>();
+        }
+    }
+}
+
+/// Replace the live scene with a stashed tab
+fn restore_stashed_scene(
+    world: &mut World,
+    stash: StashedScene,
+    path: Option<String>,
+    modified: bool,
+) {
+    clear_selection(world);
+
+    let entity_map = restore_scene_from_data(world, &stash.snapshot);
+
+    let mut history = stash.history;
+    history.remap_entities(&entity_map);
+    world.insert_resource(history);
+
+    for entity in stash.selection {
+        if let Some(&new_entity) = entity_map.get(&entity) {
+            if let Ok(mut e) = world.get_entity_mut(new_entity) {
+                e.insert(Selected);
+            }
+        }
+    }
+
+    world.insert_resource(stash.camera_marks);
+    if let Some(mut library) = world.get_resource_mut::<MaterialLibrary>() {
+        *library = stash.material_library;
+    }
+
+    if let Some((stashed_transform, stashed_fly_cam)) = stash.camera {
+        let mut query =
+            world.query_filtered::<(&mut Transform, &mut FlyCamera), With<EditorCamera>>();
+        if let Some((mut transform, mut fly_cam)) = query.iter_mut(world).next() {
+            *transform = stashed_transform;
+            *fly_cam = stashed_fly_cam;
+        }
+    }
+
+    if let Some(mut scene_file) = world.get_resource_mut::<SceneFile>() {
+        scene_file.path = path;
+        scene_file.modified = modified;
+    }
+}
+
+/// Command to stash the active tab and open a scene in a new one
+struct OpenSceneTabCommand {
+    path: Option<String>,
+}
+
+impl Command for OpenSceneTabCommand {
+    fn apply(self, world: &mut World) {
+        // Already open: just switch to it
+        if let Some(path) = &self.path {
+            if let Some(index) = world.resource::<SceneWorkspace>().find(path) {
+                SwitchSceneTabCommand { index }.apply(world);
+                return;
+            }
+        }
+
+        let Some(stash) = stash_active_scene(world) else {
+            return;
+        };
+
+        {
+            let mut workspace = world.resource_mut::<SceneWorkspace>();
+            let active = workspace.active;
+            workspace.tabs[active].stash = Some(stash);
+            workspace.tabs.push(SceneTab::untitled());
+            workspace.active = workspace.tabs.len() - 1;
+        }
+
+        // Start the new tab from an empty scene
+        clear_selection(world);
+        let entities_to_despawn: Vec<Entity> = {
+            let mut query = world.query_filtered::<Entity, With<SceneEntity>>();
+            query.iter(world).collect()
+        };
+        despawn_scene_entities(world, &entities_to_despawn);
+
+        world.insert_resource(SnapshotHistory::default());
+        world.insert_resource(CameraMarks::default());
+        if let Some(mut scene_file) = world.get_resource_mut::<SceneFile>() {
+            scene_file.path = None;
+            scene_file.clear_modified();
+        }
+
+        // Load the scene via the existing load system
+        if let Some(path) = self.path {
+            info!("Opening scene tab: {}", path);
+            world.write_message(LoadSceneEvent { path });
+        } else {
+            info!("Opened new scene tab");
+        }
+    }
+}
+
+/// Command to swap the active tab out of the world and another one in
+struct SwitchSceneTabCommand {
+    index: usize,
+}
+
+impl Command for SwitchSceneTabCommand {
+    fn apply(self, world: &mut World) {
+        {
+            let workspace = world.resource::<SceneWorkspace>();
+            if self.index == workspace.active || self.index >= workspace.tabs.len() {
+                return;
+            }
+        }
+
+        let Some(stash) = stash_active_scene(world) else {
+            return;
+        };
+
+        let (target, path, modified) = {
+            let mut workspace = world.resource_mut::<SceneWorkspace>();
+            let active = workspace.active;
+            workspace.tabs[active].stash = Some(stash);
+            workspace.active = self.index;
+            let tab = &mut workspace.tabs[self.index];
+            (tab.stash.take(), tab.path.clone(), tab.modified)
+        };
+
+        if let Some(target) = target {
+            restore_stashed_scene(world, target, path, modified);
+        }
+
+        info!("Switched to scene tab {}", self.index);
+    }
+}
+
+/// Command to close a tab, discarding its scene
+pub struct CloseSceneTabCommand {
+    pub index: usize,
+}
+
+impl Command for CloseSceneTabCommand {
+    fn apply(self, world: &mut World) {
+        let (tab_count, active) = {
+            let workspace = world.resource::<SceneWorkspace>();
+            (workspace.tabs.len(), workspace.active)
+        };
+        if self.index >= tab_count {
+            return;
+        }
+
+        if tab_count == 1 {
+            // Closing the last tab leaves an empty untitled scene
+            OpenSceneTabCommand { path: None }.apply(world);
+            let mut workspace = world.resource_mut::<SceneWorkspace>();
+            workspace.tabs.remove(0);
+            workspace.active = 0;
+            return;
+        }
+
+        if self.index == active {
+            // Bring in the neighbouring tab, dropping the live scene
+            let next = if self.index > 0 { self.index - 1 } else { 1 };
+            let (target, path, modified) = {
+                let mut workspace = world.resource_mut::<SceneWorkspace>();
+                let tab = &mut workspace.tabs[next];
+                (tab.stash.take(), tab.path.clone(), tab.modified)
+            };
+            if let Some(target) = target {
+                restore_stashed_scene(world, target, path, modified);
+            }
+            world.resource_mut::<SceneWorkspace>().active = next;
+        }
+
+        let mut workspace = world.resource_mut::<SceneWorkspace>();
+        let tab = workspace.tabs.remove(self.index);
+        if self.index < workspace.active {
+            workspace.active -= 1;
+        }
+
+        info!("Closed scene tab: {}", tab.display_name());
+    }
+}
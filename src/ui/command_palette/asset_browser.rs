@@ -7,7 +7,7 @@ use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts, EguiTextureHandle};
 
 use crate::editor::{EditorMode, InsertObjectType, InsertState, StartInsertEvent};
-use crate::scene::{LoadSceneEvent, SaveSceneEvent, SpawnSplatEvent};
+use crate::scene::{LoadSceneEvent, OpenSceneTabEvent, SaveSceneEvent, SpawnSplatEvent};
 use crate::ui::fuzzy_palette::{
     draw_fuzzy_palette, fuzzy_filter, PaletteConfig, PaletteItem, PaletteResult, PaletteState,
 };
@@ -61,6 +61,7 @@ pub struct GltfPickData {
 #[derive(Clone)]
 pub(crate) enum BrowseOperation {
     LoadScene,
+    OpenSceneTab,
     SaveScene,
     InsertGltf,
     InsertScene,
@@ -178,6 +179,7 @@ pub(super) fn draw_asset_browser(
     contexts: &mut EguiContexts,
     state: &mut ResMut<CommandPaletteState>,
     load_events: &mut MessageWriter<LoadSceneEvent>,
+    open_tab_events: &mut MessageWriter<OpenSceneTabEvent>,
     save_events: &mut MessageWriter<SaveSceneEvent>,
     insert_events: &mut MessageWriter<StartInsertEvent>,
     insert_state: &mut ResMut<InsertState>,
@@ -207,6 +209,13 @@ pub(super) fn draw_asset_browser(
             "Type to search scenes...",
             "load",
         ),
+        BrowseOperation::OpenSceneTab => (
+            "OPEN SCENE IN TAB",
+            colors::ACCENT_BLUE,
+            "Open a scene file in a new tab",
+            "Type to search scenes...",
+            "open",
+        ),
         BrowseOperation::SaveScene => (
             "SAVE SCENE",
             colors::ACCENT_GREEN,
@@ -390,6 +399,12 @@ pub(super) fn draw_asset_browser(
                     let full_path = format!("assets/{}", relative_path);
                     load_events.write(LoadSceneEvent { path: full_path });
                 }
+                BrowseOperation::OpenSceneTab => {
+                    let full_path = format!("assets/{}", relative_path);
+                    open_tab_events.write(OpenSceneTabEvent {
+                        path: Some(full_path),
+                    });
+                }
                 BrowseOperation::SaveScene => {
                     let path = if is_save_as {
                         let name = if query.is_empty() {
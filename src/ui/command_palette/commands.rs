@@ -9,6 +9,7 @@ use bevy_spline_3d::prelude::SplineType;
 
 use bevy_editor_game::{CustomEntityRegistry, MeshLibrary, PauseEvent, PlayEvent, ResetEvent};
 
+use crate::audio::ToggleSoundPreviewEvent;
 use crate::commands::{RedoEvent, UndoEvent};
 use crate::editor::{
     CameraMarks, CycleShadingModeEvent, EditorState, JumpToLastPositionEvent,
@@ -16,13 +17,14 @@ use crate::editor::{
     TogglePhysicsDebugEvent, TogglePhysicsEvent, ViewportShadingMode,
 };
 use crate::scene::{
-    PrimitiveShape, SceneFile, SpawnDemoSceneEvent, SpawnEntityEvent, SpawnEntityKind,
+    CloseSceneTabEvent, OpenSceneTabEvent, PrimitiveShape, SceneFile, SceneWorkspace,
+    SpawnDemoSceneEvent, SpawnEntityEvent, SpawnEntityKind, SwitchSceneTabEvent,
     UnparentSelectedEvent,
     generators::{GenerateSceneEvent, SceneGenerator},
 };
 use crate::selection::Selected;
 use crate::ui::theme::{colors, window_frame};
-use crate::ui::SettingsWindowState;
+use crate::ui::{HistoryWindowState, SettingsWindowState};
 
 use super::{
     CommandPaletteState, CustomMarkDialogState, HelpWindowState, PaletteMode,
@@ -68,6 +70,8 @@ pub enum CommandAction {
     SpawnDemoScene,
     Undo,
     Redo,
+    /// Open the undo history window
+    ShowUndoHistory,
     AddComponent,
     /// Open file dialog to insert a GLTF/GLB model
     InsertGltf,
@@ -77,6 +81,8 @@ pub enum CommandAction {
     InsertSplat,
     /// Spawn a spline of the specified type
     SpawnSpline(SplineType),
+    /// Spawn a spline that bends a mesh along its length
+    SpawnDeformSpline,
     /// Spawn a volumetric fog volume
     SpawnFogVolume,
     /// Spawn parametric stairs
@@ -97,12 +103,20 @@ pub enum CommandAction {
     SpawnLibraryMesh(String),
     /// Spawn a clustered decal
     SpawnDecal,
+    /// Spawn a spatial sound emitter
+    SpawnSoundEmitter,
+    /// Toggle sound emitter playback while editing
+    ToggleSoundPreview,
+    /// Spawn a trigger volume
+    SpawnTriggerVolume,
     /// Spawn a custom entity type registered by the game
     SpawnCustomEntity(String),
     /// Spawn a prefab from assets/prefabs/
     SpawnPrefab(String),
     /// Create a prefab from selected entities
     CreatePrefab,
+    /// Create a variant prefab from the selected prefab instance
+    CreatePrefabVariant,
     /// Open a prefab for editing
     OpenPrefab(String),
     /// Start simulation (play or resume)
@@ -119,6 +133,14 @@ pub enum CommandAction {
     CycleShadingMode,
     /// Rename the current scene file
     RenameScene,
+    /// Open an empty scene in a new tab
+    NewSceneTab,
+    /// Open file dialog to load a scene into a new tab
+    OpenSceneTab,
+    /// Close the active scene tab
+    CloseSceneTab,
+    /// Switch to the next or previous scene tab
+    CycleSceneTab { forward: bool },
 }
 
 /// Resource containing all available commands
@@ -260,6 +282,13 @@ impl CommandRegistry {
             action: CommandAction::SpawnSpline(SplineType::BSpline),
             insertable: true,
         });
+        self.commands.push(Command {
+            name: "Add Mesh Deform Spline".to_string(),
+            keywords: vec!["deform".into(), "bend".into(), "pipe".into(), "fence".into(), "rail".into(), "cable".into()],
+            category: "Splines",
+            action: CommandAction::SpawnDeformSpline,
+            insertable: true,
+        });
 
         // Effects (insertable)
         self.commands.push(Command {
@@ -286,6 +315,31 @@ impl CommandRegistry {
             insertable: true,
         });
 
+        // Audio
+        self.commands.push(Command {
+            name: "Add Sound Emitter".to_string(),
+            keywords: vec!["audio".into(), "sound".into(), "ambience".into(), "speaker".into(), "music".into()],
+            category: "Effects",
+            action: CommandAction::SpawnSoundEmitter,
+            insertable: true,
+        });
+        self.commands.push(Command {
+            name: "Toggle Sound Preview".to_string(),
+            keywords: vec!["audio".into(), "sound".into(), "ambience".into(), "listen".into(), "mute".into()],
+            category: "View",
+            action: CommandAction::ToggleSoundPreview,
+            insertable: false,
+        });
+
+        // Trigger volumes (insertable)
+        self.commands.push(Command {
+            name: "Add Trigger Volume".to_string(),
+            keywords: vec!["trigger".into(), "sensor".into(), "volume".into(), "zone".into(), "area".into(), "enter".into(), "exit".into()],
+            category: "Game",
+            action: CommandAction::SpawnTriggerVolume,
+            insertable: true,
+        });
+
         // Scene operations
         self.commands.push(Command {
             name: "Save Scene".to_string(),
@@ -301,6 +355,41 @@ impl CommandRegistry {
             action: CommandAction::LoadScene,
             insertable: false,
         });
+        self.commands.push(Command {
+            name: "New Scene Tab".to_string(),
+            keywords: vec!["tab".into(), "new".into(), "workspace".into()],
+            category: "Scene",
+            action: CommandAction::NewSceneTab,
+            insertable: false,
+        });
+        self.commands.push(Command {
+            name: "Open Scene in New Tab".to_string(),
+            keywords: vec!["tab".into(), "open".into(), "load".into(), "file".into(), "workspace".into()],
+            category: "Scene",
+            action: CommandAction::OpenSceneTab,
+            insertable: false,
+        });
+        self.commands.push(Command {
+            name: "Close Scene Tab".to_string(),
+            keywords: vec!["tab".into(), "close".into(), "workspace".into()],
+            category: "Scene",
+            action: CommandAction::CloseSceneTab,
+            insertable: false,
+        });
+        self.commands.push(Command {
+            name: "Next Scene Tab".to_string(),
+            keywords: vec!["tab".into(), "switch".into(), "workspace".into()],
+            category: "Scene",
+            action: CommandAction::CycleSceneTab { forward: true },
+            insertable: false,
+        });
+        self.commands.push(Command {
+            name: "Previous Scene Tab".to_string(),
+            keywords: vec!["tab".into(), "switch".into(), "workspace".into()],
+            category: "Scene",
+            action: CommandAction::CycleSceneTab { forward: false },
+            insertable: false,
+        });
         self.commands.push(Command {
             name: "Rename Scene".to_string(),
             keywords: vec!["rename".into(), "file".into(), "name".into()],
@@ -347,6 +436,13 @@ impl CommandRegistry {
             action: CommandAction::CreatePrefab,
             insertable: false,
         });
+        self.commands.push(Command {
+            name: "Create Prefab Variant from Instance".to_string(),
+            keywords: vec!["prefab".into(), "variant".into(), "override".into(), "derive".into()],
+            category: "Prefabs",
+            action: CommandAction::CreatePrefabVariant,
+            insertable: false,
+        });
 
         // Camera marks
         self.commands.push(Command {
@@ -397,6 +493,13 @@ impl CommandRegistry {
             action: CommandAction::Redo,
             insertable: false,
         });
+        self.commands.push(Command {
+            name: "Undo History".to_string(),
+            keywords: vec!["history".into(), "undo".into(), "redo".into(), "jump".into(), "steps".into()],
+            category: "Edit",
+            action: CommandAction::ShowUndoHistory,
+            insertable: false,
+        });
         self.commands.push(Command {
             name: "Add Component".to_string(),
             keywords: vec!["component".into(), "attach".into(), "insert".into(), "reflection".into()],
@@ -613,10 +716,14 @@ pub(super) fn register_prefab_commands(
     prefab_registry: Res<crate::prefabs::PrefabRegistry>,
     mut registry: ResMut<CommandRegistry>,
 ) {
-    for (name, _entry) in &prefab_registry.entries {
+    for (name, entry) in &prefab_registry.entries {
         // Insertable: spawn prefab instance
+        let display = match &entry.base {
+            Some(base) => format!("Prefab: {} (variant of {})", name, base),
+            None => format!("Prefab: {}", name),
+        };
         registry.commands.push(Command {
-            name: format!("Prefab: {}", name),
+            name: display,
             keywords: vec!["prefab".into(), "instance".into(), "spawn".into()],
             category: "Prefabs",
             action: CommandAction::SpawnPrefab(name.clone()),
@@ -739,6 +846,7 @@ pub(super) struct CommandEvents<'w> {
     pub toggle_debug: MessageWriter<'w, TogglePhysicsDebugEvent>,
     pub toggle_physics: MessageWriter<'w, TogglePhysicsEvent>,
     pub toggle_grid: MessageWriter<'w, ToggleGridEvent>,
+    pub toggle_sound_preview: MessageWriter<'w, ToggleSoundPreviewEvent>,
     pub start_insert: MessageWriter<'w, StartInsertEvent>,
     pub spawn_demo: MessageWriter<'w, SpawnDemoSceneEvent>,
     pub undo: MessageWriter<'w, UndoEvent>,
@@ -750,6 +858,9 @@ pub(super) struct CommandEvents<'w> {
     pub set_shading: MessageWriter<'w, SetShadingModeEvent>,
     pub cycle_shading: MessageWriter<'w, CycleShadingModeEvent>,
     pub open_prefab: MessageWriter<'w, crate::prefabs::OpenPrefabEvent>,
+    pub open_scene_tab: MessageWriter<'w, OpenSceneTabEvent>,
+    pub switch_scene_tab: MessageWriter<'w, SwitchSceneTabEvent>,
+    pub close_scene_tab: MessageWriter<'w, CloseSceneTabEvent>,
 }
 
 /// System parameter grouping palette UI state resources
@@ -757,12 +868,14 @@ pub(super) struct CommandEvents<'w> {
 pub(super) struct PaletteState2<'w> {
     pub help_state: ResMut<'w, HelpWindowState>,
     pub settings_state: ResMut<'w, SettingsWindowState>,
+    pub history_state: ResMut<'w, HistoryWindowState>,
     pub custom_mark_state: ResMut<'w, CustomMarkDialogState>,
     pub rename_dialog: ResMut<'w, RenameSceneDialog>,
     pub create_prefab_dialog: ResMut<'w, super::CreatePrefabDialog>,
     pub component_editor_state: ResMut<'w, super::super::inspector::ComponentEditorState>,
     pub component_registry: ResMut<'w, super::components::ComponentRegistry>,
     pub removable_cache: Res<'w, super::RemovableComponentsCache>,
+    pub workspace: Res<'w, SceneWorkspace>,
 }
 
 /// Draw the command palette (Commands mode)
@@ -960,6 +1073,13 @@ fn execute_command(
                 rotation: Quat::IDENTITY,
             });
         }
+        CommandAction::SpawnDeformSpline => {
+            events.spawn_entity.write(SpawnEntityEvent {
+                kind: SpawnEntityKind::DeformSpline,
+                position: Vec3::ZERO,
+                rotation: Quat::IDENTITY,
+            });
+        }
         CommandAction::SpawnFogVolume => {
             events.spawn_entity.write(SpawnEntityEvent {
                 kind: SpawnEntityKind::FogVolume,
@@ -1030,6 +1150,23 @@ fn execute_command(
                 rotation: Quat::IDENTITY,
             });
         }
+        CommandAction::SpawnSoundEmitter => {
+            events.spawn_entity.write(SpawnEntityEvent {
+                kind: SpawnEntityKind::SoundEmitter,
+                position: Vec3::ZERO,
+                rotation: Quat::IDENTITY,
+            });
+        }
+        CommandAction::SpawnTriggerVolume => {
+            events.spawn_entity.write(SpawnEntityEvent {
+                kind: SpawnEntityKind::TriggerVolume,
+                position: Vec3::ZERO,
+                rotation: Quat::IDENTITY,
+            });
+        }
+        CommandAction::ToggleSoundPreview => {
+            events.toggle_sound_preview.write(ToggleSoundPreviewEvent);
+        }
         CommandAction::SpawnCustomEntity(ref type_name) => {
             let position = custom_registry
                 .entries
@@ -1103,6 +1240,9 @@ fn execute_command(
         CommandAction::Redo => {
             events.redo.write(RedoEvent);
         }
+        CommandAction::ShowUndoHistory => {
+            palette_state2.history_state.open = true;
+        }
         CommandAction::AddComponent => {
             // Get first selected entity and switch to AddComponent mode
             if let Some(entity) = selected.iter().next() {
@@ -1168,6 +1308,19 @@ fn execute_command(
                 palette_state2.create_prefab_dialog.just_opened = true;
                 palette_state2.create_prefab_dialog.name.clear();
                 palette_state2.create_prefab_dialog.entities = entities;
+                palette_state2.create_prefab_dialog.variant = false;
+            }
+        }
+        CommandAction::CreatePrefabVariant => {
+            let entities: Vec<Entity> = selected.iter().collect();
+            if entities.len() != 1 {
+                warn!("Select a single prefab instance to create a variant from");
+            } else {
+                palette_state2.create_prefab_dialog.open = true;
+                palette_state2.create_prefab_dialog.just_opened = true;
+                palette_state2.create_prefab_dialog.name.clear();
+                palette_state2.create_prefab_dialog.entities = entities;
+                palette_state2.create_prefab_dialog.variant = true;
             }
         }
         CommandAction::OpenPrefab(ref prefab_name) => {
@@ -1175,6 +1328,22 @@ fn execute_command(
                 prefab_name: prefab_name.clone(),
             });
         }
+        CommandAction::NewSceneTab => {
+            events.open_scene_tab.write(OpenSceneTabEvent { path: None });
+        }
+        CommandAction::OpenSceneTab => {
+            state.open_scene_tab();
+        }
+        CommandAction::CloseSceneTab => {
+            events.close_scene_tab.write(CloseSceneTabEvent {
+                index: palette_state2.workspace.active(),
+            });
+        }
+        CommandAction::CycleSceneTab { forward } => {
+            events.switch_scene_tab.write(SwitchSceneTabEvent {
+                index: palette_state2.workspace.neighbour(forward),
+            });
+        }
     }
 }
 
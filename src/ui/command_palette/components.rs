@@ -4,7 +4,7 @@ use bevy::prelude::*;
 use bevy_egui::egui;
 use std::any::TypeId;
 
-use crate::commands::TakeSnapshotCommand;
+use crate::commands::RecordEntitiesCommand;
 use crate::selection::Selected;
 use crate::ui::component_browser::add_component_by_type_id;
 use crate::ui::fuzzy_palette::{
@@ -462,8 +462,10 @@ pub(super) fn draw_remove_component_palette(
     match result {
         PaletteResult::Selected(index) => {
             if let Some(item) = items.get(index) {
-                // Queue snapshot and remove commands
-                commands.queue(TakeSnapshotCommand {
+                // Queue record and remove commands
+                commands.queue(RecordEntitiesCommand {
+                    entities: vec![target_entity],
+                    recursive: false,
                     description: format!("Remove {} component", item.short_name),
                 });
                 commands.queue(RemoveComponentCommand {
@@ -45,13 +45,17 @@ fn action_to_preview_kind(action: &CommandAction) -> Option<InsertPreviewKind> {
         CommandAction::SpawnPointLight => Some(InsertPreviewKind::PointLight),
         CommandAction::SpawnDirectionalLight => Some(InsertPreviewKind::DirectionalLight),
         CommandAction::SpawnGroup => Some(InsertPreviewKind::Group),
-        CommandAction::SpawnSpline(_) => Some(InsertPreviewKind::Spline),
+        CommandAction::SpawnSpline(_) | CommandAction::SpawnDeformSpline => {
+            Some(InsertPreviewKind::Spline)
+        }
         CommandAction::SpawnFogVolume => Some(InsertPreviewKind::FogVolume),
         CommandAction::SpawnStairs => Some(InsertPreviewKind::Stairs),
         CommandAction::SpawnRamp => Some(InsertPreviewKind::Ramp),
         CommandAction::SpawnArch => Some(InsertPreviewKind::Arch),
         CommandAction::SpawnLShape => Some(InsertPreviewKind::LShape),
         CommandAction::SpawnDecal => Some(InsertPreviewKind::Decal),
+        CommandAction::SpawnSoundEmitter => Some(InsertPreviewKind::SoundEmitter),
+        CommandAction::SpawnTriggerVolume => Some(InsertPreviewKind::TriggerVolume),
         CommandAction::SpawnLibraryMesh(_) => None, // No 3D preview for library meshes yet
         CommandAction::SpawnParticleEffect => None, // No 3D preview for particles
         CommandAction::SpawnParticlePreset(_) => None, // No 3D preview for particle presets
@@ -275,6 +279,11 @@ pub(super) fn draw_insert_palette(
                         object_type: InsertObjectType::Spline(*spline_type),
                     });
                 }
+                CommandAction::SpawnDeformSpline => {
+                    events.start_insert.write(StartInsertEvent {
+                        object_type: InsertObjectType::DeformSpline,
+                    });
+                }
                 CommandAction::SpawnFogVolume => {
                     events.start_insert.write(StartInsertEvent {
                         object_type: InsertObjectType::FogVolume,
@@ -310,6 +319,16 @@ pub(super) fn draw_insert_palette(
                         object_type: InsertObjectType::Decal,
                     });
                 }
+                CommandAction::SpawnSoundEmitter => {
+                    events.start_insert.write(StartInsertEvent {
+                        object_type: InsertObjectType::SoundEmitter,
+                    });
+                }
+                CommandAction::SpawnTriggerVolume => {
+                    events.start_insert.write(StartInsertEvent {
+                        object_type: InsertObjectType::TriggerVolume,
+                    });
+                }
                 CommandAction::SpawnLibraryMesh(mesh_name) => {
                     events.spawn_entity.write(crate::scene::SpawnEntityEvent {
                         kind: crate::scene::SpawnEntityKind::LibraryMesh(mesh_name.clone()),
@@ -31,7 +31,9 @@ use crate::editor::{
     CameraMarks, EditorCamera, EditorMode, EditorState, FlyCamera, InsertState,
     SetCameraMarkEvent,
 };
-use crate::scene::{LoadSceneEvent, SaveSceneEvent, SceneEntity, SceneFile, SpawnSplatEvent};
+use crate::scene::{
+    LoadSceneEvent, OpenSceneTabEvent, SaveSceneEvent, SceneEntity, SceneFile, SpawnSplatEvent,
+};
 use crate::selection::Selected;
 use crate::ui::gltf_preview::GltfPreviewState;
 use crate::ui::insert_preview::InsertPreviewState;
@@ -225,6 +227,10 @@ impl CommandPaletteState {
         self.open_asset_browser(asset_browser::BrowseOperation::LoadScene, &[".scn.ron"]);
     }
 
+    pub fn open_scene_tab(&mut self) {
+        self.open_asset_browser(asset_browser::BrowseOperation::OpenSceneTab, &[".scn.ron"]);
+    }
+
     pub fn open_save_scene(&mut self, current_path: Option<&str>) {
         self.open_asset_browser(asset_browser::BrowseOperation::SaveScene, &[".scn.ron"]);
 
@@ -319,6 +325,8 @@ pub struct CreatePrefabDialog {
     pub name: String,
     pub just_opened: bool,
     pub entities: Vec<Entity>,
+    /// Create a variant of the selected prefab instance instead
+    pub variant: bool,
 }
 
 /// Resource to track rename scene dialog state
@@ -460,6 +468,7 @@ fn handle_palette_toggle(
 #[derive(SystemParam)]
 struct AssetBrowserParams<'w> {
     load_events: MessageWriter<'w, LoadSceneEvent>,
+    open_tab_events: MessageWriter<'w, OpenSceneTabEvent>,
     save_events: MessageWriter<'w, SaveSceneEvent>,
     insert_state: ResMut<'w, InsertState>,
     next_mode: ResMut<'w, NextState<EditorMode>>,
@@ -653,6 +662,7 @@ fn draw_command_palette(
                 &mut contexts,
                 &mut state,
                 &mut ab.load_events,
+                &mut ab.open_tab_events,
                 &mut ab.save_events,
                 &mut events.start_insert,
                 &mut ab.insert_state,
@@ -740,6 +750,8 @@ fn draw_help_window(
                     shortcut_row(ui, "Arrows", "Nudge selected");
                     shortcut_row(ui, "G", "Group selected");
                     shortcut_row(ui, "Delete/X", "Delete selected");
+                    shortcut_row(ui, "Ctrl+X", "Cut selected");
+                    shortcut_row(ui, "Ctrl+Tab", "Next scene tab");
                 });
 
                 ui.add_space(16.0);
@@ -1083,19 +1095,23 @@ fn draw_create_prefab_dialog(
         should_close = true;
     }
 
-    egui::Window::new("Create Prefab")
+    let title = if state.variant {
+        "Create Prefab Variant"
+    } else {
+        "Create Prefab"
+    };
+    egui::Window::new(title)
         .collapsible(false)
         .resizable(false)
         .frame(window_frame(&ctx.style()))
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
-            ui.label(
-                egui::RichText::new(format!(
-                    "Creating prefab from {} entities",
-                    state.entities.len()
-                ))
-                .color(colors::TEXT_SECONDARY),
-            );
+            let description = if state.variant {
+                "Creating variant from the selected prefab instance".to_string()
+            } else {
+                format!("Creating prefab from {} entities", state.entities.len())
+            };
+            ui.label(egui::RichText::new(description).color(colors::TEXT_SECONDARY));
             ui.add_space(8.0);
 
             let response = ui.add(
@@ -1133,11 +1149,18 @@ fn draw_create_prefab_dialog(
     if should_create {
         let name = state.name.trim().to_string();
         let entities = std::mem::take(&mut state.entities);
+        let variant = state.variant;
         commands.queue(move |world: &mut World| {
-            world.write_message(crate::prefabs::CreatePrefabEvent {
-                name,
-                entities,
-            });
+            if variant {
+                if let Some(&instance) = entities.first() {
+                    world.write_message(crate::prefabs::CreatePrefabVariantEvent { name, instance });
+                }
+            } else {
+                world.write_message(crate::prefabs::CreatePrefabEvent {
+                    name,
+                    entities,
+                });
+            }
         });
     }
 
@@ -1145,6 +1168,7 @@ fn draw_create_prefab_dialog(
         state.open = false;
         state.name.clear();
         state.entities.clear();
+        state.variant = false;
     }
 
     Ok(())
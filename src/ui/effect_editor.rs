@@ -2,21 +2,26 @@
 //!
 //! Single right-side panel with:
 //! - Header: entity name, playback controls, preset buttons
-//! - Scrollable card list of rules (each rule = trigger + actions)
+//! - Effect variables with their initial and live values
+//! - Scrollable card list of rules (each rule = trigger + condition + actions)
 //! - Mini timeline strip at bottom for time-triggered rules
 
+use std::collections::HashMap;
+
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiPrimaryContextPass};
 
 use crate::editor::{EditorMode, EditorState, PanelSide, PinnedWindows};
 use crate::effects::data::*;
 use crate::effects::EffectLibrary;
+use bevy_editor_game::GameState;
 use bevy_vfx::VfxLibrary;
 use crate::scene::PrimitiveShape;
 use crate::selection::Selected;
 use crate::ui::command_palette::{
     CommandPaletteState, GltfPickResult, TexturePickResult, TextureSlot,
 };
+use crate::ui::vfx_editor::draw_param_value;
 use crate::ui::theme::{
     colors, draw_pin_button, grid_label, panel, panel_frame, section_header,
 };
@@ -55,7 +60,7 @@ const MINI_DOT_RADIUS: f32 = 4.0;
 // Trigger accent colors
 // ---------------------------------------------------------------------------
 
-fn trigger_accent(trigger: &EffectTrigger) -> egui::Color32 {
+pub(crate) fn trigger_accent(trigger: &EffectTrigger) -> egui::Color32 {
     match trigger {
         EffectTrigger::AtTime(_) => colors::ACCENT_GREEN,
         EffectTrigger::OnCollision { .. } => colors::ACCENT_ORANGE,
@@ -64,6 +69,12 @@ fn trigger_accent(trigger: &EffectTrigger) -> egui::Color32 {
         EffectTrigger::RepeatingInterval { .. } => colors::ACCENT_CYAN,
         EffectTrigger::OnSpawn => colors::ACCENT_PURPLE,
         EffectTrigger::AfterIdleTimeout { .. } => colors::ACCENT_CYAN,
+        EffectTrigger::OnEnterVolume { .. } | EffectTrigger::OnExitVolume { .. } => {
+            colors::ACCENT_ORANGE
+        }
+        EffectTrigger::OnProximity { .. } => colors::ACCENT_ORANGE,
+        EffectTrigger::OnGameEvent(_) => colors::ACCENT_BLUE,
+        EffectTrigger::OnGameStateChange(_) => colors::ACCENT_PURPLE,
     }
 }
 
@@ -100,9 +111,45 @@ fn trigger_summary(trigger: &EffectTrigger) -> String {
         }
         EffectTrigger::OnSpawn => "on spawn".into(),
         EffectTrigger::AfterIdleTimeout { timeout } => format!("idle {:.1}s", timeout),
+        EffectTrigger::OnEnterVolume { volume, tag } => volume_summary("enter", volume, tag),
+        EffectTrigger::OnExitVolume { volume, tag } => volume_summary("exit", volume, tag),
+        EffectTrigger::OnProximity { tag, radius } => {
+            format!("{} within {:.1}m", tag, radius)
+        }
+        EffectTrigger::OnGameEvent(name) => {
+            if name.is_empty() {
+                "on game event".into()
+            } else {
+                format!("on game \"{}\"", name)
+            }
+        }
+        EffectTrigger::OnGameStateChange(state) => format!("on {:?}", state),
     }
 }
 
+fn volume_summary(verb: &str, volume: &str, tag: &str) -> String {
+    let volume = if volume.is_empty() { "volume" } else { volume };
+    if tag.is_empty() {
+        format!("{} {}", verb, volume)
+    } else {
+        format!("{} {} ({})", verb, volume, tag)
+    }
+}
+
+/// Triggers driven by the scene or game code, which the editor can fire by hand.
+fn is_simulatable(trigger: &EffectTrigger) -> bool {
+    matches!(
+        trigger,
+        EffectTrigger::OnCollision { .. }
+            | EffectTrigger::OnEffectEvent(_)
+            | EffectTrigger::OnEnterVolume { .. }
+            | EffectTrigger::OnExitVolume { .. }
+            | EffectTrigger::OnProximity { .. }
+            | EffectTrigger::OnGameEvent(_)
+            | EffectTrigger::OnGameStateChange(_)
+    )
+}
+
 /// Color accent for an action by category.
 fn action_accent(action: &EffectAction) -> egui::Color32 {
     match action {
@@ -114,11 +161,18 @@ fn action_accent(action: &EffectAction) -> egui::Color32 {
         EffectAction::SetVelocity { .. }
         | EffectAction::ApplyImpulse { .. }
         | EffectAction::SetGravity { .. } => colors::ACCENT_ORANGE,
-        EffectAction::Despawn { .. } | EffectAction::EmitEvent(_) => colors::ACCENT_BLUE,
-        EffectAction::TweenValue { .. } => colors::ACCENT_PURPLE,
+        EffectAction::Despawn { .. }
+        | EffectAction::EmitEvent(_)
+        | EffectAction::PlaySound { .. } => colors::ACCENT_BLUE,
+        EffectAction::TweenValue { .. } | EffectAction::SetVfxParam { .. } => {
+            colors::ACCENT_PURPLE
+        }
         EffectAction::InsertComponent { .. } | EffectAction::RemoveComponent { .. } => {
             colors::ACCENT_CYAN
         }
+        EffectAction::SetVariable { .. } | EffectAction::IncrementVariable { .. } => {
+            colors::STATUS_WARNING
+        }
     }
 }
 
@@ -231,7 +285,8 @@ fn collect_defined_tags(marker: &EffectMarker) -> Vec<String> {
                 | EffectAction::SpawnParticle { tag, .. }
                 | EffectAction::SpawnGltf { tag, .. }
                 | EffectAction::SpawnDecal { tag, .. }
-                | EffectAction::SpawnEffect { tag, .. } => tag,
+                | EffectAction::SpawnEffect { tag, .. }
+                | EffectAction::PlaySound { tag, .. } => tag,
                 _ => continue,
             };
             if !tag.is_empty() && !tags.contains(tag) {
@@ -262,6 +317,15 @@ fn tag_combo(ui: &mut egui::Ui, id: &str, tag: &mut String, defined_tags: &[Stri
     }
 }
 
+fn collect_variable_names(marker: &EffectMarker) -> Vec<String> {
+    marker
+        .variables
+        .iter()
+        .filter(|v| !v.name.is_empty())
+        .map(|v| v.name.clone())
+        .collect()
+}
+
 fn collect_rule_names(marker: &EffectMarker) -> Vec<String> {
     marker
         .steps
@@ -319,6 +383,10 @@ fn draw_effect_panel(world: &mut World) {
         .get::<EffectPlayback>(entity)
         .map(|p| p.elapsed)
         .unwrap_or(0.0);
+    let live_variables = world
+        .get::<EffectPlayback>(entity)
+        .map(|p| p.variables.clone())
+        .unwrap_or_default();
 
     // Get egui context
     let ctx = {
@@ -361,6 +429,14 @@ fn draw_effect_panel(world: &mut World) {
         .unwrap_or_default();
     effect_presets.sort();
 
+    // Collect named sensors (trigger volumes) for volume triggers
+    let mut volume_names: Vec<String> = {
+        let mut q = world.query_filtered::<&Name, With<avian3d::prelude::Sensor>>();
+        q.iter(world).map(|n| n.as_str().to_string()).collect()
+    };
+    volume_names.sort();
+    volume_names.dedup();
+
     // Collect deferred actions
     let mut pin_toggled = false;
     let mut save_preset_clicked = false;
@@ -379,8 +455,10 @@ fn draw_effect_panel(world: &mut World) {
         &rule_names,
         &particle_presets,
         &effect_presets,
+        &volume_names,
         playback_state,
         playback_elapsed,
+        &live_variables,
         is_pinned,
         current_mode,
         &mut pin_toggled,
@@ -471,6 +549,24 @@ fn draw_effect_panel(world: &mut World) {
         }
     }
 
+    // Handle deferred trigger simulation from the trigger editor
+    let simulate_idx =
+        ctx.memory(|m| m.data.get_temp::<usize>(egui::Id::new("effect_simulate_trigger")));
+    if let Some(idx) = simulate_idx {
+        ctx.memory_mut(|m| {
+            m.data.remove::<usize>(egui::Id::new("effect_simulate_trigger"));
+        });
+        if let (Some(step), Some(mut pb)) = (
+            marker.steps.get(idx),
+            world.get_mut::<EffectPlayback>(entity),
+        ) {
+            if pb.simulate_trigger(&step.trigger) {
+                pb.seek_to = None;
+                pb.state = PlaybackState::Playing;
+            }
+        }
+    }
+
     // Write back marker if changed
     let changed = ron::to_string(&marker).ok() != ron::to_string(&original).ok();
     if changed {
@@ -515,6 +611,16 @@ fn draw_effect_panel(world: &mut World) {
             pb.last_fire_time = 0.0;
             pb.active_tweens.clear();
             pb.repeat_counts.clear();
+            pb.collision_tags.clear();
+            pb.touching_children.clear();
+            pb.volume_entered.clear();
+            pb.volume_exited.clear();
+            pb.volume_occupants.clear();
+            pb.nearest_tagged.clear();
+            pb.game_events.clear();
+            pb.game_states_entered.clear();
+            pb.variables.clear();
+            pb.seek_to = None;
             pb.state = PlaybackState::Stopped;
         }
     }
@@ -650,8 +756,10 @@ fn draw_rules_panel(
     rule_names: &[String],
     particle_presets: &[String],
     effect_presets: &[String],
+    volume_names: &[String],
     playback_state: PlaybackState,
     playback_elapsed: f32,
+    live_variables: &HashMap<String, EffectValue>,
     is_pinned: bool,
     current_mode: EditorMode,
     pin_toggled: &mut bool,
@@ -787,6 +895,9 @@ fn draw_rules_panel(
                 .show(ui, |ui| {
                     ui.set_min_width(230.0);
 
+                    draw_variables_section(ui, marker, live_variables);
+                    ui.add_space(4.0);
+
                     let mut remove_step = None;
 
                     for step_idx in 0..marker.steps.len() {
@@ -801,6 +912,7 @@ fn draw_rules_panel(
                             rule_names,
                             particle_presets,
                             effect_presets,
+                            volume_names,
                         ) {
                             remove_step = Some(step_idx);
                         }
@@ -834,6 +946,7 @@ fn draw_rules_panel(
                                             name: format!("rule_{}", new_idx + 1),
                                             trigger: EffectTrigger::from_variant_index(i),
                                             actions: Vec::new(),
+                                            condition: None,
                                         });
                                         state.expanded_rule = Some(new_idx);
                                         ui.close();
@@ -867,11 +980,17 @@ fn draw_rule_card(
     rule_names: &[String],
     particle_presets: &[String],
     effect_presets: &[String],
+    volume_names: &[String],
 ) -> bool {
     let mut removed = false;
     let step = &marker.steps[step_idx];
     let accent = trigger_accent(&step.trigger);
     let summary = trigger_summary(&step.trigger);
+    let condition_summary = step
+        .condition
+        .as_ref()
+        .map(|c| format!(" IF {}", c))
+        .unwrap_or_default();
     let action_count = step.actions.len();
     let step_name = step.name.clone();
 
@@ -896,7 +1015,10 @@ fn draw_rule_card(
             );
 
             ui.label(
-                egui::RichText::new(format!("WHEN {} THEN {} action(s)", summary, action_count))
+                egui::RichText::new(format!(
+                    "WHEN {}{} THEN {} action(s)",
+                    summary, condition_summary, action_count
+                ))
                     .small()
                     .color(colors::TEXT_SECONDARY),
             );
@@ -970,7 +1092,28 @@ fn draw_rule_card(
             // Trigger editor
             section_header(ui, "Trigger", true, |ui| {
                 let step = &mut marker.steps[step_idx];
-                draw_trigger_editor(ui, &mut step.trigger, step_idx, defined_tags, rule_names);
+                draw_trigger_editor(
+                    ui,
+                    &mut step.trigger,
+                    step_idx,
+                    defined_tags,
+                    rule_names,
+                    volume_names,
+                );
+            });
+
+            ui.add_space(4.0);
+
+            // Condition editor
+            let variable_names = collect_variable_names(marker);
+            let has_condition = marker.steps[step_idx].condition.is_some();
+            section_header(ui, "Condition", has_condition, |ui| {
+                draw_condition_editor(
+                    ui,
+                    &mut marker.steps[step_idx].condition,
+                    step_idx,
+                    &variable_names,
+                );
             });
 
             ui.add_space(4.0);
@@ -1127,6 +1270,7 @@ fn draw_trigger_editor(
     step_idx: usize,
     defined_tags: &[String],
     rule_names: &[String],
+    volume_names: &[String],
 ) {
     let mut current_variant = trigger.variant_index();
 
@@ -1295,6 +1439,98 @@ fn draw_trigger_editor(
                     ui.end_row();
                 });
         }
+        EffectTrigger::OnEnterVolume { volume, tag }
+        | EffectTrigger::OnExitVolume { volume, tag } => {
+            egui::Grid::new(format!("trigger_volume_{step_idx}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "Volume");
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(volume)
+                                .hint_text("sensor name")
+                                .desired_width(120.0),
+                        );
+                        if !volume_names.is_empty() {
+                            ui.menu_button("\u{25be}", |ui| {
+                                for name in volume_names {
+                                    if ui.button(name).clicked() {
+                                        *volume = name.clone();
+                                        ui.close();
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Pick a trigger volume in the scene");
+                        }
+                    });
+                    ui.end_row();
+
+                    grid_label(ui, "Tag");
+                    tag_combo(ui, &format!("trigger_tag_{step_idx}"), tag, defined_tags);
+                    ui.end_row();
+                });
+        }
+        EffectTrigger::OnProximity { tag, radius } => {
+            egui::Grid::new(format!("trigger_prox_{step_idx}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "Tag");
+                    tag_combo(ui, &format!("trigger_tag_{step_idx}"), tag, defined_tags);
+                    ui.end_row();
+
+                    grid_label(ui, "Radius");
+                    ui.add(
+                        egui::DragValue::new(radius)
+                            .speed(0.1)
+                            .range(0.1..=500.0)
+                            .max_decimals(2)
+                            .suffix(" m"),
+                    );
+                    ui.end_row();
+                });
+        }
+        EffectTrigger::OnGameEvent(name) => {
+            egui::Grid::new(format!("trigger_game_event_{step_idx}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "Event");
+                    ui.add(egui::TextEdit::singleline(name).desired_width(120.0));
+                    ui.end_row();
+                });
+        }
+        EffectTrigger::OnGameStateChange(state) => {
+            egui::Grid::new(format!("trigger_game_state_{step_idx}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "State");
+                    egui::ComboBox::from_id_salt(format!("trigger_state_{step_idx}"))
+                        .selected_text(format!("{:?}", state))
+                        .show_ui(ui, |ui| {
+                            for s in [GameState::Editing, GameState::Playing, GameState::Paused] {
+                                ui.selectable_value(state, s, format!("{:?}", s));
+                            }
+                        });
+                    ui.end_row();
+                });
+        }
+    }
+
+    if is_simulatable(trigger) {
+        if ui
+            .button("Simulate")
+            .on_hover_text("Fire this trigger on the selected effect")
+            .clicked()
+        {
+            ui.ctx().memory_mut(|m| {
+                m.data
+                    .insert_temp(egui::Id::new("effect_simulate_trigger"), step_idx);
+            });
+        }
     }
 }
 
@@ -1312,6 +1548,8 @@ fn draw_actions_list(
     particle_presets: &[String],
     effect_presets: &[String],
 ) {
+    let variable_names = collect_variable_names(marker);
+
     // Partition actions into categories
     let actions = &marker.steps[step_idx].actions;
     let mut spawn_indices = Vec::new();
@@ -1319,6 +1557,7 @@ fn draw_actions_list(
     let mut event_indices = Vec::new();
     let mut animation_indices = Vec::new();
     let mut ecs_indices = Vec::new();
+    let mut logic_indices = Vec::new();
 
     for (i, action) in actions.iter().enumerate() {
         match action {
@@ -1330,13 +1569,18 @@ fn draw_actions_list(
             EffectAction::SetVelocity { .. }
             | EffectAction::ApplyImpulse { .. }
             | EffectAction::SetGravity { .. } => physics_indices.push(i),
-            EffectAction::Despawn { .. } | EffectAction::EmitEvent(_) => {
-                event_indices.push(i)
+            EffectAction::Despawn { .. }
+            | EffectAction::EmitEvent(_)
+            | EffectAction::PlaySound { .. } => event_indices.push(i),
+            EffectAction::TweenValue { .. } | EffectAction::SetVfxParam { .. } => {
+                animation_indices.push(i)
             }
-            EffectAction::TweenValue { .. } => animation_indices.push(i),
             EffectAction::InsertComponent { .. } | EffectAction::RemoveComponent { .. } => {
                 ecs_indices.push(i)
             }
+            EffectAction::SetVariable { .. } | EffectAction::IncrementVariable { .. } => {
+                logic_indices.push(i)
+            }
         }
     }
 
@@ -1367,6 +1611,7 @@ fn draw_actions_list(
                 step_idx,
                 action_idx,
                 defined_tags,
+                &variable_names,
                 particle_presets,
                 effect_presets,
             );
@@ -1398,6 +1643,7 @@ fn draw_actions_list(
                 step_idx,
                 action_idx,
                 defined_tags,
+                &variable_names,
                 particle_presets,
                 effect_presets,
             );
@@ -1411,7 +1657,7 @@ fn draw_actions_list(
         ui,
         "EVENTS",
         colors::ACCENT_BLUE,
-        &[("Despawn", 4), ("Emit Event", 5)],
+        &[("Despawn", 4), ("Emit Event", 5), ("Play Sound", 16)],
         &mut marker.steps[step_idx].actions,
     );
     for &action_idx in &event_indices {
@@ -1425,6 +1671,7 @@ fn draw_actions_list(
                 step_idx,
                 action_idx,
                 defined_tags,
+                &variable_names,
                 particle_presets,
                 effect_presets,
             );
@@ -1438,7 +1685,7 @@ fn draw_actions_list(
         ui,
         "ANIMATION",
         colors::ACCENT_PURPLE,
-        &[("Tween Value", 12)],
+        &[("Tween Value", 12), ("Set VFX Param", 13)],
         &mut marker.steps[step_idx].actions,
     );
     for &action_idx in &animation_indices {
@@ -1452,6 +1699,7 @@ fn draw_actions_list(
                 step_idx,
                 action_idx,
                 defined_tags,
+                &variable_names,
                 particle_presets,
                 effect_presets,
             );
@@ -1479,6 +1727,35 @@ fn draw_actions_list(
                 step_idx,
                 action_idx,
                 defined_tags,
+                &variable_names,
+                particle_presets,
+                effect_presets,
+            );
+        }) {
+            remove_action = Some(action_idx);
+        }
+    }
+
+    // LOGIC category
+    action_category_header(
+        ui,
+        "LOGIC",
+        colors::STATUS_WARNING,
+        &[("Set Variable", 14), ("Increment Variable", 15)],
+        &mut marker.steps[step_idx].actions,
+    );
+    for &action_idx in &logic_indices {
+        let label = marker.steps[step_idx].actions[action_idx].label();
+        let accent = action_accent(&marker.steps[step_idx].actions[action_idx]);
+        let action = &mut marker.steps[step_idx].actions[action_idx];
+        if action_card(ui, label, accent, |ui| {
+            draw_action_editor(
+                ui,
+                action,
+                step_idx,
+                action_idx,
+                defined_tags,
+                &variable_names,
                 particle_presets,
                 effect_presets,
             );
@@ -1503,6 +1780,7 @@ fn draw_action_editor(
     step_idx: usize,
     action_idx: usize,
     defined_tags: &[String],
+    variable_names: &[String],
     particle_presets: &[String],
     effect_presets: &[String],
 ) {
@@ -2096,9 +2374,231 @@ fn draw_action_editor(
                     ui.end_row();
                 });
         }
+        EffectAction::SetVfxParam { tag, name, value } => {
+            egui::Grid::new(format!("vfx_param_{id_salt}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "Tag");
+                    tag_combo(ui, &format!("tag_vp_{id_salt}"), tag, defined_tags);
+                    ui.end_row();
+
+                    grid_label(ui, "Param");
+                    ui.text_edit_singleline(name);
+                    ui.end_row();
+                });
+            draw_param_value(ui, value, &format!("vp_value_{id_salt}"));
+        }
+        EffectAction::SetVariable { name, value } => {
+            egui::Grid::new(format!("set_var_{id_salt}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "Variable");
+                    tag_combo(ui, &format!("var_set_{id_salt}"), name, variable_names);
+                    ui.end_row();
+
+                    grid_label(ui, "Value");
+                    effect_value_editor(ui, value, &format!("set_var_{id_salt}"));
+                    ui.end_row();
+                });
+        }
+        EffectAction::IncrementVariable { name, amount } => {
+            egui::Grid::new(format!("inc_var_{id_salt}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "Variable");
+                    tag_combo(ui, &format!("var_inc_{id_salt}"), name, variable_names);
+                    ui.end_row();
+
+                    grid_label(ui, "Amount");
+                    ui.add(egui::DragValue::new(amount).speed(0.1).max_decimals(2));
+                    ui.end_row();
+                });
+        }
+        EffectAction::PlaySound {
+            tag,
+            path,
+            volume,
+            radius,
+            at,
+        } => {
+            egui::Grid::new(format!("play_sound_{id_salt}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "Tag");
+                    ui.add(egui::TextEdit::singleline(tag).desired_width(100.0));
+                    ui.end_row();
+
+                    grid_label(ui, "Sound");
+                    ui.add(
+                        egui::TextEdit::singleline(path)
+                            .hint_text("audio/hit.ogg")
+                            .desired_width(140.0),
+                    );
+                    ui.end_row();
+
+                    grid_label(ui, "Volume");
+                    ui.add(
+                        egui::DragValue::new(volume)
+                            .speed(0.01)
+                            .range(0.0..=2.0)
+                            .max_decimals(2),
+                    );
+                    ui.end_row();
+
+                    grid_label(ui, "Radius");
+                    ui.add(
+                        egui::DragValue::new(radius)
+                            .speed(0.1)
+                            .range(0.0..=100.0)
+                            .suffix(" m"),
+                    );
+                    ui.end_row();
+
+                    draw_spawn_location_editor(ui, at, &format!("sound_loc_{id_salt}"));
+                });
+        }
     }
 }
 
+// ---------------------------------------------------------------------------
+// Variables and conditions
+// ---------------------------------------------------------------------------
+
+/// Type selector plus value widget for an effect variable value.
+fn effect_value_editor(ui: &mut egui::Ui, value: &mut EffectValue, id_salt: &str) {
+    ui.horizontal(|ui| {
+        let mut variant = value.variant_index();
+        egui::ComboBox::from_id_salt(format!("value_type_{id_salt}"))
+            .selected_text(EffectValue::VARIANT_LABELS[variant])
+            .width(56.0)
+            .show_ui(ui, |ui| {
+                for (i, label) in EffectValue::VARIANT_LABELS.iter().enumerate() {
+                    ui.selectable_value(&mut variant, i, *label);
+                }
+            });
+        if variant != value.variant_index() {
+            *value = value.convert_to(variant);
+        }
+
+        match value {
+            EffectValue::Int(v) => {
+                ui.add(egui::DragValue::new(v).speed(0.1));
+            }
+            EffectValue::Float(v) => {
+                ui.add(egui::DragValue::new(v).speed(0.05).max_decimals(3));
+            }
+            EffectValue::Bool(v) => {
+                ui.checkbox(v, "");
+            }
+        }
+    });
+}
+
+/// Variable declarations, with live values shown while the effect runs.
+fn draw_variables_section(
+    ui: &mut egui::Ui,
+    marker: &mut EffectMarker,
+    live_variables: &HashMap<String, EffectValue>,
+) {
+    let default_open = !marker.variables.is_empty();
+    section_header(ui, "Variables", default_open, |ui| {
+        let mut remove_variable = None;
+
+        egui::Grid::new("effect_variables_grid")
+            .num_columns(4)
+            .spacing([6.0, 4.0])
+            .show(ui, |ui| {
+                for (idx, variable) in marker.variables.iter_mut().enumerate() {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut variable.name)
+                            .hint_text("name")
+                            .desired_width(80.0),
+                    );
+                    effect_value_editor(ui, &mut variable.initial, &format!("var_{idx}"));
+
+                    let live = live_variables
+                        .get(&variable.name)
+                        .map(|v| v.to_string())
+                        .unwrap_or_default();
+                    ui.label(egui::RichText::new(live).small().color(colors::TEXT_MUTED))
+                        .on_hover_text("Current value");
+
+                    if ui
+                        .add(
+                            egui::Button::new(
+                                egui::RichText::new("\u{00d7}").color(colors::STATUS_ERROR),
+                            )
+                            .frame(false),
+                        )
+                        .on_hover_text("Remove variable")
+                        .clicked()
+                    {
+                        remove_variable = Some(idx);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(idx) = remove_variable {
+            marker.variables.remove(idx);
+        }
+
+        if ui
+            .button(
+                egui::RichText::new("+ Variable")
+                    .small()
+                    .color(colors::ACCENT_GREEN),
+            )
+            .clicked()
+        {
+            marker.variables.push(EffectVariable {
+                name: format!("var_{}", marker.variables.len() + 1),
+                initial: EffectValue::Int(0),
+            });
+        }
+    });
+}
+
+fn draw_condition_editor(
+    ui: &mut egui::Ui,
+    condition: &mut Option<EffectCondition>,
+    step_idx: usize,
+    variable_names: &[String],
+) {
+    let mut enabled = condition.is_some();
+    if ui.checkbox(&mut enabled, "Only fire when").changed() {
+        *condition = enabled.then(|| EffectCondition {
+            variable: variable_names.first().cloned().unwrap_or_default(),
+            ..Default::default()
+        });
+    }
+
+    let Some(condition) = condition else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        tag_combo(
+            ui,
+            &format!("cond_var_{step_idx}"),
+            &mut condition.variable,
+            variable_names,
+        );
+        egui::ComboBox::from_id_salt(format!("cond_op_{step_idx}"))
+            .selected_text(condition.op.symbol())
+            .width(40.0)
+            .show_ui(ui, |ui| {
+                for op in CompareOp::ALL {
+                    ui.selectable_value(&mut condition.op, op, op.symbol());
+                }
+            });
+        effect_value_editor(ui, &mut condition.value, &format!("cond_{step_idx}"));
+    });
+}
+
 // ---------------------------------------------------------------------------
 // Shared spawn location editor
 // ---------------------------------------------------------------------------
@@ -0,0 +1,441 @@
+//! Effect timeline panel — timed steps and tweens laid out on tracks.
+//!
+//! Shown along the bottom in Effect mode for the selected effect:
+//! - Ruler: click or drag to scrub (the effect is replayed from the start),
+//!   shift-drag to set a loop region
+//! - One track per timed step, with repeats and `TweenValue` spans
+//! - Drag a step marker to retime it
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiPrimaryContextPass};
+
+use crate::editor::{EditorMode, EditorState};
+use crate::effects::data::*;
+use crate::selection::Selected;
+use crate::ui::effect_editor::trigger_accent;
+use crate::ui::theme::{colors, panel, window_frame};
+
+pub struct EffectTimelinePlugin;
+
+impl Plugin for EffectTimelinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(EguiPrimaryContextPass, draw_effect_timeline);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+const DEFAULT_WIDTH: f32 = 640.0;
+const LABEL_WIDTH: f32 = 110.0;
+const RULER_HEIGHT: f32 = 18.0;
+const TRACK_HEIGHT: f32 = 20.0;
+const MARKER_RADIUS: f32 = 5.0;
+const TWEEN_BAR_HEIGHT: f32 = 6.0;
+const PLAYHEAD_WIDTH: f32 = 2.0;
+/// Retimed steps snap to this many seconds.
+const RETIME_SNAP: f32 = 0.05;
+/// Repeats drawn per `RepeatingInterval` track at most.
+const MAX_REPEAT_MARKS: u32 = 200;
+
+/// Edits made in the timeline this frame, applied after drawing.
+#[derive(Default)]
+struct TimelineResult {
+    seek: Option<f32>,
+    /// `Some(None)` clears the loop region.
+    loop_region: Option<Option<(f32, f32)>>,
+    play_toggled: bool,
+    retimed: bool,
+}
+
+/// Timeline span for a marker: the latest step or tween end, plus headroom.
+fn timeline_duration(marker: &EffectMarker, times: &[Option<f32>]) -> f32 {
+    let content_end = marker
+        .steps
+        .iter()
+        .zip(times)
+        .filter_map(|(step, time)| {
+            let time = (*time)?;
+            let tween_end = step
+                .actions
+                .iter()
+                .filter_map(|action| match action {
+                    EffectAction::TweenValue { duration, .. } => Some(*duration),
+                    _ => None,
+                })
+                .fold(0.0f32, f32::max);
+            Some(time + tween_end)
+        })
+        .fold(0.0f32, f32::max);
+    (content_end + 1.0).ceil().max(5.0)
+}
+
+fn draw_effect_timeline(world: &mut World) {
+    if !world.resource::<EditorState>().ui_enabled {
+        return;
+    }
+    if *world.resource::<State<EditorMode>>().get() != EditorMode::Effect {
+        return;
+    }
+
+    let entity = {
+        let mut q = world.query_filtered::<Entity, (With<Selected>, With<EffectMarker>)>();
+        let Some(entity) = q.iter(world).next() else {
+            return;
+        };
+        entity
+    };
+
+    let mut marker = world.get::<EffectMarker>(entity).unwrap().clone();
+    let (playback_state, elapsed, loop_region) = world
+        .get::<EffectPlayback>(entity)
+        .map(|p| (p.state, p.elapsed, p.loop_region))
+        .unwrap_or((PlaybackState::Stopped, 0.0, None));
+
+    let ctx = {
+        let Some(mut egui_ctx) = world
+            .query::<&mut bevy_egui::EguiContext>()
+            .iter_mut(world)
+            .next()
+        else {
+            return;
+        };
+        egui_ctx.get_mut().clone()
+    };
+
+    let mut result = TimelineResult::default();
+    let status_bar_offset = panel::STATUS_BAR_HEIGHT + panel::WINDOW_PADDING;
+
+    egui::Window::new("Effect Timeline")
+        .default_width(DEFAULT_WIDTH)
+        .min_width(panel::MIN_WIDTH)
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -status_bar_offset])
+        .resizable([true, false])
+        .collapsible(true)
+        .title_bar(true)
+        .frame(window_frame(&ctx.style()))
+        .show(&ctx, |ui| {
+            draw_timeline(ui, &mut marker, playback_state, elapsed, loop_region, &mut result);
+        });
+
+    if result.retimed {
+        if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.insert(marker);
+        }
+        // Refresh a scrubbed frame so it reflects the new timing
+        if playback_state == PlaybackState::Paused && result.seek.is_none() {
+            result.seek = Some(elapsed);
+        }
+    }
+
+    let Some(mut playback) = world.get_mut::<EffectPlayback>(entity) else {
+        return;
+    };
+    if let Some(time) = result.seek {
+        playback.seek_to = Some(time);
+    }
+    if let Some(region) = result.loop_region {
+        playback.loop_region = region;
+    }
+    if result.play_toggled {
+        playback.state = match playback.state {
+            PlaybackState::Playing => PlaybackState::Paused,
+            PlaybackState::Paused | PlaybackState::Stopped => PlaybackState::Playing,
+        };
+    }
+}
+
+fn draw_timeline(
+    ui: &mut egui::Ui,
+    marker: &mut EffectMarker,
+    playback_state: PlaybackState,
+    elapsed: f32,
+    loop_region: Option<(f32, f32)>,
+    result: &mut TimelineResult,
+) {
+    let times = marker.scheduled_times();
+    let duration = timeline_duration(marker, &times);
+
+    // Transport row
+    ui.horizontal(|ui| {
+        let play_label = if playback_state == PlaybackState::Playing {
+            egui::RichText::new("\u{23f8}").color(colors::STATUS_WARNING)
+        } else {
+            egui::RichText::new("\u{25b6}").color(colors::STATUS_SUCCESS)
+        };
+        if ui.small_button(play_label).on_hover_text("Play or pause").clicked() {
+            result.play_toggled = true;
+        }
+        if ui.small_button("\u{23ee}").on_hover_text("Back to start").clicked() {
+            result.seek = Some(loop_region.map_or(0.0, |(start, _)| start));
+        }
+        ui.label(
+            egui::RichText::new(format!("{:.2}s / {:.0}s", elapsed, duration))
+                .small()
+                .color(colors::TEXT_SECONDARY),
+        );
+
+        ui.separator();
+        let mut looping = loop_region.is_some();
+        if ui
+            .checkbox(&mut looping, "Loop")
+            .on_hover_text("Shift-drag the ruler to pick the looped region")
+            .changed()
+        {
+            result.loop_region = Some(looping.then_some((0.0, duration)));
+        }
+        if let Some((start, end)) = loop_region {
+            ui.label(
+                egui::RichText::new(format!("{:.2}s \u{2013} {:.2}s", start, end))
+                    .small()
+                    .color(colors::TEXT_MUTED),
+            );
+        }
+
+        let untimed = times.iter().filter(|t| t.is_none()).count();
+        if untimed > 0 {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} event-driven rule(s) not shown", untimed))
+                        .small()
+                        .color(colors::TEXT_MUTED),
+                );
+            });
+        }
+    });
+
+    ui.add_space(2.0);
+
+    // Ruler + tracks share one horizontal mapping
+    let track_count = times.iter().filter(|t| t.is_some()).count();
+    let total_height = RULER_HEIGHT + TRACK_HEIGHT * track_count.max(1) as f32;
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), total_height),
+        egui::Sense::hover(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, colors::BG_DARKEST);
+
+    let lane_left = rect.left() + LABEL_WIDTH;
+    let lane_width = (rect.right() - lane_left - 6.0).max(1.0);
+    let time_to_x = |t: f32| lane_left + (t / duration).clamp(0.0, 1.0) * lane_width;
+    let x_to_time = |x: f32| ((x - lane_left) / lane_width).clamp(0.0, 1.0) * duration;
+
+    // Ruler ticks and labels
+    let ruler = egui::Rect::from_min_max(
+        egui::pos2(lane_left, rect.top()),
+        egui::pos2(rect.right(), rect.top() + RULER_HEIGHT),
+    );
+    let tick_interval = if duration > 20.0 {
+        5.0
+    } else if duration > 10.0 {
+        2.0
+    } else {
+        1.0
+    };
+    let grid_stroke = egui::Stroke::new(0.5, egui::Color32::from_white_alpha(20));
+    let mut t = 0.0;
+    while t <= duration + 0.001 {
+        let x = time_to_x(t);
+        painter.line_segment(
+            [egui::pos2(x, ruler.bottom() - 5.0), egui::pos2(x, ruler.bottom())],
+            egui::Stroke::new(1.0, colors::TEXT_MUTED),
+        );
+        painter.line_segment(
+            [egui::pos2(x, ruler.bottom()), egui::pos2(x, rect.bottom())],
+            grid_stroke,
+        );
+        painter.text(
+            egui::pos2(x + 2.0, ruler.top() + 1.0),
+            egui::Align2::LEFT_TOP,
+            format!("{:.0}s", t),
+            egui::FontId::proportional(9.0),
+            colors::TEXT_MUTED,
+        );
+        t += tick_interval;
+    }
+
+    // Ruler interaction: scrub, or shift-drag a loop region
+    let ruler_resp = ui.interact(
+        ruler,
+        egui::Id::new("effect_timeline_ruler"),
+        egui::Sense::click_and_drag(),
+    );
+    let shift = ui.input(|i| i.modifiers.shift);
+    if let Some(pos) = ruler_resp.interact_pointer_pos() {
+        let time = x_to_time(pos.x);
+        if shift {
+            let anchor_id = egui::Id::new("effect_timeline_loop_anchor");
+            if ruler_resp.drag_started() || ruler_resp.clicked() {
+                ui.ctx().data_mut(|d| d.insert_temp(anchor_id, time));
+            }
+            let anchor: f32 = ui.ctx().data(|d| d.get_temp(anchor_id).unwrap_or(time));
+            if (time - anchor).abs() > 0.01 {
+                result.loop_region = Some(Some((anchor.min(time), anchor.max(time))));
+            }
+        } else if ruler_resp.dragged() || ruler_resp.clicked() {
+            result.seek = Some(time);
+        }
+    }
+
+    // Loop region shading
+    if let Some((start, end)) = loop_region {
+        let region = egui::Rect::from_min_max(
+            egui::pos2(time_to_x(start), rect.top()),
+            egui::pos2(time_to_x(end), rect.bottom()),
+        );
+        painter.rect_filled(region, 0.0, colors::ACCENT_BLUE.gamma_multiply(0.12));
+        for x in [region.left(), region.right()] {
+            painter.line_segment(
+                [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+                egui::Stroke::new(1.0, colors::ACCENT_BLUE.gamma_multiply(0.6)),
+            );
+        }
+    }
+
+    // One track per timed step
+    let mut row = 0;
+    for step_idx in 0..marker.steps.len() {
+        let Some(time) = times[step_idx] else {
+            continue;
+        };
+        let step = &marker.steps[step_idx];
+        let top = ruler.bottom() + row as f32 * TRACK_HEIGHT;
+        let center_y = top + TRACK_HEIGHT * 0.5;
+        row += 1;
+
+        if row % 2 == 0 {
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(rect.left(), top),
+                    egui::pos2(rect.right(), top + TRACK_HEIGHT),
+                ),
+                0.0,
+                egui::Color32::from_white_alpha(6),
+            );
+        }
+
+        let name = if step.name.is_empty() {
+            format!("Rule #{}", step_idx + 1)
+        } else {
+            step.name.clone()
+        };
+        painter.text(
+            egui::pos2(rect.left() + 6.0, center_y),
+            egui::Align2::LEFT_CENTER,
+            name,
+            egui::FontId::proportional(11.0),
+            colors::TEXT_SECONDARY,
+        );
+
+        let accent = trigger_accent(&step.trigger);
+
+        // Tween spans start when the step fires
+        for action in &step.actions {
+            if let EffectAction::TweenValue {
+                property, duration, ..
+            } = action
+            {
+                let bar = egui::Rect::from_min_max(
+                    egui::pos2(time_to_x(time), center_y - TWEEN_BAR_HEIGHT * 0.5),
+                    egui::pos2(
+                        time_to_x(time + duration.max(0.0)),
+                        center_y + TWEEN_BAR_HEIGHT * 0.5,
+                    ),
+                );
+                painter.rect_filled(bar, 2.0, colors::ACCENT_PURPLE.gamma_multiply(0.6));
+                ui.interact(
+                    bar,
+                    egui::Id::new(("effect_timeline_tween", step_idx, property.label())),
+                    egui::Sense::hover(),
+                )
+                .on_hover_text(format!("Tween {} over {:.2}s", property.label(), duration));
+            }
+        }
+
+        // Later repeats of an interval trigger
+        if let EffectTrigger::RepeatingInterval {
+            interval,
+            max_count,
+        } = &step.trigger
+        {
+            if *interval > 0.0 {
+                let count = max_count.unwrap_or(MAX_REPEAT_MARKS).min(MAX_REPEAT_MARKS);
+                for n in 2..=count {
+                    let repeat_time = *interval * n as f32;
+                    if repeat_time > duration {
+                        break;
+                    }
+                    painter.circle_filled(
+                        egui::pos2(time_to_x(repeat_time), center_y),
+                        MARKER_RADIUS * 0.6,
+                        accent.gamma_multiply(0.5),
+                    );
+                }
+            }
+        }
+
+        // Step marker — dragging retimes the step
+        let marker_pos = egui::pos2(time_to_x(time), center_y);
+        let draggable = !matches!(step.trigger, EffectTrigger::OnSpawn);
+        let marker_resp = ui.interact(
+            egui::Rect::from_center_size(marker_pos, egui::Vec2::splat(MARKER_RADIUS * 3.0)),
+            egui::Id::new(("effect_timeline_step", step_idx)),
+            if draggable {
+                egui::Sense::drag()
+            } else {
+                egui::Sense::hover()
+            },
+        );
+        let stroke = if marker_resp.hovered() || marker_resp.dragged() {
+            egui::Stroke::new(1.5, colors::TEXT_PRIMARY)
+        } else {
+            egui::Stroke::NONE
+        };
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                marker_pos + egui::vec2(0.0, -MARKER_RADIUS),
+                marker_pos + egui::vec2(MARKER_RADIUS, 0.0),
+                marker_pos + egui::vec2(0.0, MARKER_RADIUS),
+                marker_pos + egui::vec2(-MARKER_RADIUS, 0.0),
+            ],
+            accent,
+            stroke,
+        ));
+
+        if draggable {
+            let pointer = marker_resp.interact_pointer_pos();
+            if let Some(pos) = pointer.filter(|_| marker_resp.dragged()) {
+                let new_time = (x_to_time(pos.x) / RETIME_SNAP).round() * RETIME_SNAP;
+                if (new_time - time).abs() > f32::EPSILON {
+                    result.retimed |= marker.retime_step(step_idx, new_time);
+                }
+            }
+            marker_resp
+                .on_hover_cursor(egui::CursorIcon::ResizeHorizontal)
+                .on_hover_text(format!("{:.2}s \u{2014} drag to retime", time));
+        } else {
+            marker_resp.on_hover_text("Fires on spawn");
+        }
+    }
+
+    if track_count == 0 {
+        painter.text(
+            egui::pos2(lane_left + 6.0, ruler.bottom() + TRACK_HEIGHT * 0.5),
+            egui::Align2::LEFT_CENTER,
+            "No timed rules",
+            egui::FontId::proportional(11.0),
+            colors::TEXT_MUTED,
+        );
+    }
+
+    // Playhead
+    if playback_state != PlaybackState::Stopped || elapsed > 0.0 {
+        let x = time_to_x(elapsed);
+        painter.line_segment(
+            [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+            egui::Stroke::new(PLAYHEAD_WIDTH, colors::STATUS_ERROR),
+        );
+    }
+}
@@ -7,7 +7,7 @@ use std::collections::HashSet;
 
 use bevy_procedural::ProceduralEntity;
 
-use crate::commands::TakeSnapshotCommand;
+use crate::commands::RecordReparentCommand;
 use crate::editor::{EditorMode, EditorState, PanelSide, PinnedWindows};
 use crate::prefabs::{PrefabEditingContext, PrefabRegistry};
 use crate::scene::{GroupMarker, Locked, PrimitiveMarker, PrimitiveShape, SceneEntity, SceneLightMarker};
@@ -341,7 +341,8 @@ fn draw_hierarchy_panel(
 
     // Apply reparenting after UI is done
     if let Some((child, new_parent)) = reparent_op {
-        commands.queue(TakeSnapshotCommand {
+        commands.queue(RecordReparentCommand {
+            entities: vec![child],
             description: "Reparent entity".to_string(),
         });
         if let Some(parent) = new_parent {
@@ -0,0 +1,196 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::commands::{HistoryEntry, JumpToHistoryEvent, SnapshotHistory};
+use crate::editor::EditorState;
+use crate::ui::theme::{colors, window_frame};
+use crate::ui::Settings;
+
+/// Maximum number of touched entity names listed in an entry's tooltip
+const MAX_TOUCHED_NAMES: usize = 12;
+
+/// Resource to track if the undo history window is open
+#[derive(Resource, Default)]
+pub struct HistoryWindowState {
+    pub open: bool,
+}
+
+pub struct HistoryPanelPlugin;
+
+impl Plugin for HistoryPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HistoryWindowState>()
+            .add_systems(EguiPrimaryContextPass, draw_history_window);
+    }
+}
+
+/// Format the time since an entry was recorded
+fn format_age(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    if seconds < 60 {
+        format!("{}s ago", seconds)
+    } else if seconds < 3600 {
+        format!("{}m ago", seconds / 60)
+    } else {
+        format!("{}h {}m ago", seconds / 3600, (seconds % 3600) / 60)
+    }
+}
+
+/// Draw one history row. Returns true if it was clicked.
+fn history_row(
+    ui: &mut egui::Ui,
+    entry: &HistoryEntry,
+    is_current: bool,
+    is_future: bool,
+    now: f64,
+    history: &SnapshotHistory,
+    names: &Query<&Name>,
+) -> bool {
+    let touched = entry.touched_entities();
+    let text_color = if is_future {
+        colors::TEXT_MUTED
+    } else {
+        colors::TEXT_PRIMARY
+    };
+
+    let mut clicked = false;
+    ui.horizontal(|ui| {
+        let response = ui.selectable_label(
+            is_current,
+            egui::RichText::new(entry.description()).color(text_color),
+        );
+        clicked = response.clicked();
+
+        response.on_hover_ui(|ui| match &touched {
+            None => {
+                ui.label("Full scene snapshot");
+            }
+            Some(entities) if entities.is_empty() => {
+                ui.label("Spawned new entities");
+            }
+            Some(entities) => {
+                for &entity in entities.iter().take(MAX_TOUCHED_NAMES) {
+                    let entity = history.resolve(entity);
+                    let label = names
+                        .get(entity)
+                        .map(|n| n.as_str().to_string())
+                        .unwrap_or_else(|_| format!("{:?} (deleted)", entity));
+                    ui.label(label);
+                }
+                if entities.len() > MAX_TOUCHED_NAMES {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "... and {} more",
+                            entities.len() - MAX_TOUCHED_NAMES
+                        ))
+                        .color(colors::TEXT_MUTED),
+                    );
+                }
+            }
+        });
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.label(
+                egui::RichText::new(format_age(now - entry.timestamp()))
+                    .small()
+                    .color(colors::TEXT_MUTED),
+            );
+            if let Some(entities) = &touched {
+                if !entities.is_empty() {
+                    ui.label(
+                        egui::RichText::new(format!("{}", entities.len()))
+                            .small()
+                            .color(colors::TEXT_SECONDARY),
+                    );
+                }
+            }
+        });
+    });
+
+    clicked
+}
+
+/// Draw the undo history window
+#[allow(clippy::too_many_arguments)]
+fn draw_history_window(
+    mut contexts: EguiContexts,
+    mut window_state: ResMut<HistoryWindowState>,
+    history: Res<SnapshotHistory>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+    names: Query<&Name>,
+    mut jump_events: MessageWriter<JumpToHistoryEvent>,
+    editor_state: Res<EditorState>,
+) -> Result {
+    // Don't draw UI when editor is disabled
+    if !editor_state.ui_enabled {
+        return Ok(());
+    }
+
+    if !window_state.open {
+        return Ok(());
+    }
+
+    let ctx = contexts.ctx_mut()?;
+    let now = time.elapsed_secs_f64();
+    let undo_count = history.undo_count();
+
+    let mut open = window_state.open;
+    let mut jump_to: Option<usize> = None;
+    egui::Window::new("Undo History")
+        .open(&mut open)
+        .default_width(280.0)
+        .resizable(true)
+        .frame(window_frame(&ctx.style()))
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} / {} steps, {} to redo",
+                    undo_count,
+                    settings.undo_history_size,
+                    history.redo_count()
+                ))
+                .small()
+                .color(colors::TEXT_MUTED),
+            );
+
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .max_height(400.0)
+                .show(ui, |ui| {
+                    let oldest = if history.is_trimmed() {
+                        "Oldest retained state"
+                    } else {
+                        "Initial state"
+                    };
+                    if ui.selectable_label(undo_count == 0, oldest).clicked() {
+                        jump_to = Some(0);
+                    }
+
+                    for (i, entry) in history.undo_entries().enumerate() {
+                        let position = i + 1;
+                        let is_current = position == undo_count;
+                        if history_row(ui, entry, is_current, false, now, &history, &names) {
+                            jump_to = Some(position);
+                        }
+                    }
+
+                    for (i, entry) in history.redo_entries().enumerate() {
+                        let position = undo_count + i + 1;
+                        if history_row(ui, entry, false, true, now, &history, &names) {
+                            jump_to = Some(position);
+                        }
+                    }
+                });
+        });
+
+    if let Some(position) = jump_to {
+        if position != undo_count {
+            jump_events.write(JumpToHistoryEvent { position });
+        }
+    }
+
+    window_state.open = open;
+    Ok(())
+}
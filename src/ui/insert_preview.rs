@@ -41,6 +41,8 @@ pub enum InsertPreviewKind {
     Arch,
     LShape,
     Decal,
+    SoundEmitter,
+    TriggerVolume,
     Prefab,
 }
 
@@ -240,6 +242,24 @@ fn sync_insert_preview_mesh(
             });
             (mesh, mat)
         }
+        InsertPreviewKind::SoundEmitter => {
+            let mesh: Mesh = Sphere::new(0.4).into();
+            let mat = materials.add(StandardMaterial {
+                base_color: Color::srgba(0.3, 0.7, 1.0, 0.6),
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            });
+            (mesh, mat)
+        }
+        InsertPreviewKind::TriggerVolume => {
+            let mesh: Mesh = Cuboid::new(1.0, 1.0, 1.0).into();
+            let mat = materials.add(StandardMaterial {
+                base_color: Color::srgba(0.2, 0.9, 0.5, 0.4),
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            });
+            (mesh, mat)
+        }
         InsertPreviewKind::Prefab => {
             let mesh: Mesh = Cuboid::new(0.5, 0.5, 0.5).into();
             let mat = materials.add(StandardMaterial {
@@ -5,19 +5,29 @@ use bevy::reflect::TypeInfo;
 use bevy_egui::{egui, EguiPrimaryContextPass};
 use bevy_procedural::{PlacementOrientation, ProceduralPlacer, SamplingMode};
 use bevy_spline_3d::path_follow::{FollowerState, LoopMode, SplineFollower};
+use bevy_spline_3d::prelude::{DeformMode, SplineMeshDeform};
 use std::any::TypeId;
 
-use bevy_editor_game::{CustomEntityRegistry, InspectorWidgetFn, SceneComponentRegistry};
+use bevy_editor_game::{
+    AnimationRef, AnimationSource, CustomEntityRegistry, InspectorWidgetFn, MeshLibrary, MeshRef,
+    SceneComponentRegistry,
+};
 
 use super::command_palette::{open_add_component_palette, CommandPaletteState, TexturePickResult, TextureSlot, draw_name_entity_field, make_callback_id, PendingEntitySelection};
 use super::reflect_editor::{clear_focus_state, component_editor, ReflectEditorConfig};
 use super::InspectorPanelState;
-use crate::commands::TakeSnapshotCommand;
+use crate::commands::RecordEntitiesCommand;
 use crate::editor::{EditorMode, EditorState, PanelSide, PinnedWindows};
+use crate::prefabs::{
+    ApplyPrefabOverrideEvent, PrefabInstance, PrefabOverride, PrefabOverrides,
+    RevertPrefabOverrideEvent,
+};
+use crate::audio::{SoundEmitterMarker, SoundPreview};
 use crate::scene::{
+    animation_duration, available_animation_clips,
     blockout::{ArchMarker, LShapeMarker, RampMarker, StairsMarker},
-    DecalMarker, DecalType, DirectionalLightMarker, FogVolumeMarker, Locked, SceneEntity,
-    SceneLightMarker,
+    AnimationBinding, DecalMarker, DecalType, DeformSource, DirectionalLightMarker, FogVolumeMarker, Locked,
+    PrimitiveShape, SceneEntity, SceneLightMarker, TriggerFilter, TriggerShape, TriggerVolumeMarker,
 };
 use crate::selection::Selected;
 use crate::ui::theme::{colors, draw_pin_button, grid_label, panel, panel_frame, section_header, value_slider, DRAG_VALUE_WIDTH};
@@ -298,6 +308,88 @@ impl From<&SplineFollower> for SplineFollowerData {
     }
 }
 
+/// Data for SplineMeshDeform editing
+#[derive(Clone)]
+struct SplineDeformData {
+    source: DeformSource,
+    mode: DeformMode,
+    spacing: f32,
+    auto_update: bool,
+    generate_collider: bool,
+    /// Meshes in the mesh library, for the source picker
+    library_meshes: Vec<String>,
+}
+
+impl SplineDeformData {
+    fn from_world(world: &World, deform: &SplineMeshDeform, source: &DeformSource) -> Self {
+        let mut library_meshes: Vec<String> = world
+            .get_resource::<MeshLibrary>()
+            .map(|library| library.meshes.keys().cloned().collect())
+            .unwrap_or_default();
+        library_meshes.sort();
+        Self {
+            source: source.clone(),
+            mode: deform.mode,
+            spacing: deform.spacing,
+            auto_update: deform.auto_update,
+            generate_collider: deform.generate_collider,
+            library_meshes,
+        }
+    }
+}
+
+/// Data for AnimationRef editing
+#[derive(Clone)]
+struct AnimationData {
+    from_gltf: bool,
+    clip: String,
+    autoplay: bool,
+    looping: bool,
+    speed: f32,
+    /// Clips available for the current source kind
+    clips: Vec<String>,
+    /// Clip length, once the clip is bound to an animation player
+    duration: Option<f32>,
+    preview_time: f32,
+    previewing: bool,
+}
+
+impl AnimationData {
+    fn from_world(world: &World, entity: Entity, animation: &AnimationRef) -> Self {
+        let from_gltf = matches!(animation.source, AnimationSource::Gltf(_));
+        let binding = world.get::<AnimationBinding>(entity);
+        Self {
+            from_gltf,
+            clip: animation.source.clip().to_string(),
+            autoplay: animation.autoplay,
+            looping: animation.looping,
+            speed: animation.speed,
+            clips: available_animation_clips(world, entity, from_gltf),
+            duration: binding
+                .filter(|b| b.is_bound())
+                .and_then(|_| animation_duration(world, entity)),
+            preview_time: binding.map(|b| b.preview_time).unwrap_or(0.0),
+            previewing: binding.is_some_and(|b| b.previewing),
+        }
+    }
+
+    fn source(&self) -> AnimationSource {
+        if self.from_gltf {
+            AnimationSource::Gltf(self.clip.clone())
+        } else {
+            AnimationSource::Library(self.clip.clone())
+        }
+    }
+}
+
+/// Result from drawing the Animation section
+struct AnimationResult {
+    /// The `AnimationRef` itself was edited
+    changed: bool,
+    /// The editor preview (timeline position or play state) was changed
+    preview_changed: bool,
+}
+
 /// Template data for UI editing
 #[derive(Clone)]
 struct TemplateData {
@@ -579,6 +671,141 @@ fn draw_fog_volume_section(ui: &mut egui::Ui, data: &mut FogVolumeData) -> bool
     changed
 }
 
+/// Draw sound emitter properties section. `preview` is the editor-wide
+/// sound preview toggle, not part of the emitter.
+fn draw_sound_emitter_section(
+    ui: &mut egui::Ui,
+    data: &mut SoundEmitterMarker,
+    preview: &mut bool,
+) -> bool {
+    let mut changed = false;
+
+    section_header(ui, "Sound Emitter", true, |ui| {
+        egui::Grid::new("sound_emitter_grid")
+            .num_columns(2)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                grid_label(ui, "Sound");
+                changed |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut data.path)
+                            .hint_text("audio/ambience.ogg")
+                            .desired_width(140.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                grid_label(ui, "Volume");
+                changed |= value_slider(ui, &mut data.volume, 0.0..=2.0);
+                ui.end_row();
+
+                grid_label(ui, "Loop");
+                changed |= ui.checkbox(&mut data.looping, "").changed();
+                ui.end_row();
+
+                grid_label(ui, "Radius");
+                changed |= value_slider(ui, &mut data.radius, 0.0..=100.0);
+                ui.end_row();
+
+                grid_label(ui, "Preview");
+                ui.checkbox(preview, "Play while editing");
+                ui.end_row();
+            });
+    });
+
+    changed
+}
+
+/// Draw trigger volume properties section
+fn draw_trigger_volume_section(ui: &mut egui::Ui, data: &mut TriggerVolumeMarker) -> bool {
+    let mut changed = false;
+
+    section_header(ui, "Trigger Volume", true, |ui| {
+        egui::Grid::new("trigger_volume_grid")
+            .num_columns(2)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                grid_label(ui, "Shape");
+                let mut shape_idx = data.shape.variant_index();
+                egui::ComboBox::from_id_salt("trigger_shape")
+                    .selected_text(data.shape.label())
+                    .show_ui(ui, |ui| {
+                        for (i, label) in TriggerShape::VARIANT_LABELS.iter().enumerate() {
+                            ui.selectable_value(&mut shape_idx, i, *label);
+                        }
+                    });
+                if shape_idx != data.shape.variant_index() {
+                    data.shape = TriggerShape::from_variant_index(shape_idx);
+                    changed = true;
+                }
+                ui.end_row();
+
+                match &mut data.shape {
+                    TriggerShape::Box { half_extents } => {
+                        grid_label(ui, "Half Extents");
+                        ui.horizontal(|ui| {
+                            for axis in [&mut half_extents.x, &mut half_extents.y, &mut half_extents.z] {
+                                changed |= ui
+                                    .add(egui::DragValue::new(axis).speed(0.05).range(0.01..=1000.0))
+                                    .changed();
+                            }
+                        });
+                        ui.end_row();
+                    }
+                    TriggerShape::Sphere { radius } => {
+                        grid_label(ui, "Radius");
+                        changed |= value_slider(ui, radius, 0.01..=100.0);
+                        ui.end_row();
+                    }
+                    TriggerShape::Capsule { radius, half_height } => {
+                        grid_label(ui, "Radius");
+                        changed |= value_slider(ui, radius, 0.01..=100.0);
+                        ui.end_row();
+
+                        grid_label(ui, "Half Height");
+                        changed |= value_slider(ui, half_height, 0.0..=100.0);
+                        ui.end_row();
+                    }
+                }
+
+                grid_label(ui, "Filter");
+                let mut filter_idx = data.filter.variant_index();
+                egui::ComboBox::from_id_salt("trigger_filter")
+                    .selected_text(data.filter.label())
+                    .show_ui(ui, |ui| {
+                        for (i, label) in TriggerFilter::VARIANT_LABELS.iter().enumerate() {
+                            ui.selectable_value(&mut filter_idx, i, *label);
+                        }
+                    });
+                if filter_idx != data.filter.variant_index() {
+                    data.filter = TriggerFilter::from_variant_index(filter_idx);
+                    changed = true;
+                }
+                ui.end_row();
+
+                match &mut data.filter {
+                    TriggerFilter::Any => {}
+                    TriggerFilter::Tag(tag) => {
+                        grid_label(ui, "Name");
+                        changed |= ui
+                            .add(egui::TextEdit::singleline(tag).hint_text("Player").desired_width(140.0))
+                            .changed();
+                        ui.end_row();
+                    }
+                    TriggerFilter::Component(type_name) => {
+                        grid_label(ui, "Component");
+                        changed |= ui
+                            .add(egui::TextEdit::singleline(type_name).hint_text("PlayerController").desired_width(140.0))
+                            .changed();
+                        ui.end_row();
+                    }
+                }
+            });
+    });
+
+    changed
+}
+
 /// Draw stairs properties section
 fn draw_stairs_section(ui: &mut egui::Ui, data: &mut StairsData) -> bool {
     let mut changed = false;
@@ -949,6 +1176,224 @@ fn draw_spline_follower_section(
     result
 }
 
+/// Result from drawing the spline mesh deform section
+struct SplineDeformResult {
+    changed: bool,
+    /// The source mesh changed and needs resolving again
+    source_changed: bool,
+}
+
+/// Draw SplineMeshDeform properties section
+fn draw_spline_deform_section(ui: &mut egui::Ui, data: &mut SplineDeformData) -> SplineDeformResult {
+    let mut result = SplineDeformResult {
+        changed: false,
+        source_changed: false,
+    };
+
+    section_header(ui, "Mesh Deform", true, |ui| {
+        egui::Grid::new("spline_deform_grid")
+            .num_columns(2)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                let from_library = matches!(data.source, DeformSource::Mesh(_));
+                grid_label(ui, "Source");
+                ui.horizontal(|ui| {
+                    if ui.selectable_label(!from_library, "Primitive").clicked() && from_library {
+                        data.source = DeformSource::default();
+                        result.source_changed = true;
+                    }
+                    if ui.selectable_label(from_library, "Library").clicked() && !from_library {
+                        let name = data.library_meshes.first().cloned().unwrap_or_default();
+                        data.source = DeformSource::Mesh(MeshRef::Library(name));
+                        result.source_changed = true;
+                    }
+                });
+                ui.end_row();
+
+                grid_label(ui, "Mesh");
+                match &mut data.source {
+                    DeformSource::Primitive(shape) => {
+                        egui::ComboBox::from_id_salt("deform_primitive")
+                            .selected_text(shape.display_name())
+                            .show_ui(ui, |ui| {
+                                for s in [
+                                    PrimitiveShape::Cube,
+                                    PrimitiveShape::Sphere,
+                                    PrimitiveShape::Cylinder,
+                                    PrimitiveShape::Capsule,
+                                    PrimitiveShape::Plane,
+                                ] {
+                                    if ui.selectable_value(shape, s, s.display_name()).clicked() {
+                                        result.source_changed = true;
+                                    }
+                                }
+                            });
+                    }
+                    DeformSource::Mesh(MeshRef::Library(name)) => {
+                        let selected_text = if name.is_empty() { "None" } else { name.as_str() };
+                        egui::ComboBox::from_id_salt("deform_library_mesh")
+                            .selected_text(selected_text)
+                            .width(180.0)
+                            .show_ui(ui, |ui| {
+                                if data.library_meshes.is_empty() {
+                                    ui.label(
+                                        egui::RichText::new("No library meshes")
+                                            .color(colors::TEXT_MUTED),
+                                    );
+                                }
+                                for mesh in &data.library_meshes {
+                                    if ui.selectable_label(*name == *mesh, mesh).clicked() && *name != *mesh {
+                                        *name = mesh.clone();
+                                        result.source_changed = true;
+                                    }
+                                }
+                            });
+                    }
+                }
+                ui.end_row();
+
+                grid_label(ui, "Mode");
+                ui.horizontal(|ui| {
+                    for (mode, label) in [(DeformMode::Repeat, "Repeat"), (DeformMode::Stretch, "Stretch")] {
+                        if ui.selectable_label(data.mode == mode, label).clicked() && data.mode != mode {
+                            data.mode = mode;
+                            result.changed = true;
+                        }
+                    }
+                });
+                ui.end_row();
+
+                if data.mode == DeformMode::Repeat {
+                    grid_label(ui, "Spacing");
+                    result.changed |= value_slider(ui, &mut data.spacing, 0.0..=10.0);
+                    ui.end_row();
+                }
+
+                grid_label(ui, "Auto Update");
+                result.changed |= ui.checkbox(&mut data.auto_update, "").changed();
+                ui.end_row();
+
+                grid_label(ui, "Collider");
+                result.changed |= ui.checkbox(&mut data.generate_collider, "").changed();
+                ui.end_row();
+            });
+    });
+
+    result.changed |= result.source_changed;
+    result
+}
+
+/// Draw an AnimationRef section with a preview timeline
+fn draw_animation_section(ui: &mut egui::Ui, data: &mut AnimationData) -> AnimationResult {
+    let mut result = AnimationResult {
+        changed: false,
+        preview_changed: false,
+    };
+
+    section_header(ui, "Animation", true, |ui| {
+        egui::Grid::new("animation_grid")
+            .num_columns(2)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                grid_label(ui, "Source");
+                ui.horizontal(|ui| {
+                    if ui.selectable_label(!data.from_gltf, "Library").clicked() && data.from_gltf {
+                        data.from_gltf = false;
+                        result.changed = true;
+                    }
+                    if ui.selectable_label(data.from_gltf, "GLTF").clicked() && !data.from_gltf {
+                        data.from_gltf = true;
+                        result.changed = true;
+                    }
+                });
+                ui.end_row();
+
+                grid_label(ui, "Clip");
+                let selected_text = if data.clip.is_empty() {
+                    "None"
+                } else {
+                    data.clip.as_str()
+                };
+                egui::ComboBox::from_id_salt("animation_clip")
+                    .selected_text(selected_text)
+                    .width(180.0)
+                    .show_ui(ui, |ui| {
+                        if data.clips.is_empty() {
+                            ui.label(
+                                egui::RichText::new("No clips available")
+                                    .color(colors::TEXT_MUTED),
+                            );
+                        }
+                        for clip in &data.clips {
+                            if ui.selectable_label(data.clip == *clip, clip).clicked()
+                                && data.clip != *clip
+                            {
+                                data.clip = clip.clone();
+                                result.changed = true;
+                            }
+                        }
+                    });
+                ui.end_row();
+
+                grid_label(ui, "Autoplay");
+                result.changed |= ui.checkbox(&mut data.autoplay, "On game start").changed();
+                ui.end_row();
+
+                grid_label(ui, "Loop");
+                result.changed |= ui.checkbox(&mut data.looping, "").changed();
+                ui.end_row();
+
+                grid_label(ui, "Speed");
+                result.changed |= value_slider(ui, &mut data.speed, 0.0..=4.0);
+                ui.end_row();
+            });
+
+        ui.add_space(4.0);
+
+        // Preview timeline
+        match data.duration {
+            Some(duration) => {
+                ui.horizontal(|ui| {
+                    let label = if data.previewing { "\u{23F8}" } else { "\u{25B6}" };
+                    if ui
+                        .small_button(label)
+                        .on_hover_text("Play or pause the preview")
+                        .clicked()
+                    {
+                        data.previewing = !data.previewing;
+                        result.preview_changed = true;
+                    }
+                    if ui
+                        .small_button("\u{23EE}")
+                        .on_hover_text("Back to start")
+                        .clicked()
+                    {
+                        data.preview_time = 0.0;
+                        result.preview_changed = true;
+                    }
+                    let slider = ui.add(
+                        egui::Slider::new(&mut data.preview_time, 0.0..=duration)
+                            .suffix(" s")
+                            .max_decimals(2),
+                    );
+                    if slider.changed() {
+                        data.previewing = false;
+                        result.preview_changed = true;
+                    }
+                });
+            }
+            None => {
+                ui.label(
+                    egui::RichText::new("Waiting for clip and model to load")
+                        .color(colors::TEXT_MUTED),
+                );
+            }
+        }
+    });
+
+    result
+}
+
 /// Result from drawing the ProceduralPlacer section
 struct ProceduralPlacerResult {
     changed: bool,
@@ -1214,6 +1659,72 @@ fn draw_rigidbody_section(ui: &mut egui::Ui, current_type: Option<RigidBodyType>
     action
 }
 
+/// Action requested from the prefab overrides section. `None` targets all overrides.
+enum PrefabOverrideAction {
+    Revert(Option<PrefabOverride>),
+    Apply(Option<PrefabOverride>),
+}
+
+/// Draw the overrides of a prefab instance entity with revert/apply buttons
+fn draw_prefab_overrides_section(
+    ui: &mut egui::Ui,
+    prefab_name: &str,
+    overrides: &[PrefabOverride],
+) -> Option<PrefabOverrideAction> {
+    let mut action = None;
+
+    section_header(ui, "Prefab Overrides", true, |ui| {
+        ui.label(
+            egui::RichText::new(format!("Instance of \"{}\"", prefab_name))
+                .small()
+                .color(colors::TEXT_MUTED),
+        );
+
+        if overrides.is_empty() {
+            ui.label(
+                egui::RichText::new("Matches prefab")
+                    .color(colors::TEXT_MUTED)
+                    .italics(),
+            );
+            return;
+        }
+
+        for o in overrides {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(o.label()).color(colors::TEXT_SECONDARY));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .small_button("Apply")
+                        .on_hover_text("Write this value into the prefab")
+                        .clicked()
+                    {
+                        action = Some(PrefabOverrideAction::Apply(Some(o.clone())));
+                    }
+                    if ui
+                        .small_button("Revert")
+                        .on_hover_text("Restore the prefab value")
+                        .clicked()
+                    {
+                        action = Some(PrefabOverrideAction::Revert(Some(o.clone())));
+                    }
+                });
+            });
+        }
+
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            if ui.button("Revert All").clicked() {
+                action = Some(PrefabOverrideAction::Revert(None));
+            }
+            if ui.button("Apply All to Prefab").clicked() {
+                action = Some(PrefabOverrideAction::Apply(None));
+            }
+        });
+    });
+
+    action
+}
+
 /// Draw the component inspector panel
 fn draw_inspector_panel(world: &mut World) {
     // Don't draw UI when editor is disabled
@@ -1292,6 +1803,14 @@ fn draw_inspector_panel(world: &mut World) {
         world.get::<FogVolumeMarker>(e).map(|m| FogVolumeData::from(m))
     });
 
+    // Get sound emitter data for single selection
+    let mut sound_emitter_data = single_entity.and_then(|e| world.get::<SoundEmitterMarker>(e).cloned());
+    let mut sound_preview = world.resource::<SoundPreview>().enabled;
+    let original_sound_preview = sound_preview;
+
+    // Get trigger volume data for single selection
+    let mut trigger_volume_data = single_entity.and_then(|e| world.get::<TriggerVolumeMarker>(e).cloned());
+
     // Get decal data for single selection
     let mut decal_data = single_entity.and_then(|e| {
         world.get::<DecalMarker>(e).map(|m| DecalData::from(m))
@@ -1356,11 +1875,33 @@ fn draw_inspector_panel(world: &mut World) {
 
     // spline_follower_data.spline is already the name string — no separate lookup needed
 
+    // Get spline mesh deform data for single selection
+    let mut spline_deform_data = single_entity.and_then(|e| {
+        let deform = world.get::<SplineMeshDeform>(e)?;
+        let source = world.get::<DeformSource>(e)?;
+        Some(SplineDeformData::from_world(world, deform, source))
+    });
+
+    // Get animation data for single selection
+    let mut animation_data = single_entity.and_then(|e| {
+        world
+            .get::<AnimationRef>(e)
+            .map(|animation| AnimationData::from_world(world, e, animation))
+    });
+
     // Get procedural placer component data for single selection
     let mut procedural_placer_data = single_entity.and_then(|e| {
         world.get::<ProceduralPlacer>(e).map(|p| ProceduralPlacerData::from_placer(p, world))
     });
 
+    // Get prefab overrides for single selection (instance entities with a prefab source)
+    let prefab_overrides = single_entity.and_then(|e| {
+        let instance = world.get::<PrefabInstance>(e)?;
+        instance.source_id?;
+        let tracked = world.get::<PrefabOverrides>(e)?;
+        Some((instance.prefab_name.clone(), tracked.overrides.clone()))
+    });
+
     // Get egui context
     let ctx = {
         let Some(mut egui_ctx) = world
@@ -1374,10 +1915,13 @@ fn draw_inspector_panel(world: &mut World) {
     };
 
     let mut transform_changed = false;
+    let mut prefab_override_action: Option<PrefabOverrideAction> = None;
     let mut rigidbody_action: ComponentAction<RigidBodyType> = ComponentAction::None;
     let mut point_light_changed = false;
     let mut directional_light_changed = false;
     let mut fog_volume_changed = false;
+    let mut sound_emitter_changed = false;
+    let mut trigger_volume_changed = false;
     let mut decal_changed = false;
     let mut decal_browse_requested: Option<TextureSlot> = None;
     let mut stairs_changed = false;
@@ -1386,6 +1930,12 @@ fn draw_inspector_panel(world: &mut World) {
     let mut lshape_changed = false;
     let mut spline_follower_changed = false;
     let mut open_spline_picker = false;
+    let mut spline_deform_result = SplineDeformResult {
+        changed: false,
+        source_changed: false,
+    };
+    let mut animation_changed = false;
+    let mut animation_preview_changed = false;
     let mut custom_inspector_changed = false;
 
     // Procedural placer change tracking
@@ -1514,6 +2064,13 @@ fn draw_inspector_panel(world: &mut World) {
 
                         // Custom Transform section with colored labels (disabled if locked)
                         if !is_locked {
+                            // Prefab overrides
+                            if let Some((ref prefab_name, ref overrides)) = prefab_overrides {
+                                prefab_override_action =
+                                    draw_prefab_overrides_section(ui, prefab_name, overrides);
+                                ui.add_space(4.0);
+                            }
+
                             if let Some(ref mut transform) = transform_copy {
                                 transform_changed = draw_transform_section(ui, transform);
                             }
@@ -1544,6 +2101,18 @@ fn draw_inspector_panel(world: &mut World) {
                                 ui.add_space(4.0);
                             }
 
+                            // Sound emitter properties
+                            if let Some(ref mut data) = sound_emitter_data {
+                                sound_emitter_changed = draw_sound_emitter_section(ui, data, &mut sound_preview);
+                                ui.add_space(4.0);
+                            }
+
+                            // Trigger volume properties
+                            if let Some(ref mut data) = trigger_volume_data {
+                                trigger_volume_changed = draw_trigger_volume_section(ui, data);
+                                ui.add_space(4.0);
+                            }
+
                             // Decal properties
                             if let Some(ref mut data) = decal_data {
                                 let result = draw_decal_section(ui, data);
@@ -1578,6 +2147,20 @@ fn draw_inspector_panel(world: &mut World) {
                                 ui.add_space(4.0);
                             }
 
+                            // Spline mesh deform properties
+                            if let Some(ref mut data) = spline_deform_data {
+                                spline_deform_result = draw_spline_deform_section(ui, data);
+                                ui.add_space(4.0);
+                            }
+
+                            // Animation playback and preview
+                            if let Some(ref mut data) = animation_data {
+                                let result = draw_animation_section(ui, data);
+                                animation_changed = result.changed;
+                                animation_preview_changed = result.preview_changed;
+                                ui.add_space(4.0);
+                            }
+
                             // Procedural placer properties
                             if let Some(ref mut data) = procedural_placer_data {
                                 let result = draw_procedural_placer_section(ui, data);
@@ -1814,23 +2397,29 @@ fn draw_inspector_panel(world: &mut World) {
         || point_light_changed
         || directional_light_changed
         || fog_volume_changed
+        || sound_emitter_changed
+        || trigger_volume_changed
         || decal_changed
         || stairs_changed
         || ramp_changed
         || arch_changed
         || lshape_changed
         || spline_follower_changed
+        || spline_deform_result.changed
+        || animation_changed
         || procedural_placer_changed
         || custom_inspector_changed;
 
-    // Take a snapshot before the first change in an editing session
+    // Record the selection before the first change in an editing session
     if any_change {
         let needs = world
             .get_resource::<InspectorPanelState>()
             .map(|s| s.needs_snapshot)
             .unwrap_or(true);
         if needs {
-            TakeSnapshotCommand {
+            RecordEntitiesCommand {
+                entities: selected_entities.clone(),
+                recursive: false,
                 description: "Inspector edit".to_string(),
             }
             .apply(world);
@@ -1843,6 +2432,18 @@ fn draw_inspector_panel(world: &mut World) {
         state.needs_snapshot = true;
     }
 
+    // Revert or apply prefab overrides
+    if let (Some(entity), Some(action)) = (single_entity, prefab_override_action) {
+        match action {
+            PrefabOverrideAction::Revert(target) => {
+                world.write_message(RevertPrefabOverrideEvent { entity, target });
+            }
+            PrefabOverrideAction::Apply(target) => {
+                world.write_message(ApplyPrefabOverrideEvent { entity, target });
+            }
+        }
+    }
+
     // Apply transform changes back to the entity (single selection only)
     if transform_changed {
         if let (Some(entity), Some(new_transform)) = (single_entity, transform_copy) {
@@ -2001,6 +2602,27 @@ fn draw_inspector_panel(world: &mut World) {
         }
     }
 
+    // Apply sound emitter changes (the audio plugin restarts voices as needed)
+    if sound_emitter_changed {
+        if let (Some(entity), Some(data)) = (single_entity, sound_emitter_data) {
+            if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+                entity_mut.insert(data);
+            }
+        }
+    }
+    if sound_preview != original_sound_preview {
+        world.resource_mut::<SoundPreview>().enabled = sound_preview;
+    }
+
+    // Apply trigger volume changes (the sensor collider is rebuilt on change)
+    if trigger_volume_changed {
+        if let (Some(entity), Some(data)) = (single_entity, trigger_volume_data) {
+            if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+                entity_mut.insert(data);
+            }
+        }
+    }
+
     // Apply decal changes (sync_decal_markers handles runtime component rebuild)
     if decal_changed {
         if let (Some(entity), Some(data)) = (single_entity, decal_data) {
@@ -2085,6 +2707,45 @@ fn draw_inspector_panel(world: &mut World) {
         }
     }
 
+    // Apply spline mesh deform changes (a new source is resolved to its mesh next frame)
+    if spline_deform_result.changed {
+        if let (Some(entity), Some(data)) = (single_entity, spline_deform_data) {
+            if let Some(mut deform) = world.get_mut::<SplineMeshDeform>(entity) {
+                deform.mode = data.mode;
+                deform.spacing = data.spacing;
+                deform.auto_update = data.auto_update;
+                deform.generate_collider = data.generate_collider;
+            }
+            if spline_deform_result.source_changed {
+                if let Some(mut source) = world.get_mut::<DeformSource>(entity) {
+                    *source = data.source;
+                }
+            }
+        }
+    }
+
+    // Apply animation changes
+    if animation_changed {
+        if let (Some(entity), Some(data)) = (single_entity, animation_data.as_ref()) {
+            if let Some(mut animation) = world.get_mut::<AnimationRef>(entity) {
+                animation.source = data.source();
+                animation.autoplay = data.autoplay;
+                animation.looping = data.looping;
+                animation.speed = data.speed;
+            }
+        }
+    }
+
+    // Preview state lives on the runtime binding and isn't part of the scene
+    if animation_preview_changed {
+        if let (Some(entity), Some(data)) = (single_entity, animation_data.as_ref()) {
+            if let Some(mut binding) = world.get_mut::<AnimationBinding>(entity) {
+                binding.preview_time = data.preview_time;
+                binding.previewing = data.previewing;
+            }
+        }
+    }
+
     // Remove template from procedural placer
     if let Some(remove_index) = remove_placer_template_index {
         if let Some(entity) = single_entity {
@@ -2305,8 +2966,12 @@ fn draw_all_components(world: &mut World, entity: Entity, ui: &mut egui::Ui) {
             || name == "SceneLightMarker"
             || name == "DirectionalLightMarker"
             || name == "FogVolumeMarker"
+            || name == "SoundEmitterMarker"
+            || name == "TriggerVolumeMarker"
             || name == "RigidBody"
             || name == "SplineFollower"
+            || name == "SplineMeshDeform"
+            || name == "DeformSource"
             || name == "StairsMarker"
             || name == "RampMarker"
             || name == "ArchMarker"
@@ -1,28 +1,73 @@
 //! Right-side panel for the mesh modeling tool.
 //!
 //! Shows grid type selection, grid size controls, operation buttons,
-//! extrude settings, and selected face count.
+//! extrude settings, brush tool settings, the target's modifier stack and
+//! material slots, and selected face count.
 
 use bevy::prelude::*;
+use bevy_editor_game::{MaterialLibrary, MaterialRef};
 use bevy_egui::{egui, EguiPrimaryContextPass};
 
+use crate::commands::RecordEntitiesCommand;
 use crate::editor::{EditorMode, EditorState, PanelSide, PinnedWindows};
 use crate::modeling::boolean::BooleanOp;
+use crate::modeling::half_edge::HalfEdgeMesh;
+use crate::modeling::marker::EditMeshMarker;
+use crate::modeling::material_slots::MaterialSlots;
 use crate::modeling::mirror::MirrorAxis;
+use crate::modeling::modifier::{
+    collect_boolean_operands, rebuild_evaluated_mesh, MeshModifier, MeshModifierStack,
+    ModifierEntry, MAX_SUBDIVISION_LEVELS,
+};
 use crate::modeling::snap::SnapMode;
 use crate::modeling::soft_select::FalloffCurve;
 use crate::modeling::uv_project::{ProjectionAxis, UvProjection};
-use crate::modeling::{GridType, MeshModelState, ModelOperation, SelectionMode};
+use crate::modeling::paint::PaintChannel;
+use crate::modeling::sculpt::SculptBrush;
+use crate::modeling::{GridType, MeshModelState, ModelOperation, ModelTool, SelectionMode};
 use crate::ui::theme::{colors, draw_pin_button, panel, panel_frame, section_header, value_slider};
 
 pub struct MeshModelPanelPlugin;
 
 impl Plugin for MeshModelPanelPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(EguiPrimaryContextPass, draw_mesh_model_panel);
+        app.init_resource::<ModifierPanelState>()
+            .add_systems(EguiPrimaryContextPass, draw_mesh_model_panel);
     }
 }
 
+/// Undo bookkeeping for modifier stack edits.
+#[derive(Resource)]
+struct ModifierPanelState {
+    /// Whether to record the target before the next stack edit. Resets when
+    /// a frame has no edits, so a slider drag records a single undo step.
+    needs_snapshot: bool,
+}
+
+impl Default for ModifierPanelState {
+    fn default() -> Self {
+        Self {
+            needs_snapshot: true,
+        }
+    }
+}
+
+/// Stack edits made in the panel this frame, applied after drawing.
+#[derive(Default)]
+struct ModifierPanelResult {
+    changed: bool,
+    /// Bake modifiers up to and including this index into the base mesh.
+    apply_through: Option<usize>,
+}
+
+/// Per-card buttons, deferred until the stack is no longer borrowed.
+enum ModifierCardAction {
+    MoveUp(usize),
+    MoveDown(usize),
+    Apply(usize),
+    Remove(usize),
+}
+
 fn draw_mesh_model_panel(world: &mut World) {
     if !world.resource::<EditorState>().ui_enabled {
         return;
@@ -39,6 +84,23 @@ fn draw_mesh_model_panel(world: &mut World) {
 
     // Clone lightweight state snapshot to avoid borrow conflicts
     let mut state = world.resource::<MeshModelState>().clone_state();
+    let mut modifier_stack = state
+        .target_entity
+        .and_then(|entity| world.get::<MeshModifierStack>(entity).cloned())
+        .unwrap_or_default();
+    let mut modifier_result = ModifierPanelResult::default();
+    let mut material_slots = state
+        .target_entity
+        .and_then(|entity| world.get::<MaterialSlots>(entity).cloned())
+        .unwrap_or_default();
+    let mut material_slots_changed = false;
+    let mut material_names: Vec<String> = world
+        .resource::<MaterialLibrary>()
+        .materials
+        .keys()
+        .cloned()
+        .collect();
+    material_names.sort();
 
     // Get egui context
     let ctx = {
@@ -111,6 +173,31 @@ fn draw_mesh_model_panel(world: &mut World) {
 
                 ui.add_space(8.0);
 
+                // Tool section (element editing or brushes)
+                section_header(ui, "Tool", true, |ui| {
+                    ui.horizontal(|ui| {
+                        for tool in [ModelTool::Edit, ModelTool::Paint, ModelTool::Sculpt] {
+                            let is_active = state.model_tool == tool;
+                            let label = format!("{} ({})", tool.display_name(), tool.key_hint());
+                            let text = if is_active {
+                                egui::RichText::new(label).strong().color(colors::ACCENT_BLUE)
+                            } else {
+                                egui::RichText::new(label).color(colors::TEXT_SECONDARY)
+                            };
+                            if ui.selectable_label(is_active, text).clicked() {
+                                state.model_tool = tool;
+                            }
+                        }
+                    });
+
+                    if state.model_tool != ModelTool::Edit {
+                        ui.add_space(4.0);
+                        draw_brush_settings(ui, &mut state);
+                    }
+                });
+
+                ui.add_space(4.0);
+
                 // Selection Mode section
                 section_header(ui, "Element Mode", true, |ui| {
                     ui.horizontal(|ui| {
@@ -408,6 +495,36 @@ fn draw_mesh_model_panel(world: &mut World) {
 
                 ui.add_space(8.0);
 
+                // Modifiers section (non-destructive whole-mesh operations)
+                if state.edit_mesh_face_count.is_some() {
+                    section_header(ui, "Modifiers", true, |ui| {
+                        draw_modifier_stack(ui, &mut modifier_stack, &mut modifier_result);
+                    });
+
+                    ui.add_space(8.0);
+
+                    // Materials section (per-face material slots)
+                    section_header(ui, "Materials", true, |ui| {
+                        material_slots_changed = draw_material_slots(
+                            ui,
+                            &mut material_slots,
+                            &mut state.material_slot,
+                            &material_names,
+                        );
+                        if has_selection
+                            && state.selection_mode == SelectionMode::Face
+                            && ui
+                                .button(egui::RichText::new("Assign to Selection").color(colors::TEXT_SECONDARY))
+                                .clicked()
+                        {
+                            state.pending_operation = ModelOperation::AssignMaterial;
+                            state.confirm_requested = true;
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
+
                 // UV Tools section
                 section_header(ui, "UV Tools", true, |ui| {
                     // UV Projection
@@ -632,6 +749,13 @@ fn draw_mesh_model_panel(world: &mut World) {
         }
     }
 
+    if let Some(entity) = state.target_entity {
+        apply_modifier_changes(world, entity, modifier_stack, &modifier_result);
+        if material_slots_changed {
+            apply_material_slot_changes(world, entity, material_slots);
+        }
+    }
+
     // Apply state changes back to the resource
     let mut model_state = world.resource_mut::<MeshModelState>();
     model_state.grid_type = state.grid_type;
@@ -662,11 +786,462 @@ fn draw_mesh_model_panel(world: &mut World) {
     model_state.soft_selection = state.soft_selection;
     model_state.soft_radius = state.soft_radius;
     model_state.soft_falloff = state.soft_falloff;
+    model_state.material_slot = state.material_slot;
+    if model_state.model_tool != state.model_tool {
+        model_state.model_tool = state.model_tool;
+        model_state.pending_operation = ModelOperation::Select;
+        model_state.grab_stroke = None;
+    }
+    model_state.brush_radius = state.brush_radius;
+    model_state.brush_strength = state.brush_strength;
+    model_state.paint_color = state.paint_color;
+    model_state.paint_channel = state.paint_channel;
+    model_state.sculpt_brush = state.sculpt_brush;
+    model_state.sculpt_symmetry = state.sculpt_symmetry;
     if state.confirm_requested {
         model_state.confirm_requested = true;
     }
 }
 
+/// Radius, strength and tool-specific settings for the active brush.
+fn draw_brush_settings(ui: &mut egui::Ui, state: &mut PanelSnapshot) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Radius").color(colors::TEXT_SECONDARY));
+        value_slider(ui, &mut state.brush_radius, 0.01..=10.0);
+    });
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Strength").color(colors::TEXT_SECONDARY));
+        value_slider(ui, &mut state.brush_strength, 0.05..=1.0);
+    });
+
+    if state.model_tool == ModelTool::Paint {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Channel").color(colors::TEXT_SECONDARY));
+            for channel in [PaintChannel::Color, PaintChannel::Mask] {
+                let is_active = state.paint_channel == channel;
+                let text = if is_active {
+                    egui::RichText::new(channel.display_name()).strong().color(colors::ACCENT_BLUE)
+                } else {
+                    egui::RichText::new(channel.display_name()).color(colors::TEXT_SECONDARY)
+                };
+                if ui.selectable_label(is_active, text).clicked() {
+                    state.paint_channel = channel;
+                }
+            }
+        });
+
+        match state.paint_channel {
+            PaintChannel::Color => {
+                // Vertex colors are linear; the picker works in sRGB
+                let c = state.paint_color;
+                let srgba = Srgba::from(LinearRgba::new(c.x, c.y, c.z, c.w));
+                let mut rgb = [srgba.red, srgba.green, srgba.blue];
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Color").color(colors::TEXT_SECONDARY));
+                    if ui.color_edit_button_rgb(&mut rgb).changed() {
+                        let linear = LinearRgba::from(Srgba::rgb(rgb[0], rgb[1], rgb[2]));
+                        state.paint_color =
+                            Vec4::new(linear.red, linear.green, linear.blue, c.w);
+                    }
+                });
+            }
+            PaintChannel::Mask => {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Mask Value").color(colors::TEXT_SECONDARY));
+                    value_slider(ui, &mut state.paint_color.w, 0.0..=1.0);
+                });
+            }
+        }
+    }
+
+    if state.model_tool == ModelTool::Sculpt {
+        ui.horizontal_wrapped(|ui| {
+            for brush in SculptBrush::ALL {
+                let is_active = state.sculpt_brush == brush;
+                let label = format!("{} ({})", brush.display_name(), brush.key_hint());
+                let text = if is_active {
+                    egui::RichText::new(label).strong().color(colors::ACCENT_BLUE)
+                } else {
+                    egui::RichText::new(label).color(colors::TEXT_SECONDARY)
+                };
+                if ui.selectable_label(is_active, text).clicked() {
+                    state.sculpt_brush = brush;
+                }
+            }
+        });
+
+        // Symmetry reuses the mirror operation's axis
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut state.sculpt_symmetry, "");
+            ui.label(egui::RichText::new("Symmetry (M)").color(if state.sculpt_symmetry {
+                colors::ACCENT_GREEN
+            } else {
+                colors::TEXT_SECONDARY
+            }));
+            for axis in [MirrorAxis::X, MirrorAxis::Y, MirrorAxis::Z] {
+                let is_active = state.mirror_axis == axis;
+                let text = if is_active {
+                    egui::RichText::new(axis.display_name()).strong().color(colors::ACCENT_BLUE)
+                } else {
+                    egui::RichText::new(axis.display_name()).color(colors::TEXT_SECONDARY)
+                };
+                if ui.selectable_label(is_active, text).clicked() {
+                    state.mirror_axis = axis;
+                }
+            }
+        });
+    }
+
+    ui.label(
+        egui::RichText::new("[ / ] radius, Shift+[ / ] strength")
+            .small()
+            .color(colors::TEXT_MUTED),
+    );
+    if state.model_tool == ModelTool::Sculpt {
+        ui.label(
+            egui::RichText::new("Hold Ctrl to invert the brush")
+                .small()
+                .color(colors::TEXT_MUTED),
+        );
+    }
+}
+
+fn draw_modifier_stack(
+    ui: &mut egui::Ui,
+    stack: &mut MeshModifierStack,
+    result: &mut ModifierPanelResult,
+) {
+    if stack.modifiers.is_empty() {
+        ui.label(
+            egui::RichText::new("No modifiers \u{2014} edits bake into the mesh")
+                .small()
+                .color(colors::TEXT_MUTED),
+        );
+    }
+
+    let count = stack.modifiers.len();
+    let mut card_action = None;
+    for (idx, entry) in stack.modifiers.iter_mut().enumerate() {
+        let frame = egui::Frame::new()
+            .fill(colors::BG_MEDIUM)
+            .corner_radius(egui::CornerRadius::same(4))
+            .inner_margin(egui::Margin::same(6));
+        frame.show(ui, |ui| {
+            ui.horizontal(|ui| {
+                result.changed |= ui.checkbox(&mut entry.enabled, "").changed();
+                let label_color = if entry.enabled {
+                    colors::ACCENT_BLUE
+                } else {
+                    colors::TEXT_MUTED
+                };
+                ui.label(egui::RichText::new(entry.modifier.label()).strong().color(label_color));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("\u{2715}").on_hover_text("Remove").clicked() {
+                        card_action = Some(ModifierCardAction::Remove(idx));
+                    }
+                    if ui
+                        .small_button("Apply")
+                        .on_hover_text("Bake this and the modifiers above it into the mesh")
+                        .clicked()
+                    {
+                        card_action = Some(ModifierCardAction::Apply(idx));
+                    }
+                    if idx + 1 < count && ui.small_button("\u{25bc}").clicked() {
+                        card_action = Some(ModifierCardAction::MoveDown(idx));
+                    }
+                    if idx > 0 && ui.small_button("\u{25b2}").clicked() {
+                        card_action = Some(ModifierCardAction::MoveUp(idx));
+                    }
+                });
+            });
+            result.changed |= draw_modifier_params(ui, idx, &mut entry.modifier);
+        });
+        ui.add_space(4.0);
+    }
+
+    match card_action {
+        Some(ModifierCardAction::MoveUp(idx)) => {
+            stack.modifiers.swap(idx, idx - 1);
+            result.changed = true;
+        }
+        Some(ModifierCardAction::MoveDown(idx)) => {
+            stack.modifiers.swap(idx, idx + 1);
+            result.changed = true;
+        }
+        Some(ModifierCardAction::Apply(idx)) => {
+            result.apply_through = Some(idx);
+            result.changed = true;
+        }
+        Some(ModifierCardAction::Remove(idx)) => {
+            stack.modifiers.remove(idx);
+            result.changed = true;
+        }
+        None => {}
+    }
+
+    ui.horizontal(|ui| {
+        ui.menu_button(
+            egui::RichText::new("+ Add Modifier").color(colors::TEXT_SECONDARY),
+            |ui| {
+                for (variant_idx, name) in MeshModifier::VARIANT_LABELS.iter().enumerate() {
+                    if ui.button(*name).clicked() {
+                        stack
+                            .modifiers
+                            .push(ModifierEntry::new(MeshModifier::from_variant_index(variant_idx)));
+                        result.changed = true;
+                        ui.close();
+                    }
+                }
+            },
+        );
+        if !stack.modifiers.is_empty()
+            && ui
+                .button(egui::RichText::new("Apply All").color(colors::TEXT_SECONDARY))
+                .clicked()
+        {
+            result.apply_through = Some(stack.modifiers.len() - 1);
+            result.changed = true;
+        }
+    });
+}
+
+/// Parameter widgets for one modifier. Returns true if anything changed.
+fn draw_modifier_params(ui: &mut egui::Ui, idx: usize, modifier: &mut MeshModifier) -> bool {
+    let mut changed = false;
+    match modifier {
+        MeshModifier::Mirror { axis } => {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Axis").color(colors::TEXT_SECONDARY));
+                for option in [MirrorAxis::X, MirrorAxis::Y, MirrorAxis::Z] {
+                    let selected = *axis == option;
+                    if ui.selectable_label(selected, option.display_name()).clicked() && !selected {
+                        *axis = option;
+                        changed = true;
+                    }
+                }
+            });
+        }
+        MeshModifier::Smooth { iterations, factor } => {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Iterations").color(colors::TEXT_SECONDARY));
+                changed |= ui
+                    .add(egui::DragValue::new(iterations).range(1..=20))
+                    .changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Factor").color(colors::TEXT_SECONDARY));
+                changed |= value_slider(ui, factor, 0.1..=1.0);
+            });
+        }
+        MeshModifier::Subdivide { levels } | MeshModifier::CatmullClark { levels } => {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Levels").color(colors::TEXT_SECONDARY));
+                changed |= ui
+                    .add(egui::DragValue::new(levels).range(1..=MAX_SUBDIVISION_LEVELS))
+                    .changed();
+            });
+        }
+        MeshModifier::Weld { threshold } => {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Threshold").color(colors::TEXT_SECONDARY));
+                changed |= value_slider(ui, threshold, 0.001..=1.0);
+            });
+        }
+        MeshModifier::Simplify { ratio } => {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Ratio").color(colors::TEXT_SECONDARY));
+                changed |= value_slider(ui, ratio, 0.05..=0.95);
+            });
+        }
+        MeshModifier::Remesh { edge_length } => {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Edge Length").color(colors::TEXT_SECONDARY));
+                changed |= value_slider(ui, edge_length, 0.05..=2.0);
+            });
+        }
+        MeshModifier::AutoSmooth { angle } => {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Angle").color(colors::TEXT_SECONDARY));
+                changed |= value_slider(ui, angle, 0.0..=180.0);
+                ui.label(egui::RichText::new("°").color(colors::TEXT_MUTED));
+            });
+        }
+        MeshModifier::Boolean { op, target } => {
+            ui.horizontal(|ui| {
+                for option in [BooleanOp::Union, BooleanOp::Subtract, BooleanOp::Intersect] {
+                    let selected = *op == option;
+                    if ui.selectable_label(selected, option.display_name()).clicked() && !selected {
+                        *op = option;
+                        changed = true;
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Target").color(colors::TEXT_SECONDARY));
+                changed |= ui
+                    .add(
+                        egui::TextEdit::singleline(target)
+                            .id(egui::Id::new(("modifier_boolean_target", idx)))
+                            .hint_text("Entity name"),
+                    )
+                    .changed();
+            });
+        }
+        MeshModifier::FillHoles | MeshModifier::FlatNormals => {}
+    }
+    changed
+}
+
+/// Write the edited stack back to `entity`, recording undo first and baking
+/// applied modifiers into the base mesh.
+fn apply_modifier_changes(
+    world: &mut World,
+    entity: Entity,
+    mut stack: MeshModifierStack,
+    result: &ModifierPanelResult,
+) {
+    if !result.changed {
+        world.resource_mut::<ModifierPanelState>().needs_snapshot = true;
+        return;
+    }
+
+    if world.resource::<ModifierPanelState>().needs_snapshot {
+        RecordEntitiesCommand {
+            entities: vec![entity],
+            recursive: false,
+            description: "Edit modifiers".to_string(),
+        }
+        .apply(world);
+        world.resource_mut::<ModifierPanelState>().needs_snapshot = false;
+    }
+
+    // The stack needs a stored base mesh to evaluate on top of
+    let base = match world.get::<EditMeshMarker>(entity) {
+        Some(marker) => marker.to_edit_mesh(),
+        None => {
+            let Some(edit_mesh) = world.resource::<MeshModelState>().edit_mesh.clone() else {
+                return;
+            };
+            edit_mesh
+        }
+    };
+
+    let base = match result.apply_through {
+        Some(idx) => {
+            let operands = collect_boolean_operands(world, entity, &stack);
+            let baked = stack.apply_through(idx, &base, &operands);
+            let mut model_state = world.resource_mut::<MeshModelState>();
+            model_state.half_edge_mesh = Some(HalfEdgeMesh::from_edit_mesh(&baked));
+            model_state.edit_mesh = Some(baked.clone());
+            model_state.selected_faces.clear();
+            model_state.element_selection.clear();
+            baked
+        }
+        None => base,
+    };
+
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if result.apply_through.is_some() || !entity_mut.contains::<EditMeshMarker>() {
+        entity_mut.insert(EditMeshMarker::from_edit_mesh(&base));
+    }
+    if stack.modifiers.is_empty() {
+        // Nothing left to evaluate; show the plain base mesh
+        entity_mut.remove::<MeshModifierStack>();
+        rebuild_evaluated_mesh(world, entity);
+    } else {
+        entity_mut.insert(stack);
+    }
+}
+
+/// Slot list for per-face materials: pick the active slot and each extra
+/// slot's library material. Returns true if the slots changed.
+fn draw_material_slots(
+    ui: &mut egui::Ui,
+    slots: &mut MaterialSlots,
+    active_slot: &mut u32,
+    material_names: &[String],
+) -> bool {
+    let mut changed = false;
+
+    // Slot 0 always renders with the entity's own material
+    ui.horizontal(|ui| {
+        if ui.selectable_label(*active_slot == 0, "Base").clicked() {
+            *active_slot = 0;
+        }
+        ui.label(egui::RichText::new("Entity material").small().color(colors::TEXT_MUTED));
+    });
+
+    let count = slots.slots.len();
+    let mut remove_last = false;
+    for (idx, material) in slots.slots.iter_mut().enumerate() {
+        let slot = idx as u32 + 1;
+        ui.horizontal(|ui| {
+            if ui
+                .selectable_label(*active_slot == slot, format!("Slot {}", slot))
+                .clicked()
+            {
+                *active_slot = slot;
+            }
+            let current = match material {
+                MaterialRef::Library(name) => name.clone(),
+                MaterialRef::Inline(_) => "Default".to_string(),
+            };
+            egui::ComboBox::from_id_salt(("material_slot", idx))
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for name in material_names {
+                        let selected = matches!(&*material, MaterialRef::Library(n) if n == name);
+                        if ui.selectable_label(selected, name).clicked() && !selected {
+                            *material = MaterialRef::Library(name.clone());
+                            changed = true;
+                        }
+                    }
+                });
+            // Only the last slot can go, so face assignments never shift
+            if idx + 1 == count
+                && ui.small_button("\u{2715}").on_hover_text("Remove").clicked()
+            {
+                remove_last = true;
+            }
+        });
+    }
+
+    if remove_last {
+        slots.slots.pop();
+        *active_slot = (*active_slot).min(slots.slots.len() as u32);
+        changed = true;
+    }
+
+    if ui
+        .button(egui::RichText::new("+ Slot").color(colors::TEXT_SECONDARY))
+        .clicked()
+    {
+        slots.slots.push(MaterialRef::default());
+        *active_slot = slots.slots.len() as u32;
+        changed = true;
+    }
+
+    changed
+}
+
+/// Write edited material slots to `entity` and rebuild its slot meshes.
+fn apply_material_slot_changes(world: &mut World, entity: Entity, slots: MaterialSlots) {
+    RecordEntitiesCommand {
+        entities: vec![entity],
+        recursive: false,
+        description: "Edit material slots".to_string(),
+    }
+    .apply(world);
+
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    entity_mut.insert(slots);
+    rebuild_evaluated_mesh(world, entity);
+}
+
 /// Lightweight snapshot of MeshModelState for UI rendering.
 /// Avoids borrowing the full resource (which contains `EditMesh`) through egui.
 pub struct PanelSnapshot {
@@ -710,6 +1285,15 @@ pub struct PanelSnapshot {
     pub soft_selection: bool,
     pub soft_radius: f32,
     pub soft_falloff: FalloffCurve,
+    pub material_slot: u32,
+    // Brush tools
+    pub model_tool: ModelTool,
+    pub brush_radius: f32,
+    pub brush_strength: f32,
+    pub paint_color: Vec4,
+    pub paint_channel: PaintChannel,
+    pub sculpt_brush: SculptBrush,
+    pub sculpt_symmetry: bool,
 }
 
 impl MeshModelState {
@@ -728,8 +1312,13 @@ impl MeshModelState {
             push_pull_distance: self.push_pull_distance,
             weld_threshold: self.weld_threshold,
             target_entity: self.target_entity,
-            edit_mesh_face_count: self.edit_mesh.as_ref().map(|m| m.face_count()),
-            selected_faces_count: self.selected_faces.len(),
+            edit_mesh_face_count: self.edit_mesh.as_ref().map(|m| m.polygon_count()),
+            selected_faces_count: self
+                .edit_mesh
+                .as_ref()
+                .map_or(self.selected_faces.len(), |m| {
+                    m.selected_polygons(&self.selected_faces).len()
+                }),
             selected_faces_empty: self.selected_faces.is_empty(),
             element_selection_count: self.element_selection.count(),
             element_selection_empty: self.element_selection.is_empty(),
@@ -752,6 +1341,14 @@ impl MeshModelState {
             soft_selection: self.soft_selection,
             soft_radius: self.soft_radius,
             soft_falloff: self.soft_falloff,
+            material_slot: self.material_slot,
+            model_tool: self.model_tool,
+            brush_radius: self.brush_radius,
+            brush_strength: self.brush_strength,
+            paint_color: self.paint_color,
+            paint_channel: self.paint_channel,
+            sculpt_brush: self.sculpt_brush,
+            sculpt_symmetry: self.sculpt_symmetry,
         }
     }
 }
@@ -4,10 +4,12 @@ mod command_palette;
 mod component_browser;
 mod edit_info;
 mod effect_editor;
+mod effect_timeline;
 pub mod fuzzy_palette;
 pub mod gltf_preview;
 pub mod prefab_preview;
 mod hierarchy;
+mod history;
 pub mod insert_preview;
 pub mod preview_common;
 mod inspector;
@@ -18,6 +20,7 @@ mod mesh_model_panel;
 mod panels;
 mod vfx_editor;
 mod reflect_editor;
+mod scene_tabs;
 mod settings;
 pub mod theme;
 mod toolbar;
@@ -33,6 +36,7 @@ pub use fuzzy_palette::{
     PaletteItem, PaletteResult, PaletteState, SimpleItem,
 };
 pub use hierarchy::*;
+pub use history::*;
 pub use inspector::*;
 pub use marks::*;
 pub use panels::*;
@@ -91,12 +95,15 @@ impl Plugin for UiPlugin {
                 prefab_preview::PrefabPreviewPlugin,
                 ViewGizmoPlugin,
                 EditInfoPlugin,
+                scene_tabs::SceneTabsPlugin,
+                effect_timeline::EffectTimelinePlugin,
             ))
             // Popups and dialogs - modal UI elements
             .add_plugins((
                 CommandPalettePlugin,
                 ComponentBrowserPlugin,
                 MarksPlugin,
+                HistoryPanelPlugin,
                 uv_editor_panel::UvEditorPlugin,
             ))
             // Validation
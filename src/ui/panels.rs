@@ -403,6 +403,8 @@ fn get_hints_for_mode(
             ("1-4", "Grid"),
             ("Click", "Select"),
             ("X", "X-ray"),
+            ("C", "Paint"),
+            ("K", "Sculpt"),
             ("Q", "Extrude"),
             ("W", "Cut"),
             ("Enter", "Apply"),
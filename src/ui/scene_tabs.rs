@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::editor::EditorState;
+use crate::scene::{
+    CloseSceneTabEvent, CloseTabConfirmDialog, OpenSceneTabEvent, SceneFile, SceneWorkspace,
+    SwitchSceneTabEvent,
+};
+use crate::ui::hierarchy::icons;
+use crate::ui::theme::{colors, draw_centered_dialog, DialogResult};
+
+pub struct SceneTabsPlugin;
+
+impl Plugin for SceneTabsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            EguiPrimaryContextPass,
+            (draw_scene_tabs, draw_close_tab_confirm_dialog),
+        );
+    }
+}
+
+/// Draw the tab bar above the viewport when more than one scene is open
+fn draw_scene_tabs(
+    mut contexts: EguiContexts,
+    workspace: Res<SceneWorkspace>,
+    scene_file: Res<SceneFile>,
+    editor_state: Res<EditorState>,
+    mut open_events: MessageWriter<OpenSceneTabEvent>,
+    mut switch_events: MessageWriter<SwitchSceneTabEvent>,
+    mut close_events: MessageWriter<CloseSceneTabEvent>,
+) -> Result {
+    // Don't draw UI when editor is disabled
+    if !editor_state.ui_enabled {
+        return Ok(());
+    }
+
+    if workspace.tabs().len() < 2 {
+        return Ok(());
+    }
+
+    let ctx = contexts.ctx_mut()?;
+
+    egui::TopBottomPanel::top("scene_tabs")
+        .frame(
+            egui::Frame::side_top_panel(&ctx.style())
+                .fill(colors::BG_DARK)
+                .inner_margin(egui::Margin::symmetric(8, 4)),
+        )
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for (index, tab) in workspace.tabs().iter().enumerate() {
+                    let is_active = index == workspace.active();
+                    // The active tab's state lives in SceneFile
+                    let (name, modified) = if is_active {
+                        (scene_file.display_name(), scene_file.modified)
+                    } else {
+                        (tab.display_name(), tab.modified)
+                    };
+
+                    let label = if modified {
+                        format!("{} {} {}", icons::FILE, name, icons::DOT)
+                    } else {
+                        format!("{} {}", icons::FILE, name)
+                    };
+                    let text_color = if is_active {
+                        colors::TEXT_PRIMARY
+                    } else {
+                        colors::TEXT_SECONDARY
+                    };
+
+                    let response =
+                        ui.selectable_label(is_active, egui::RichText::new(label).color(text_color));
+                    if let Some(path) = &tab.path {
+                        response.clone().on_hover_text(path);
+                    }
+                    if response.clicked() {
+                        switch_events.write(SwitchSceneTabEvent { index });
+                    }
+                    if response.middle_clicked()
+                        || ui
+                            .small_button(egui::RichText::new("x").color(colors::TEXT_MUTED))
+                            .on_hover_text("Close tab")
+                            .clicked()
+                    {
+                        close_events.write(CloseSceneTabEvent { index });
+                    }
+
+                    ui.separator();
+                }
+
+                if ui
+                    .small_button(egui::RichText::new("+").strong().color(colors::ACCENT_GREEN))
+                    .on_hover_text("New scene tab")
+                    .clicked()
+                {
+                    open_events.write(OpenSceneTabEvent { path: None });
+                }
+            });
+        });
+
+    Ok(())
+}
+
+/// Draw the confirmation dialog when closing a tab with unsaved changes
+fn draw_close_tab_confirm_dialog(
+    mut contexts: EguiContexts,
+    mut confirm_dialog: ResMut<CloseTabConfirmDialog>,
+    mut workspace: ResMut<SceneWorkspace>,
+    mut scene_file: ResMut<SceneFile>,
+    mut close_events: MessageWriter<CloseSceneTabEvent>,
+) -> Result {
+    if !confirm_dialog.open {
+        return Ok(());
+    }
+
+    let index = confirm_dialog.index;
+    let Some(tab) = workspace.tabs().get(index) else {
+        confirm_dialog.open = false;
+        return Ok(());
+    };
+    let is_active = index == workspace.active();
+    let name = if is_active {
+        scene_file.display_name().to_string()
+    } else {
+        tab.display_name().to_string()
+    };
+
+    let ctx = contexts.ctx_mut()?;
+
+    let result = draw_centered_dialog(ctx, "Unsaved Changes", [340.0, 110.0], |ui| {
+        let mut result = DialogResult::None;
+        ui.vertical_centered(|ui| {
+            ui.add_space(8.0);
+            ui.label(
+                egui::RichText::new(format!(
+                    "\"{}\" has unsaved changes.\nClose it anyway?",
+                    name
+                ))
+                .color(colors::TEXT_PRIMARY),
+            );
+            ui.add_space(16.0);
+            ui.horizontal(|ui| {
+                ui.add_space(90.0);
+                if ui
+                    .button(egui::RichText::new("Discard").color(colors::STATUS_WARNING))
+                    .clicked()
+                {
+                    result = DialogResult::Confirmed;
+                }
+                ui.add_space(8.0);
+                if ui.button("Cancel").clicked() {
+                    result = DialogResult::Close;
+                }
+            });
+        });
+        result
+    });
+
+    match result {
+        DialogResult::Confirmed => {
+            confirm_dialog.open = false;
+            // Clear the modified flag so the re-sent event proceeds
+            if is_active {
+                scene_file.clear_modified();
+            } else {
+                workspace.discard_changes(index);
+            }
+            close_events.write(CloseSceneTabEvent { index });
+        }
+        DialogResult::Close => {
+            confirm_dialog.open = false;
+        }
+        DialogResult::None => {}
+    }
+
+    Ok(())
+}
@@ -95,6 +95,9 @@ pub struct Settings {
     /// Gizmo appearance settings
     #[serde(default)]
     pub gizmos: GizmoSettings,
+    /// Save scenes keyed by persistent entity IDs in a deterministic order
+    #[serde(default = "default_stable_scene_ids")]
+    pub stable_scene_ids: bool,
 }
 
 fn default_show_hints() -> bool {
@@ -105,6 +108,10 @@ fn default_undo_history_size() -> usize {
     50
 }
 
+fn default_stable_scene_ids() -> bool {
+    true
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
@@ -117,6 +124,7 @@ impl Default for Settings {
             fonts: FontSettings::default(),
             show_hints: true,
             gizmos: GizmoSettings::default(),
+            stable_scene_ids: true,
         }
     }
 }
@@ -459,6 +467,24 @@ fn draw_settings_window(
                 });
             });
 
+            // Files Section
+            section_header(ui, "Files", true, |ui| {
+            egui::Grid::new("settings_files_grid")
+                .num_columns(2)
+                .spacing(GRID_SPACING)
+                .show(ui, |ui| {
+                    grid_label(ui, "Stable Scene IDs");
+                    if ui
+                        .checkbox(&mut settings.stable_scene_ids, "")
+                        .on_hover_text("Save entities by persistent ID in a fixed order, for clean diffs and merges")
+                        .changed()
+                    {
+                        settings.save();
+                    }
+                    ui.end_row();
+                });
+            });
+
             ui.add_space(8.0);
             ui.separator();
             ui.add_space(4.0);
@@ -5,13 +5,17 @@
 //! - **Init**: green accent
 //! - **Update**: blue accent
 //! - **Render**: purple accent
+//! - **Parameters / Bindings**: cyan accent
 
+use avian3d::prelude::{ColliderAabb, ColliderOf, RigidBody};
 use bevy::prelude::*;
 use bevy_editor_game::MaterialLibrary;
 use bevy_egui::{egui, EguiPrimaryContextPass};
 use bevy_vfx::curve::{Curve, CurveKey, Gradient, GradientKey, Interp};
 use bevy_vfx::data::*;
+use bevy_vfx::field::{VectorField, VfxFieldCache};
 use bevy_vfx::mesh_particles::MeshParticleStates;
+use bevy_vfx::{static_collider_boxes, SceneColliderFilter};
 
 use crate::editor::{EditorMode, EditorState, PanelSide, PinnedWindows};
 use crate::selection::Selected;
@@ -23,7 +27,7 @@ pub struct VfxEditorPlugin;
 impl Plugin for VfxEditorPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(EguiPrimaryContextPass, draw_vfx_panel)
-            .add_systems(Update, vfx_restart_keybinding);
+            .add_systems(Update, (vfx_restart_keybinding, draw_vfx_field_gizmos));
     }
 }
 
@@ -49,6 +53,74 @@ fn vfx_restart_keybinding(
     }
 }
 
+/// Draw bounds and flow arrows for the field modules of the selected VFX
+/// system. `ConformToSdf` fields show surface normals near the level set.
+fn draw_vfx_field_gizmos(
+    mut gizmos: Gizmos,
+    mode: Res<State<EditorMode>>,
+    editor_state: Res<EditorState>,
+    field_cache: Res<VfxFieldCache>,
+    field_assets: Res<Assets<VectorField>>,
+    selected: Query<(&VfxSystem, &GlobalTransform), With<Selected>>,
+) {
+    if *mode.get() != EditorMode::Particle || !editor_state.gizmos_visible {
+        return;
+    }
+
+    for (system, transform) in &selected {
+        for emitter in system.emitters.iter().filter(|e| e.enabled) {
+            // Local fields are sampled relative to the emitter position
+            let origin = match emitter.sim_space {
+                SimSpace::Local => transform.translation(),
+                SimSpace::World => Vec3::ZERO,
+            };
+            for module in &emitter.update {
+                let (path, surface, color) = match module {
+                    UpdateModule::VectorField { path, .. } => (path, None, Color::srgba(0.3, 0.7, 1.0, 0.7)),
+                    UpdateModule::ConformToSdf { path, surface, .. } => {
+                        (path, Some(*surface), Color::srgba(1.0, 0.6, 0.2, 0.7))
+                    }
+                    _ => continue,
+                };
+                let Some(field) = field_cache.field(path, &field_assets) else {
+                    continue;
+                };
+
+                let size = field.max - field.min;
+                gizmos.cube(
+                    Transform::from_translation(origin + field.min + size * 0.5).with_scale(size),
+                    color.with_alpha(0.3),
+                );
+
+                // Sparse sample grid so dense fields stay readable
+                let steps = field.resolution.min(UVec3::splat(8)).max(UVec3::ONE);
+                let cell = size / steps.as_vec3();
+                let arrow_len = cell.min_element() * 0.8;
+                for x in 0..steps.x {
+                    for y in 0..steps.y {
+                        for z in 0..steps.z {
+                            let p = field.min + (UVec3::new(x, y, z).as_vec3() + 0.5) * cell;
+                            let dir = match surface {
+                                Some(surface) => {
+                                    if (field.sample(p).w - surface).abs() > cell.max_element() {
+                                        continue;
+                                    }
+                                    field.distance_gradient(p)
+                                }
+                                None => field.sample(p).truncate().clamp_length_max(1.0),
+                            };
+                            if dir.length_squared() < 1e-6 {
+                                continue;
+                            }
+                            gizmos.arrow(origin + p, origin + p + dir * arrow_len, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Card / section drawing helpers
 // ---------------------------------------------------------------------------
@@ -121,6 +193,23 @@ struct GradientDragState {
     color_picker_key: Option<usize>,
 }
 
+/// Per-module vector field bake settings stored in egui temp memory.
+#[derive(Clone, Copy)]
+struct FieldBakeSettings {
+    /// Half size of the baked volume around the emitter.
+    extent: Vec3,
+    /// Ambient flow deflected around the scene geometry.
+    flow: Vec3,
+    /// Grid spacing in world units.
+    cell: f32,
+}
+
+impl Default for FieldBakeSettings {
+    fn default() -> Self {
+        Self { extent: Vec3::splat(4.0), flow: Vec3::Y, cell: 0.25 }
+    }
+}
+
 /// Per-scale3d axis linking state stored in egui temp memory.
 #[derive(Clone, Copy, Default)]
 struct AxisLinkState {
@@ -692,6 +781,9 @@ fn draw_vfx_panel(world: &mut World) {
                     ui.end_row();
                 });
 
+            // Exposed parameters (cyan)
+            draw_params_section(ui, &mut system.params);
+
             ui.add_space(4.0);
             ui.separator();
 
@@ -786,11 +878,11 @@ fn draw_vfx_panel(world: &mut World) {
             if let Some(idx) = duplicate_emitter {
                 let mut dup = system.emitters[idx].clone();
                 dup.name = format!("{} (Copy)", dup.name.trim_end_matches(" (Copy)"));
-                system.emitters.insert(idx + 1, dup);
+                system.insert_emitter(idx + 1, dup);
                 selected_emitter = idx + 1;
             }
             if let Some(idx) = remove_emitter {
-                system.emitters.remove(idx);
+                system.remove_emitter(idx);
                 if selected_emitter >= system.emitters.len() {
                     selected_emitter = system.emitters.len().saturating_sub(1);
                 }
@@ -800,6 +892,8 @@ fn draw_vfx_panel(world: &mut World) {
             ui.separator();
 
             // Selected emitter detail editor
+            let param_names: Vec<String> = system.params.iter().map(|p| p.name.clone()).collect();
+            let emitter_names: Vec<String> = system.emitters.iter().map(|e| e.name.clone()).collect();
             if let Some(emitter) = system.emitters.get_mut(selected_emitter) {
                 egui::ScrollArea::vertical()
                     .auto_shrink([false, false])
@@ -826,6 +920,12 @@ fn draw_vfx_panel(world: &mut World) {
                         // Update section (blue)
                         draw_update_section(ui, &mut emitter.update);
 
+                        // Parameter bindings (cyan)
+                        draw_bindings_section(ui, &mut emitter.bindings, &param_names);
+
+                        // Sub-emitter links (orange)
+                        draw_sub_emitters_section(ui, &mut emitter.sub_emitters, &emitter_names, selected_emitter);
+
                         // Render section (purple)
                         if draw_render_section(ui, &mut emitter.render, &material_names) {
                             ui.ctx().memory_mut(|mem| {
@@ -869,6 +969,14 @@ fn draw_vfx_panel(world: &mut World) {
             .open_mesh_shape_picker();
     }
 
+    // Check for vector field bake request — bake, save and point the module at it
+    let bake_request = ctx.memory_mut(|mem| {
+        mem.data.remove_temp::<(usize, FieldBakeSettings)>(egui::Id::new("vfx_field_bake_request"))
+    });
+    if let Some((module_idx, settings)) = bake_request {
+        bake_field_for_module(world, entity, &mut system, selected_emitter, module_idx, settings);
+    }
+
     // Write back if changed
     let changed = ron::to_string(&system).ok() != ron::to_string(&original).ok();
     if changed {
@@ -1584,9 +1692,190 @@ fn draw_update_body(ui: &mut egui::Ui, m: &mut UpdateModule, idx: usize) {
         UpdateModule::EmissiveOverLife(gradient) => {
             draw_interactive_gradient(ui, gradient, idx);
         }
+        UpdateModule::Collide { shapes, scene_colliders, radius, bounce, friction, kill_on_hit } => {
+            let mut remove = None;
+            for (i, shape) in shapes.iter_mut().enumerate() {
+                let mut type_idx = shape.variant_index();
+                ui.horizontal(|ui| {
+                    grid_label(ui, "Shape");
+                    egui::ComboBox::from_id_salt(format!("collide_shape_{idx}_{i}"))
+                        .selected_text(CollisionShape::VARIANT_LABELS[type_idx])
+                        .show_ui(ui, |ui| {
+                            for (t, label) in CollisionShape::VARIANT_LABELS.iter().enumerate() {
+                                ui.selectable_value(&mut type_idx, t, *label);
+                            }
+                        });
+                    if ui.small_button(egui::RichText::new("X").color(colors::STATUS_ERROR)).clicked() {
+                        remove = Some(i);
+                    }
+                });
+                if type_idx != shape.variant_index() {
+                    *shape = CollisionShape::from_variant_index(type_idx);
+                }
+                match shape {
+                    CollisionShape::Plane { point, normal } => {
+                        draw_vec3_grid(ui, "Point", point, 0.1, idx, &format!("col_p_{i}"));
+                        draw_vec3_grid(ui, "Normal", normal, 0.01, idx, &format!("col_n_{i}"));
+                    }
+                    CollisionShape::Sphere { center, radius } => {
+                        draw_vec3_grid(ui, "Center", center, 0.1, idx, &format!("col_c_{i}"));
+                        egui::Grid::new(format!("col_r_{idx}_{i}")).num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                            grid_label(ui, "Radius");
+                            ui.add(egui::DragValue::new(radius).speed(0.1).range(0.0..=f32::MAX).max_decimals(3));
+                            ui.end_row();
+                        });
+                    }
+                    CollisionShape::Box { center, half_extents } => {
+                        draw_vec3_grid(ui, "Center", center, 0.1, idx, &format!("col_bc_{i}"));
+                        draw_vec3_grid(ui, "Half Size", half_extents, 0.1, idx, &format!("col_bhs_{i}"));
+                    }
+                }
+            }
+            if let Some(i) = remove {
+                shapes.remove(i);
+            }
+            if ui.button(egui::RichText::new("+ Shape").color(colors::ACCENT_GREEN)).clicked() {
+                shapes.push(CollisionShape::default());
+            }
+            egui::Grid::new(format!("collide_{idx}")).num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                grid_label(ui, "Radius");
+                ui.add(egui::DragValue::new(radius).speed(0.01).range(0.0..=10.0).max_decimals(3));
+                ui.end_row();
+                grid_label(ui, "Bounce");
+                ui.add(egui::DragValue::new(bounce).speed(0.01).range(0.0..=1.0).max_decimals(2));
+                ui.end_row();
+                grid_label(ui, "Friction");
+                ui.add(egui::DragValue::new(friction).speed(0.01).range(0.0..=1.0).max_decimals(2));
+                ui.end_row();
+            });
+            ui.checkbox(scene_colliders, "Scene colliders (GPU only)");
+            ui.checkbox(kill_on_hit, "Kill on hit");
+        }
+        UpdateModule::VectorField { path, strength, tightness } => {
+            egui::Grid::new(format!("vector_field_{idx}")).num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                grid_label(ui, "Field");
+                ui.text_edit_singleline(path).on_hover_text("Asset path of a .vfield or .vfield.ron file");
+                ui.end_row();
+                grid_label(ui, "Strength");
+                ui.add(egui::DragValue::new(strength).speed(0.1).max_decimals(3));
+                ui.end_row();
+                grid_label(ui, "Tightness");
+                ui.add(egui::DragValue::new(tightness).speed(0.01).range(0.0..=1.0).max_decimals(2));
+                ui.end_row();
+            });
+            draw_field_bake_row(ui, idx);
+        }
+        UpdateModule::ConformToSdf { path, surface, attraction, damping } => {
+            egui::Grid::new(format!("conform_sdf_{idx}")).num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                grid_label(ui, "Field");
+                ui.text_edit_singleline(path).on_hover_text("Asset path of a field with baked distances");
+                ui.end_row();
+                grid_label(ui, "Surface");
+                ui.add(egui::DragValue::new(surface).speed(0.01).max_decimals(3));
+                ui.end_row();
+                grid_label(ui, "Attraction");
+                ui.add(egui::DragValue::new(attraction).speed(0.1).range(0.0..=f32::MAX).max_decimals(3));
+                ui.end_row();
+                grid_label(ui, "Damping");
+                ui.add(egui::DragValue::new(damping).speed(0.1).range(0.0..=f32::MAX).max_decimals(3));
+                ui.end_row();
+            });
+            draw_field_bake_row(ui, idx);
+        }
+    }
+}
+
+/// Bake static scene colliders around the emitter into a field asset and set
+/// it as the path of update module `module_idx`.
+fn bake_field_for_module(
+    world: &mut World,
+    entity: Entity,
+    system: &mut VfxSystem,
+    emitter_idx: usize,
+    module_idx: usize,
+    settings: FieldBakeSettings,
+) {
+    let Some(emitter) = system.emitters.get_mut(emitter_idx) else {
+        return;
+    };
+    let translation = world
+        .get::<GlobalTransform>(entity)
+        .map(|t| t.translation())
+        .unwrap_or_default();
+    // Local fields are sampled relative to the emitter position
+    let origin = match emitter.sim_space {
+        SimSpace::Local => translation,
+        SimSpace::World => Vec3::ZERO,
+    };
+    let center = translation - origin;
+    let extent = settings.extent.max(Vec3::splat(settings.cell));
+
+    // Same static geometry the GPU scene collision sees
+    let mut colliders = world.query_filtered::<(&ColliderAabb, &ColliderOf), SceneColliderFilter>();
+    let world_ref: &World = world;
+    let boxes: Vec<(Vec3, Vec3)> = static_collider_boxes(colliders.iter(world_ref), |body| {
+        world_ref.get::<RigidBody>(body).copied()
+    })
+    .into_iter()
+    .map(|(min, max)| (min - origin, max - origin))
+    .collect();
+
+    let resolution = (extent * 2.0 / settings.cell).as_uvec3() + UVec3::ONE;
+    let field = VectorField::bake_from_boxes(
+        center - extent,
+        center + extent,
+        resolution.clamp(UVec3::splat(2), UVec3::splat(64)),
+        &boxes,
+        settings.flow,
+        settings.cell * 4.0,
+    );
+
+    let name = world
+        .get::<Name>(entity)
+        .map(|n| n.as_str().to_string())
+        .unwrap_or_else(|| "vfx".to_string());
+    let Some(path) = crate::vfx::save_vector_field(&format!("{}_{}_{}", name, emitter.name, module_idx), &field) else {
+        return;
+    };
+    // Re-baking overwrites the same file, so make sure a loaded copy refreshes
+    world.resource::<AssetServer>().reload(path.clone());
+    info!("Baked vector field from {} colliders to '{}'", boxes.len(), path);
+
+    match emitter.update.get_mut(module_idx) {
+        Some(UpdateModule::VectorField { path: module_path, .. })
+        | Some(UpdateModule::ConformToSdf { path: module_path, .. }) => *module_path = path,
+        _ => {}
     }
 }
 
+/// Bake settings and "Bake from Scene" button shared by the field modules.
+/// The bake itself runs in `draw_vfx_panel`, which has world access.
+fn draw_field_bake_row(ui: &mut egui::Ui, idx: usize) {
+    let id = egui::Id::new(("vfx_field_bake", idx));
+    let mut settings: FieldBakeSettings = ui.ctx().data(|d| d.get_temp(id).unwrap_or_default());
+    egui::CollapsingHeader::new(egui::RichText::new("Bake").small().color(colors::TEXT_SECONDARY))
+        .id_salt(id)
+        .show(ui, |ui| {
+            draw_vec3_grid(ui, "Extent", &mut settings.extent, 0.1, idx, "field_extent");
+            draw_vec3_grid(ui, "Flow", &mut settings.flow, 0.1, idx, "field_flow");
+            egui::Grid::new(format!("field_cell_{idx}")).num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                grid_label(ui, "Cell Size");
+                ui.add(egui::DragValue::new(&mut settings.cell).speed(0.01).range(0.05..=4.0).max_decimals(2));
+                ui.end_row();
+            });
+            if ui
+                .button(egui::RichText::new("Bake from Scene").color(colors::ACCENT_BLUE))
+                .on_hover_text("Bake static collider bounds around the emitter into a field asset")
+                .clicked()
+            {
+                ui.ctx().data_mut(|d| {
+                    d.insert_temp(egui::Id::new("vfx_field_bake_request"), (idx, settings));
+                });
+            }
+        });
+    ui.ctx().data_mut(|d| d.insert_temp(id, settings));
+}
+
 // ---------------------------------------------------------------------------
 // Render section (purple)
 // ---------------------------------------------------------------------------
@@ -1797,6 +2086,205 @@ fn draw_mesh_config(
     open_shape_picker
 }
 
+// ---------------------------------------------------------------------------
+// Parameters & bindings (cyan)
+// ---------------------------------------------------------------------------
+
+fn draw_params_section(ui: &mut egui::Ui, params: &mut Vec<VfxParam>) {
+    let options: &[(&str, fn() -> VfxParam)] = &[
+        ("Float", || VfxParam { name: "param".into(), value: VfxParamValue::from_variant_index(0) }),
+        ("Vec3", || VfxParam { name: "param".into(), value: VfxParamValue::from_variant_index(1) }),
+        ("Color", || VfxParam { name: "param".into(), value: VfxParamValue::from_variant_index(2) }),
+        ("Curve", || VfxParam { name: "param".into(), value: VfxParamValue::from_variant_index(3) }),
+    ];
+    category_header(ui, "PARAMETERS", colors::ACCENT_CYAN, options, params);
+    let mut remove = None;
+    for (i, param) in params.iter_mut().enumerate() {
+        let id = ui.id().with("vfx_param").with(i);
+        if modifier_card(ui, param.value.label(), colors::ACCENT_CYAN, id, |ui| {
+            ui.horizontal(|ui| {
+                grid_label(ui, "Name");
+                ui.text_edit_singleline(&mut param.name);
+            });
+            draw_param_value(ui, &mut param.value, &format!("vfx_param_{i}"));
+        }) {
+            remove = Some(i);
+        }
+    }
+    if let Some(idx) = remove {
+        params.remove(idx);
+    }
+}
+
+fn draw_bindings_section(ui: &mut egui::Ui, bindings: &mut Vec<ParamBinding>, param_names: &[String]) {
+    category_header(
+        ui,
+        "BINDINGS",
+        colors::ACCENT_CYAN,
+        &[("Binding", ParamBinding::default)],
+        bindings,
+    );
+    let mut remove = None;
+    for (i, binding) in bindings.iter_mut().enumerate() {
+        let id = ui.id().with("vfx_binding").with(i);
+        if modifier_card(ui, binding.target.label(), colors::ACCENT_CYAN, id, |ui| {
+            egui::Grid::new(format!("vfx_binding_{i}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "Param");
+                    let missing = !param_names.contains(&binding.param);
+                    let text = if binding.param.is_empty() {
+                        egui::RichText::new("(none)").color(colors::TEXT_MUTED)
+                    } else if missing {
+                        egui::RichText::new(&binding.param).color(colors::STATUS_ERROR)
+                    } else {
+                        egui::RichText::new(&binding.param)
+                    };
+                    egui::ComboBox::from_id_salt(format!("vfx_binding_param_{i}"))
+                        .selected_text(text)
+                        .show_ui(ui, |ui| {
+                            for name in param_names {
+                                ui.selectable_value(&mut binding.param, name.clone(), name);
+                            }
+                        });
+                    ui.end_row();
+
+                    grid_label(ui, "Target");
+                    egui::ComboBox::from_id_salt(format!("vfx_binding_target_{i}"))
+                        .selected_text(binding.target.label())
+                        .show_ui(ui, |ui| {
+                            for target in &ParamTarget::ALL {
+                                ui.selectable_value(&mut binding.target, *target, target.label());
+                            }
+                        });
+                    ui.end_row();
+                });
+        }) {
+            remove = Some(i);
+        }
+    }
+    if let Some(idx) = remove {
+        bindings.remove(idx);
+    }
+}
+
+fn draw_sub_emitters_section(
+    ui: &mut egui::Ui,
+    links: &mut Vec<SubEmitter>,
+    emitter_names: &[String],
+    own_index: usize,
+) {
+    category_header(
+        ui,
+        "SUB-EMITTERS",
+        colors::ACCENT_ORANGE,
+        &[("Sub-Emitter", SubEmitter::default)],
+        links,
+    );
+    let mut remove = None;
+    for (i, link) in links.iter_mut().enumerate() {
+        let id = ui.id().with("vfx_sub_emitter").with(i);
+        if modifier_card(ui, link.trigger.label(), colors::ACCENT_ORANGE, id, |ui| {
+            egui::Grid::new(format!("vfx_sub_emitter_{i}"))
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    grid_label(ui, "Emitter");
+                    let text = match emitter_names.get(link.emitter) {
+                        Some(name) if link.emitter != own_index => egui::RichText::new(name),
+                        _ => egui::RichText::new("(none)").color(colors::STATUS_ERROR),
+                    };
+                    egui::ComboBox::from_id_salt(format!("vfx_sub_emitter_child_{i}"))
+                        .selected_text(text)
+                        .show_ui(ui, |ui| {
+                            for (idx, name) in emitter_names.iter().enumerate() {
+                                if idx != own_index {
+                                    ui.selectable_value(&mut link.emitter, idx, name);
+                                }
+                            }
+                        });
+                    ui.end_row();
+
+                    grid_label(ui, "Trigger");
+                    let mut trigger_idx = link.trigger.variant_index();
+                    egui::ComboBox::from_id_salt(format!("vfx_sub_emitter_trigger_{i}"))
+                        .selected_text(link.trigger.label())
+                        .show_ui(ui, |ui| {
+                            for (t, label) in SubEmitterTrigger::VARIANT_LABELS.iter().enumerate() {
+                                ui.selectable_value(&mut trigger_idx, t, *label);
+                            }
+                        });
+                    if trigger_idx != link.trigger.variant_index() {
+                        link.trigger = SubEmitterTrigger::from_variant_index(trigger_idx);
+                    }
+                    ui.end_row();
+
+                    if let SubEmitterTrigger::Interval(interval) = &mut link.trigger {
+                        grid_label(ui, "Interval");
+                        ui.add(egui::DragValue::new(interval).speed(0.01).range(0.01..=60.0).max_decimals(2).suffix(" s"));
+                        ui.end_row();
+                    }
+
+                    grid_label(ui, "Count");
+                    ui.add(egui::DragValue::new(&mut link.count).range(1..=256));
+                    ui.end_row();
+
+                    grid_label(ui, "Inherit Vel");
+                    ui.add(egui::DragValue::new(&mut link.inherit_velocity).speed(0.01).range(0.0..=2.0).max_decimals(2));
+                    ui.end_row();
+
+                    grid_label(ui, "Inherit Color");
+                    ui.checkbox(&mut link.inherit_color, "");
+                    ui.end_row();
+                });
+        }) {
+            remove = Some(i);
+        }
+    }
+    if let Some(idx) = remove {
+        links.remove(idx);
+    }
+}
+
+/// Type selector plus value editor for a parameter value.
+/// Shared with the effect editor's `SetVfxParam` action.
+pub(crate) fn draw_param_value(ui: &mut egui::Ui, value: &mut VfxParamValue, salt: &str) {
+    let mut type_idx = value.variant_index();
+    ui.horizontal(|ui| {
+        grid_label(ui, "Type");
+        egui::ComboBox::from_id_salt(format!("{salt}_type"))
+            .selected_text(VfxParamValue::VARIANT_LABELS[type_idx])
+            .show_ui(ui, |ui| {
+                for (i, label) in VfxParamValue::VARIANT_LABELS.iter().enumerate() {
+                    ui.selectable_value(&mut type_idx, i, *label);
+                }
+            });
+    });
+    if type_idx != value.variant_index() {
+        *value = VfxParamValue::from_variant_index(type_idx);
+    }
+
+    match value {
+        VfxParamValue::Float(v) => {
+            ui.horizontal(|ui| {
+                grid_label(ui, "Value");
+                ui.add(egui::DragValue::new(v).speed(0.1).max_decimals(3));
+            });
+        }
+        VfxParamValue::Vec3(v) => {
+            ui.horizontal(|ui| {
+                grid_label(ui, "Value");
+                ui.add(egui::DragValue::new(&mut v.x).speed(0.1).prefix("x:").max_decimals(3));
+                ui.add(egui::DragValue::new(&mut v.y).speed(0.1).prefix("y:").max_decimals(3));
+                ui.add(egui::DragValue::new(&mut v.z).speed(0.1).prefix("z:").max_decimals(3));
+            });
+        }
+        VfxParamValue::Color(c) => draw_linear_rgba_color(ui, "Value", c),
+        VfxParamValue::Curve(curve) => draw_curve_editor(ui, curve, 0, salt),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Shared helpers
 // ---------------------------------------------------------------------------
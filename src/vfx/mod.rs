@@ -10,11 +10,12 @@ use bevy::prelude::*;
 use bevy_editor_game::{MaterialLibrary, MeshLibrary};
 use bevy_vfx::data::{InitModule, RenderModule, UpdateModule};
 use bevy_vfx::mesh_particles::{MeshParticleAssets, MeshParticleStates, MeshShapeKey, VfxMaterialPending};
-use bevy_vfx::{VfxLibrary, VfxSystem};
+use bevy_vfx::{VectorField, VfxLibrary, VfxSystem};
 
 use crate::materials::{apply_material_def_standalone, remove_all_material_components, resolve_material_ref, set_entity_base_color, set_entity_emissive, set_entity_uv_transform};
 
 const VFX_DIR: &str = "assets/vfx";
+const FIELD_DIR: &str = "assets/vfx/fields";
 
 pub struct VfxEditorPlugin;
 
@@ -80,6 +81,22 @@ fn save_preset_to_disk(name: &str, system: &VfxSystem) {
     }
 }
 
+/// Write a baked vector field to `assets/vfx/fields/`, returning its asset path.
+pub fn save_vector_field(name: &str, field: &VectorField) -> Option<String> {
+    let dir = Path::new(FIELD_DIR);
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        warn!("Failed to create vector field directory: {}", e);
+        return None;
+    }
+
+    let filename = format!("{}.vfield", sanitize_filename(name));
+    if let Err(e) = std::fs::write(dir.join(&filename), field.to_bytes()) {
+        warn!("Failed to write vector field '{}': {}", name, e);
+        return None;
+    }
+    Some(format!("vfx/fields/{}", filename))
+}
+
 fn load_presets_from_disk(library: &mut VfxLibrary) {
     let dir = Path::new(VFX_DIR);
     if !dir.is_dir() {